@@ -0,0 +1,140 @@
+//! Benchmarks of the costs the performance work on the viewer is measured against.
+//!
+//! - `sphere_construction`: building a sphere mesh at increasing resolutions.
+//! - `add_meshes_and_flush`: adding N meshes to a running 3D viewer and waiting for the frame
+//!   which uploads their buffers, covering the state and the GPU update together. The GPU
+//!   context needs a window, so it is only reached through a running viewer.
+//! - `rpc_add_mesh`: the round trip of adding a single mesh of increasing size over RPC.
+//!
+//! The RPC benchmarks launch the viewer executable, like the RPC tests, and are skipped when it
+//! has not been built. Run them with `cargo bench`.
+//--------------------------------------------------------------------------------------------------
+
+//{{{ crate imports
+use topohedral_viewer::app::locate_executable;
+use topohedral_viewer::d3::{self, Mesh3D, SphereDescriptor};
+use topohedral_viewer::{CellType, Color, Vec3};
+//}}}
+//{{{ std imports
+use std::process::{Child, Command};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+//}}}
+//{{{ dep imports
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+//}}}
+//--------------------------------------------------------------------------------------------------
+
+/// The port the viewer launched by the RPC benchmarks listens on, away from that of the tests.
+const PORT: usize = 50071;
+
+//{{{ fun: sphere
+/// Returns a sphere of `n_lat` by `2 * n_lat` quads.
+fn sphere<'a>(n_lat: usize) -> d3::Mesh<'a> {
+    d3::Mesh::create_sphere(&SphereDescriptor {
+        origin: Vec3::zeros(),
+        axis: Vec3::z(),
+        radius: 1.0,
+        n_lat: n_lat,
+        n_long: 2 * n_lat,
+        line_color: Color::Black,
+        tri_color: Color::Red,
+        cell_type: CellType::Triangle,
+        color_by: d3::ColorBy::None,
+    })
+}
+//}}}
+//{{{ fun: launch_viewer
+/// Launches a 3D viewer serving RPC on `PORT` and connects to it, or returns `None` if the
+/// executable has not been built.
+fn launch_viewer() -> Option<(Child, d3::Client3D)> {
+    let Ok(executable) = locate_executable() else {
+        eprintln!("Viewer executable not found, skipping the RPC benchmarks");
+        return None;
+    };
+    let server = Command::new(executable)
+        .arg("d3")
+        .arg("with-port")
+        .arg(PORT.to_string())
+        .spawn()
+        .expect("Failed to start topoviewer");
+    sleep(Duration::from_millis(2000));
+    let mut client = d3::Client3D::new(PORT).expect("Failed to connect to topoviewer");
+    client.ping().expect("Failed to ping server");
+    Some((server, client))
+}
+//}}}
+//{{{ fun: sphere_construction
+fn sphere_construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sphere_construction");
+    for n_lat in [16, 64, 256] {
+        group.throughput(Throughput::Elements((2 * n_lat * n_lat) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n_lat), &n_lat, |b, &n_lat| {
+            b.iter(|| sphere(n_lat))
+        });
+    }
+    group.finish();
+}
+//}}}
+//{{{ fun: rpc
+fn rpc(c: &mut Criterion) {
+    let Some((mut server, mut client)) = launch_viewer() else {
+        return;
+    };
+
+    //{{{ com: add meshes and flush
+    let mut group = c.benchmark_group("add_meshes_and_flush");
+    group.sample_size(10);
+    let mesh = sphere(16);
+    for num_meshes in [1, 10, 100] {
+        group.throughput(Throughput::Elements(num_meshes as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_meshes),
+            &num_meshes,
+            |b, &num_meshes| {
+                b.iter_custom(|iters| {
+                    let mut elapsed = Duration::ZERO;
+                    for _ in 0..iters {
+                        let start = Instant::now();
+                        for _ in 0..num_meshes {
+                            client.add_mesh(mesh.clone()).unwrap();
+                        }
+                        client.flush().unwrap();
+                        elapsed += start.elapsed();
+                        client.clear().unwrap();
+                    }
+                    elapsed
+                })
+            },
+        );
+    }
+    group.finish();
+    //}}}
+    //{{{ com: add mesh round trip
+    let mut group = c.benchmark_group("rpc_add_mesh");
+    for n_lat in [4, 32, 128] {
+        let mesh = sphere(n_lat);
+        group.throughput(Throughput::Elements(mesh.num_vertices() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n_lat), &mesh, |b, mesh| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    let start = Instant::now();
+                    client.add_mesh(mesh.clone()).unwrap();
+                    elapsed += start.elapsed();
+                    client.clear().unwrap();
+                }
+                elapsed
+            })
+        });
+    }
+    group.finish();
+    //}}}
+
+    client.kill_server().expect("Failed to kill server");
+    let _ = server.wait();
+}
+//}}}
+
+criterion_group!(benches, sphere_construction, rpc);
+criterion_main!(benches);
@@ -5,15 +5,19 @@
 //--------------------------------------------------------------------------------------------------
 
 //{{{ crate imports
-use crate::d2;
-use crate::d3;
+use crate::common::{AddMeshError, BudgetError, Mat4, SceneBudget, SceneStats, Vec3};
+use crate::core::{StateCore, VertexCore, ViewStateCore};
+use crate::d2::{self, Mesh2D};
+use crate::d3::{self, Mesh3D, State3D};
+use crate::depth_texture::DepthFormat;
 //}}}
 //{{{ std imports
 use core::net::SocketAddr;
 use std::fmt::Display;
 use std::net::{IpAddr, Ipv4Addr};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 //}}}
 //{{{ dep imports
 use winit::event::WindowEvent;
@@ -24,14 +28,24 @@ use winit::{
 };
 
 use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::runtime::{Handle, Runtime};
 use tokio::task;
 use tokio::{runtime, sync::mpsc};
+use tracing_subscriber::EnvFilter;
 
 use topohedral_tracing::*;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
+//{{{ col: constants
+/// The environment variable the log filter is read from when the tracing subscriber is
+/// initialized without the log filter option, see [`TopoViewerOptions::init_tracing`].
+pub const LOG_FILTER_VAR: &str = "TOPO_LOG";
+/// The levels a log filter directive can set, from the fewest messages to the most.
+const LOG_LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+//}}}
 //{{{ col: StateHandles
 /// A shared, thread-safe handle to the 2D viewer state.
 pub type State2Handle<'a> = Arc<Mutex<d2::State<'a>>>;
@@ -48,6 +62,9 @@ pub enum RPCOption {
     None,
     /// The WithPort option indicates that the RPC server should be started on the specified
     WithPort { port: u16 },
+    /// The Schema option prints the protobuf schema of the RPC service of the mode and exits,
+    /// without opening the viewer, so that clients in other languages can generate their stubs.
+    Schema,
 }
 //..................................................................................................
 //}}}
@@ -62,10 +79,37 @@ pub enum Mode {
 }
 //..................................................................................................
 //}}}
+//{{{ col: UpAxis
+//{{{ enum: UpAxis
+/// The axis pointing up in the 3D shapes of a scene file. The viewer itself is z-up, so the
+/// shapes of a scene made y-up, as most modelling tools export them, are rotated to match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum UpAxis {
+    /// The y-axis points up, the shapes are rotated a quarter turn about the x-axis.
+    Y,
+    /// The z-axis points up, as in the viewer, the shapes are left as they are.
+    #[default]
+    Z,
+}
+//}}}
+//{{{ impl: UpAxis
+impl UpAxis {
+    /// Returns the model matrix rotating shapes with this up axis into the z-up viewer, so that
+    /// their up axis points along the z-axis, keeping the handedness of their axes.
+    pub fn to_z_up(&self) -> Mat4 {
+        match self {
+            UpAxis::Y => Mat4::from_axis_angle(&Vec3::x_axis(), std::f32::consts::FRAC_PI_2),
+            UpAxis::Z => Mat4::identity(),
+        }
+    }
+}
+//}}}
+//..................................................................................................
+//}}}
 //{{{ struct: TopoViewerOptions
 /// The TopoViewerOptions struct contains the options that can be passed to the TopoViewer
 /// constructor.
-#[derive(Debug, Clone, Copy, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[command(
     name = "TopoViewer",
     about = "A 2D and 3D viewer for topological data",
@@ -79,6 +123,147 @@ pub struct TopoViewerOptions {
     /// The with_rpc option indicates whether to start the RPC server.
     #[command(subcommand)]
     pub with_rpc: RPCOption,
+    /// A JSON scene file, an array of shape descriptors tagged by their type, whose shapes are
+    /// added to the viewer before the window opens.
+    #[arg(long)]
+    pub scene: Option<PathBuf>,
+    /// The axis pointing up in the shapes of the scene file, which are rotated so that it points
+    /// up the z-axis of the 3D viewer. Ignored by the 2D viewer.
+    #[arg(long, value_enum, default_value_t = UpAxis::Z)]
+    pub up_axis: UpAxis,
+    /// Checks every shape of this scene file for the mode, within the scene budget, and exits,
+    /// with a nonzero status if any is invalid, instead of opening the viewer. It needs neither a
+    /// display nor a GPU, so scene files can be checked in CI.
+    #[arg(long, value_name = "SCENE")]
+    pub validate: Option<PathBuf>,
+    /// Caps the rate at which windows are redrawn while the camera is animating, in frames per
+    /// second. Without it they are redrawn as fast as the display allows.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_fps: Option<u32>,
+    /// The precision of the depth buffer. Formats the GPU cannot render to fall back to depth32.
+    #[arg(long, value_enum, default_value_t = DepthFormat::Depth32)]
+    pub depth_format: DepthFormat,
+    /// Uses reversed-Z depth in the 3D viewer, which improves depth precision far from the
+    /// camera, most of all with the 32-bit depth format.
+    #[arg(long)]
+    pub reversed_z: bool,
+    /// Shows the x, y and z axes in a corner of the 3D viewer, turning with the camera. They can
+    /// be toggled with the `g` key either way.
+    #[arg(long)]
+    pub axes_gizmo: bool,
+    /// The octant, 0 to 7, the 3D camera starts in and returns to when the view is reset. Octants
+    /// 0 to 3 lie above the xy-plane, counter-clockwise from the positive x and y axes, and 4 to
+    /// 7 lie below them in the same order.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(i8).range(0..8))]
+    pub octant: i8,
+    /// Stretches the 2D view over the whole window. By default the view keeps the proportions of
+    /// world space, so shapes are not distorted by a window which is not square.
+    #[arg(long)]
+    pub stretch: bool,
+    /// Logs the world coordinates under the cursor, at debug level, as it moves over the 2D
+    /// viewer.
+    #[arg(long)]
+    pub coordinate_readout: bool,
+    /// The width, in pixels, of the edges of triangle meshes. Edges wider than the default of one
+    /// pixel stay visible over the faces on high resolution displays.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_positive)]
+    pub edge_width: f32,
+    /// Draws the edges of triangle meshes as line cells generated from their triangles, for GPU
+    /// backends without line polygon mode, which cannot draw them otherwise.
+    #[arg(long)]
+    pub wireframe_fallback: bool,
+    /// The number of world units in a meter of the scene. The 3D camera, its clipping planes and
+    /// how far it pans per key press are made for scenes a few meters across, and are scaled by
+    /// it, so a scene in millimeters is viewed with 1000.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_positive)]
+    pub units_per_meter: f32,
+    /// Shuts the RPC server and viewer down once every client has disconnected, or none has
+    /// connected, for the idle timeout. A connected client holds the server up however long it
+    /// stays silent. Without it the server runs until it is killed or the window is closed.
+    #[arg(long)]
+    pub exit_on_idle: bool,
+    /// How long, in seconds, the RPC server waits without active clients before shutting down
+    /// when exiting on idle.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    pub idle_timeout: u64,
+    /// The largest number of meshes the viewer holds. Adding meshes past it fails, and over RPC
+    /// is answered with a resource exhausted error. Unlimited by default.
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_meshes: Option<usize>,
+    /// The largest total number of vertices over every mesh the viewer holds, enforced like the
+    /// maximum number of meshes. Unlimited by default.
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_vertices: Option<usize>,
+    /// Filters the log messages, with a level applying to every target, such as `warn`, or a
+    /// comma separated list of `target=level` directives, such as
+    /// `info,topohedral_viewer::core::state=warn`. It takes the place of the `TOPO_LOG`
+    /// environment variable. Messages are only logged when built with the `enable_trace` feature.
+    #[arg(long, alias = "log-level", value_parser = parse_log_filter)]
+    pub log_filter: Option<String>,
+}
+
+//{{{ fun: parse_positive
+/// Parses a number, such as an edge width, which must be finite and strictly positive.
+fn parse_positive(arg: &str) -> Result<f32, String> {
+    let number: f32 = arg.parse().map_err(|err| format!("{}", err))?;
+    if number.is_finite() && number > 0.0 {
+        Ok(number)
+    } else {
+        Err(format!("{} is not a positive number", arg))
+    }
+}
+//}}}
+//{{{ fun: parse_log_filter
+/// Parses a log filter, a comma separated list of directives each made of a level, optionally
+/// preceded by a target and `=`.
+fn parse_log_filter(arg: &str) -> Result<String, String> {
+    for directive in arg.split(',') {
+        let (target, level) = match directive.split_once('=') {
+            Some((target, level)) => (Some(target), level),
+            None => (None, directive),
+        };
+        if target.is_some_and(|target| target.trim().is_empty()) {
+            return Err(format!("{} has an empty target", directive));
+        }
+        if !LOG_LEVELS.contains(&level.trim().to_lowercase().as_str()) {
+            return Err(format!(
+                "{} is not a log level, expected one of {}",
+                level,
+                LOG_LEVELS.join(", ")
+            ));
+        }
+    }
+    Ok(arg.to_string())
+}
+//}}}
+
+impl TopoViewerOptions {
+    /// Returns the scene budget set by the `max_meshes` and `max_vertices` options.
+    pub fn scene_budget(&self) -> SceneBudget {
+        SceneBudget {
+            max_meshes: self.max_meshes,
+            max_vertices: self.max_vertices,
+        }
+    }
+
+    /// Returns the filter of the log filter option, if it is set.
+    pub fn log_env_filter(&self) -> Option<EnvFilter> {
+        // the option parser has already checked the directives
+        self.log_filter.as_ref().and_then(|log_filter| EnvFilter::try_new(log_filter).ok())
+    }
+
+    /// Initializes the tracing subscriber with the log filter option if it is set, and otherwise
+    /// as `topohedral_tracing::init` does, with the filter of the `TOPO_LOG` environment
+    /// variable. Fails if a subscriber has already been initialized.
+    pub fn init_tracing(&self) -> Result<(), String> {
+        match self.log_env_filter() {
+            Some(filter) => tracing_subscriber::fmt()
+                .with_env_filter(filter)
+                .try_init()
+                .map_err(|e| e.to_string()),
+            None => init().map_err(|e| format!("{:?}", e)),
+        }
+    }
 }
 
 impl Display for TopoViewerOptions {
@@ -92,23 +277,284 @@ impl Display for TopoViewerOptions {
         match self.with_rpc {
             RPCOption::None => write!(f, "None")?,
             RPCOption::WithPort { port } => write!(f, "RPC server on port {}", port)?,
+            RPCOption::Schema => write!(f, "Print schema")?,
+        }
+        if let Some(scene) = self.scene.as_ref() {
+            write!(f, ", Scene: {}", scene.display())?;
+        }
+        if self.up_axis != UpAxis::Z {
+            write!(f, ", Up axis: {:?}", self.up_axis)?;
+        }
+        if let Some(scene) = self.validate.as_ref() {
+            write!(f, ", Validate: {}", scene.display())?;
+        }
+        if let Some(max_fps) = self.max_fps {
+            write!(f, ", Max FPS: {}", max_fps)?;
+        }
+        write!(f, ", Depth format: {:?}", self.depth_format)?;
+        if self.reversed_z {
+            write!(f, ", Reversed-Z")?;
+        }
+        if self.axes_gizmo {
+            write!(f, ", Axes gizmo")?;
+        }
+        if self.octant != 0 {
+            write!(f, ", Octant: {}", self.octant)?;
+        }
+        if self.stretch {
+            write!(f, ", Stretch")?;
+        }
+        if self.coordinate_readout {
+            write!(f, ", Coordinate readout")?;
+        }
+        if self.edge_width != 1.0 {
+            write!(f, ", Edge width: {}", self.edge_width)?;
+        }
+        if self.wireframe_fallback {
+            write!(f, ", Wireframe fallback")?;
+        }
+        if self.units_per_meter != 1.0 {
+            write!(f, ", Units per meter: {}", self.units_per_meter)?;
+        }
+        if self.exit_on_idle {
+            write!(f, ", Exit on idle after {}s", self.idle_timeout)?;
+        }
+        if let Some(max_meshes) = self.max_meshes {
+            write!(f, ", Max meshes: {}", max_meshes)?;
+        }
+        if let Some(max_vertices) = self.max_vertices {
+            write!(f, ", Max vertices: {}", max_vertices)?;
+        }
+        if let Some(log_filter) = self.log_filter.as_ref() {
+            write!(f, ", Log filter: {}", log_filter)?;
         }
         std::fmt::Result::Ok(())
     }
 }
 //..................................................................................................
 //}}}
-//{{{ enum: RcpStatus
-/// ENcodes the status of the RPC server. This can be non-existent if running topoviewer in non-rcp
-/// mode, running if the rcp exists and is running or stopped if the rcp exists but is not running.
-enum RcpStatus {
-    /// Denotes non-existent RPC server.    
-    None,
-    /// Denotes that the RPC server exists but is running.
-    Running,
-    /// Denotes that the RPC server exists but is not running.
-    Stopped,
+//{{{ col: SceneFile
+//{{{ enum: SceneError
+/// Errors raised while loading a scene file.
+#[derive(Debug, Error)]
+pub enum SceneError {
+    #[error("Could not read scene file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    /// The file is not a JSON array, the error carries the line and column.
+    #[error("Invalid scene file: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// An entry is not a valid shape descriptor for the viewer's mode.
+    #[error("Invalid scene entry {index}: {message}")]
+    Entry { index: usize, message: String },
+    /// The shapes of the scene do not fit within the viewer's scene budget.
+    #[error("Scene does not fit: {0}")]
+    Budget(#[from] BudgetError),
+}
+//}}}
+//{{{ impl: SceneError
+impl SceneError {
+    /// Returns the error of the entry at `index` when the state turns its mesh away, which is
+    /// only a budget error if the mesh does not fit.
+    fn from_add(index: usize, e: AddMeshError) -> Self {
+        match e {
+            AddMeshError::Budget(e) => SceneError::Budget(e),
+            AddMeshError::Empty => SceneError::Entry {
+                index: index,
+                message: e.to_string(),
+            },
+        }
+    }
+}
+//}}}
+//{{{ struct: SceneFile
+/// A scene description, read from a JSON array of shape descriptors tagged by their `type`, e.g.
+///
+/// ```json
+/// [
+///     {"type": "axes", "origin": [0, 0, 0], "x_axis": [1, 0, 0], "y_axis": [0, 1, 0],
+///      "z_axis": [0, 0, 1], "neg_len": 0.5, "pos_len": 1.0},
+///     {"type": "sphere", "origin": [0, 0, 0], "axis": [0, 0, 1], "radius": 0.5,
+///      "n_lat": 16, "n_long": 32, "line_color": "Black", "tri_color": "Blue",
+///      "cell_type": "Triangle"}
+/// ]
+/// ```
+///
+/// The entries are interpreted as [`d2::Descriptor`]s or [`d3::Descriptor`]s depending on the
+/// viewer they are applied to, and are checked with the same rules as RPC add requests.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SceneFile {
+    pub entries: Vec<serde_json::Value>,
+}
+//}}}
+//{{{ impl: SceneFile
+impl SceneFile {
+    //{{{ fun: load
+    /// Reads a scene file from disk.
+    pub fn load(path: &Path) -> Result<Self, SceneError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| SceneError::Io(path.to_path_buf(), e))?;
+        Self::from_json(&json)
+    }
+    //}}}
+    //{{{ fun: from_json
+    /// Parses a scene from a JSON string. Only the overall structure is checked here, the entries
+    /// are checked when the scene is applied to a viewer.
+    pub fn from_json(json: &str) -> Result<Self, SceneError> {
+        Ok(serde_json::from_str(json)?)
+    }
+    //}}}
+    //{{{ fun: descriptor
+    /// Parses and validates the entry at `index`.
+    fn descriptor<D>(
+        index: usize,
+        entry: &serde_json::Value,
+        validate: &impl Fn(&D) -> Result<(), String>,
+    ) -> Result<D, SceneError>
+    where
+        D: serde::de::DeserializeOwned,
+    {
+        let descriptor: D = serde_json::from_value(entry.clone())
+            .map_err(|e| SceneError::Entry { index, message: e.to_string() })?;
+        validate(&descriptor).map_err(|message| SceneError::Entry { index, message })?;
+        Ok(descriptor)
+    }
+    //}}}
+    //{{{ fun: descriptors
+    /// Parses and validates every entry, failing on the first bad one so that a scene is never
+    /// applied in part.
+    fn descriptors<D>(&self, validate: impl Fn(&D) -> Result<(), String>) -> Result<Vec<D>, SceneError>
+    where
+        D: serde::de::DeserializeOwned,
+    {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| Self::descriptor(index, entry, &validate))
+            .collect()
+    }
+    //}}}
+    //{{{ fun: check_entries
+    /// Parses, validates and builds the shape of every entry, carrying on past bad ones. Returns
+    /// the number of good entries and of the vertices of their shapes, along with the errors of
+    /// the bad ones.
+    fn check_entries<D>(
+        &self,
+        validate: impl Fn(&D) -> Result<(), String>,
+        num_vertices: impl Fn(&D) -> usize,
+    ) -> (usize, usize, Vec<SceneError>)
+    where
+        D: serde::de::DeserializeOwned,
+    {
+        let mut counts = (0, 0);
+        let mut errors = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            match Self::descriptor(index, entry, &validate) {
+                Ok(descriptor) => counts = (counts.0 + 1, counts.1 + num_vertices(&descriptor)),
+                Err(e) => errors.push(e),
+            }
+        }
+        (counts.0, counts.1, errors)
+    }
+    //}}}
+    //{{{ fun: validate
+    /// Checks the scene as applying it to a viewer in `mode` with the scene budget `budget`
+    /// would, building every shape, but without a viewer to add them to. Unlike applying it,
+    /// every bad entry is reported, followed by the scene not fitting within the budget if its
+    /// good entries do not. Returns the number of shapes of a valid scene.
+    pub fn validate(&self, mode: Mode, budget: SceneBudget) -> Result<usize, Vec<SceneError>> {
+        let (num_meshes, num_vertices, mut errors) = match mode {
+            Mode::D2 => self.check_entries(d2::validate_descriptor, |descriptor| {
+                d2::Mesh::from_descriptor(descriptor).num_vertices()
+            }),
+            // validated descriptors always give a mesh
+            Mode::D3 => self.check_entries(d3::validate_descriptor, |descriptor| {
+                d3::Mesh::from_descriptor(descriptor).map_or(0, |mesh| mesh.num_vertices())
+            }),
+        };
+        let empty = SceneStats {
+            num_meshes: 0,
+            num_vertices: 0,
+            budget: budget,
+        };
+        if let Err(e) = empty.check(num_meshes, num_vertices) {
+            errors.push(e.into());
+        }
+        if errors.is_empty() {
+            Ok(num_meshes)
+        } else {
+            Err(errors)
+        }
+    }
+    //}}}
+    //{{{ fun: apply_2d
+    /// Adds the shapes of the scene to a 2D state through its `add_*` methods, so that they are
+    /// tessellated at its quality, returning their uids in order. Nothing is added unless all of
+    /// the shapes fit within the scene budget.
+    pub fn apply_2d(&self, state: &mut d2::State) -> Result<Vec<usize>, SceneError> {
+        let descriptors = self.descriptors(d2::validate_descriptor)?;
+        Self::add_all(state, &descriptors, |state, index, descriptor| {
+            state
+                .add_descriptor(descriptor)
+                .map_err(|e| SceneError::from_add(index, e))
+        })
+    }
+    //}}}
+    //{{{ fun: apply_3d
+    /// Adds the shapes of the scene to a 3D state through its `add_*` methods, so that they are
+    /// tessellated at its quality, returning their uids in order. The shapes are made with
+    /// `up_axis` pointing up, and are placed in the viewer with the model matrix rotating it onto
+    /// the z-axis. Nothing is added unless all of the shapes fit within the scene budget.
+    pub fn apply_3d(
+        &self,
+        state: &mut d3::State,
+        up_axis: UpAxis,
+    ) -> Result<Vec<usize>, SceneError> {
+        let descriptors = self.descriptors(d3::validate_descriptor)?;
+        Self::add_all(state, &descriptors, |state, index, descriptor| {
+            let uid = state.add_descriptor(descriptor).map_err(|e| match e {
+                d3::AddShapeError::Mesh(e) => SceneError::Entry {
+                    index: index,
+                    message: e.to_string(),
+                },
+                d3::AddShapeError::Add(e) => SceneError::from_add(index, e),
+            })?;
+            if up_axis != UpAxis::Z {
+                state.set_mesh_model(uid, up_axis.to_z_up());
+            }
+            Ok(uid)
+        })
+    }
+    //}}}
+    //{{{ fun: add_all
+    /// Adds every descriptor to the state with `add`, removing the shapes already added again
+    /// if one of them fails, so that a scene is added either whole or not at all.
+    fn add_all<'a, V, W, D>(
+        state: &mut StateCore<'a, V, W>,
+        descriptors: &[D],
+        add: impl Fn(&mut StateCore<'a, V, W>, usize, &D) -> Result<usize, SceneError>,
+    ) -> Result<Vec<usize>, SceneError>
+    where
+        V: VertexCore + Deserialize<'a> + Serialize,
+        W: ViewStateCore + Default,
+    {
+        let mut uids = Vec::with_capacity(descriptors.len());
+        for (index, descriptor) in descriptors.iter().enumerate() {
+            match add(state, index, descriptor) {
+                Ok(uid) => uids.push(uid),
+                Err(e) => {
+                    for uid in uids {
+                        state.remove_mesh(uid);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(uids)
+    }
+    //}}}
 }
+//}}}
 //..................................................................................................
 //}}}
 //{{{ col: TopoViewer
@@ -124,6 +570,9 @@ pub struct TopoViewer<'a> {
     rpc_handle_3d: Option<task::JoinHandle<()>>,
     tokio_runtime: Runtime,
     shutdown_sender: Option<mpsc::Sender<()>>,
+    frame_time: Option<Duration>,
+    last_frame: Instant,
+    exit_on_idle: Option<Duration>,
 }
 //}}}
 //{{{ impl TopoViewer
@@ -155,7 +604,19 @@ impl<'a> TopoViewer<'a> {
                 //{{{ trace
                 info!("Creating  2D state");
                 //}}}
-                Some(d2::State::new_arc_mutex())
+                let state = d2::State::new_arc_mutex();
+                {
+                    let mut state = state.lock().unwrap();
+                    state.set_depth_format(topoviewer_options.depth_format);
+                    state.set_keep_aspect(!topoviewer_options.stretch);
+                    state.set_coordinate_readout(topoviewer_options.coordinate_readout);
+                    state
+                        .set_edge_width(topoviewer_options.edge_width)
+                        .expect("edge width is validated when parsed");
+                    state.set_wireframe_fallback(topoviewer_options.wireframe_fallback);
+                    state.set_scene_budget(topoviewer_options.scene_budget());
+                }
+                Some(state)
             }
             Mode::D3 => None,
         };
@@ -166,12 +627,30 @@ impl<'a> TopoViewer<'a> {
                 //{{{ trace
                 info!("Creating 3D state");
                 //}}}
-                Some(d3::State::new_arc_mutex())
+                let state = d3::State::new_arc_mutex();
+                {
+                    let mut state = state.lock().unwrap();
+                    state.set_depth_format(topoviewer_options.depth_format);
+                    state.set_reversed_z(topoviewer_options.reversed_z);
+                    state.set_axes_gizmo(topoviewer_options.axes_gizmo);
+                    state
+                        .set_initial_octant(topoviewer_options.octant)
+                        .expect("octant is validated when parsed");
+                    state
+                        .set_edge_width(topoviewer_options.edge_width)
+                        .expect("edge width is validated when parsed");
+                    state.set_wireframe_fallback(topoviewer_options.wireframe_fallback);
+                    state
+                        .set_units_per_meter(topoviewer_options.units_per_meter)
+                        .expect("units per meter are validated when parsed");
+                    state.set_scene_budget(topoviewer_options.scene_budget());
+                }
+                Some(state)
             }
         };
 
         let tokio_runtime = match topoviewer_options.with_rpc {
-            RPCOption::None => {
+            RPCOption::None | RPCOption::Schema => {
                 //{{{ trace
                 info!("Building single-threaded tokio runtime");
                 //}}}
@@ -196,7 +675,7 @@ impl<'a> TopoViewer<'a> {
             event_loop_proxy: event_loop_proxy,
             mode: topoviewer_options.mode,
             rpc_port: match topoviewer_options.with_rpc {
-                RPCOption::None => None,
+                RPCOption::None | RPCOption::Schema => None,
                 RPCOption::WithPort { port } => Some(port),
             },
             state_2d: state_2d,
@@ -205,9 +684,37 @@ impl<'a> TopoViewer<'a> {
             rpc_handle_3d: None,
             tokio_runtime: tokio_runtime,
             shutdown_sender: None,
+            frame_time: topoviewer_options
+                .max_fps
+                .map(|max_fps| Duration::from_secs_f64(1.0 / max_fps as f64)),
+            last_frame: Instant::now(),
+            exit_on_idle: topoviewer_options
+                .exit_on_idle
+                .then(|| Duration::from_secs(topoviewer_options.idle_timeout)),
         }
     }
     //}}}
+    //{{{ fun: load_scene
+    /// Loads a scene file and adds its shapes to the active viewer state, returning the number of
+    /// shapes added. The 3D shapes are rotated from `up_axis` to the z-up viewer.
+    pub fn load_scene(&mut self, path: &Path, up_axis: UpAxis) -> Result<usize, SceneError> {
+        //{{{ trace
+        info!("Loading scene from {}", path.display());
+        //}}}
+        let scene = SceneFile::load(path)?;
+        let uids = match self.mode {
+            Mode::D2 => match self.state_2d.as_ref() {
+                Some(state) => scene.apply_2d(&mut state.lock().unwrap())?,
+                None => Vec::new(),
+            },
+            Mode::D3 => match self.state_3d.as_ref() {
+                Some(state) => scene.apply_3d(&mut state.lock().unwrap(), up_axis)?,
+                None => Vec::new(),
+            },
+        };
+        Ok(uids.len())
+    }
+    //}}}
     //{{{ fun: runtime_handle
     pub fn runtime_handle(&self) -> Handle {
         self.tokio_runtime.handle().clone()
@@ -247,36 +754,90 @@ impl<'a> TopoViewer<'a> {
         }
     }
     //}}}
-    //{{{ fun: server_status
-    /// Returns whether the RPC server is currently running.
+    //{{{ fun: launch_window
+    /// Opens a window onto the active viewer state. Every window after the first has its own
+    /// camera onto the same meshes.
+    fn launch_window(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        let window_id = match self.mode {
+            Mode::D2 => self.state_2d.as_ref().map(|state| {
+                self.tokio_runtime
+                    .block_on(state.lock().unwrap().launch_window(event_loop))
+            }),
+            Mode::D3 => self.state_3d.as_ref().map(|state| {
+                self.tokio_runtime
+                    .block_on(state.lock().unwrap().launch_window(event_loop))
+            }),
+        };
+        //{{{ trace
+        info!("Launched window {:?}", window_id);
+        //}}}
+    }
+    //}}}
+    //{{{ fun: close_window_with_id
+    /// Closes a single window of the active viewer state, returning how many windows remain.
+    fn close_window_with_id(&mut self, window_id: &winit::window::WindowId) -> usize {
+        match self.mode {
+            Mode::D2 => self
+                .state_2d
+                .as_ref()
+                .map_or(0, |state| state.lock().unwrap().close_window_with_id(window_id)),
+            Mode::D3 => self
+                .state_3d
+                .as_ref()
+                .map_or(0, |state| state.lock().unwrap().close_window_with_id(window_id)),
+        }
+    }
+    //}}}
+    //{{{ fun: is_animating
+    /// Returns whether the camera of any window of the active viewer state is animating.
+    fn is_animating(&self) -> bool {
+        match self.mode {
+            Mode::D2 => self
+                .state_2d
+                .as_ref()
+                .map_or(false, |state| state.lock().unwrap().is_animating()),
+            Mode::D3 => self
+                .state_3d
+                .as_ref()
+                .map_or(false, |state| state.lock().unwrap().is_animating()),
+        }
+    }
+    //}}}
+    //{{{ fun: window_request_redraw
+    /// Requests a redraw of every window of the active viewer state.
+    fn window_request_redraw(&mut self) {
+        match self.mode {
+            Mode::D2 => {
+                if let Some(state) = self.state_2d.as_ref() {
+                    state.lock().unwrap().window_request_redraw();
+                }
+            }
+            Mode::D3 => {
+                if let Some(state) = self.state_3d.as_ref() {
+                    state.lock().unwrap().window_request_redraw();
+                }
+            }
+        }
+    }
+    //}}}
+    //{{{ fun: close_window
+    /// Closes the windows of the active viewer state, if there is one.
     ///
-    /// This method checks the status of the RPC server handle for the current mode (2D or 3D) and
-    /// returns `true` if the handle is still running, indicating that the RPC server is active.
-    fn server_status(&self) -> RcpStatus {
-        let mut status = RcpStatus::None;
-
+    /// This is called on shutdown so that the windows disappear as soon as the shutdown event is
+    /// handled, rather than lingering until the event loop itself is torn down.
+    fn close_window(&mut self) {
         match self.mode {
             Mode::D2 => {
-                if let Some(handle) = self.rpc_handle_2d.as_ref() {
-                    if handle.is_finished() {
-                        status = RcpStatus::Stopped;
-                    } else {
-                        status = RcpStatus::Running;
-                    }
+                if let Some(state) = self.state_2d.as_ref() {
+                    state.lock().unwrap().close_window();
                 }
             }
             Mode::D3 => {
-                if let Some(handle) = self.rpc_handle_3d.as_ref() {
-                    if handle.is_finished() {
-                        status = RcpStatus::Stopped;
-                    } else {
-                        status = RcpStatus::Running;
-                    }
+                if let Some(state) = self.state_3d.as_ref() {
+                    state.lock().unwrap().close_window();
                 }
             }
         }
-
-        status
     }
     //}}}
 }
@@ -317,42 +878,48 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
                 Mode::D2 => {
 
                     let state_clone = self.state_2d.clone().unwrap();
-                    let state_clone_2 = state_clone.clone();
                     let ev_prox = self.event_loop_proxy.clone();
+                    let exit_on_idle = self.exit_on_idle;
                     let handle = self.tokio_runtime.spawn(async move {
                         //{{{ trace
                         info!("Launching 2D RPC server");
                         //}}}
-                        d2::run_server(state_clone, socket, shutdown_sender1, shutdown_receiver, ev_prox)
-                            .await
+                        d2::run_server(
+                            state_clone,
+                            socket,
+                            shutdown_sender1,
+                            shutdown_receiver,
+                            ev_prox,
+                            exit_on_idle,
+                        )
+                        .await
                     });
 
                     self.rpc_handle_2d = Some(handle);
-
-                    info!("Launching 2D window");
-                    self.tokio_runtime
-                        .block_on(state_clone_2.lock().unwrap().launch_window(event_loop));
                 },
                 //}}}
                 //{{{ case: 3D
                 Mode::D3 => {
 
                     let state_clone = self.state_3d.clone().unwrap();
-                    let state_clone_2 = state_clone.clone();
                     let ev_prox = self.event_loop_proxy.clone();
+                    let exit_on_idle = self.exit_on_idle;
                     let handle = self.tokio_runtime.spawn(async move {
                         //{{{ trace
                         info!("Launching 3D RPC server");
                         //}}}
-                        d3::run_server(state_clone, socket, shutdown_sender1, shutdown_receiver, ev_prox)
-                            .await
+                        d3::run_server(
+                            state_clone,
+                            socket,
+                            shutdown_sender1,
+                            shutdown_receiver,
+                            ev_prox,
+                            exit_on_idle,
+                        )
+                        .await
                     });
 
                     self.rpc_handle_3d = Some(handle);
-                    info!("Launching 3D window");
-                    self.tokio_runtime
-                        .block_on(state_clone_2.lock().unwrap().launch_window(event_loop));
-
                 },
                 //}}}
                 //{{{ default
@@ -363,6 +930,11 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
             };
             
         }
+        // the window is launched without an RPC server too, e.g. to view a scene file
+        //{{{ trace
+        info!("Launching window");
+        //}}}
+        self.launch_window(event_loop);
     }
     //}}}
     //{{{ fun: window_event
@@ -378,6 +950,12 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
                 //{{{ trace
                 info!("Close requested");
                 //}}}
+                if self.close_window_with_id(&window_id) > 0 {
+                    //{{{ trace
+                    info!("Other windows remain open, not exiting");
+                    //}}}
+                    return;
+                }
                 if let Some(shutdown_sender) = self.shutdown_sender.as_ref() {
                     //{{{ trace
                     info!("Shutting down RPC server");
@@ -407,19 +985,35 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
             },
             //}}}
         }
-
-        match self.server_status() {
-            //{{{ case: Stopped
-            RcpStatus::Stopped => {
-                //{{{ trace
-                info!("RPC server is stopped, closing 2D window");
-                //}}}
-                event_loop.exit();
+    }
+    //}}}
+    //{{{ fun: about_to_wait
+    /// Keeps the windows redrawing while the camera is animating.
+    ///
+    /// Without a frame cap a redraw is requested every time the event loop goes idle. With one,
+    /// the event loop sleeps until the next frame is due, so that animations run at the capped
+    /// rate rather than busy-looping. Once nothing is animating it waits for events as usual.
+    fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        if !self.is_animating() {
+            event_loop.set_control_flow(ControlFlow::Wait);
+            return;
+        }
+        match self.frame_time {
+            None => {
+                self.window_request_redraw();
+                event_loop.set_control_flow(ControlFlow::Wait);
+            }
+            Some(frame_time) => {
+                let now = Instant::now();
+                let next_frame = self.last_frame + frame_time;
+                if now >= next_frame {
+                    self.window_request_redraw();
+                    self.last_frame = now;
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(now + frame_time));
+                } else {
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame));
+                }
             }
-            //}}}
-            //{{{ default
-            _ => {}
-            //}}}
         }
     }
     //}}}
@@ -434,8 +1028,15 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
                 //{{{ trace
                 info!("Received RPC shutdown event, exiting application");
                 //}}}
+                self.close_window();
                 event_loop.exit();
             }
+            TopoHedralEvent::OpenWindow => {
+                //{{{ trace
+                info!("Received open window event");
+                //}}}
+                self.launch_window(event_loop);
+            }
         }
     }
     //}}}
@@ -447,6 +1048,8 @@ impl ApplicationHandler<TopoHedralEvent> for TopoViewer<'static> {
 #[derive(Debug)]
 pub enum TopoHedralEvent {
     RcpShutdown,
+    /// Opens another window onto the state, sent by the `OpenWindow` RPC
+    OpenWindow,
 }
 //}}}
 //{{{ fun: locate_executable
@@ -478,8 +1081,48 @@ pub fn locate_executable() -> Result<PathBuf, std::io::Error> {
     }
 }
 //}}}
+//{{{ fun: rpc_schema
+/// Returns the protobuf schema of the RPC service of the 2D or 3D viewer.
+pub fn rpc_schema(mode: Mode) -> &'static str {
+    match mode {
+        Mode::D2 => d2::RPC_SCHEMA,
+        Mode::D3 => d3::RPC_SCHEMA,
+    }
+}
+//}}}
+//{{{ fun: validate_scene
+/// Loads and validates the scene file at `path`, see [`SceneFile::validate`], printing the number
+/// of shapes of a valid scene or every error of an invalid one. Returns whether it is valid.
+pub fn validate_scene(path: &Path, mode: Mode, budget: SceneBudget) -> bool {
+    let errors = match SceneFile::load(path) {
+        Ok(scene) => match scene.validate(mode, budget) {
+            Ok(num_shapes) => {
+                println!("{}: {} valid shapes", path.display(), num_shapes);
+                return true;
+            }
+            Err(errors) => errors,
+        },
+        Err(e) => vec![e],
+    };
+    for e in errors.iter() {
+        eprintln!("{}: {}", path.display(), e);
+    }
+    false
+}
+//}}}
 //{{{ fun: run_topoviewer
 pub fn run_topoviewer(topoviewer_options: &TopoViewerOptions) {
+    if let RPCOption::Schema = topoviewer_options.with_rpc {
+        print!("{}", rpc_schema(topoviewer_options.mode));
+        return;
+    }
+    if let Some(scene) = topoviewer_options.validate.as_ref() {
+        let budget = topoviewer_options.scene_budget();
+        if !validate_scene(scene, topoviewer_options.mode, budget) {
+            std::process::exit(1);
+        }
+        return;
+    }
     //{{{ trace
     info!("Initializing winit event loop");
     //}}}
@@ -491,6 +1134,22 @@ pub fn run_topoviewer(topoviewer_options: &TopoViewerOptions) {
     let event_loop_proxy = event_loop.create_proxy();
     event_loop.set_control_flow(ControlFlow::Wait);
     let mut app = TopoViewer::new(event_loop_proxy, topoviewer_options);
+    if let Some(scene) = topoviewer_options.scene.as_ref() {
+        match app.load_scene(scene, topoviewer_options.up_axis) {
+            Ok(num_shapes) => {
+                //{{{ trace
+                info!("Added {} shapes from {}", num_shapes, scene.display());
+                //}}}
+            }
+            Err(e) => {
+                //{{{ trace
+                error!("Failed to load scene {}: {}", scene.display(), e);
+                //}}}
+                eprintln!("{}: {}", scene.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
     event_loop.run_app(&mut app).unwrap();
 }
 //}}}
@@ -501,6 +1160,275 @@ pub fn run_topoviewer(topoviewer_options: &TopoViewerOptions) {
 mod tests
 {
     use super::*;
+    use crate::common::TessellationQuality;
+
+    #[test]
+    fn test_scene_file() {
+        let json = r#"[
+            {"type": "line", "v1": [0.0, 0.0], "v2": [1.0, 0.0], "color": "Red"},
+            {"type": "circle", "center": [0.0, 0.0], "radius": 0.5, "num_sides": 16,
+             "line_color": "Black", "tri_color": "Blue", "cell_type": "Triangle"}
+        ]"#;
+        let scene = SceneFile::from_json(json).unwrap();
+        let mut state = d2::State::new();
+        let uids = scene.apply_2d(&mut state).unwrap();
+        assert_eq!(uids.len(), 2);
+        assert!(state.get_mesh(uids[1]).unwrap().is_triangle());
+
+        // a 2D scene is not a valid 3D scene
+        let mut state_3d = d3::State::new();
+        let res = scene.apply_3d(&mut state_3d, UpAxis::Z);
+        assert!(matches!(res, Err(SceneError::Entry { index: 0, .. })));
+
+        // the failing entry is reported, and nothing is added
+        let json = r#"[
+            {"type": "line", "v1": [0.0, 0.0], "v2": [1.0, 0.0], "color": "Red"},
+            {"type": "line", "v1": [0.5, 0.5], "v2": [0.5, 0.5], "color": "Red"}
+        ]"#;
+        let mut state = d2::State::new();
+        match SceneFile::from_json(json).unwrap().apply_2d(&mut state) {
+            Err(SceneError::Entry { index, message }) => {
+                assert_eq!(index, 1);
+                assert!(message.contains("coincide"));
+            }
+            _ => panic!("expected an invalid entry"),
+        }
+        assert!(state.get_mesh(5).is_none());
+
+        // a scene which does not fit within the budget is not added in part
+        let mut state = d2::State::new();
+        state.set_scene_budget(SceneBudget {
+            max_meshes: Some(1),
+            max_vertices: None,
+        });
+        let res = scene.apply_2d(&mut state);
+        assert!(matches!(res, Err(SceneError::Budget(BudgetError::Meshes { max: 1, .. }))));
+        assert_eq!(state.scene_stats().num_meshes, 0);
+
+        // subdivision counts left at zero come from the tessellation quality of the state
+        let json = r#"[
+            {"type": "circle", "center": [0.0, 0.0], "radius": 0.5, "num_sides": 0,
+             "line_color": "Black", "tri_color": "Blue", "cell_type": "Line"}
+        ]"#;
+        let scene = SceneFile::from_json(json).unwrap();
+        let num_vertices = |quality: TessellationQuality| {
+            let mut state = d2::State::new();
+            state.set_tessellation_quality(quality).unwrap();
+            scene.apply_2d(&mut state).unwrap();
+            state.scene_stats().num_vertices
+        };
+        assert!(num_vertices(TessellationQuality::Low) < num_vertices(TessellationQuality::High));
+
+        // a y-up scene is stood upright in the z-up viewer, its height along the z-axis
+        let json = r#"[
+            {"type": "cuboid", "origin": [0, 0, 0], "x_axis": [1, 0, 0], "y_axis": [0, 1, 0],
+             "z_axis": [0, 0, 1], "lenx": 1.0, "leny": 2.0, "lenz": 3.0, "line_color": "Black",
+             "tri_color": "Blue", "cell_type": "Triangle"}
+        ]"#;
+        let scene = SceneFile::from_json(json).unwrap();
+        let mut state = d3::State::new();
+        let uids = scene.apply_3d(&mut state, UpAxis::Y).unwrap();
+        let mesh = state.get_mesh(uids[0]).unwrap();
+        let model = mesh.model();
+        let corners: Vec<Vec3> = mesh
+            .positions()
+            .iter()
+            .map(|p| (model * p.push(1.0)).xyz())
+            .collect();
+        let min = corners.iter().fold(Vec3::repeat(f32::MAX), |m, p| m.inf(p));
+        let max = corners.iter().fold(Vec3::repeat(f32::MIN), |m, p| m.sup(p));
+        assert!((min - Vec3::new(0.0, -3.0, 0.0)).norm() < 1e-6);
+        assert!((max - Vec3::new(1.0, 0.0, 2.0)).norm() < 1e-6);
+        let uids = scene.apply_3d(&mut state, UpAxis::Z).unwrap();
+        assert_eq!(state.get_mesh(uids[0]).unwrap().model(), Mat4::identity());
+
+        // syntax errors carry their position in the file
+        let err = SceneFile::from_json("[\n{\"type\": }\n]").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_rpc_schema() {
+        let options = TopoViewerOptions::try_parse_from(["viewer", "d3", "schema"]).unwrap();
+        assert!(matches!(options.with_rpc, RPCOption::Schema));
+
+        // each mode has its own package, both named after the generated modules
+        assert!(rpc_schema(Mode::D2).contains("package d2rpc;"));
+        assert!(rpc_schema(Mode::D3).contains("package d3rpc;"));
+        assert!(rpc_schema(Mode::D3).contains("service StateService"));
+    }
+
+    #[test]
+    fn test_validate_scene() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args).unwrap();
+        assert!(parse(&["viewer", "d2", "none"]).validate.is_none());
+        let options = parse(&["viewer", "d2", "--validate", "scene.json", "none"]);
+        assert_eq!(options.validate.as_deref(), Some(Path::new("scene.json")));
+        assert!(options.to_string().contains("Validate: scene.json"));
+
+        let json = r#"[
+            {"type": "line", "v1": [0.0, 0.0], "v2": [1.0, 0.0], "color": "Red"},
+            {"type": "circle", "center": [0.0, 0.0], "radius": 0.5, "num_sides": 16,
+             "line_color": "Black", "tri_color": "Blue", "cell_type": "Triangle"}
+        ]"#;
+        let scene = SceneFile::from_json(json).unwrap();
+        assert!(matches!(scene.validate(Mode::D2, SceneBudget::default()), Ok(2)));
+
+        // a 2D scene is not a valid 3D scene
+        let errors = scene.validate(Mode::D3, SceneBudget::default()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        // every bad entry is reported, not just the first
+        let json = r#"[
+            {"type": "line", "v1": [0.5, 0.5], "v2": [0.5, 0.5], "color": "Red"},
+            {"type": "line", "v1": [0.0, 0.0], "v2": [1.0, 0.0], "color": "Red"},
+            {"type": "square"}
+        ]"#;
+        let errors = SceneFile::from_json(json)
+            .unwrap()
+            .validate(Mode::D2, SceneBudget::default())
+            .unwrap_err();
+        let indices: Vec<usize> = errors
+            .iter()
+            .map(|e| match e {
+                SceneError::Entry { index, .. } => *index,
+                _ => panic!("expected an invalid entry"),
+            })
+            .collect();
+        assert_eq!(indices, vec![0, 2]);
+
+        // so is a scene which does not fit within the budget
+        let budget = SceneBudget {
+            max_meshes: Some(1),
+            max_vertices: None,
+        };
+        let errors = scene.validate(Mode::D2, budget).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [SceneError::Budget(BudgetError::Meshes { used: 0, added: 2, max: 1 })]
+        ));
+    }
+
+    #[test]
+    fn test_up_axis_option() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args);
+        assert_eq!(parse(&["viewer", "d3", "none"]).unwrap().up_axis, UpAxis::Z);
+        let options = parse(&["viewer", "d3", "--up-axis", "y", "none"]).unwrap();
+        assert_eq!(options.up_axis, UpAxis::Y);
+        assert!(options.to_string().contains("Up axis: Y"));
+        assert!(parse(&["viewer", "d3", "--up-axis", "x", "none"]).is_err());
+
+        // y-up shapes are turned so that their y-axis points up the z-axis, keeping handedness
+        let rotation = UpAxis::Y.to_z_up();
+        assert!((rotation.transform_vector(&Vec3::y()) - Vec3::z()).norm() < 1e-6);
+        assert!((rotation.transform_vector(&Vec3::z()) + Vec3::y()).norm() < 1e-6);
+        assert_eq!(UpAxis::Z.to_z_up(), Mat4::identity());
+    }
+
+    #[test]
+    fn test_max_fps_option() {
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--max-fps", "60", "none"]).unwrap();
+        assert_eq!(options.max_fps, Some(60));
+        let options = TopoViewerOptions::try_parse_from(["viewer", "d3", "none"]).unwrap();
+        assert_eq!(options.max_fps, None);
+        assert!(TopoViewerOptions::try_parse_from(["viewer", "d3", "--max-fps", "0", "none"]).is_err());
+    }
+
+    #[test]
+    fn test_depth_format_option() {
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--depth-format", "depth16", "none"])
+                .unwrap();
+        assert_eq!(options.depth_format, DepthFormat::Depth16);
+        assert!(!options.reversed_z);
+        let options = TopoViewerOptions::try_parse_from(["viewer", "d3", "none"]).unwrap();
+        assert_eq!(options.depth_format, DepthFormat::Depth32);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--reversed-z", "none"]).unwrap();
+        assert!(options.reversed_z);
+        assert!(!options.axes_gizmo);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--axes-gizmo", "none"]).unwrap();
+        assert!(options.axes_gizmo);
+        assert_eq!(options.octant, 0);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--octant", "5", "none"]).unwrap();
+        assert_eq!(options.octant, 5);
+        assert!(TopoViewerOptions::try_parse_from(["viewer", "d3", "--octant", "8", "none"]).is_err());
+        assert!(!options.stretch);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d2", "--stretch", "none"]).unwrap();
+        assert!(options.stretch);
+        assert_eq!(options.edge_width, 1.0);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--edge-width", "2.5", "none"])
+                .unwrap();
+        assert_eq!(options.edge_width, 2.5);
+        assert!(TopoViewerOptions::try_parse_from(["viewer", "d3", "--edge-width", "0", "none"]).is_err());
+        assert_eq!(options.units_per_meter, 1.0);
+        let options =
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--units-per-meter", "1000", "none"])
+                .unwrap();
+        assert_eq!(options.units_per_meter, 1000.0);
+        assert!(
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--units-per-meter", "0", "none"])
+                .is_err()
+        );
+        assert!(
+            TopoViewerOptions::try_parse_from(["viewer", "d3", "--depth-format", "depth8", "none"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_wireframe_fallback_option() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args).unwrap();
+        assert!(!parse(&["viewer", "d3", "none"]).wireframe_fallback);
+        let options = parse(&["viewer", "d3", "--wireframe-fallback", "with-port", "50051"]);
+        assert!(options.wireframe_fallback);
+        assert!(options.to_string().contains("Wireframe fallback"));
+        assert!(parse(&["viewer", "d2", "--wireframe-fallback", "none"]).wireframe_fallback);
+    }
+
+    #[test]
+    fn test_coordinate_readout_option() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args).unwrap();
+        assert!(!parse(&["viewer", "d2", "none"]).coordinate_readout);
+        let options = parse(&["viewer", "d2", "--coordinate-readout", "none"]);
+        assert!(options.coordinate_readout);
+        assert!(options.to_string().contains("Coordinate readout"));
+    }
+
+    #[test]
+    fn test_exit_on_idle_option() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args);
+        let options = parse(&["viewer", "d3", "with-port", "50051"]).unwrap();
+        assert!(!options.exit_on_idle);
+        let options = parse(&["viewer", "d3", "--exit-on-idle", "with-port", "50051"]).unwrap();
+        assert!(options.exit_on_idle);
+        assert_eq!(options.idle_timeout, 10);
+        let options = parse(&["viewer", "d2", "--exit-on-idle", "--idle-timeout", "3", "none"]);
+        assert_eq!(options.unwrap().idle_timeout, 3);
+        assert!(parse(&["viewer", "d2", "--idle-timeout", "0", "none"]).is_err());
+    }
+
+    #[test]
+    fn test_log_filter_option() {
+        let parse = |args: &[&str]| TopoViewerOptions::try_parse_from(args);
+        assert_eq!(parse(&["viewer", "d3", "none"]).unwrap().log_filter, None);
+        let options = parse(&["viewer", "d3", "--log-level", "warn", "none"]).unwrap();
+        assert_eq!(options.log_filter.as_deref(), Some("warn"));
+        let filter = "info,topohedral_viewer::core::state=Debug";
+        let options = parse(&["viewer", "d2", "--log-filter", filter, "none"]).unwrap();
+        assert_eq!(options.log_filter.as_deref(), Some(filter));
+        assert!(options.log_env_filter().unwrap().to_string().contains("core::state=debug"));
+        assert!(parse(&["viewer", "d3", "none"]).unwrap().log_env_filter().is_none());
+
+        assert!(parse(&["viewer", "d2", "--log-filter", "loud", "none"]).is_err());
+        assert!(parse(&["viewer", "d2", "--log-filter", "info,state=", "none"]).is_err());
+        assert!(parse(&["viewer", "d2", "--log-filter", "=info", "none"]).is_err());
+    }
 
     #[test]
     fn test_locate_executable() {
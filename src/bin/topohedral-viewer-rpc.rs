@@ -10,15 +10,16 @@ use topohedral_viewer::app::{run_topoviewer, TopoViewerOptions};
 //}}}
 //{{{ dep imports 
 use clap::Parser;
-use topohedral_tracing::{topo_log, init, info};
+use topohedral_tracing::{topo_log, info};
 //}}}
 //--------------------------------------------------------------------------------------------------
 
 
 fn main()
 {
-    init().unwrap();
+    // the options are parsed first, so that their log filter applies from the very start
     let opts = TopoViewerOptions::parse();
+    opts.init_tracing().unwrap();
     //{{{ trace
     info!("Starting TopoViewer with options {}", opts);
     //}}}
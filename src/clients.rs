@@ -0,0 +1,160 @@
+//! This module keeps track of the clients of the RPC servers.
+//!
+//! A client registers by name on its first request, and stays connected until it disconnects.
+//! With `--exit-on-idle` set, the server shuts itself down once every client has disconnected
+//! and none has made a request for the idle timeout.
+//--------------------------------------------------------------------------------------------------
+
+//{{{ crate imports
+//}}}
+//{{{ std imports
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+//}}}
+//{{{ dep imports
+use tokio::sync::mpsc;
+use topohedral_tracing::*;
+//}}}
+//--------------------------------------------------------------------------------------------------
+
+//{{{ collection: constants
+/// The longest time between two checks of whether the server has gone idle.
+const IDLE_CHECK_PERIOD: Duration = Duration::from_secs(1);
+//}}}
+//{{{ collection: ClientTracker
+//{{{ struct: ClientTracker
+/// Records the clients connected to a server, by name, along with when each was last heard from.
+#[derive(Debug, Default)]
+pub struct ClientTracker {
+    clients: HashMap<String, Instant>,
+    /// The time of the last request or disconnection of any client, `None` until the first
+    /// client connects.
+    last_activity: Option<Instant>,
+}
+//}}}
+//{{{ impl: ClientTracker
+impl ClientTracker {
+    //{{{ fun: new
+    pub fn new() -> Self {
+        Self::default()
+    }
+    //}}}
+    //{{{ fun: new_arc_mutex
+    pub fn new_arc_mutex() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::new()))
+    }
+    //}}}
+    //{{{ fun: touch
+    /// Registers the client on its first request, and records that it is still active on later
+    /// ones.
+    pub fn touch(&mut self, client_name: &str, now: Instant) {
+        if self.clients.insert(client_name.to_string(), now).is_none() {
+            //{{{ trace
+            info!("Client {} connected", client_name);
+            //}}}
+        }
+        self.last_activity = Some(now);
+    }
+    //}}}
+    //{{{ fun: disconnect
+    /// Forgets the client, returning whether it was connected.
+    pub fn disconnect(&mut self, client_name: &str, now: Instant) -> bool {
+        let was_connected = self.clients.remove(client_name).is_some();
+        if was_connected {
+            //{{{ trace
+            info!("Client {} disconnected, {} left", client_name, self.clients.len());
+            //}}}
+            self.last_activity = Some(now);
+        }
+        was_connected
+    }
+    //}}}
+    //{{{ fun: num_clients
+    /// Returns the number of clients which have connected and not disconnected since.
+    pub fn num_clients(&self) -> usize {
+        self.clients.len()
+    }
+    //}}}
+    //{{{ fun: is_idle
+    /// Returns whether the server has been idle for at least `timeout`, that is whether no
+    /// client is connected and none has made a request or disconnected since.
+    ///
+    /// A connected client holds the server up however long it stays silent. A server no client
+    /// has connected to yet is never idle.
+    pub fn is_idle(&self, now: Instant, timeout: Duration) -> bool {
+        self.num_clients() == 0
+            && self.last_activity.is_some_and(|last_activity| {
+                now.saturating_duration_since(last_activity) >= timeout
+            })
+    }
+    //}}}
+}
+//..................................................................................................
+//}}}
+//}}}
+//{{{ fun: shutdown_when_idle
+/// Waits until the server has been idle for `timeout`, see [`ClientTracker::is_idle`], then sends
+/// the shutdown signal.
+pub async fn shutdown_when_idle(
+    clients: Arc<Mutex<ClientTracker>>,
+    timeout: Duration,
+    shutdown_sender: mpsc::Sender<()>,
+) {
+    let mut interval = tokio::time::interval(timeout.min(IDLE_CHECK_PERIOD));
+    loop {
+        interval.tick().await;
+        if clients.lock().unwrap().is_idle(Instant::now(), timeout) {
+            break;
+        }
+    }
+    //{{{ trace
+    info!("No client connected for {:?}, sending shutdown signal", timeout);
+    //}}}
+    if shutdown_sender.send(()).await.is_err() {
+        //{{{ trace
+        info!("Server already shut down");
+        //}}}
+    }
+}
+//}}}
+
+//-------------------------------------------------------------------------------------------------
+//{{{ mod: tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_idle_test() {
+        let timeout = Duration::from_secs(5);
+        let start = Instant::now();
+        let after = |secs: u64| start + Duration::from_secs(secs);
+        let mut clients = ClientTracker::new();
+
+        // the server waits for a first client however long it takes
+        assert!(!clients.is_idle(after(60), timeout));
+
+        clients.touch("a", start);
+        clients.touch("b", after(2));
+        assert_eq!(clients.num_clients(), 2);
+
+        // a silent client still holds the server up
+        assert!(!clients.is_idle(after(60), timeout));
+
+        // the timeout runs from the last client disconnecting
+        assert!(clients.disconnect("a", after(61)));
+        assert!(!clients.disconnect("a", after(62)));
+        assert_eq!(clients.num_clients(), 1);
+        assert!(!clients.is_idle(after(120), timeout));
+        assert!(clients.disconnect("b", after(121)));
+        assert_eq!(clients.num_clients(), 0);
+        assert!(!clients.is_idle(after(125), timeout));
+        assert!(clients.is_idle(after(126), timeout));
+
+        // a client connecting again restarts the timeout
+        clients.touch("a", after(126));
+        assert!(!clients.is_idle(after(200), timeout));
+    }
+}
+//}}}
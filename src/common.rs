@@ -16,6 +16,7 @@ use std::f32::consts::PI;
 use na::Const;
 use nalgebra as na;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
@@ -272,9 +273,106 @@ pub fn orthogonal_vector(vec: &Vec3) -> Vec3 {
     ortho_vector
 }
 //}}}
+//{{{ collection: polygons
+//{{{ fun: segments_intersect
+/// Returns whether the segments from `a` to `b` and from `c` to `d` cross or touch.
+fn segments_intersect(a: &Vec2, b: &Vec2, c: &Vec2, d: &Vec2) -> bool {
+    let orient = |p: &Vec2, q: &Vec2, r: &Vec2| (q - p).perp(&(r - p));
+    // whether r, in line with p and q, lies between them
+    let within = |p: &Vec2, q: &Vec2, r: &Vec2| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+    let (d1, d2) = (orient(c, d, a), orient(c, d, b));
+    let (d3, d4) = (orient(a, b, c), orient(a, b, d));
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    (d1 == 0.0 && within(c, d, a))
+        || (d2 == 0.0 && within(c, d, b))
+        || (d3 == 0.0 && within(a, b, c))
+        || (d4 == 0.0 && within(a, b, d))
+}
+//}}}
+//{{{ fun: is_simple_polygon
+/// Returns whether the closed polygon through `points` is simple, that is whether its sides
+/// only meet at the corners they share with the sides either side of them.
+pub(crate) fn is_simple_polygon(points: &[Vec2]) -> bool {
+    let n = points.len();
+    for i in 0..n {
+        let (a, b, c) = (&points[i], &points[(i + 1) % n], &points[(i + 2) % n]);
+        // a side doubling back along the one before it overlaps it
+        if (b - a).perp(&(c - b)) == 0.0 && (b - a).dot(&(c - b)) < 0.0 {
+            return false;
+        }
+        for j in (i + 2)..n {
+            // the last side shares a corner with the first
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if segments_intersect(a, b, &points[j], &points[(j + 1) % n]) {
+                return false;
+            }
+        }
+    }
+    true
+}
+//}}}
+//{{{ fun: ear_clip
+/// Triangulates the simple polygon through `points`, wound counterclockwise, by cutting off one
+/// ear after another, an ear being a convex corner whose triangle holds no other corner. The
+/// triangles index into `points` and are wound counterclockwise too.
+pub(crate) fn ear_clip(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let cross =
+        |a: usize, b: usize, c: usize| (points[b] - points[a]).perp(&(points[c] - points[a]));
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let corner = |i: usize| (remaining[(i + m - 1) % m], remaining[i], remaining[(i + 1) % m]);
+        let ear = (0..m).find(|&i| {
+            let (a, b, c) = corner(i);
+            cross(a, b, c) > 0.0
+                && remaining.iter().all(|&p| {
+                    p == a
+                        || p == b
+                        || p == c
+                        || cross(a, b, p) < 0.0
+                        || cross(b, c, p) < 0.0
+                        || cross(c, a, p) < 0.0
+                })
+        });
+        match ear {
+            Some(i) => {
+                let (a, b, c) = corner(i);
+                triangles.push([a, b, c]);
+                remaining.remove(i);
+            }
+            None => {
+                // only corners in line with their neighbours are left, which hold no area
+                let flattest = (0..m)
+                    .min_by(|&i, &j| {
+                        let (a, b, c) = corner(i);
+                        let (d, e, f) = corner(j);
+                        cross(a, b, c).abs().total_cmp(&cross(d, e, f).abs())
+                    })
+                    .unwrap();
+                remaining.remove(flattest);
+            }
+        }
+    }
+    if let [a, b, c] = remaining[..] {
+        if cross(a, b, c) > 0.0 {
+            triangles.push([a, b, c]);
+        }
+    }
+    triangles
+}
+//}}}
+//..................................................................................................
+//}}}
 //{{{ collection: Color
 //{{{ struct: Color
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Color {
     Red,
     Green,
@@ -295,6 +393,8 @@ pub enum Color {
     Gray,
     White,
     Other((f32, f32, f32)),
+    /// A color with an alpha channel, all other colors are opaque.
+    Rgba((f32, f32, f32, f32)),
 }
 //}}}
 //{{{ impl: Color
@@ -320,6 +420,18 @@ impl Color {
             Color::Gray => [0.5, 0.5, 0.5],
             Color::White => [1.0, 1.0, 1.0],
             Color::Other(rgb) => [rgb.0, rgb.1, rgb.2],
+            Color::Rgba(rgba) => [rgba.0, rgba.1, rgba.2],
+        }
+    }
+
+    /// Returns the color with its alpha channel, which is 1 for every color but `Rgba`.
+    pub fn to_rgba(&self) -> [f32; 4] {
+        match *self {
+            Color::Rgba(rgba) => [rgba.0, rgba.1, rgba.2, rgba.3],
+            _ => {
+                let [r, g, b] = self.to_rgb();
+                [r, g, b, 1.0]
+            }
         }
     }
 }
@@ -334,8 +446,12 @@ impl Default for Color {
 //}}}
 //{{{ collection: CellType
 //{{{ struct: CellType
+/// The kind of cells a mesh is made of.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
 pub enum CellType {
+    /// No cells at all, the default. The shape constructors give an empty mesh for it, which
+    /// draws nothing, rather than failing, so a descriptor whose cell type was left unset never
+    /// panics.
     None = 0,
     Line = 1,
     Triangle = 2,
@@ -379,9 +495,496 @@ impl From<CellType> for i32
 }
 //}}}
 //}}}
+//{{{ collection: DrawContext
+//{{{ struct: DrawContext
+/// The colors and cell type the simplified `add_*_simple` methods of a state or client draw shapes
+/// with, so that a session drawing everything the same way need not repeat them in every
+/// descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DrawContext {
+    pub default_line_color: Color,
+    pub default_tri_color: Color,
+    pub default_cell_type: CellType,
+}
+//}}}
+//{{{ impl: Default for DrawContext
+impl Default for DrawContext {
+    fn default() -> Self {
+        Self {
+            default_line_color: Color::Black,
+            default_tri_color: Color::default(),
+            default_cell_type: CellType::Triangle,
+        }
+    }
+}
+//..................................................................................................
+//}}}
+//}}}
+//{{{ struct: MeshOptions
+/// Options controlling how a single mesh is drawn, set on a state with `set_mesh_options`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MeshOptions {
+    /// The color the edges drawn over the triangle cells of the mesh take, chosen independently
+    /// of the line color of its vertices, for instance black edges on a solid whose lines are
+    /// white. When `None` the edges take the line color of their vertices, or the uniform color
+    /// of the mesh.
+    #[serde(default)]
+    pub edge_color: Option<Color>,
+    /// The lengths of the dashes, and of the gaps between them, the line cells of the mesh are
+    /// drawn with, in world units. The lines are split into dashes when the mesh is uploaded to
+    /// the GPU. When `None`, or unless both lengths are positive, the lines are solid.
+    #[serde(default)]
+    pub dash_pattern: Option<(f32, f32)>,
+}
+//}}}
+//{{{ enum: MeshColorMode
+/// Where the colors a mesh is drawn with come from.
+///
+/// By default each vertex carries its own line and triangle colors, which gradients and colormaps
+/// need. A mesh of a single color can instead be drawn with a uniform color, in which case only
+/// the positions, normals and screen offsets of its vertices are uploaded to the GPU and the
+/// color is passed in a small uniform, cutting the size of its vertex buffer by more than half.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum MeshColorMode {
+    /// The line and triangle colors of each vertex are used.
+    #[default]
+    PerVertex,
+    /// Every line and triangle of the mesh is drawn with the given color, whatever the colors of
+    /// its vertices.
+    Uniform(Color),
+}
+//}}}
+//{{{ collection: ViewSensitivity
+//{{{ struct: ViewSensitivity
+/// How far the camera moves in response to a single arrow key press or mouse wheel step.
+///
+/// Different input devices need very different speeds, a trackpad for instance sends many more
+/// wheel events than a mouse. In 3D views the rotate delta is used both for orbiting and for
+/// rolling the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ViewSensitivity {
+    /// The distance the camera pans for each arrow key press.
+    pub key_pan_delta: f32,
+    /// The angle, in radians, the camera rotates for each arrow key press.
+    pub key_rotate_delta: f32,
+    /// The sensitivity of the mouse wheel when zooming. In 3D views it is the fraction of the
+    /// distance of the camera from its focus moved per unit of wheel delta.
+    pub zoom_speed: f32,
+}
+//}}}
+//{{{ impl: ViewSensitivity
+impl ViewSensitivity {
+    /// Checks that every value is finite and strictly positive, returning the name of the first
+    /// field which is not.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let fields = [
+            ("key_pan_delta", self.key_pan_delta),
+            ("key_rotate_delta", self.key_rotate_delta),
+            ("zoom_speed", self.zoom_speed),
+        ];
+        match fields
+            .iter()
+            .find(|(_, value)| !value.is_finite() || *value <= 0.0)
+        {
+            Some((name, _)) => Err(name),
+            None => Ok(()),
+        }
+    }
+}
+//}}}
+//}}}
+//{{{ collection: DepthBuffer
+//{{{ struct: DepthBuffer
+/// The contents of a window's depth buffer as left by its last render, used to debug occlusion
+/// and clipping.
+///
+/// The depths are stored row by row from the top-left corner of the window. In 3D views they
+/// are linearized to the distance in front of the camera, so pixels no mesh covers hold the
+/// distance to the far plane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthBuffer {
+    pub width: u32,
+    pub height: u32,
+    pub depths: Vec<f32>,
+}
+//}}}
+//{{{ impl: DepthBuffer
+impl DepthBuffer {
+    /// Returns the depth at the given pixel, measured from the top-left corner of the window,
+    /// or `None` if the pixel lies outside it.
+    pub fn depth_at(&self, x: u32, y: u32) -> Option<f32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.depths.get((y * self.width + x) as usize).copied()
+    }
+}
+//}}}
+//}}}
+//{{{ collection: ClipMode
+//{{{ constant: MAX_CLIP_PLANES
+/// The most clipping planes a 3D view cuts through at once, enough for a box.
+pub const MAX_CLIP_PLANES: usize = 6;
+//}}}
+//{{{ enum: ClipMode
+/// How the clipping planes of a 3D view combine. A fragment is inside a plane when it lies on
+/// the side opposite its normal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipMode {
+    /// Fragments are kept only if they are inside every plane, which keeps a convex region such
+    /// as a box.
+    #[default]
+    InsideAll,
+    /// Fragments are kept if they are inside any of the planes, which cuts a wedge or corner out
+    /// of the scene.
+    InsideAny,
+}
+//}}}
+//{{{ enum: FaceCulling
+/// Which side of the triangle cells is left undrawn. The front of a triangle is the side its
+/// corners run counterclockwise when seen from, unless its mesh is declared wound clockwise, see
+/// [`FrontFace`], so culling shows up triangles wound the wrong way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaceCulling {
+    /// Both sides are drawn.
+    #[default]
+    None,
+    /// Only the front of each triangle is drawn.
+    Back,
+    /// Only the back of each triangle is drawn.
+    Front,
+}
+//}}}
+//{{{ impl: FaceCulling
+impl FaceCulling {
+    /// Returns the mode after this one, cycling from `None` through `Back` and `Front`.
+    pub fn next(self) -> Self {
+        match self {
+            FaceCulling::None => FaceCulling::Back,
+            FaceCulling::Back => FaceCulling::Front,
+            FaceCulling::Front => FaceCulling::None,
+        }
+    }
+}
+//}}}
+//{{{ enum: FrontFace
+/// Which way the corners of the triangles of a mesh run when seen from their front, as meshes
+/// made by other tools do not all wind their triangles the same way. It only matters when faces
+/// are culled, see [`FaceCulling`], since the shading follows the normals rather than the winding.
+///
+/// The triangles are taken as they are: declaring the winding of a mesh does not reorder the
+/// corners of its triangles, so the triangles of a mesh must all be wound the same way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrontFace {
+    /// The corners of the triangles run counterclockwise seen from their front.
+    #[default]
+    Ccw,
+    /// The corners of the triangles run clockwise seen from their front.
+    Cw,
+}
+//}}}
+//{{{ impl: FrontFace
+impl FrontFace {
+    /// Returns the culling which, applied to triangles wound counterclockwise, leaves undrawn the
+    /// side `culling` names of triangles with this front face.
+    pub fn ccw_culling(self, culling: FaceCulling) -> FaceCulling {
+        match (self, culling) {
+            (FrontFace::Cw, FaceCulling::Back) => FaceCulling::Front,
+            (FrontFace::Cw, FaceCulling::Front) => FaceCulling::Back,
+            (_, culling) => culling,
+        }
+    }
+}
+//}}}
+//{{{ constant: MIN_ADAPTIVE_SIDES
+/// The fewest sides an adaptive tessellation gives a curve, however small.
+const MIN_ADAPTIVE_SIDES: usize = 3;
+//}}}
+//{{{ constant: MAX_ADAPTIVE_SIDES
+/// The most sides an adaptive tessellation gives a curve, however large.
+const MAX_ADAPTIVE_SIDES: usize = 1024;
+//}}}
+//{{{ enum: TessellationQuality
+/// How finely curved shapes are divided when their descriptor leaves a subdivision count at zero.
+/// Counts given explicitly are always kept. The fixed levels map to the counts:
+///
+/// | Level    | Sides of circles, discs and cylinders | Latitudes | Longitudes |
+/// |----------|---------------------------------------|-----------|------------|
+/// | `Low`    | 12                                    | 8         | 12         |
+/// | `Medium` | 32                                    | 16        | 32         |
+/// | `High`   | 64                                    | 32        | 64         |
+///
+/// `Adaptive` instead gives each shape the fewest sides for which no chord strays further than
+/// `chord_error` from the curve, that is `ceil(π / acos(1 - chord_error / radius))` sides, half
+/// as many latitudes, and as many longitudes. The error is in the units of the radius, so small
+/// shapes get few sides and large ones many, between 3 and 1024.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TessellationQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Adaptive { chord_error: f32 },
+}
+//}}}
+//{{{ impl: TessellationQuality
+impl TessellationQuality {
+    /// Returns `num_sides`, or the number of sides of the level for a circle of the given radius
+    /// if it is zero.
+    pub fn sides(&self, num_sides: usize, radius: f32) -> usize {
+        match (num_sides, self) {
+            (0, TessellationQuality::Low) => 12,
+            (0, TessellationQuality::Medium) => 32,
+            (0, TessellationQuality::High) => 64,
+            (0, TessellationQuality::Adaptive { chord_error }) => {
+                adaptive_sides(radius, *chord_error)
+            }
+            (n, _) => n,
+        }
+    }
+
+    /// Returns `n_lat`, or the number of latitudes of the level for a sphere of the given radius
+    /// if it is zero.
+    pub fn latitudes(&self, n_lat: usize, radius: f32) -> usize {
+        match (n_lat, self) {
+            (0, TessellationQuality::Low) => 8,
+            (0, TessellationQuality::Medium) => 16,
+            (0, TessellationQuality::High) => 32,
+            // from pole to pole is half way around
+            (0, TessellationQuality::Adaptive { .. }) => self.sides(0, radius).div_ceil(2).max(2),
+            (n, _) => n,
+        }
+    }
+
+    /// Returns `n_long`, or the number of longitudes of the level for a sphere of the given
+    /// radius if it is zero.
+    pub fn longitudes(&self, n_long: usize, radius: f32) -> usize {
+        self.sides(n_long, radius)
+    }
+
+    /// Returns whether the chord error of an adaptive quality is finite and positive.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            TessellationQuality::Adaptive { chord_error } => {
+                chord_error.is_finite() && *chord_error > 0.0
+            }
+            _ => true,
+        }
+    }
+}
+//}}}
+//{{{ fun: adaptive_sides
+/// Returns the number of sides of the polygon approximating a circle of the given radius whose
+/// sides lie at most `chord_error` from it.
+fn adaptive_sides(radius: f32, chord_error: f32) -> usize {
+    // the middle of a side spanning an angle of 2π/n lies r(1 - cos(π/n)) inside the circle
+    let cos_half_angle = (1.0 - chord_error / radius).clamp(-1.0, 1.0);
+    let sides = (std::f32::consts::PI / cos_half_angle.acos()).ceil();
+    // an exact fit, a zero radius or a NaN all end up at one of the bounds
+    (sides.min(MAX_ADAPTIVE_SIDES as f32) as usize).max(MIN_ADAPTIVE_SIDES)
+}
+//}}}
+//{{{ impl: From<(i32, f32)> for TessellationQuality
+/// The RPC encoding, a level, in which `Medium` is zero so that it is the default, along with
+/// the chord error of the adaptive level.
+impl From<(i32, f32)> for TessellationQuality {
+    fn from((level, chord_error): (i32, f32)) -> Self {
+        match level {
+            1 => TessellationQuality::Low,
+            2 => TessellationQuality::High,
+            3 => TessellationQuality::Adaptive { chord_error: chord_error },
+            _ => TessellationQuality::Medium,
+        }
+    }
+}
+//}}}
+//{{{ impl: From<TessellationQuality> for (i32, f32)
+impl From<TessellationQuality> for (i32, f32) {
+    fn from(quality: TessellationQuality) -> Self {
+        match quality {
+            TessellationQuality::Medium => (0, 0.0),
+            TessellationQuality::Low => (1, 0.0),
+            TessellationQuality::High => (2, 0.0),
+            TessellationQuality::Adaptive { chord_error } => (3, chord_error),
+        }
+    }
+}
+//}}}
+//{{{ fun: clip_box_planes
+/// Returns the six planes, as points and outward normals, bounding the axis-aligned box from
+/// `min` to `max`. Combined with [`ClipMode::InsideAll`] they keep only what lies in the box.
+pub fn clip_box_planes(min: &Vec3, max: &Vec3) -> Vec<(Vec3, Vec3)> {
+    (0..3)
+        .flat_map(|axis| {
+            let normal = Vec3::ith(axis, 1.0);
+            [(*min, -normal), (*max, normal)]
+        })
+        .collect()
+}
+//}}}
+//}}}
+//{{{ collection: SceneBudget
+//{{{ struct: SceneBudget
+/// Limits on the geometry a state holds, so that a client adding unbounded geometry to a long
+/// running server is turned away before the GPU runs out of memory. `None` leaves a quantity
+/// unlimited, which is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SceneBudget {
+    pub max_meshes: Option<usize>,
+    pub max_vertices: Option<usize>,
+}
+//}}}
+//{{{ enum: BudgetError
+/// Raised when a state turns geometry away because adding it would exceed the state's
+/// [`SceneBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BudgetError {
+    #[error("adding {added} meshes to the {used} in the scene would exceed the limit of {max}")]
+    Meshes { used: usize, added: usize, max: usize },
+    #[error("adding {added} vertices to the {used} in the scene would exceed the limit of {max}")]
+    Vertices { used: usize, added: usize, max: usize },
+}
+//}}}
+//{{{ enum: AddMeshError
+/// Raised when a state turns a mesh away, either because it does not fit within the state's
+/// [`SceneBudget`] or because it is empty and the state rejects empty meshes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AddMeshError {
+    #[error(transparent)]
+    Budget(#[from] BudgetError),
+    #[error("the mesh has no cells to draw")]
+    Empty,
+}
+//}}}
+//{{{ struct: SceneStats
+/// The geometry currently held by a state, alongside the budget it must stay within.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SceneStats {
+    pub num_meshes: usize,
+    pub num_vertices: usize,
+    pub budget: SceneBudget,
+}
+//}}}
+//{{{ impl: SceneStats
+impl SceneStats {
+    /// Checks that `num_meshes` more meshes holding `num_vertices` vertices in all fit within the
+    /// budget.
+    pub fn check(&self, num_meshes: usize, num_vertices: usize) -> Result<(), BudgetError> {
+        if let Some(max) = self.budget.max_meshes {
+            if self.num_meshes + num_meshes > max {
+                return Err(BudgetError::Meshes {
+                    used: self.num_meshes,
+                    added: num_meshes,
+                    max: max,
+                });
+            }
+        }
+        if let Some(max) = self.budget.max_vertices {
+            if self.num_vertices + num_vertices > max {
+                return Err(BudgetError::Vertices {
+                    used: self.num_vertices,
+                    added: num_vertices,
+                    max: max,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+//}}}
+//}}}
+//{{{ struct: MeshDiagnostics
+/// A report on the geometry of a mesh, as checked before it is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MeshDiagnostics {
+    pub num_vertices: usize,
+    /// The number of triangle cells, zero for line meshes.
+    pub num_triangles: usize,
+    /// The minimum and maximum corners of the bounding box of the vertices with a finite
+    /// position, `None` if there are none. 2D meshes lie in the plane `z = 0`.
+    pub bounds: Option<(Vec3, Vec3)>,
+    /// Whether every edge of the triangle cells borders at most two triangles.
+    pub is_manifold: bool,
+    /// Whether a cell has coincident corners, a triangle no area, or an index past the last
+    /// vertex.
+    pub has_degenerate: bool,
+    /// The number of vertices with a NaN or infinite value in their data.
+    pub non_finite_count: usize,
+}
+//}}}
 //{{{ trait: Validated
 pub trait Validated {
+    /// Checks that the required fields are present and that lengths, radii and counts are in
+    /// range.
     fn is_valid(&self) -> bool;
+
+    /// Returns the name of the first field holding a NaN or infinite value, if there is one.
+    fn non_finite_field(&self) -> Option<&'static str>;
+
+    /// Describes the geometry of the message if it is degenerate, for instance a zero-length or
+    /// parallel axis, which would otherwise produce NaNs when the mesh is built.
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Runs all the checks, describing the first failure.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(field) = self.non_finite_field() {
+            Err(format!("non-finite value in {}", field))
+        } else if !self.is_valid() {
+            Err("missing or out of range field".to_string())
+        } else if let Some(reason) = self.degenerate_reason() {
+            Err(reason.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+//}}}
+//{{{ fun: first_degenerate
+/// Given a list of checks, each flagging whether some geometry is degenerate and describing how,
+/// returns the description of the first check which is flagged.
+pub fn first_degenerate(checks: &[(bool, &'static str)]) -> Option<&'static str> {
+    checks
+        .iter()
+        .find(|(is_degenerate, _)| *is_degenerate)
+        .map(|(_, reason)| *reason)
+}
+//}}}
+//{{{ fun: first_non_finite
+/// Given a list of field names paired with whether that field holds only finite values, returns
+/// the name of the first field which does not.
+pub fn first_non_finite(fields: &[(&'static str, bool)]) -> Option<&'static str> {
+    fields
+        .iter()
+        .find(|(_, is_finite)| !is_finite)
+        .map(|(name, _)| *name)
+}
+//}}}
+//{{{ fun: model_to_values
+/// Returns the entries of the model matrix `model` column by column, as RPC messages carry it,
+/// or none for the identity, which is what a message without them stands for.
+pub fn model_to_values(model: &Mat4) -> Vec<f32> {
+    if *model == Mat4::identity() {
+        Vec::new()
+    } else {
+        model.as_slice().to_vec()
+    }
+}
+//}}}
+//{{{ fun: model_from_values
+/// Returns the model matrix with the entries `values`, column by column as RPC messages carry
+/// it, the identity for no entries, or `None` for any other number of entries than 16.
+pub fn model_from_values(values: &[f32]) -> Option<Mat4> {
+    match values.len() {
+        0 => Some(Mat4::identity()),
+        16 => Some(Mat4::from_column_slice(values)),
+        _ => None,
+    }
 }
 //}}}
 
@@ -393,6 +996,69 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn to_rgba_test() {
+        assert_eq!(Color::Orange.to_rgba(), [1.0, 0.5, 0.0, 1.0]);
+        assert_eq!(Color::Other((0.1, 0.2, 0.3)).to_rgba(), [0.1, 0.2, 0.3, 1.0]);
+        let translucent = Color::Rgba((0.1, 0.2, 0.3, 0.4));
+        assert_eq!(translucent.to_rgba(), [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(translucent.to_rgb(), [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn tessellation_quality_test() {
+        let low = TessellationQuality::Low;
+        let high = TessellationQuality::High;
+        let adaptive = TessellationQuality::Adaptive { chord_error: 0.01 };
+        assert_eq!(TessellationQuality::default(), TessellationQuality::Medium);
+        assert_eq!(low.sides(0, 1.0), 12);
+        assert_eq!(high.latitudes(0, 1.0), 32);
+        assert_eq!(TessellationQuality::default().longitudes(0, 1.0), 32);
+        // explicit counts win over the level
+        assert_eq!(low.sides(5, 1.0), 5);
+        assert_eq!(high.latitudes(3, 1.0), 3);
+        assert_eq!(adaptive.sides(7, 1.0), 7);
+        for quality in [low, TessellationQuality::Medium, high, adaptive] {
+            assert_eq!(TessellationQuality::from(<(i32, f32)>::from(quality)), quality);
+        }
+        assert_eq!(TessellationQuality::from((0, 0.0)), TessellationQuality::Medium);
+        assert!(adaptive.is_valid());
+        assert!(!TessellationQuality::Adaptive { chord_error: 0.0 }.is_valid());
+    }
+
+    #[test]
+    fn adaptive_sides_test() {
+        let adaptive = TessellationQuality::Adaptive { chord_error: 0.01 };
+        // π / acos(0.99) = 22.2
+        assert_eq!(adaptive.sides(0, 1.0), 23);
+        assert_eq!(adaptive.latitudes(0, 1.0), 12);
+        // every side lies within the error of the circle, and one fewer would not
+        for radius in [0.1, 1.0, 10.0] {
+            let n = adaptive.sides(0, radius);
+            let error = |n: usize| radius * (1.0 - (std::f32::consts::PI / n as f32).cos());
+            assert!(error(n) <= 0.01);
+            assert!(error(n - 1) > 0.01);
+        }
+        // shapes smaller than the error get the fewest sides, huge ones the most
+        assert_eq!(adaptive.sides(0, 0.001), MIN_ADAPTIVE_SIDES);
+        assert_eq!(adaptive.latitudes(0, 0.001), 2);
+        assert_eq!(adaptive.sides(0, 1.0e6), MAX_ADAPTIVE_SIDES);
+        assert_eq!(adaptive.sides(0, 0.0), MIN_ADAPTIVE_SIDES);
+    }
+
+    #[test]
+    fn depth_at_test() {
+        let depth_buffer = DepthBuffer {
+            width: 3,
+            height: 2,
+            depths: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+        };
+        assert_eq!(depth_buffer.depth_at(0, 0), Some(0.0));
+        assert_eq!(depth_buffer.depth_at(2, 1), Some(5.0));
+        assert_eq!(depth_buffer.depth_at(3, 0), None);
+        assert_eq!(depth_buffer.depth_at(0, 2), None);
+    }
+
     #[test]
 
     fn pitch_and_yaw_test() {
@@ -495,6 +1161,52 @@ mod tests {
         assert_eq!(wrap(3, 3), 0);
     }
 
+    #[test]
+    fn ear_clip_test() {
+        // an L shape, whose reflex corner rules out some of the ears
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+        assert!(is_simple_polygon(&points));
+        let triangles = ear_clip(&points);
+        assert_eq!(triangles.len(), 4);
+        let area =
+            |[a, b, c]: [usize; 3]| 0.5 * (points[b] - points[a]).perp(&(points[c] - points[a]));
+        // every triangle is wound counterclockwise and together they cover the L exactly
+        assert!(triangles.iter().all(|t| area(*t) > 0.0));
+        assert!((triangles.iter().map(|t| area(*t)).sum::<f32>() - 3.0).abs() < 1e-6);
+
+        // a corner in line with its neighbours adds no triangle
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+        let triangles = ear_clip(&square);
+        assert!(triangles.iter().all(|t| {
+            let [a, b, c] = *t;
+            (square[b] - square[a]).perp(&(square[c] - square[a])) > 0.0
+        }));
+
+        // a bow tie crosses itself, and a side doubling back overlaps the one before
+        let bow_tie = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        assert!(!is_simple_polygon(&bow_tie));
+        let spike = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(1.0, 0.0)];
+        assert!(!is_simple_polygon(&spike));
+    }
+
     #[test]
     fn orthogonal_vector_test() {
         {
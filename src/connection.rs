@@ -0,0 +1,178 @@
+//! This module defines the connection the 2D and 3D RPC clients hold to their server.
+//!
+//! The connection owns the runtime the calls are blocked on and the generated stub, and when the
+//! channel to the server breaks it reconnects on the same port. Only calls which are safe to send
+//! twice are sent again after reconnecting, since a request the server received before the
+//! connection broke would otherwise be carried out twice.
+//--------------------------------------------------------------------------------------------------
+
+//{{{ crate imports
+//}}}
+//{{{ std imports
+use std::future::Future;
+use std::time::Duration;
+//}}}
+//{{{ dep imports
+use tokio::runtime;
+use tokio::runtime::Runtime;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Response, Status};
+use topohedral_tracing::*;
+//}}}
+//--------------------------------------------------------------------------------------------------
+
+//{{{ collection: constants
+/// How long a call waits before each attempt at reconnecting to the server.
+pub(crate) const RECONNECT_DELAY: Duration = Duration::from_millis(250);
+//}}}
+//{{{ enum: Resend
+/// Whether a call may be sent to the server again once its connection has been re-established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Resend {
+    /// The call is idempotent, such as a query or a setter, and is sent again after reconnecting.
+    Idempotent,
+    /// The call has an effect each time the server receives it, such as adding a mesh, so it is
+    /// never sent twice: a broken connection fails it straight away.
+    Never,
+}
+//}}}
+//{{{ fun: is_broken_connection
+/// Returns whether the status reports the connection to the server failing, as opposed to an
+/// answer from the server, which has no source on this side.
+pub(crate) fn is_broken_connection(status: &Status) -> bool {
+    std::error::Error::source(status)
+        .is_some_and(|source| source.is::<tonic::transport::Error>())
+}
+//}}}
+//{{{ collection: Connection
+//{{{ struct: Connection
+/// The connection of a client to the RPC server listening on `port`, through a stub of type `S`.
+///
+/// - `new_stub`: Builds the stub over a channel, the `new` function of the generated client.
+/// - `reconnect_attempts`: How many times an idempotent call reconnects before failing.
+pub(crate) struct Connection<S> {
+    port: usize,
+    stub: S,
+    tokio_runtime: Runtime,
+    reconnect_attempts: u32,
+    new_stub: fn(Channel) -> S,
+}
+//..............................................................................
+//}}}
+//{{{ impl: Connection
+impl<S: Clone> Connection<S> {
+    //{{{ fun: new
+    /// Connects to the server on `port`, failing if nothing is listening there yet.
+    pub fn new(port: usize, new_stub: fn(Channel) -> S) -> Result<Self, tonic::transport::Error> {
+        let tokio_runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let channel = tokio_runtime.block_on(endpoint(port)?.connect())?;
+        Ok(Self {
+            port: port,
+            stub: new_stub(channel),
+            tokio_runtime: tokio_runtime,
+            reconnect_attempts: 0,
+            new_stub: new_stub,
+        })
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: new_lazy
+    /// Returns a connection to `port` which only connects on its first call, so that clients
+    /// can be tested without a server.
+    #[cfg(test)]
+    pub fn new_lazy(port: usize, new_stub: fn(Channel) -> S) -> Self {
+        let tokio_runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let channel = {
+            let _guard = tokio_runtime.enter();
+            endpoint(port).unwrap().connect_lazy()
+        };
+        Self {
+            port: port,
+            stub: new_stub(channel),
+            tokio_runtime: tokio_runtime,
+            reconnect_attempts: 0,
+            new_stub: new_stub,
+        }
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_reconnect_attempts
+    pub fn set_reconnect_attempts(&mut self, attempts: u32) {
+        self.reconnect_attempts = attempts;
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: reconnect
+    /// Replaces the channel to the server with a new one, on the same port.
+    fn reconnect(&mut self) -> Result<(), tonic::transport::Error> {
+        let channel = self.tokio_runtime.block_on(endpoint(self.port)?.connect())?;
+        self.stub = (self.new_stub)(channel);
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: call
+    /// Sends `message` to the server through `send`. If the connection has broken and the call
+    /// may be sent again, spends the reconnect attempts on reconnecting and sending it again.
+    pub fn call<M, R, F, Fut>(
+        &mut self,
+        method: &'static str,
+        mut message: M,
+        resend: Resend,
+        send: F,
+    ) -> Result<Response<R>, Status>
+    where
+        M: Clone + Default,
+        F: Fn(S, Request<M>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let mut attempts_left = match resend {
+            Resend::Idempotent => self.reconnect_attempts,
+            Resend::Never => 0,
+        };
+        loop {
+            // a message which cannot be sent again is moved rather than copied
+            let request = if attempts_left > 0 {
+                message.clone()
+            } else {
+                std::mem::take(&mut message)
+            };
+            let status = match self
+                .tokio_runtime
+                .block_on(send(self.stub.clone(), Request::new(request)))
+            {
+                Ok(response) => return Ok(response),
+                Err(status) => status,
+            };
+            if attempts_left == 0 || !is_broken_connection(&status) {
+                return Err(status);
+            }
+            //{{{ trace
+            warn!("Connection to the server broken during {}, reconnecting", method);
+            //}}}
+            while attempts_left > 0 {
+                attempts_left -= 1;
+                std::thread::sleep(RECONNECT_DELAY);
+                if self.reconnect().is_ok() {
+                    break;
+                }
+            }
+        }
+    }
+    //..............................................................................
+    //}}}
+}
+//}}}
+//}}}
+//{{{ fun: endpoint
+/// Returns the endpoint of the server listening on `port` of the local host.
+fn endpoint(port: usize) -> Result<Endpoint, tonic::transport::Error> {
+    Endpoint::from_shared(format!("http://127.0.0.1:{}", port))
+}
+//}}}
@@ -0,0 +1,33 @@
+// Vertical background gradient, drawn as a single triangle covering the whole window before the
+// meshes.
+
+struct Gradient {
+    top: vec4<f32>,
+    bottom: vec4<f32>,
+};
+@group(0) @binding(0)
+var<uniform> gradient: Gradient;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // 0 at the bottom of the window, 1 at the top
+    @location(0) height: f32,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) index: u32,
+) -> VertexOutput {
+    // the corners (-1, -1), (3, -1) and (-1, 3) enclose the clip space square
+    let x = f32(i32(index & 1u) * 4 - 1);
+    let y = f32(i32(index >> 1u) * 4 - 1);
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(x, y, 0.0, 1.0);
+    out.height = 0.5 * (y + 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return mix(gradient.bottom, gradient.top, clamp(in.height, 0.0, 1.0));
+}
@@ -2,17 +2,22 @@
 //!
 //! Because the topology of the mesh is the same in both viewers, that being lines and triangles, 
 //! much of the functionality is shared between the two. The main difference is the vertex type, 
-//! which for 2D is 8 f32s, (x,y) and a triplet of rgbs for lines and triangles. For 3D is 9 f32s, 
-//! ((x,y,z) and a triplet of rgbs for lines and triangles.  
+//! which for 2D is 12 f32s, (x,y), an rgba color for lines and one for triangles, and a screen
+//! offset. For 3D it is 14 f32s, (x,y,z), the normal and the two rgba colors.
 //--------------------------------------------------------------------------------------------------
 
 //{{{ crate imports 
-use crate::common::{CellType, Color};
+use crate::common::{
+    CellType, Color, FrontFace, Mat4, MeshColorMode, MeshDiagnostics, MeshOptions, Vec3,
+};
 use crate::Colormap;
 //}}}
 //{{{ std imports 
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
+use std::ops::Index;
 //}}}
 //{{{ dep imports 
 use serde::{Deserialize, Serialize};
@@ -35,14 +40,14 @@ pub trait VertexViewCore<'a>
     fn get_position(&self) -> Self::Vec;
     fn set_line_color(
         &mut self,
-        color: &[f32; 3],
+        color: &[f32; 4],
     );
-    fn get_line_color(&self) -> [f32; 3];
+    fn get_line_color(&self) -> [f32; 4];
     fn set_triangle_color(
         &mut self,
-        color: &[f32; 3],
+        color: &[f32; 4],
     );
-    fn get_triangle_color(&self) -> [f32; 3];
+    fn get_triangle_color(&self) -> [f32; 4];
 }
 //..................................................................................................
 //}}}
@@ -61,6 +66,14 @@ pub trait VertexCore
     /// This layout is used to define how vertex data is structured in a wgpu vertex buffer.
     fn desc() -> wgpu::VertexBufferLayout<'static>;
 
+    /// Returns the vertex buffer layout of meshes drawn with a uniform color, see
+    /// [`MeshColorMode`]. It keeps every attribute but the colors, at the same shader locations
+    /// as in `desc`.
+    fn compact_desc() -> wgpu::VertexBufferLayout<'static>;
+
+    /// Appends the attributes of the compact layout, in order, from the data of a single vertex.
+    fn write_compact(vertex: &[f32], out: &mut Vec<f32>);
+
     /// Returns the offset in the vertex data slice where the position data is stored.
     fn position_offset() -> usize;
 
@@ -73,11 +86,28 @@ pub trait VertexCore
     /// Returns the number of elements in the vertex data slice.
     fn len() -> usize;
 
+    /// Reads the position from the data of a single vertex.
+    fn position(vertex: &[f32]) -> Self::Vec;
+
     /// Returns the dimension of the uncerlying vector type of the vertex.
     fn dim() -> usize;
+
+    /// Writes the normal into the data of a single vertex. Vertices without a normal ignore it.
+    fn set_normal(_vertex: &mut [f32], _normal: &Vec3) {}
+
+    /// Reads the normal from the data of a single vertex, `None` for vertices without a normal.
+    fn normal(_vertex: &[f32]) -> Option<Vec3>
+    {
+        None
+    }
 }
 //..................................................................................................
 //}}}
+//{{{ constant: MAX_DASHES_PER_LINE
+/// The most dashes a single line cell is split into by [`MeshCore::with_dashed_lines`]. Longer
+/// lines, relative to their dash pattern, are drawn solid rather than flooding the vertex buffer.
+const MAX_DASHES_PER_LINE: f32 = 4096.0;
+//}}}
 //{{{ collection: MeshCore
 //{{{ struct: MeshCore
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -88,6 +118,46 @@ where
     pub(crate) vertices: Vec<f32>,
     pub(crate) indices: Vec<u32>,
     pub(crate) cell_type: CellType,
+    /// Line cells drawn on top of the triangle cells of a triangle mesh, indexing the same
+    /// vertices. Always empty for line meshes, whose lines all live in `indices`.
+    #[serde(default)]
+    pub(crate) line_indices: Vec<u32>,
+    /// When set, the line cells of the mesh are drawn on top of all other geometry, ignoring the
+    /// depth buffer. Useful for annotations such as axes or guides.
+    #[serde(default)]
+    pub(crate) overlay: bool,
+    /// Depth bias applied to the line cells of the mesh, in a few steps of the depth buffer each.
+    /// Negative values pull the lines towards the camera, so lines lying on a surface are not
+    /// hidden by it. Zero, the default, applies no bias.
+    #[serde(default)]
+    pub(crate) line_depth_bias: i32,
+    /// Optional name the mesh can be looked up by once added to a state, see
+    /// `StateCore::add_named_mesh`.
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    /// Optional tag naming the group the mesh belongs to, which unlike a name is shared by any
+    /// number of meshes, so that they can be moved together, see `StateCore::transform_group`.
+    #[serde(default)]
+    pub(crate) tag: Option<String>,
+    /// Whether the mesh is drawn with the colors of its vertices or with a single color, in
+    /// which case its vertex buffer leaves the colors out.
+    #[serde(default)]
+    pub(crate) color_mode: MeshColorMode,
+    /// Options controlling how the mesh is drawn, such as the color of its edges.
+    #[serde(default)]
+    pub(crate) options: MeshOptions,
+    /// Places the mesh in the world, applied to its vertices on the GPU before the view, so the
+    /// mesh can be moved without uploading its vertices again. The identity by default.
+    #[serde(default = "Mat4::identity")]
+    pub(crate) model: Mat4,
+    /// The layer the mesh is drawn in, meshes in higher layers being drawn after and, in 2D, on
+    /// top of those in lower layers. Zero by default.
+    #[serde(default)]
+    pub(crate) layer: i32,
+    /// Which way the triangles of the mesh are wound seen from their front, counterclockwise by
+    /// default. Only face culling depends on it.
+    #[serde(default)]
+    pub(crate) front_face: FrontFace,
     #[serde(skip)]
     pub uid: usize,
     #[serde(skip)]
@@ -105,6 +175,16 @@ where
             vertices: Vec::<f32>::with_capacity(2 * V::len() * num_lines),
             indices: Vec::<u32>::with_capacity(2 * num_lines),
             cell_type: CellType::Line,
+            line_indices: Vec::new(),
+            overlay: false,
+            line_depth_bias: 0,
+            name: None,
+            tag: None,
+            color_mode: MeshColorMode::PerVertex,
+            options: MeshOptions::default(),
+            model: Mat4::identity(),
+            layer: 0,
+            front_face: FrontFace::Ccw,
             uid: 0,
             phant: PhantomData,
         }
@@ -116,31 +196,668 @@ where
             vertices: Vec::<f32>::with_capacity(3 * V::len() * num_triangles),
             indices: Vec::<u32>::with_capacity(3 * num_triangles),
             cell_type: CellType::Triangle,
+            line_indices: Vec::new(),
+            overlay: false,
+            line_depth_bias: 0,
+            name: None,
+            tag: None,
+            color_mode: MeshColorMode::PerVertex,
+            options: MeshOptions::default(),
+            model: Mat4::identity(),
+            layer: 0,
+            front_face: FrontFace::Ccw,
             uid: 0,
             phant: PhantomData,
         }
     }
 
+    /// Returns `true` if the cells of the mesh are lines.
+    ///
+    /// ```
+    /// use topohedral_viewer::d3::Mesh;
+    ///
+    /// assert!(Mesh::from_num_lines(1).is_line());
+    /// assert!(!Mesh::from_num_triangles(1).is_line());
+    /// ```
     pub fn is_line(&self) -> bool
     {
         self.cell_type == CellType::Line
     }
 
+    /// Returns `true` if the cells of the mesh are triangles. Such a mesh may also carry extra
+    /// line cells, see [`Self::has_lines`].
+    ///
+    /// ```
+    /// use topohedral_viewer::d3::Mesh;
+    ///
+    /// assert!(Mesh::from_num_triangles(1).is_triangle());
+    /// assert!(!Mesh::from_num_lines(1).is_triangle());
+    /// ```
     pub fn is_triangle(&self) -> bool
     {
         self.cell_type == CellType::Triangle
     }
 
+    /// Marks the line cells of the mesh as overlay geometry, drawn on top of everything else
+    /// regardless of depth.
+    pub fn set_overlay(
+        &mut self,
+        overlay: bool,
+    )
+    {
+        self.overlay = overlay;
+    }
+
+    /// Returns `true` if the line cells of the mesh are drawn as overlay geometry.
+    pub fn is_overlay(&self) -> bool
+    {
+        self.overlay
+    }
+
+    /// Sets the depth bias of the line cells of the mesh. A small negative value, such as `-2`,
+    /// lets lines drawn over coplanar triangles render without z-fighting.
+    pub fn set_line_depth_bias(
+        &mut self,
+        depth_bias: i32,
+    )
+    {
+        self.line_depth_bias = depth_bias;
+    }
+
+    /// Returns the depth bias of the line cells of the mesh.
+    pub fn line_depth_bias(&self) -> i32
+    {
+        self.line_depth_bias
+    }
+
+    /// Sets the layer the mesh is drawn in. Meshes are drawn in order of their layers, and 2D
+    /// meshes in a higher layer are drawn on top of those in a lower one whatever the order the
+    /// passes draw lines and triangles in, so fills can be kept under outlines and annotations
+    /// over both. Meshes sharing a layer, as every mesh does by default, are left to the order
+    /// they happen to be drawn in. 3D meshes are ordered by depth alone.
+    pub fn set_layer(
+        &mut self,
+        layer: i32,
+    )
+    {
+        self.layer = layer;
+    }
+
+    /// Returns the layer the mesh is drawn in.
+    pub fn layer(&self) -> i32
+    {
+        self.layer
+    }
+
+    /// Sets which way the triangles of the mesh are wound seen from their front, so that face
+    /// culling leaves the right side of meshes made with clockwise triangles undrawn. The
+    /// triangles themselves are left as they are, see [`FrontFace`].
+    pub fn set_front_face(
+        &mut self,
+        front_face: FrontFace,
+    )
+    {
+        self.front_face = front_face;
+    }
+
+    /// Returns which way the triangles of the mesh are wound seen from their front.
+    pub fn front_face(&self) -> FrontFace
+    {
+        self.front_face
+    }
+
+    /// Returns the name of the mesh, if it has one.
+    pub fn name(&self) -> Option<&str>
+    {
+        self.name.as_deref()
+    }
+
+    /// Returns the tag of the group the mesh belongs to, if it has one.
+    pub fn tag(&self) -> Option<&str>
+    {
+        self.tag.as_deref()
+    }
+
+    /// Puts the mesh in the group with the given tag, or in no group with `None`.
+    pub fn set_tag(&mut self, tag: Option<&str>)
+    {
+        self.tag = tag.map(str::to_string);
+    }
+
+    /// Sets where the colors of the mesh come from. With [`MeshColorMode::Uniform`] every cell
+    /// is drawn with the one color and only the compact layout of the vertices, see
+    /// [`VertexCore::compact_desc`], is uploaded to the GPU. The vertices keep their own colors,
+    /// which show again when switching back to [`MeshColorMode::PerVertex`].
+    pub fn set_color_mode(
+        &mut self,
+        color_mode: MeshColorMode,
+    )
+    {
+        self.color_mode = color_mode;
+    }
+
+    /// Returns where the colors of the mesh come from.
+    pub fn color_mode(&self) -> MeshColorMode
+    {
+        self.color_mode
+    }
+
+    /// Sets the options controlling how the mesh is drawn, see [`MeshOptions`].
+    pub fn set_options(
+        &mut self,
+        options: MeshOptions,
+    )
+    {
+        self.options = options;
+    }
+
+    /// Returns the options controlling how the mesh is drawn.
+    pub fn options(&self) -> MeshOptions
+    {
+        self.options
+    }
+
+    /// Sets the model matrix placing the mesh in the world. The vertices stay in the coordinates
+    /// of the mesh, and the matrix is applied to them when drawing, so moving a mesh this way does
+    /// not touch its vertex buffer. The normals of 3D meshes follow its inverse transpose.
+    pub fn set_model(
+        &mut self,
+        model: Mat4,
+    )
+    {
+        self.model = model;
+    }
+
+    /// Returns the model matrix placing the mesh in the world, the identity unless set.
+    pub fn model(&self) -> Mat4
+    {
+        self.model
+    }
+
+    /// Returns the position of the vertex at `index` in the world, that is with the model matrix
+    /// applied, see [`Self::vertex_position`].
+    pub fn world_position(&self, index: usize) -> Vec3
+    {
+        to_world(&self.model, &self.vertex_position(index))
+    }
+
+    /// Returns the color the edges of the triangle cells of the mesh are drawn with, when it
+    /// does not come from the line color of each vertex: the edge color of the mesh if it has
+    /// one, or else its uniform color.
+    pub fn edge_color(&self) -> Option<Color>
+    {
+        match self.color_mode
+        {
+            MeshColorMode::Uniform(color) => Some(self.options.edge_color.unwrap_or(color)),
+            MeshColorMode::PerVertex => self.options.edge_color,
+        }
+    }
+
+    /// Returns the vertices of the mesh with the line color of every vertex replaced by `color`.
+    pub fn vertices_with_line_color(&self, color: &Color) -> Vec<f32>
+    {
+        let rgba = color.to_rgba();
+        let mut vertices = self.vertices.clone();
+        for vertex in vertices.chunks_exact_mut(V::len())
+        {
+            let offset = V::line_color_offset();
+            vertex[offset..offset + 4].copy_from_slice(&rgba);
+        }
+        vertices
+    }
+
+    /// Returns the vertices of the mesh in the compact layout, which leaves out their colors, as
+    /// uploaded for meshes with a uniform color.
+    pub fn compact_vertices(&self) -> Vec<f32>
+    {
+        let mut compact = Vec::with_capacity(self.vertices.len());
+        for vertex in self.vertices.chunks_exact(V::len())
+        {
+            V::write_compact(vertex, &mut compact);
+        }
+        compact
+    }
+
+    /// Returns `true` if the mesh has any line cells, either as a line mesh or as a triangle mesh
+    /// carrying extra line cells.
+    pub fn has_lines(&self) -> bool
+    {
+        self.is_line() || !self.line_indices.is_empty()
+    }
+
+    /// Returns `true` if the mesh has triangle cells.
+    pub fn has_triangles(&self) -> bool
+    {
+        self.is_triangle()
+    }
+
+    /// Returns `true` if the mesh has at least one line or triangle cell, and so something to
+    /// draw. Unlike [`Self::is_empty`] this also catches meshes whose vertices no cell uses.
+    pub fn has_cells(&self) -> bool
+    {
+        !self.line_cell_indices().is_empty() || !self.triangle_cell_indices().is_empty()
+    }
+
+    /// Returns the indices of all the line cells of the mesh.
+    pub fn line_cell_indices(&self) -> &[u32]
+    {
+        if self.is_line() {
+            self.indices.as_slice()
+        } else {
+            self.line_indices.as_slice()
+        }
+    }
+
+    /// Returns the indices of all the triangle cells of the mesh.
+    pub fn triangle_cell_indices(&self) -> &[u32]
+    {
+        if self.is_triangle() {
+            self.indices.as_slice()
+        } else {
+            &[]
+        }
+    }
+
+    /// Returns line cell indices for the edges of the triangle cells of the mesh. Edges shared by
+    /// neighbouring triangles appear once, in the order they are first met.
+    pub fn triangle_edge_indices(&self) -> Vec<u32>
+    {
+        let mut seen = HashSet::new();
+        let mut edges = Vec::new();
+        for tri in self.triangle_cell_indices().chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                if seen.insert((a.min(b), a.max(b))) {
+                    edges.extend([a, b]);
+                }
+            }
+        }
+        edges
+    }
+
+    /// Returns the data of the two vertices of every edge of the triangle cells of the mesh, one
+    /// after the other, with the edges in the order of [`Self::triangle_edge_indices`]. With an
+    /// edge color, see [`Self::edge_color`], the line color of every vertex is replaced by it.
+    pub fn triangle_edge_vertices(&self) -> Vec<f32>
+    {
+        let edge_color = self.edge_color().map(|color| color.to_rgba());
+        let mut vertices = Vec::new();
+        for index in self.triangle_edge_indices() {
+            let start = index as usize * V::len();
+            let offset = vertices.len() + V::line_color_offset();
+            vertices.extend_from_slice(&self.vertices[start..start + V::len()]);
+            if let Some(rgba) = edge_color {
+                vertices[offset..offset + 4].copy_from_slice(&rgba);
+            }
+        }
+        vertices
+    }
+
+    /// Returns the position of the vertex at `index`, padded with zeros to three components for
+    /// 2D meshes, which lie in the plane `z = 0`.
+    pub fn vertex_position(&self, index: usize) -> Vec3
+    {
+        position_at::<V>(&self.vertices, index)
+    }
+
+    /// Returns the positions of the vertices of the mesh, in order, decoded from the vertex data.
+    pub fn positions(&self) -> Vec<V::Vec>
+    {
+        self.vertices.chunks_exact(V::len()).map(V::position).collect()
+    }
+
+    /// Returns the normals of the vertices of the mesh, in order, or `None` for meshes whose
+    /// vertices have no normal, such as 2D meshes.
+    pub fn normals(&self) -> Option<Vec<Vec3>>
+    {
+        self.vertices.chunks_exact(V::len()).map(V::normal).collect()
+    }
+
+    /// Returns the RGBA colors the cells of the mesh take from its vertices, in order: the
+    /// triangle colors for triangle meshes and the line colors for line meshes.
+    pub fn colors(&self) -> Vec<[f32; 4]>
+    {
+        let offset =
+            if self.is_triangle() { V::triangle_color_offset() } else { V::line_color_offset() };
+        self.vertices
+            .chunks_exact(V::len())
+            .map(|vertex| {
+                let mut color = [0.0; 4];
+                color.copy_from_slice(&vertex[offset..offset + 4]);
+                color
+            })
+            .collect()
+    }
+
+    /// Returns the corners of the smallest axis-aligned box holding every vertex of the mesh in
+    /// the world, see [`Self::world_position`], as the minimum and maximum of each coordinate,
+    /// or `None` if the mesh has no vertices. 2D meshes with the identity model matrix give a
+    /// flat box in the plane `z = 0`.
+    pub fn bounding_box(&self) -> Option<(Vec3, Vec3)>
+    {
+        (0..self.num_vertices())
+            .map(|i| self.world_position(i))
+            .fold(None, |bounds, p| match bounds {
+                None => Some((p, p)),
+                Some((min, max)) => Some((min.inf(&p), max.sup(&p))),
+            })
+    }
+
+    /// Checks the geometry of the mesh, see [`MeshDiagnostics`], leaving it as it is. Cells with
+    /// an index past the last vertex count as degenerate rather than being read.
+    pub fn diagnostics(&self) -> MeshDiagnostics
+    {
+        let num_vertices = self.num_vertices();
+        let non_finite_count = self
+            .vertices
+            .chunks_exact(V::len())
+            .filter(|vertex| !vertex.iter().all(|x| x.is_finite()))
+            .count();
+        let bounds = (0..num_vertices)
+            .map(|i| self.vertex_position(i))
+            .filter(|p| p.iter().all(|x| x.is_finite()))
+            .fold(None, |bounds, p| match bounds {
+                None => Some((p, p)),
+                Some((min, max)) => Some((min.inf(&p), max.sup(&p))),
+            });
+        let position = |i: u32| {
+            let i = i as usize;
+            (i < num_vertices).then(|| self.vertex_position(i))
+        };
+        let degenerate_line = self.line_cell_indices().chunks_exact(2).any(|line| {
+            match (position(line[0]), position(line[1])) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+        });
+        let degenerate_triangle = self.triangle_cell_indices().chunks_exact(3).any(|tri| {
+            match (position(tri[0]), position(tri[1]), position(tri[2])) {
+                (Some(a), Some(b), Some(c)) => (b - a).cross(&(c - a)) == Vec3::zeros(),
+                _ => true,
+            }
+        });
+        let mut edge_counts = HashMap::new();
+        for tri in self.triangle_cell_indices().chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                *edge_counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+        MeshDiagnostics {
+            num_vertices: num_vertices,
+            num_triangles: self.triangle_cell_indices().len() / 3,
+            bounds: bounds,
+            is_manifold: edge_counts.values().all(|&count| count <= 2),
+            has_degenerate: degenerate_line || degenerate_triangle,
+            non_finite_count: non_finite_count,
+        }
+    }
+
+    /// Moves every vertex of the mesh by `offset`. Normals are left as they are.
+    pub fn translate(
+        &mut self,
+        offset: &V::Vec,
+    )
+    where
+        V::Vec: Index<usize, Output = f32>,
+    {
+        for vertex in self.vertices.chunks_exact_mut(V::len()) {
+            for i in 0..V::dim() {
+                vertex[V::position_offset() + i] += offset[i];
+            }
+        }
+    }
+
+    /// Scales the mesh by `factor` about the point `about`, which stays where it is. Uniform
+    /// scaling keeps the directions of the normals, so they are left as they are. A negative
+    /// factor mirrors the mesh through `about`, which turns its triangles inside out.
+    pub fn scale_uniform(
+        &mut self,
+        factor: f32,
+        about: &V::Vec,
+    )
+    where
+        V::Vec: Index<usize, Output = f32>,
+    {
+        for vertex in self.vertices.chunks_exact_mut(V::len()) {
+            for i in 0..V::dim() {
+                let x = &mut vertex[V::position_offset() + i];
+                *x = about[i] + factor * (*x - about[i]);
+            }
+        }
+    }
+
+    /// Returns a line mesh holding the edges of the bounding box of the mesh, see
+    /// [`Self::bounding_box`], drawn in `color`: the 12 edges of a cuboid, or the 4 edges of a
+    /// rectangle for a flat box such as that of a 2D mesh. Edges of zero length are left out, so
+    /// a mesh without vertices gives an empty line mesh.
+    pub fn bounding_box_edges(&self, color: &Color) -> Self
+    {
+        let mut out = Self::from_num_lines(12);
+        let Some((min, max)) = self.bounding_box() else {
+            return out;
+        };
+        let rgba = color.to_rgba();
+        // the ends of the box along each axis, a single one along an axis the box is flat along
+        let ends = |i: usize| {
+            if min[i] == max[i] { vec![min[i]] } else { vec![min[i], max[i]] }
+        };
+        for axis in 0..3 {
+            if min[axis] == max[axis] {
+                continue;
+            }
+            let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+            for x in ends(a) {
+                for y in ends(b) {
+                    let mut start = min;
+                    start[a] = x;
+                    start[b] = y;
+                    let mut end = start;
+                    end[axis] = max[axis];
+                    push_line_vertex::<V>(&mut out.vertices, &start, &rgba);
+                    push_line_vertex::<V>(&mut out.vertices, &end, &rgba);
+                }
+            }
+        }
+        out.indices = (0..out.num_vertices() as u32).collect();
+        out
+    }
+
+    /// Returns a line mesh holding a segment from each vertex of the triangle mesh along its
+    /// normal, `length` long, drawn in `color`, or `None` for meshes whose vertices have no
+    /// normals: line meshes and 2D meshes. Vertices with a zero normal are left out. The lines
+    /// share the model matrix of the mesh, so they follow it around.
+    pub fn normal_lines(
+        &self,
+        length: f32,
+        color: &Color,
+    ) -> Option<Self>
+    {
+        if !self.is_triangle() {
+            return None;
+        }
+        let rgba = color.to_rgba();
+        let mut out = Self::from_num_lines(self.num_vertices());
+        for (i, vertex) in self.vertices.chunks_exact(V::len()).enumerate() {
+            let normal = V::normal(vertex)?;
+            if normal == Vec3::zeros() {
+                continue;
+            }
+            let position = self.vertex_position(i);
+            push_line_vertex::<V>(&mut out.vertices, &position, &rgba);
+            push_line_vertex::<V>(&mut out.vertices, &(position + normal * length), &rgba);
+        }
+        out.indices = (0..out.num_vertices() as u32).collect();
+        out.model = self.model;
+        Some(out)
+    }
+
+    /// Returns a copy of the mesh whose line cells are split into dashes `dash` long, separated
+    /// by gaps `gap` long, in world units. The dash ends are new vertices, interpolating the data
+    /// of the two ends of the line they lie on, appended after the existing ones, so the triangle
+    /// cells are unchanged.
+    ///
+    /// The pattern carries on from one line cell to the next when the second starts where the
+    /// first ends, so polylines made of short segments, such as circles, are dashed as a whole.
+    /// Unless both `dash` and `gap` are positive and finite the copy is left solid.
+    pub fn with_dashed_lines(
+        &self,
+        dash: f32,
+        gap: f32,
+    ) -> Self
+    {
+        // field by field, as deriving `Clone` asks the vertex type to be `Clone` too
+        let mut out = Self {
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            cell_type: self.cell_type,
+            line_indices: self.line_indices.clone(),
+            overlay: self.overlay,
+            line_depth_bias: self.line_depth_bias,
+            name: self.name.clone(),
+            tag: self.tag.clone(),
+            color_mode: self.color_mode,
+            options: self.options,
+            model: self.model,
+            layer: self.layer,
+            front_face: self.front_face,
+            uid: self.uid,
+            phant: PhantomData,
+        };
+        let period = dash + gap;
+        if !(dash > 0.0 && gap > 0.0 && period.is_finite()) {
+            return out;
+        }
+        let mut dashes = Vec::with_capacity(self.line_cell_indices().len());
+        // how far into the pattern the current line cell starts
+        let mut phase = 0.0;
+        let mut last_end = None;
+        for cell in self.line_cell_indices().chunks_exact(2) {
+            let (a, b) = (cell[0], cell[1]);
+            if last_end != Some(a) {
+                phase = 0.0;
+            }
+            last_end = Some(b);
+            let (pa, pb) = (self.vertex_position(a as usize), self.vertex_position(b as usize));
+            let length = (pb - pa).norm();
+            if length / period > MAX_DASHES_PER_LINE {
+                dashes.extend([a, b]);
+                continue;
+            }
+            // the vertex at arc length `t` along the line, reusing its ends
+            let mut vertex_at = |t: f32| -> u32 {
+                if t <= 0.0 {
+                    return a;
+                }
+                if t >= length {
+                    return b;
+                }
+                let u = t / length;
+                let (start_a, start_b) = (a as usize * V::len(), b as usize * V::len());
+                let index = out.num_vertices() as u32;
+                for i in 0..V::len() {
+                    let (x, y) = (self.vertices[start_a + i], self.vertices[start_b + i]);
+                    out.vertices.push(x + u * (y - x));
+                }
+                index
+            };
+            // dash pieces shorter than this are rounding errors of the phase, and left out
+            let tolerance = 1e-5 * period;
+            let mut start = -phase;
+            while start < length - tolerance {
+                let end = start + dash;
+                if end > tolerance {
+                    dashes.extend([vertex_at(start), vertex_at(end)]);
+                }
+                start += period;
+            }
+            phase = (phase + length) % period;
+        }
+        if out.is_line() {
+            out.indices = dashes;
+        } else {
+            out.line_indices = dashes;
+        }
+        out
+    }
+
+    /// Iterates over the triangle cells of the mesh, yielding the positions of their corners.
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_
+    {
+        // borrow the vertices alone so the iterator does not capture the lifetime of the mesh
+        let vertices = self.vertices.as_slice();
+        self.triangle_cell_indices().chunks_exact(3).map(move |tri| {
+            [
+                position_at::<V>(vertices, tri[0] as usize),
+                position_at::<V>(vertices, tri[1] as usize),
+                position_at::<V>(vertices, tri[2] as usize),
+            ]
+        })
+    }
+
+    /// Casts a ray, given in the world, against the triangle cells of the mesh placed by its
+    /// model matrix, returning the distance along the ray of the nearest hit, in units of
+    /// `direction`. Line cells are never hit.
+    pub fn ray_intersection(
+        &self,
+        origin: &Vec3,
+        direction: &Vec3,
+    ) -> Option<f32>
+    {
+        self.triangles()
+            .map(|tri| tri.map(|p| to_world(&self.model, &p)))
+            .filter_map(|tri| ray_triangle_intersection(origin, direction, &tri))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Returns the number of vertices in the mesh, the length of the vertex data divided by the
+    /// number of elements per vertex.
+    ///
+    /// ```
+    /// use topohedral_viewer::d3::{Mesh, Mesh3D};
+    /// use topohedral_viewer::{Color, Vec3};
+    ///
+    /// let mut mesh = Mesh::from_num_lines(1);
+    /// mesh.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+    /// assert_eq!(mesh.num_vertices(), 2);
+    /// ```
     pub fn num_vertices(&self) -> usize
     {
         self.vertices.len() / V::len()
     }
 
+    /// Returns the number of indices of the cells of the mesh, two per line of a line mesh or
+    /// three per triangle of a triangle mesh. The extra line cells of a triangle mesh are not
+    /// counted.
+    ///
+    /// ```
+    /// use topohedral_viewer::d3::{Mesh, Mesh3D};
+    /// use topohedral_viewer::{Color, Vec3};
+    ///
+    /// let mut mesh = Mesh::from_num_triangles(1);
+    /// mesh.add_triangle(&Vec3::zeros(), &Vec3::x(), &Vec3::y(), &Color::Black, &Color::Green);
+    /// mesh.append_line_indices(&[0, 1]);
+    /// assert_eq!(mesh.num_indices(), 3);
+    /// ```
     pub fn num_indices(&self) -> usize
     {
         self.indices.len()
     }
 
+    /// Returns `true` if the mesh has no vertices, and so nothing to draw.
+    ///
+    /// ```
+    /// use topohedral_viewer::d3::{Mesh, Mesh3D};
+    /// use topohedral_viewer::{Color, Vec3};
+    ///
+    /// let mut mesh = Mesh::from_num_lines(1);
+    /// assert!(mesh.is_empty());
+    /// mesh.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+    /// assert!(!mesh.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool
+    {
+        self.vertices.is_empty()
+    }
+
     pub fn num_triangles(&self) -> usize
     {
         self.indices.len() / 3
@@ -162,6 +879,7 @@ where
     )
     {
         self.indices.iter_mut().for_each(|x| *x += shift as u32);
+        self.line_indices.iter_mut().for_each(|x| *x += shift as u32);
     }
 
     pub fn append_vertex(
@@ -180,6 +898,55 @@ where
         self.indices.extend_from_slice(indices);
     }
 
+    /// Appends line cells to the mesh. For a triangle mesh these are kept alongside the triangle
+    /// cells and drawn with the line pipeline, sharing the mesh's vertices.
+    pub fn append_line_indices(
+        &mut self,
+        indices: &[u32],
+    )
+    {
+        if self.is_line() {
+            self.indices.extend_from_slice(indices);
+        } else {
+            self.line_indices.extend_from_slice(indices);
+        }
+    }
+
+    /// Appends the triangles of a fan around the vertex `center`, whose rim is the `num_rim`
+    /// consecutive vertices starting at `first_rim`. The fan is open, it has `num_rim - 1`
+    /// triangles, each wound in the same order as the rim.
+    pub fn append_fan_indices(
+        &mut self,
+        center: u32,
+        first_rim: u32,
+        num_rim: usize,
+    )
+    {
+        for i in 1..num_rim as u32 {
+            self.indices
+                .extend_from_slice(&[center, first_rim + i - 1, first_rim + i]);
+        }
+    }
+
+    /// Appends the triangles of a strip over the `num_points` consecutive vertices starting at
+    /// `first`. Every other triangle has its first two corners swapped, so that the whole strip
+    /// winds the same way as its first triangle.
+    pub fn append_strip_indices(
+        &mut self,
+        first: u32,
+        num_points: usize,
+    )
+    {
+        for i in 0..num_points.saturating_sub(2) as u32 {
+            let (a, b, c) = (first + i, first + i + 1, first + i + 2);
+            if i % 2 == 0 {
+                self.indices.extend_from_slice(&[a, b, c]);
+            } else {
+                self.indices.extend_from_slice(&[b, a, c]);
+            }
+        }
+    }
+
     pub fn merge(
         &mut self,
         mut other: MeshCore<'a, V>,
@@ -191,6 +958,7 @@ where
 
         self.vertices.extend_from_slice(&other.vertices);
         self.indices.extend_from_slice(&other.indices);
+        self.line_indices.extend_from_slice(&other.line_indices);
     }
 
     pub fn check(&self) -> bool
@@ -213,7 +981,7 @@ where
         for i in 0..nv
         {
             let offset = i * V::len() + V::line_color_offset();
-            self.vertices[offset..offset + 3].copy_from_slice(&color.to_rgb());
+            self.vertices[offset..offset + 4].copy_from_slice(&color.to_rgba());
         }
     }
 
@@ -226,7 +994,7 @@ where
         for i in 0..nv
         {
             let offset = i * V::len() + V::triangle_color_offset();
-            self.vertices[offset..offset + 3].copy_from_slice(&color.to_rgb());
+            self.vertices[offset..offset + 4].copy_from_slice(&color.to_rgba());
         }
     }
 
@@ -243,8 +1011,8 @@ where
         for i in 0..nv
         {
             let offset = i * V::len() + V::line_color_offset();
-            let color = cmap.get_color(fvals[i]);
-            self.vertices[offset..offset + 3].copy_from_slice(&color);
+            let [r, g, b] = cmap.get_color(fvals[i]);
+            self.vertices[offset..offset + 4].copy_from_slice(&[r, g, b, 1.0]);
         }
     }
 
@@ -261,9 +1029,127 @@ where
         for i in 0..nv
         {
             let offset = i * V::len() + V::triangle_color_offset();
-            let color = cmap.get_color(fvals[i]);
-            self.vertices[offset..offset + 3].copy_from_slice(&color);
+            let [r, g, b] = cmap.get_color(fvals[i]);
+            self.vertices[offset..offset + 4].copy_from_slice(&[r, g, b, 1.0]);
+        }
+    }
+
+    /// Returns a line mesh holding the contours of `values`, given at each vertex, at each of
+    /// `levels`, found by marching through the triangle cells of the mesh.
+    ///
+    /// A triangle whose corners lie on both sides of a level is crossed by one segment, between
+    /// the two of its edges whose ends do. The ends of the segment interpolate all the data of
+    /// the vertices of these edges linearly, and are drawn in `color`. Corners exactly at the
+    /// level count as above it, so a contour along a shared edge is drawn once. The contours share
+    /// the model matrix of the mesh.
+    pub fn contours(
+        &self,
+        values: &[f32],
+        levels: &[f32],
+        color: &Color,
+    ) -> Self
+    {
+        assert_eq!(values.len(), self.num_vertices());
+        let mut out = Self::from_num_lines(0);
+        let vertex = |i: u32| &self.vertices[i as usize * V::len()..(i as usize + 1) * V::len()];
+        for tri in self.triangle_cell_indices().chunks_exact(3) {
+            let edges = [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])];
+            for level in levels {
+                let above = |i: u32| values[i as usize] >= *level;
+                let crossings: Vec<(u32, u32)> =
+                    edges.into_iter().filter(|(a, b)| above(*a) != above(*b)).collect();
+                // a plane crosses either none of the edges of a triangle or two of them
+                if crossings.len() != 2 {
+                    continue;
+                }
+                for (a, b) in crossings {
+                    let (fa, fb) = (values[a as usize], values[b as usize]);
+                    let t = (level - fa) / (fb - fa);
+                    let start = out.vertices.len();
+                    out.vertices
+                        .extend(vertex(a).iter().zip(vertex(b)).map(|(x, y)| x + t * (y - x)));
+                    let offset = start + V::line_color_offset();
+                    out.vertices[offset..offset + 4].copy_from_slice(&color.to_rgba());
+                }
+                let nv = out.num_vertices() as u32;
+                out.indices.extend_from_slice(&[nv - 2, nv - 1]);
+            }
+        }
+        out.model = self.model;
+        out
+    }
+
+    /// Reduces the triangle cells of the mesh to about `target_ratio` of their number, by
+    /// collapsing edges in order of increasing quadric error, after Garland and Heckbert.
+    ///
+    /// Each collapsed edge is replaced by whichever of its two ends and its midpoint is closest
+    /// to the planes of the triangles merged into it. Vertices on boundary edges and on edges
+    /// shared by more than two triangles never move. An edge is only collapsed if its ends have
+    /// no neighbours besides the two opposite corners in common, so the surface stays manifold,
+    /// and if no triangle turns too far, let alone over. When no edge can be collapsed the mesh
+    /// stops short of the target.
+    ///
+    /// The remaining vertices keep their colors. The normals of 3D meshes are recomputed from
+    /// the new triangles, and line cells follow their vertices, those reduced to a point being
+    /// dropped. Line meshes are left unchanged.
+    pub fn simplify(
+        &mut self,
+        target_ratio: f32,
+    )
+    {
+        if !self.is_triangle() {
+            return;
+        }
+        let target = (target_ratio.clamp(0.0, 1.0) * self.num_triangles() as f32).ceil() as usize;
+        let positions = (0..self.num_vertices()).map(|i| self.vertex_position(i)).collect();
+        let mut simplifier = Simplifier::new(positions, &self.indices);
+        simplifier.run(target);
+
+        //{{{ com: compact the remaining vertices and rewrite the cells
+        let mut new_index = vec![u32::MAX; self.num_vertices()];
+        let mut vertices = Vec::with_capacity(self.vertices.len());
+        for (i, position) in simplifier.positions.iter().enumerate() {
+            if simplifier.parent[i] != i as u32 {
+                continue;
+            }
+            new_index[i] = (vertices.len() / V::len()) as u32;
+            let start = vertices.len();
+            vertices.extend_from_slice(&self.vertices[i * V::len()..(i + 1) * V::len()]);
+            let offset = start + V::position_offset();
+            vertices[offset..offset + V::dim()].copy_from_slice(&position.as_slice()[..V::dim()]);
+        }
+        self.indices = simplifier
+            .triangles
+            .iter()
+            .zip(&simplifier.alive)
+            .filter(|(_, alive)| **alive)
+            .flat_map(|(tri, _)| tri.map(|i| new_index[i as usize]))
+            .collect();
+        self.line_indices = self
+            .line_indices
+            .chunks_exact(2)
+            .map(|line| line.iter().map(|i| new_index[simplifier.root(*i) as usize]))
+            .map(|mut line| [line.next().unwrap(), line.next().unwrap()])
+            .filter(|line| line[0] != line[1])
+            .flatten()
+            .collect();
+        self.vertices = vertices;
+        //}}}
+        //{{{ com: recompute the normals of the vertices of the triangles
+        let mut normals = vec![Vec3::zeros(); self.num_vertices()];
+        for tri in self.indices.chunks_exact(3) {
+            let [p1, p2, p3] = [0, 1, 2].map(|k| self.vertex_position(tri[k] as usize));
+            let n = (p2 - p1).cross(&(p3 - p1));
+            for k in 0..3 {
+                normals[tri[k] as usize] += n;
+            }
+        }
+        for (i, normal) in normals.iter().enumerate() {
+            if let Some(normal) = normal.try_normalize(f32::EPSILON) {
+                V::set_normal(&mut self.vertices[i * V::len()..(i + 1) * V::len()], &normal);
+            }
         }
+        //}}}
     }
 }
 //}}}
@@ -297,12 +1183,829 @@ where
 //..................................................................................................
 //}}}
 //}}}
+//{{{ fun: to_world
+/// Applies the model matrix to a position, dividing through by the homogeneous coordinate.
+fn to_world(
+    model: &Mat4,
+    position: &Vec3,
+) -> Vec3
+{
+    model.transform_point(&(*position).into()).coords
+}
+//}}}
+//{{{ fun: position_at
+/// Reads the position of the vertex at `index` from packed vertex data, padding 2D positions with
+/// a zero z-component.
+fn position_at<V: VertexCore>(
+    vertices: &[f32],
+    index: usize,
+) -> Vec3
+{
+    let start = index * V::len();
+    let mut position = Vec3::zeros();
+    for (i, x) in vertices[start..start + V::dim()].iter().enumerate() {
+        position[i] = *x;
+    }
+    position
+}
+//..................................................................................................
+//}}}
+//{{{ fun: push_line_vertex
+/// Appends a vertex at `position`, whose line and triangle colors are both `rgba`, to packed
+/// vertex data. Any other attribute of the vertex is zero.
+fn push_line_vertex<V: VertexCore>(
+    vertices: &mut Vec<f32>,
+    position: &Vec3,
+    rgba: &[f32; 4],
+)
+{
+    let start = vertices.len();
+    vertices.resize(start + V::len(), 0.0);
+    let offset = start + V::position_offset();
+    vertices[offset..offset + V::dim()].copy_from_slice(&position.as_slice()[..V::dim()]);
+    let offset = start + V::line_color_offset();
+    vertices[offset..offset + 4].copy_from_slice(rgba);
+    let offset = start + V::triangle_color_offset();
+    vertices[offset..offset + 4].copy_from_slice(rgba);
+}
+//..................................................................................................
+//}}}
+//{{{ collection: Simplifier
+//{{{ struct: Quadric
+/// The quadric error of a point, the weighted sum of its squared distances to a set of planes,
+/// stored as the upper triangle of a symmetric 4x4 matrix.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric
+{
+    /// Returns the quadric of the plane through `point` with unit normal `normal`.
+    fn from_plane(
+        normal: &Vec3,
+        point: &Vec3,
+        weight: f64,
+    ) -> Self
+    {
+        let [a, b, c] = [normal.x as f64, normal.y as f64, normal.z as f64];
+        let d = -(a * point.x as f64 + b * point.y as f64 + c * point.z as f64);
+        let q = [a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d];
+        Self(q.map(|x| weight * x))
+    }
+
+    fn add(
+        &self,
+        other: &Self,
+    ) -> Self
+    {
+        Self(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+
+    /// Returns the weighted sum of the squared distances of `point` to the planes.
+    fn error(
+        &self,
+        point: &Vec3,
+    ) -> f64
+    {
+        let [x, y, z] = [point.x as f64, point.y as f64, point.z as f64];
+        let q = &self.0;
+        q[0] * x * x + q[4] * y * y + q[7] * z * z + q[9]
+            + 2.0 * (q[1] * x * y + q[2] * x * z + q[3] * x + q[5] * y * z + q[6] * y + q[8] * z)
+    }
+}
+//}}}
+//{{{ struct: Collapse
+/// An edge waiting to be collapsed, along with the versions of its ends when its cost was
+/// computed. Collapses ending at a vertex bump its version, which makes such entries stale.
+struct Collapse
+{
+    cost: f64,
+    edge: [u32; 2],
+    versions: [u32; 2],
+}
+
+impl PartialEq for Collapse
+{
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool
+    {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse
+{
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering>
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse
+{
+    fn cmp(
+        &self,
+        other: &Self,
+    ) -> Ordering
+    {
+        self.cost.total_cmp(&other.cost)
+    }
+}
+//}}}
+//{{{ struct: Simplifier
+/// The working state of [`MeshCore::simplify`], the positions and triangles of the mesh as edges
+/// are collapsed. Collapsing an edge merges its second end into its first, and kills the
+/// triangles which shared it.
+struct Simplifier
+{
+    positions: Vec<Vec3>,
+    quadrics: Vec<Quadric>,
+    triangles: Vec<[u32; 3]>,
+    alive: Vec<bool>,
+    num_alive: usize,
+    /// The triangles around each vertex, dead ones included.
+    vertex_triangles: Vec<Vec<usize>>,
+    /// The vertex each vertex was merged into, or the vertex itself while it remains.
+    parent: Vec<u32>,
+    /// Vertices on the boundary, or on non-manifold edges, which must not move.
+    locked: Vec<bool>,
+    versions: Vec<u32>,
+}
+
+impl Simplifier
+{
+    fn new(
+        positions: Vec<Vec3>,
+        indices: &[u32],
+    ) -> Self
+    {
+        let num_vertices = positions.len();
+        let triangles: Vec<[u32; 3]> =
+            indices.chunks_exact(3).map(|tri| [tri[0], tri[1], tri[2]]).collect();
+        let mut quadrics = vec![Quadric::default(); num_vertices];
+        let mut vertex_triangles = vec![Vec::new(); num_vertices];
+        let mut edge_counts = HashMap::new();
+        for (t, tri) in triangles.iter().enumerate() {
+            let [p1, p2, p3] = tri.map(|i| positions[i as usize]);
+            let n = (p2 - p1).cross(&(p3 - p1));
+            // planes are weighted by the area of their triangles, degenerate ones have none
+            let area = 0.5 * n.norm() as f64;
+            let quadric = n
+                .try_normalize(f32::EPSILON)
+                .map(|n| Quadric::from_plane(&n, &p1, area))
+                .unwrap_or_default();
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                quadrics[a as usize] = quadrics[a as usize].add(&quadric);
+                vertex_triangles[a as usize].push(t);
+                *edge_counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+        let mut locked = vec![false; num_vertices];
+        for ((a, b), count) in edge_counts {
+            if count != 2 {
+                locked[a as usize] = true;
+                locked[b as usize] = true;
+            }
+        }
+        Self {
+            positions: positions,
+            quadrics: quadrics,
+            alive: vec![true; triangles.len()],
+            num_alive: triangles.len(),
+            triangles: triangles,
+            vertex_triangles: vertex_triangles,
+            parent: (0..num_vertices as u32).collect(),
+            locked: locked,
+            versions: vec![0; num_vertices],
+        }
+    }
+
+    /// Returns the vertex `v` was merged into, through any number of collapses.
+    fn root(
+        &self,
+        mut v: u32,
+    ) -> u32
+    {
+        while self.parent[v as usize] != v {
+            v = self.parent[v as usize];
+        }
+        v
+    }
+
+    /// Returns the live triangles around `v`.
+    fn triangles_around(
+        &self,
+        v: u32,
+    ) -> impl Iterator<Item = usize> + '_
+    {
+        self.vertex_triangles[v as usize]
+            .iter()
+            .copied()
+            .filter(|t| self.alive[*t])
+    }
+
+    /// Returns the vertices sharing a live triangle with `v`.
+    fn neighbours(
+        &self,
+        v: u32,
+    ) -> HashSet<u32>
+    {
+        self.triangles_around(v)
+            .flat_map(|t| self.triangles[t])
+            .filter(|n| *n != v)
+            .collect()
+    }
+
+    /// Returns the cost of collapsing the edge from `a` to `b` and where the merged vertex goes,
+    /// or `None` if either end is locked.
+    fn cost(
+        &self,
+        a: u32,
+        b: u32,
+    ) -> Option<(f64, Vec3)>
+    {
+        if self.locked[a as usize] || self.locked[b as usize] {
+            return None;
+        }
+        let quadric = self.quadrics[a as usize].add(&self.quadrics[b as usize]);
+        let (pa, pb) = (self.positions[a as usize], self.positions[b as usize]);
+        [pa, pb, 0.5 * (pa + pb)]
+            .into_iter()
+            .map(|p| (quadric.error(&p), p))
+            .min_by(|x, y| x.0.total_cmp(&y.0))
+    }
+
+    /// Returns whether moving `a` and `b` to `position` keeps the surface manifold and turns no
+    /// triangle by more than 60 degrees. Smaller turns can still add up over many collapses to
+    /// turn a triangle over, hence the margin.
+    fn can_collapse(
+        &self,
+        a: u32,
+        b: u32,
+        position: &Vec3,
+    ) -> bool
+    {
+        let shared = self
+            .triangles_around(a)
+            .filter(|t| self.triangles[*t].contains(&b))
+            .count();
+        if shared != 2 || self.neighbours(a).intersection(&self.neighbours(b)).count() != 2 {
+            return false;
+        }
+        self.triangles_around(a).chain(self.triangles_around(b)).all(|t| {
+            let tri = self.triangles[t];
+            if tri.contains(&a) && tri.contains(&b) {
+                return true;
+            }
+            let old = tri.map(|i| self.positions[i as usize]);
+            let new = tri.map(|i| {
+                if i == a || i == b { *position } else { self.positions[i as usize] }
+            });
+            let old_normal = (old[1] - old[0]).cross(&(old[2] - old[0]));
+            let new_normal = (new[1] - new[0]).cross(&(new[2] - new[0]));
+            old_normal.dot(&new_normal) > 0.5 * old_normal.norm() * new_normal.norm()
+        })
+    }
+
+    /// Merges `b` into `a`, moving `a` to `position`.
+    fn collapse(
+        &mut self,
+        a: u32,
+        b: u32,
+        position: Vec3,
+    )
+    {
+        self.positions[a as usize] = position;
+        self.quadrics[a as usize] = self.quadrics[a as usize].add(&self.quadrics[b as usize]);
+        self.parent[b as usize] = a;
+        for t in std::mem::take(&mut self.vertex_triangles[b as usize]) {
+            if !self.alive[t] {
+                continue;
+            }
+            if self.triangles[t].contains(&a) {
+                self.alive[t] = false;
+                self.num_alive -= 1;
+            } else {
+                for i in self.triangles[t].iter_mut().filter(|i| **i == b) {
+                    *i = a;
+                }
+                self.vertex_triangles[a as usize].push(t);
+            }
+        }
+        self.versions[a as usize] += 1;
+        self.versions[b as usize] += 1;
+    }
+
+    /// Queues the collapse of every edge from `v`.
+    fn push_edges(
+        &self,
+        v: u32,
+        heap: &mut BinaryHeap<Reverse<Collapse>>,
+    )
+    {
+        for n in self.neighbours(v) {
+            if let Some((cost, _)) = self.cost(v, n) {
+                heap.push(Reverse(Collapse {
+                    cost: cost,
+                    edge: [v, n],
+                    versions: [self.versions[v as usize], self.versions[n as usize]],
+                }));
+            }
+        }
+    }
+
+    /// Collapses the cheapest edges until at most `target` triangles are left, or none can be.
+    fn run(
+        &mut self,
+        target: usize,
+    )
+    {
+        let mut heap = BinaryHeap::new();
+        for v in 0..self.positions.len() as u32 {
+            self.push_edges(v, &mut heap);
+        }
+        while self.num_alive > target {
+            let Some(Reverse(collapse)) = heap.pop() else {
+                break;
+            };
+            let [a, b] = collapse.edge;
+            if collapse.versions != [self.versions[a as usize], self.versions[b as usize]] {
+                continue;
+            }
+            let Some((_, position)) = self.cost(a, b) else {
+                continue;
+            };
+            if !self.can_collapse(a, b, &position) {
+                continue;
+            }
+            self.collapse(a, b, position);
+            self.push_edges(a, &mut heap);
+        }
+    }
+}
+//}}}
+//..................................................................................................
+//}}}
+//{{{ fun: ray_triangle_intersection
+/// Intersects a ray with a triangle using the Moller-Trumbore algorithm, returning the distance
+/// along the ray to the hit point. Hits on the edges of the triangle count, hits behind the
+/// origin of the ray and rays parallel to the triangle do not.
+fn ray_triangle_intersection(
+    origin: &Vec3,
+    direction: &Vec3,
+    tri: &[Vec3; 3],
+) -> Option<f32>
+{
+    const EPS: f32 = 1e-7;
+
+    let e1 = tri[1] - tri[0];
+    let e2 = tri[2] - tri[0];
+    let p = direction.cross(&e2);
+    let det = e1.dot(&p);
+    if det.abs() < EPS {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    let s = origin - tri[0];
+    let u = s.dot(&p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(&e1);
+    let v = direction.dot(&q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = e2.dot(&q) * inv_det;
+    (t >= 0.0).then_some(t)
+}
+//..................................................................................................
+//}}}
 
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
 mod tests
 {
-  
+    use crate::common::{CellType, Color, MeshColorMode, MeshOptions, Vec2, Vec3};
+    use crate::d2::Mesh2D;
+    use crate::d3::{ColorBy, Mesh, Mesh3D, SphereDescriptor};
+
+    #[test]
+    fn mixed_cells_test()
+    {
+        let mut mesh = Mesh::from_num_triangles(1);
+        let (v1, v2, v3) = (Vec3::zeros(), Vec3::x(), Vec3::y());
+        mesh.add_triangle(&v1, &v2, &v3, &Color::Black, &Color::Green);
+        assert!(!mesh.has_lines());
+
+        mesh.append_line_indices(&[0, 1, 1, 2, 2, 0]);
+        assert!(mesh.has_lines() && mesh.has_triangles());
+        assert_eq!(mesh.triangle_cell_indices(), &[0, 1, 2]);
+        assert_eq!(mesh.line_cell_indices(), &[0, 1, 1, 2, 2, 0]);
+
+        // merged line cells are shifted onto the merged vertices
+        let other = mesh.clone();
+        mesh.merge(other);
+        assert_eq!(mesh.triangle_cell_indices(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(&mesh.line_cell_indices()[6..], &[3, 4, 4, 5, 5, 3]);
+
+        // a line mesh keeps all its lines in the one index set
+        let mut lines = Mesh::from_num_lines(1);
+        lines.add_line(&v1, &v2, &Color::Black, &Color::Black);
+        lines.append_line_indices(&[1, 0]);
+        assert_eq!(lines.line_cell_indices(), &[0, 1, 1, 0]);
+        assert!(lines.triangle_cell_indices().is_empty());
+    }
+
+    #[test]
+    fn overlay_test()
+    {
+        let mut mesh = Mesh::from_num_lines(1);
+        mesh.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Red, &Color::Red);
+        assert!(!mesh.is_overlay());
+
+        mesh.set_overlay(true);
+        assert!(mesh.is_overlay());
+
+        // the flag is kept when merging into an overlay mesh
+        let other = mesh.clone();
+        mesh.merge(other);
+        assert!(mesh.is_overlay());
+    }
+
+    #[test]
+    fn line_depth_bias_test()
+    {
+        let mut mesh = Mesh::from_num_lines(1);
+        assert_eq!(mesh.line_depth_bias(), 0);
+
+        mesh.set_line_depth_bias(-2);
+        assert_eq!(mesh.line_depth_bias(), -2);
+    }
+
+    #[test]
+    fn ray_intersection_test()
+    {
+        let mut mesh = Mesh::from_num_triangles(2);
+        let (v1, v2, v3) = (Vec3::zeros(), Vec3::x(), Vec3::y());
+        mesh.add_triangle(&v1, &v2, &v3, &Color::Black, &Color::Green);
+        let up = Vec3::z();
+        mesh.add_triangle(&(v1 + up), &(v2 + up), &(v3 + up), &Color::Black, &Color::Green);
+        assert_eq!(mesh.triangles().count(), 2);
+
+        // the nearest of the two stacked triangles is hit
+        let origin = Vec3::new(0.25, 0.25, 3.0);
+        let t = mesh.ray_intersection(&origin, &-Vec3::z()).unwrap();
+        assert!((t - 2.0).abs() < 1e-6);
+
+        // edges count as hits, but points outside or behind the ray do not
+        assert!(mesh.ray_intersection(&Vec3::new(0.5, 0.5, 3.0), &-Vec3::z()).is_some());
+        assert!(mesh.ray_intersection(&Vec3::new(0.75, 0.75, 3.0), &-Vec3::z()).is_none());
+        assert!(mesh.ray_intersection(&origin, &Vec3::z()).is_none());
+
+        let mut lines = Mesh::from_num_lines(1);
+        lines.add_line(&v1, &v2, &Color::Black, &Color::Black);
+        assert!(lines.ray_intersection(&origin, &-Vec3::z()).is_none());
+    }
+
+    #[test]
+    fn triangle_edge_indices_test()
+    {
+        // two triangles sharing the edge 1-2
+        let mut mesh = Mesh::from_num_triangles(2);
+        mesh.indices = vec![0, 1, 2, 2, 1, 3];
+        assert_eq!(mesh.triangle_edge_indices(), vec![0, 1, 1, 2, 2, 0, 1, 3, 3, 2]);
+
+        let lines = Mesh::from_num_lines(1);
+        assert!(lines.triangle_edge_indices().is_empty());
+    }
+
+    #[test]
+    fn triangle_edge_vertices_test()
+    {
+        let mut mesh = Mesh::from_num_triangles(1);
+        let (v1, v2, v3) = (Vec3::zeros(), Vec3::x(), Vec3::y());
+        mesh.add_triangle(&v1, &v2, &v3, &Color::Red, &Color::Blue);
+        // three edges of two vertices each, whose positions lead their data
+        let vertices = mesh.triangle_edge_vertices();
+        let len = vertices.len() / 6;
+        assert_eq!(len * 6, vertices.len());
+        let positions: Vec<Vec3> = vertices
+            .chunks_exact(len)
+            .map(|vertex| Vec3::new(vertex[0], vertex[1], vertex[2]))
+            .collect();
+        assert_eq!(positions, vec![v1, v2, v2, v3, v3, v1]);
+
+        let lines = Mesh::from_num_lines(1);
+        assert!(lines.triangle_edge_vertices().is_empty());
+    }
+
+    #[test]
+    fn color_mode_test()
+    {
+        let mut mesh = Mesh::from_num_triangles(1);
+        let (v1, v2, v3) = (Vec3::zeros(), Vec3::x(), Vec3::y());
+        mesh.add_triangle(&v1, &v2, &v3, &Color::Red, &Color::Blue);
+        assert_eq!(mesh.color_mode(), MeshColorMode::PerVertex);
+        let len = mesh.vertex_slice().len() / 3;
+
+        // position and normal of each vertex
+        let compact = mesh.compact_vertices();
+        assert_eq!(compact.len(), 3 * 6);
+        assert_eq!(&compact[6..9], v2.as_slice());
+        assert_eq!(&compact[9..12], &mesh.vertex_slice()[len + 3..len + 6]);
+
+        // the edges take the uniform color in place of the line color of their vertices
+        mesh.set_color_mode(MeshColorMode::Uniform(Color::Green));
+        let vertices = mesh.triangle_edge_vertices();
+        for vertex in vertices.chunks_exact(len) {
+            // the line color follows the position and the normal
+            assert_eq!(&vertex[6..10], &Color::Green.to_rgba());
+        }
+
+        // an edge color overrides both the uniform color and the line color of the vertices
+        mesh.set_options(MeshOptions { edge_color: Some(Color::Black), ..MeshOptions::default() });
+        assert_eq!(mesh.edge_color(), Some(Color::Black));
+        mesh.set_color_mode(MeshColorMode::PerVertex);
+        assert_eq!(mesh.edge_color(), Some(Color::Black));
+        for vertex in mesh.triangle_edge_vertices().chunks_exact(len) {
+            assert_eq!(&vertex[6..10], &Color::Black.to_rgba());
+        }
+        let vertices = mesh.vertices_with_line_color(&Color::Black);
+        let originals = mesh.vertex_slice().chunks_exact(len);
+        for (vertex, original) in vertices.chunks_exact(len).zip(originals) {
+            assert_eq!(&vertex[6..10], &Color::Black.to_rgba());
+            assert_eq!(&vertex[..6], &original[..6]);
+            assert_eq!(&vertex[10..], &original[10..]);
+        }
+        mesh.set_options(MeshOptions::default());
+        assert_eq!(mesh.edge_color(), None);
+
+        // position and screen offset of each 2D vertex
+        let mut mesh = crate::d2::Mesh::from_num_lines(1);
+        let (a, b) = (Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0));
+        mesh.add_line(&a, &b, &Color::Red, &Color::Red);
+        assert_eq!(mesh.compact_vertices(), vec![1.0, 2.0, 0.0, 0.0, 3.0, 4.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn contours_test()
+    {
+        // a unit square split into two triangles, with values rising along x from 0 to 4
+        let mut mesh = Mesh::from_num_triangles(2);
+        let corners = [Vec3::zeros(), Vec3::x(), Vec3::new(1.0, 1.0, 0.0), Vec3::y()];
+        mesh.add_triangle(&corners[0], &corners[1], &corners[2], &Color::Black, &Color::Red);
+        mesh.add_triangle(&corners[0], &corners[2], &corners[3], &Color::Black, &Color::Red);
+        let values: Vec<f32> = (0..mesh.num_vertices())
+            .map(|i| 4.0 * mesh.vertex_position(i).x)
+            .collect();
+
+        let contours = mesh.contours(&values, &[1.0, 2.0, 3.0], &Color::Blue);
+        assert!(contours.is_line());
+        // each level crosses both triangles, the two segments meeting on the diagonal
+        assert_eq!(contours.num_indices(), 2 * 2 * 3);
+        for line in contours.index_slice().chunks_exact(2) {
+            let p = contours.vertex_position(line[0] as usize);
+            let q = contours.vertex_position(line[1] as usize);
+            // every segment runs across the square at a constant x
+            assert!((p.x - q.x).abs() < 1e-6);
+            let level = 4.0 * p.x;
+            assert!([1.0, 2.0, 3.0].iter().any(|l: &f32| (l - level).abs() < 1e-5));
+        }
+        // the contours cover the whole height of the square, evenly spaced
+        for x in [0.25, 0.5, 0.75] {
+            let length: f32 = contours
+                .index_slice()
+                .chunks_exact(2)
+                .map(|line| {
+                    let p = contours.vertex_position(line[0] as usize);
+                    let q = contours.vertex_position(line[1] as usize);
+                    if (p.x - x).abs() < 1e-6 { (p - q).norm() } else { 0.0 }
+                })
+                .sum();
+            assert!((length - 1.0).abs() < 1e-5);
+        }
+        // levels outside the range of the values give no contours
+        assert_eq!(mesh.contours(&values, &[-1.0, 5.0], &Color::Blue).num_indices(), 0);
+    }
+
+    #[test]
+    fn simplify_test()
+    {
+        let mut sphere = Mesh::create_sphere(&SphereDescriptor {
+            origin: Vec3::new(1.0, 2.0, 3.0),
+            axis: Vec3::z(),
+            radius: 2.0,
+            n_lat: 40,
+            n_long: 40,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        let num_triangles = sphere.num_triangles();
+        let (min, max) = sphere.bounding_box().unwrap();
+
+        sphere.simplify(0.25);
+        assert!(sphere.num_triangles() <= num_triangles / 4 + 1);
+        assert!(sphere.index_slice().iter().all(|i| (*i as usize) < sphere.num_vertices()));
+        let (new_min, new_max) = sphere.bounding_box().unwrap();
+        assert!((new_min - min).amax() < 0.1 && (new_max - max).amax() < 0.1);
+        // the recomputed normals still point outwards
+        for i in 0..sphere.num_vertices() {
+            let p = sphere.vertex_position(i);
+            let normal = Vec3::from_column_slice(&sphere.vertex_slice()[i * 14 + 3..i * 14 + 6]);
+            assert!(normal.dot(&(p - Vec3::new(1.0, 2.0, 3.0))) > 0.0);
+        }
+
+        // line meshes are left alone
+        let mut lines = Mesh::from_num_lines(1);
+        lines.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+        lines.simplify(0.0);
+        assert_eq!(lines.num_indices(), 2);
+    }
+
+    #[test]
+    fn bounding_box_test()
+    {
+        let mut mesh = Mesh::from_num_lines(2);
+        assert!(mesh.bounding_box().is_none());
+
+        let (v1, v2) = (Vec3::new(1.0, -2.0, 0.5), Vec3::new(-1.0, 3.0, 0.0));
+        mesh.add_line(&v1, &v2, &Color::Black, &Color::Black);
+        mesh.add_line(&Vec3::zeros(), &Vec3::new(0.0, 0.0, 4.0), &Color::Black, &Color::Black);
+        let (min, max) = mesh.bounding_box().unwrap();
+        assert_eq!(min, Vec3::new(-1.0, -2.0, 0.0));
+        assert_eq!(max, Vec3::new(1.0, 3.0, 4.0));
+
+        // 12 edges along the three axes, 4 along each
+        let edges = mesh.bounding_box_edges(&Color::Red);
+        assert!(edges.is_line());
+        assert_eq!(edges.num_indices(), 24);
+        for i in (0..edges.num_vertices()).step_by(2) {
+            let d = edges.vertex_position(i + 1) - edges.vertex_position(i);
+            let axis = d.iamax();
+            assert_eq!(d.iter().filter(|x| **x != 0.0).count(), 1);
+            assert_eq!(d[axis], (max - min)[axis]);
+        }
+        assert_eq!(edges.bounding_box(), Some((min, max)));
+
+        // a 2D mesh gives the 4 edges of a rectangle, and no vertices no edges at all
+        let mut mesh = crate::d2::Mesh::from_num_lines(1);
+        mesh.add_line(&Vec2::new(1.0, 2.0), &Vec2::new(3.0, 5.0), &Color::Red, &Color::Red);
+        assert_eq!(mesh.bounding_box_edges(&Color::Red).num_indices(), 8);
+        assert!(Mesh::from_num_lines(0).bounding_box_edges(&Color::Red).is_empty());
+    }
+
+    #[test]
+    fn translate_scale_test()
+    {
+        let mut triangle = Mesh::from_num_triangles(1);
+        let (v1, v2, v3) = (Vec3::zeros(), Vec3::x(), Vec3::y());
+        triangle.add_triangle(&v1, &v2, &v3, &Color::Black, &Color::Red);
+        let normals = triangle.normal_lines(1.0, &Color::Black).unwrap();
+
+        let offset = Vec3::new(1.0, -2.0, 3.0);
+        triangle.translate(&offset);
+        assert_eq!(triangle.vertex_position(0), v1 + offset);
+        assert_eq!(triangle.vertex_position(1), v2 + offset);
+        assert_eq!(triangle.vertex_position(2), v3 + offset);
+
+        // the point scaled about stays put, the others move twice as far from it
+        triangle.scale_uniform(2.0, &offset);
+        assert_eq!(triangle.vertex_position(0), offset);
+        assert_eq!(triangle.vertex_position(1), offset + 2.0 * v2);
+        assert_eq!(triangle.vertex_position(2), offset + 2.0 * v3);
+
+        // the normals and colors are untouched
+        let moved = triangle.normal_lines(1.0, &Color::Black).unwrap();
+        for i in (0..moved.num_vertices()).step_by(2) {
+            let d = moved.vertex_position(i + 1) - moved.vertex_position(i);
+            assert_eq!(d, normals.vertex_position(i + 1) - normals.vertex_position(i));
+        }
+
+        // 2D meshes are moved in their plane
+        let mut triangle = crate::d2::Mesh::from_num_triangles(1);
+        let (a, b, c) = (Vec2::zeros(), Vec2::x(), Vec2::y());
+        triangle.add_triangle(&a, &b, &c, &Color::Black, &Color::Red);
+        let colors = triangle.vertex_slice()[2..10].to_vec();
+        triangle.translate(&Vec2::new(0.5, 1.5));
+        assert_eq!(triangle.vertex_position(1), Vec3::new(1.5, 1.5, 0.0));
+        triangle.scale_uniform(0.5, &Vec2::zeros());
+        assert_eq!(triangle.vertex_position(2), Vec3::new(0.25, 1.25, 0.0));
+        assert_eq!(&triangle.vertex_slice()[2..10], colors.as_slice());
+    }
+
+    #[test]
+    fn normal_lines_test()
+    {
+        let sphere = Mesh::create_sphere(&SphereDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            n_lat: 8,
+            n_long: 8,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        // the normals of a sphere point straight out of it
+        let normals = sphere.normal_lines(0.5, &Color::Blue).unwrap();
+        assert_eq!(normals.num_vertices(), 2 * sphere.num_vertices());
+        for i in (0..normals.num_vertices()).step_by(2) {
+            let (start, end) = (normals.vertex_position(i), normals.vertex_position(i + 1));
+            assert!((end - start * 1.5).norm() < 1e-4);
+        }
+
+        // 2D vertices have no normals
+        let mut triangle = crate::d2::Mesh::from_num_triangles(1);
+        let (a, b, c) = (Vec2::zeros(), Vec2::x(), Vec2::y());
+        triangle.add_triangle(&a, &b, &c, &Color::Black, &Color::Red);
+        assert!(triangle.normal_lines(0.5, &Color::Blue).is_none());
+    }
+
+    #[test]
+    fn diagnostics_test()
+    {
+        // two triangles sharing the edge from the origin along x, a third makes it non-manifold
+        let mut fan = Mesh::from_num_triangles(3);
+        for p in [Vec3::zeros(), Vec3::x(), Vec3::y(), Vec3::z(), -Vec3::y()] {
+            fan.add_vertex(&p, &Vec3::z(), &Color::Black, &Color::Red);
+        }
+        fan.add_triangle_indices(0, 1, 2).unwrap();
+        fan.add_triangle_indices(1, 0, 3).unwrap();
+        let diagnostics = fan.diagnostics();
+        assert_eq!(diagnostics.num_vertices, 5);
+        assert_eq!(diagnostics.num_triangles, 2);
+        assert_eq!(diagnostics.bounds, Some((-Vec3::y(), Vec3::new(1.0, 1.0, 1.0))));
+        assert!(diagnostics.is_manifold);
+        assert!(!diagnostics.has_degenerate);
+        assert_eq!(diagnostics.non_finite_count, 0);
+
+        fan.add_triangle_indices(0, 1, 4).unwrap();
+        assert!(!fan.diagnostics().is_manifold);
+
+        // a NaN vertex is counted and left out of the bounds, and a collinear triangle flagged
+        fan.add_vertex(&Vec3::new(f32::NAN, 0.0, 0.0), &Vec3::z(), &Color::Black, &Color::Red);
+        fan.append_indices(&[0, 1, 1]);
+        let diagnostics = fan.diagnostics();
+        assert_eq!(diagnostics.non_finite_count, 1);
+        assert_eq!(diagnostics.bounds, Some((-Vec3::y(), Vec3::new(1.0, 1.0, 1.0))));
+        assert!(diagnostics.has_degenerate);
+
+        // indices past the last vertex are flagged, not read
+        let mut line = Mesh::from_num_lines(1);
+        line.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+        line.append_indices(&[1, 7]);
+        assert!(line.diagnostics().has_degenerate);
+        assert!(Mesh::from_num_lines(0).diagnostics().bounds.is_none());
+    }
+
+    #[test]
+    fn with_dashed_lines_test()
+    {
+        // a polyline of two cells, 1 and 1.5 long
+        let mut polyline = crate::d2::Mesh::from_num_lines(2);
+        for x in [0.0, 1.0, 2.5] {
+            polyline.add_vertex(&Vec2::new(x, 0.0), &Color::Red, &Color::Red);
+        }
+        polyline.add_line_indices(0, 1).unwrap();
+        polyline.add_line_indices(1, 2).unwrap();
+
+        // the pattern carries on over the shared vertex, ending on a short dash
+        let dashed = polyline.with_dashed_lines(0.4, 0.2);
+        let dashes: Vec<(f32, f32)> = dashed
+            .line_cell_indices()
+            .chunks_exact(2)
+            .map(|d| (d[0] as usize, d[1] as usize))
+            .map(|(a, b)| (dashed.vertex_position(a).x, dashed.vertex_position(b).x))
+            .collect();
+        let expected = [(0.0, 0.4), (0.6, 1.0), (1.2, 1.6), (1.8, 2.2), (2.4, 2.5)];
+        assert_eq!(dashes.len(), expected.len());
+        for ((a, b), (ea, eb)) in dashes.iter().zip(expected) {
+            assert!((a - ea).abs() < 1e-5 && (b - eb).abs() < 1e-5);
+        }
+        // the new vertices interpolate the data of the line ends
+        let len = polyline.vertex_slice().len();
+        assert_eq!(&dashed.vertex_slice()[..len], polyline.vertex_slice());
+        assert!(dashed.vertex_slice().chunks_exact(12).all(|v| v[2..6] == Color::Red.to_rgba()));
+
+        // without a gap the lines stay solid
+        let solid = polyline.with_dashed_lines(0.4, 0.0);
+        assert_eq!(solid.line_cell_indices(), polyline.line_cell_indices());
+        assert_eq!(solid.num_vertices(), 3);
+    }
 }
 //}}}
\ No newline at end of file
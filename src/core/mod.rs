@@ -3,4 +3,4 @@ mod mesh;
 mod state;
 
 pub use mesh::{VertexCore, VertexViewCore, MeshCore};
-pub use state::{StateCore, ViewStateCore};
+pub use state::{MeshEditError, StateCore, ViewStateCore};
@@ -4,16 +4,24 @@
 //--------------------------------------------------------------------------------------------------
 
 //{{{ crate imports
+use crate::common::{
+    clip_box_planes, AddMeshError, BudgetError, ClipMode, Color, DepthBuffer, DrawContext,
+    FaceCulling, FrontFace, Mat4, MeshColorMode, MeshOptions, SceneBudget, SceneStats,
+    TessellationQuality, Vec2, Vec3, ViewSensitivity, MAX_CLIP_PLANES,
+};
 use crate::core::{MeshCore, VertexCore};
 use crate::depth_texture as dt;
 use crate::events::EventController;
 //}}}
 //{{{ std imports
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Index;
 use std::sync::{Arc, Mutex};
 //}}}
 //{{{ dep imports
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::oneshot;
 use topohedral_tracing::*;
 use wgpu::{self, util::DeviceExt, Device, Features};
 use winit::window::Window;
@@ -24,6 +32,7 @@ use winit::{self, event::WindowEvent, event_loop::ActiveEventLoop};
 //{{{ collection: constants
 const SHADER_2D: &str = include_str!("../d2/shader2d.wgsl");
 const SHADER_3D: &str = include_str!("../d3/shader3d.wgsl");
+const SHADER_BACKGROUND: &str = include_str!("background.wgsl");
 //}}}
 //{{{ fun: shader_module_desc
 /// This function creates a WGPU shader module descriptor based on the provided dimension value.
@@ -66,29 +75,16 @@ fn shader_module_desc(d: usize) -> wgpu::ShaderModuleDescriptor<'static> {
     //}}}
 }
 //}}}
-//{{{ fun: create_render_pipelines
-/// Creates the render pipelines for the application.
-///
-/// This function creates the necessary render pipelines for rendering lines, triangle edges, and triangle faces.
-/// It takes in the device, surface configuration, depth texture, vertex buffer layout, and a descriptor index.
-/// The function returns the created render pipelines, which can be used for rendering the corresponding geometry.
-fn create_render_pipelines(
+//{{{ fun: create_render_pipeline_layout
+/// Creates the pipeline layout of the render pipelines, which binds the camera uniform at group 0
+/// and the model uniform of the mesh drawn at group 1, with the layout `mesh_model_layout`. The
+/// pipelines drawing meshes with a uniform color also bind that color at group 2, with the given
+/// layout.
+fn create_render_pipeline_layout(
     device: &Device,
-    config: &wgpu::SurfaceConfiguration,
-    depth_texture: &dt::DepthTexture,
-    vert_buf_layout: &[wgpu::VertexBufferLayout],
-    d: usize,
-) -> (
-    wgpu::RenderPipeline,
-    Option<wgpu::RenderPipeline>,
-    wgpu::RenderPipeline,
-) {
-    //{{{ com: compute shader
-    //{{{ trace
-    info!("Computing the shader");
-    //}}}
-    let shader = device.create_shader_module(shader_module_desc(d));
-    //}}}
+    mesh_model_layout: &wgpu::BindGroupLayout,
+    mesh_color_layout: Option<&wgpu::BindGroupLayout>,
+) -> wgpu::PipelineLayout {
     //{{{ com: compute camera bind group layout
     //{{{ trace
     info!("Computing the camera bind group layout");
@@ -97,7 +93,7 @@ fn create_render_pipelines(
         device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -112,30 +108,120 @@ fn create_render_pipelines(
     //{{{ trace
     info!("Computing the render pipeline layout");
     //}}}
-    let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+    let mut bind_group_layouts = vec![&camera_bind_group_layout, mesh_model_layout];
+    bind_group_layouts.extend(mesh_color_layout);
+    device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: Some("Render Pipeline Layout 1"),
-        bind_group_layouts: &[&camera_bind_group_layout],
+        bind_group_layouts: &bind_group_layouts,
         push_constant_ranges: &[],
-    });
-    //}}}
-    //{{{ com: compute line render pipeline
-    //{{{ trace
-    info!("Computing the line render pipeline");
+    })
     //}}}
-    let line_render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: Some("Line Render Pipeline"),
-        layout: Some(&render_pipeline_layout),
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_mesh_uniform_bind_group_layout
+/// Creates the layout of a bind group holding a single uniform buffer of a mesh: its model matrix,
+/// or the line and triangle colors of a mesh drawn with a uniform color.
+fn create_mesh_uniform_bind_group_layout(device: &Device, label: &str) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+        label: Some(label),
+    })
+}
+//..................................................................................................
+//}}}
+//{{{ constant: MAX_LAYER
+/// The highest layer a mesh can be drawn in, and the negation of the lowest, see
+/// [`MeshCore::set_layer`]. Layers past it share its depth.
+const MAX_LAYER: i32 = 2047;
+//}}}
+//{{{ fun: layer_depth
+/// Returns the depth 2D meshes in `layer` are drawn at, halfway into the depth range for layer 0
+/// and nearer the camera for each layer above it. The depths of neighbouring layers are told apart
+/// by the 16-bit depth format.
+fn layer_depth(layer: i32) -> f32 {
+    0.5 - 0.5 * layer.clamp(-MAX_LAYER, MAX_LAYER) as f32 / (MAX_LAYER + 1) as f32
+}
+//}}}
+//{{{ struct: ModelUniform
+/// The model uniform of a mesh: its model matrix, the inverse transpose of the matrix, which
+/// carries the normals of 3D meshes, the depth of the layer of 2D meshes, and the offsets added
+/// to the depth of its line cells and of the fallback edges of its triangle cells, see
+/// [`dt::DepthMode::line_offset`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct ModelUniform {
+    model: [[f32; 4]; 4],
+    normal_matrix: [[f32; 4]; 4],
+    layer_depth: f32,
+    line_depth_offset: f32,
+    edge_depth_offset: f32,
+    _padding: f32,
+}
+//}}}
+//{{{ fun: model_uniform
+/// Returns the model uniform of a mesh placed by `model` in `layer`, whose line cells have the
+/// depth bias `line_depth_bias`, drawn with the depth mode `depth_mode`. A singular model matrix
+/// flattens the mesh, and its normals are left as they are.
+fn model_uniform(
+    model: &Mat4,
+    layer: i32,
+    line_depth_bias: i32,
+    depth_mode: dt::DepthMode,
+) -> ModelUniform {
+    let normal_matrix = model
+        .try_inverse()
+        .map_or_else(Mat4::identity, |inverse| inverse.transpose());
+    ModelUniform {
+        model: (*model).into(),
+        normal_matrix: normal_matrix.into(),
+        layer_depth: layer_depth(layer),
+        line_depth_offset: depth_mode.line_offset(line_depth_bias),
+        edge_depth_offset: depth_mode.line_offset(FALLBACK_EDGE_DEPTH_BIAS),
+        _padding: 0.0,
+    }
+}
+//}}}
+//{{{ fun: create_line_render_pipeline
+/// Creates a render pipeline for line list geometry with the given vertex entry point and depth
+/// stencil state.
+///
+/// All the line pipelines share the same shader and layout, and differ only in how they test
+/// against and write to the depth buffer, or in the vertex entry point, which sets the color the
+/// lines are drawn with and the offset added to their depth.
+fn create_line_render_pipeline(
+    device: &Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    vert_buf_layout: &[wgpu::VertexBufferLayout],
+    label: &str,
+    vs_entry_point: &str,
+    depth_stencil: wgpu::DepthStencilState,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
         vertex: wgpu::VertexState {
-            module: &shader,
-            entry_point: "vs_main",
+            module: shader,
+            entry_point: vs_entry_point,
             buffers: vert_buf_layout,
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         },
         fragment: Some(wgpu::FragmentState {
-            module: &shader,
+            module: shader,
             entry_point: "fs_main_line",
             targets: &[Some(wgpu::ColorTargetState {
-                format: config.format,
+                format: format,
                 blend: Some(wgpu::BlendState {
                     color: wgpu::BlendComponent::REPLACE,
                     alpha: wgpu::BlendComponent::REPLACE,
@@ -148,10 +234,250 @@ fn create_render_pipelines(
             topology: wgpu::PrimitiveTopology::LineList,
             ..Default::default()
         },
+        depth_stencil: Some(depth_stencil),
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+    })
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_thick_edge_render_pipeline
+/// Creates the render pipeline drawing the edges of triangle cells as quads of the edge width set
+/// in the view uniform, for edges wider or narrower than the single pixel of line polygon mode.
+///
+/// Each instance is one edge, read from a buffer holding the two vertices of every edge one after
+/// the other. The near vertex keeps the attributes of `vert_buf_layout`, those of the far vertex
+/// follow at the next shader locations, and the shader expands the pair into the six corners of
+/// a quad. The depth bias is that of the triangle edge pipeline, which applies as the quads are
+/// triangles.
+fn create_thick_edge_render_pipeline(
+    device: &Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth_mode: dt::DepthMode,
+    vert_buf_layout: &wgpu::VertexBufferLayout,
+) -> wgpu::RenderPipeline {
+    //{{{ trace
+    info!("Computing the thick edge render pipeline");
+    //}}}
+    let num_attributes = vert_buf_layout.attributes.len() as u32;
+    let far_attributes = vert_buf_layout.attributes.iter().map(|attribute| wgpu::VertexAttribute {
+        offset: attribute.offset + vert_buf_layout.array_stride,
+        shader_location: attribute.shader_location + num_attributes,
+        format: attribute.format,
+    });
+    let attributes: Vec<wgpu::VertexAttribute> = vert_buf_layout
+        .attributes
+        .iter()
+        .copied()
+        .chain(far_attributes)
+        .collect();
+    let edge_buf_layout = wgpu::VertexBufferLayout {
+        array_stride: 2 * vert_buf_layout.array_stride,
+        step_mode: wgpu::VertexStepMode::Instance,
+        attributes: &attributes,
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Thick Edge Render Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main_thick_edge",
+            buffers: &[edge_buf_layout],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main_line",
+            targets: &[Some(wgpu::ColorTargetState {
+                format: format,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent::REPLACE,
+                    alpha: wgpu::BlendComponent::REPLACE,
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            strip_index_format: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: depth_mode.format,
+            depth_write_enabled: true,
+            depth_compare: depth_mode.compare(),
+            stencil: wgpu::StencilState::default(),
+            bias: depth_mode.bias(FALLBACK_EDGE_DEPTH_BIAS),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+    })
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_highlight_render_pipelines
+/// Creates the render pipelines used to draw the highlighted mesh: one for its line cells and, if
+/// the device supports line polygon mode, one for the edges of its triangle cells.
+///
+/// Both draw with the highlight color of the view uniform instead of the vertex colors, always
+/// pass the depth test and do not write depth, so the highlight is drawn on top of everything.
+/// `vs_entry_point` is the vertex entry point substituting that color.
+fn create_highlight_render_pipelines(
+    device: &Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth_mode: dt::DepthMode,
+    vert_buf_layout: &[wgpu::VertexBufferLayout],
+    vs_entry_point: &str,
+) -> (wgpu::RenderPipeline, Option<wgpu::RenderPipeline>) {
+    let on_top = wgpu::DepthStencilState {
+        format: depth_mode.format,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    };
+    //{{{ com: compute highlight line render pipeline
+    //{{{ trace
+    info!("Computing the highlight line render pipeline");
+    //}}}
+    let highlight_line_render_pipeline = create_line_render_pipeline(
+        device,
+        shader,
+        layout,
+        format,
+        vert_buf_layout,
+        "Highlight Line Render Pipeline",
+        vs_entry_point,
+        on_top.clone(),
+    );
+    //}}}
+    //{{{ com: compute highlight edge render pipeline if supported
+    let highlight_edge_render_pipeline = device
+        .features()
+        .contains(wgpu::Features::POLYGON_MODE_LINE)
+        .then(|| {
+            //{{{ trace
+            info!("Computing the highlight edge render pipeline");
+            //}}}
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Highlight Edge Render Pipeline"),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: vs_entry_point,
+                    buffers: vert_buf_layout,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: shader,
+                    entry_point: "fs_main_line",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: format,
+                        blend: Some(wgpu::BlendState {
+                            color: wgpu::BlendComponent::REPLACE,
+                            alpha: wgpu::BlendComponent::REPLACE,
+                        }),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    strip_index_format: None,
+                    polygon_mode: wgpu::PolygonMode::Line,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(on_top),
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+            })
+        });
+    //}}}
+    (highlight_line_render_pipeline, highlight_edge_render_pipeline)
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_tri_face_render_pipeline
+/// Creates the render pipeline drawing the faces of triangle cells, leaving out the side of each
+/// triangle given by `culling`, see [`FaceCulling`].
+fn create_tri_face_render_pipeline(
+    device: &Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth_mode: dt::DepthMode,
+    vert_buf_layout: &[wgpu::VertexBufferLayout],
+    vs_entry_point: &str,
+    culling: FaceCulling,
+) -> wgpu::RenderPipeline {
+    //{{{ trace
+    info!("Computing the triangle face render pipeline culling {:?}", culling);
+    //}}}
+    let cull_mode = match culling {
+        FaceCulling::None => None,
+        FaceCulling::Back => Some(wgpu::Face::Back),
+        FaceCulling::Front => Some(wgpu::Face::Front),
+    };
+    let label = format!("Triangle Face Render Pipeline (cull {:?})", culling);
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label.as_str()),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: vs_entry_point,
+            buffers: vert_buf_layout,
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main_triangle",
+            targets: &[Some(wgpu::ColorTargetState {
+                format: format,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent::REPLACE,
+                    alpha: wgpu::BlendComponent::REPLACE,
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: cull_mode,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
         depth_stencil: Some(wgpu::DepthStencilState {
-            format: dt::DepthTexture::DEPTH_FORMAT,
+            format: depth_mode.format,
             depth_write_enabled: true,
-            depth_compare: wgpu::CompareFunction::Less,
+            depth_compare: depth_mode.compare(),
             stencil: wgpu::StencilState::default(),
             bias: wgpu::DepthBiasState::default(),
         }),
@@ -161,9 +487,78 @@ fn create_render_pipelines(
             alpha_to_coverage_enabled: false,
         },
         multiview: None,
-    });
+    })
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_render_pipelines
+/// Creates the render pipelines for the application.
+///
+/// This function creates the necessary render pipelines for rendering lines, overlay lines,
+/// triangle edges, and triangle faces. Overlay lines always pass the depth test and do not write
+/// depth, so they are drawn on top of all other geometry.
+/// It takes in the device, the shader and pipeline layout, the surface format, the depth mode,
+/// the vertex buffer layout and the vertex entry point reading it, along with the one offsetting
+/// the depth of line cells by the line depth bias of their mesh.
+/// The function returns the created render pipelines, which can be used for rendering the corresponding geometry.
+fn create_render_pipelines(
+    device: &Device,
+    shader: &wgpu::ShaderModule,
+    render_pipeline_layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth_mode: dt::DepthMode,
+    vert_buf_layout: &[wgpu::VertexBufferLayout],
+    vs_entry_point: &str,
+    line_entry_point: &str,
+) -> (
+    wgpu::RenderPipeline,
+    wgpu::RenderPipeline,
+    Option<wgpu::RenderPipeline>,
+    wgpu::RenderPipeline,
+) {
+    //{{{ com: compute line render pipeline
+    //{{{ trace
+    info!("Computing the line render pipeline");
+    //}}}
+    let line_render_pipeline = create_line_render_pipeline(
+        device,
+        shader,
+        render_pipeline_layout,
+        format,
+        vert_buf_layout,
+        "Line Render Pipeline",
+        line_entry_point,
+        wgpu::DepthStencilState {
+            format: depth_mode.format,
+            depth_write_enabled: true,
+            depth_compare: depth_mode.compare(),
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        },
+    );
+    //}}}
+    //{{{ com: compute overlay line render pipeline
+    //{{{ trace
+    info!("Computing the overlay line render pipeline");
+    //}}}
+    let overlay_line_render_pipeline = create_line_render_pipeline(
+        device,
+        shader,
+        render_pipeline_layout,
+        format,
+        vert_buf_layout,
+        "Overlay Line Render Pipeline",
+        vs_entry_point,
+        wgpu::DepthStencilState {
+            format: depth_mode.format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        },
+    );
     //}}}
-    //{{{ com: triangle edge render pipeline if supported
+//{{{ com: triangle edge render pipeline if supported
     let tri_edge_render_pipeline = if device
         .features()
         .contains(wgpu::Features::POLYGON_MODE_LINE)
@@ -175,18 +570,18 @@ fn create_render_pipelines(
         Some(
             device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: Some("Triangle Edge Render Pipeline"),
-                layout: Some(&render_pipeline_layout),
+                layout: Some(render_pipeline_layout),
                 vertex: wgpu::VertexState {
-                    module: &shader,
-                    entry_point: "vs_main",
+                    module: shader,
+                    entry_point: vs_entry_point,
                     buffers: vert_buf_layout,
                     compilation_options: wgpu::PipelineCompilationOptions::default(),
                 },
                 fragment: Some(wgpu::FragmentState {
-                    module: &shader,
+                    module: shader,
                     entry_point: "fs_main_line",
                     targets: &[Some(wgpu::ColorTargetState {
-                        format: config.format,
+                        format: format,
                         blend: Some(wgpu::BlendState {
                             color: wgpu::BlendComponent::REPLACE,
                             alpha: wgpu::BlendComponent::REPLACE,
@@ -205,15 +600,11 @@ fn create_render_pipelines(
                     conservative: false,
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
-                    format: dt::DepthTexture::DEPTH_FORMAT,
+                    format: depth_mode.format,
                     depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Less,
+                    depth_compare: depth_mode.compare(),
                     stencil: wgpu::StencilState::default(),
-                    bias: wgpu::DepthBiasState {
-                        constant: -2,
-                        slope_scale: -2.0,
-                        clamp: 0.0,
-                    },
+                    bias: depth_mode.bias(-2),
                 }),
                 multisample: wgpu::MultisampleState {
                     count: 1,
@@ -236,44 +627,94 @@ fn create_render_pipelines(
     //}}}
     //}}}
     //{{{ com: compute triangle face render pipeline
+    let tri_face_render_pipeline = create_tri_face_render_pipeline(
+        device,
+        shader,
+        render_pipeline_layout,
+        format,
+        depth_mode,
+        vert_buf_layout,
+        vs_entry_point,
+        FaceCulling::None,
+    );
+    //}}}
+    //{{{ com: yield the result
+    //{{{ trace
+    info!("Yielding the result");
+    //}}}
+    (
+        line_render_pipeline,
+        overlay_line_render_pipeline,
+        tri_edge_render_pipeline,
+        tri_face_render_pipeline,
+    )
+    //}}}
+}
+//..................................................................................................
+//}}}
+//{{{ fun: create_background_render_pipeline
+/// Creates the render pipeline drawing the background gradient, along with the bind group layout
+/// of the uniform holding its two colors.
+///
+/// The gradient is drawn first, as a single triangle covering the window, so it passes every depth
+/// test and writes no depth, leaving the cleared depth buffer to the meshes.
+fn create_background_render_pipeline(
+    device: &Device,
+    format: wgpu::TextureFormat,
+    depth_mode: dt::DepthMode,
+) -> (wgpu::RenderPipeline, wgpu::BindGroupLayout) {
     //{{{ trace
-    info!("Computing the triangle face render pipeline");
+    info!("Computing the background render pipeline");
     //}}}
-    let tri_face_render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: Some("Triangle Face Render Pipeline"),
-        layout: Some(&render_pipeline_layout),
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Shader Module Background"),
+        source: wgpu::ShaderSource::Wgsl(SHADER_BACKGROUND.into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+        label: Some("background_bind_group_layout"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Background Pipeline Layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Background Render Pipeline"),
+        layout: Some(&layout),
         vertex: wgpu::VertexState {
             module: &shader,
             entry_point: "vs_main",
-            buffers: vert_buf_layout,
+            buffers: &[],
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         },
         fragment: Some(wgpu::FragmentState {
             module: &shader,
-            entry_point: "fs_main_triangle",
+            entry_point: "fs_main",
             targets: &[Some(wgpu::ColorTargetState {
-                format: config.format,
-                blend: Some(wgpu::BlendState {
-                    color: wgpu::BlendComponent::REPLACE,
-                    alpha: wgpu::BlendComponent::REPLACE,
-                }),
+                format: format,
+                blend: Some(wgpu::BlendState::REPLACE),
                 write_mask: wgpu::ColorWrites::ALL,
             })],
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         }),
         primitive: wgpu::PrimitiveState {
             topology: wgpu::PrimitiveTopology::TriangleList,
-            strip_index_format: None,
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: None,
-            polygon_mode: wgpu::PolygonMode::Fill,
-            unclipped_depth: false,
-            conservative: false,
+            ..Default::default()
         },
         depth_stencil: Some(wgpu::DepthStencilState {
-            format: dt::DepthTexture::DEPTH_FORMAT,
-            depth_write_enabled: true,
-            depth_compare: wgpu::CompareFunction::Less,
+            format: depth_mode.format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
             stencil: wgpu::StencilState::default(),
             bias: wgpu::DepthBiasState::default(),
         }),
@@ -284,74 +725,304 @@ fn create_render_pipelines(
         },
         multiview: None,
     });
-    //}}}
-    //{{{ com: yield the result
-    //{{{ trace
-    info!("Yielding the result");
-    //}}}
-    (
-        line_render_pipeline,
-        tri_edge_render_pipeline,
-        tri_face_render_pipeline,
-    )
-    //}}}
+    (pipeline, bind_group_layout)
 }
 //..................................................................................................
 //}}}
-//{{{ collection: WgpuState
-//{{{ struct: WgpuState
-/// The `WgpuState` struct represents the state of the WGPU (WebGPU) rendering system. It contains
-/// the necessary WGPU infrastructure, such as the surface, device, queue, configuration, depth
-/// texture, and render pipelines. It also manages the camera buffer, bind group, and mesh buffers.
-/// This struct is used to manage the rendering state and provide access to the WGPU resources
-/// required for rendering.
-struct WgpuState<'a> {
-    //................................. wgpu infrastructure
-    surface: wgpu::Surface<'a>,
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    config: wgpu::SurfaceConfiguration,
-    depth_texture: dt::DepthTexture,
-    line_render_pipeline: wgpu::RenderPipeline,
-    tri_edge_render_pipeline: Option<wgpu::RenderPipeline>,
-    tri_face_render_pipeline: wgpu::RenderPipeline,
-    // ............................... uniforms
-    camera_buffer: wgpu::Buffer,
-    camera_bind_group: wgpu::BindGroup,
-    // ............................... mesh buffers
-    wgpu_line_buffers: HashMap<usize, (u32, wgpu::Buffer, wgpu::Buffer)>,
-    wgpu_tri_buffers: HashMap<usize, (u32, wgpu::Buffer, wgpu::Buffer)>,
-    // ............................... Winit data
-    window: Arc<Window>,
+//{{{ collection: MeshPipelines
+//{{{ struct: MeshPipelines
+/// The render pipelines drawing the meshes whose vertices share one layout, along with the
+/// pipeline layout, vertex buffer layout and vertex entry point they are built from.
+///
+/// Meshes colored per vertex and meshes with a uniform color, whose vertices leave the colors
+/// out, each have a set of their own.
+///
+/// The line cells are offset in depth by their vertex entry point, rather than by a depth bias of
+/// the pipeline, which only applies to triangles. So one line pipeline serves every mesh, each
+/// setting its own bias in its model uniform.
+struct MeshPipelines {
+    layout: wgpu::PipelineLayout,
+    vert_buf_layout: wgpu::VertexBufferLayout<'static>,
+    vs_entry_point: &'static str,
+    line: wgpu::RenderPipeline,
+    overlay_line: wgpu::RenderPipeline,
+    tri_edge: Option<wgpu::RenderPipeline>,
+    tri_face: wgpu::RenderPipeline,
+    highlight_line: wgpu::RenderPipeline,
+    highlight_edge: Option<wgpu::RenderPipeline>,
+    /// Draws the edges of triangle cells as line cells for the wireframe fallback, offset in depth
+    /// like the triangle edge pipeline. Only built when that pipeline is unsupported.
+    fallback_edge: Option<wgpu::RenderPipeline>,
+    /// Triangle face pipelines culling one side of the triangles, built on demand.
+    culled_tri_face: HashMap<FaceCulling, wgpu::RenderPipeline>,
 }
 //}}}
-//{{{ impl: WgpuState
-impl<'a> WgpuState<'a> {
+//{{{ impl: MeshPipelines
+impl MeshPipelines {
+    //{{{ fun: new
+    /// Builds the pipelines drawing vertices of the layout `vert_buf_layout` with the vertex
+    /// entry point `vs_entry_point`, their line cells with `line_entry_point`, the fallback
+    /// triangle edges with `edge_entry_point` and the highlight with `highlight_entry_point`.
+    pub fn new(
+        device: &Device,
+        shader: &wgpu::ShaderModule,
+        layout: wgpu::PipelineLayout,
+        format: wgpu::TextureFormat,
+        depth_mode: dt::DepthMode,
+        vert_buf_layout: wgpu::VertexBufferLayout<'static>,
+        vs_entry_point: &'static str,
+        line_entry_point: &str,
+        edge_entry_point: &str,
+        highlight_entry_point: &str,
+    ) -> Self {
+        let buffers = [vert_buf_layout.clone()];
+        let (line, overlay_line, tri_edge, tri_face) = create_render_pipelines(
+            device,
+            shader,
+            &layout,
+            format,
+            depth_mode,
+            &buffers,
+            vs_entry_point,
+            line_entry_point,
+        );
+        let fallback_edge = tri_edge.is_none().then(|| {
+            //{{{ trace
+            info!("Computing the fallback triangle edge render pipeline");
+            //}}}
+            create_line_render_pipeline(
+                device,
+                shader,
+                &layout,
+                format,
+                &buffers,
+                "Fallback Edge Render Pipeline",
+                edge_entry_point,
+                wgpu::DepthStencilState {
+                    format: depth_mode.format,
+                    depth_write_enabled: true,
+                    depth_compare: depth_mode.compare(),
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                },
+            )
+        });
+        let (highlight_line, highlight_edge) = create_highlight_render_pipelines(
+            device,
+            shader,
+            &layout,
+            format,
+            depth_mode,
+            &buffers,
+            highlight_entry_point,
+        );
+        Self {
+            layout: layout,
+            vert_buf_layout: vert_buf_layout,
+            vs_entry_point: vs_entry_point,
+            line: line,
+            overlay_line: overlay_line,
+            tri_edge: tri_edge,
+            tri_face: tri_face,
+            highlight_line: highlight_line,
+            highlight_edge: highlight_edge,
+            fallback_edge: fallback_edge,
+            culled_tri_face: HashMap::new(),
+        }
+    }
+    //}}}
+    //{{{ fun: build_culled_tri_face
+    /// Builds the triangle face pipeline culling the given side, unless it was built already or
+    /// nothing is culled.
+    pub fn build_culled_tri_face(
+        &mut self,
+        device: &Device,
+        shader: &wgpu::ShaderModule,
+        format: wgpu::TextureFormat,
+        depth_mode: dt::DepthMode,
+        culling: FaceCulling,
+    ) {
+        if culling == FaceCulling::None || self.culled_tri_face.contains_key(&culling) {
+            return;
+        }
+        let pipeline = create_tri_face_render_pipeline(
+            device,
+            shader,
+            &self.layout,
+            format,
+            depth_mode,
+            &[self.vert_buf_layout.clone()],
+            self.vs_entry_point,
+            culling,
+        );
+        self.culled_tri_face.insert(culling, pipeline);
+    }
+    //}}}
+    //{{{ fun: tri_face_pipeline
+    /// Returns the pipeline drawing triangle faces with the given culling, falling back on the
+    /// one culling nothing if it was never built.
+    pub fn tri_face_pipeline(&self, culling: FaceCulling) -> &wgpu::RenderPipeline {
+        self.culled_tri_face.get(&culling).unwrap_or(&self.tri_face)
+    }
+    //}}}
+}
+//..................................................................................................
+//}}}
+//}}}
+//{{{ struct: MeshBuffers
+/// The GPU buffers of a single mesh: one vertex buffer shared by an index buffer for each kind of
+/// cell the mesh has, stored along with its index count, and whether the mesh's lines are drawn
+/// as an overlay. The depth bias of its lines is held in its model uniform.
+struct MeshBuffers {
+    vertex_buffer: wgpu::Buffer,
+    /// The buffer and bind group holding the model matrix of the mesh, rewritten in place when
+    /// the mesh is moved.
+    model: (wgpu::Buffer, wgpu::BindGroup),
+    /// The buffer and bind group holding the color of a mesh with a uniform color, whose vertex
+    /// buffer then holds the compact layout of its vertices. `None` for meshes colored per
+    /// vertex.
+    uniform_color: Option<(wgpu::Buffer, wgpu::BindGroup)>,
+    /// What the edges of the triangle cells are drawn with when the mesh has an edge color of its
+    /// own, see [`MeshOptions`]. `None` when they take the line color of the mesh.
+    edge_color: Option<EdgeColorBuffers>,
+    line_indices: Option<(u32, wgpu::Buffer)>,
+    tri_indices: Option<(u32, wgpu::Buffer)>,
+    /// The edges of the triangle cells as line cells, only built by the wireframe fallback.
+    edge_indices: Option<(u32, wgpu::Buffer)>,
+    /// The two vertices of every edge of the triangle cells, along with the number of edges,
+    /// only built while the edges are drawn as quads.
+    edge_vertices: Option<(u32, wgpu::Buffer)>,
+    overlay: bool,
+    /// The layer of the mesh, which orders the meshes in each render pass
+    layer: i32,
+    /// The winding of the triangles of the mesh, which decides the side face culling leaves out
+    front_face: FrontFace,
+}
+//}}}
+//{{{ impl: MeshBuffers
+impl MeshBuffers {
+    /// Binds the vertex buffer of the mesh to slot 0, the bind group holding its model matrix to
+    /// group 1 and, for a mesh with a uniform color, the bind group holding its color to group 2.
+    fn bind<'p>(&'p self, render_pass: &mut wgpu::RenderPass<'p>) {
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_bind_group(1, &self.model.1, &[]);
+        if let Some((_, bind_group)) = &self.uniform_color {
+            render_pass.set_bind_group(2, bind_group, &[]);
+        }
+    }
+
+    /// Binds the buffers of the mesh like [`Self::bind`], but for drawing the edges of its
+    /// triangle cells, so in the edge color of the mesh when it has one.
+    fn bind_edges<'p>(&'p self, render_pass: &mut wgpu::RenderPass<'p>) {
+        match &self.edge_color {
+            None => self.bind(render_pass),
+            Some(EdgeColorBuffers::Vertices(vertex_buffer)) => {
+                render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                render_pass.set_bind_group(1, &self.model.1, &[]);
+            }
+            Some(EdgeColorBuffers::Uniform((_, bind_group))) => {
+                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                render_pass.set_bind_group(1, &self.model.1, &[]);
+                render_pass.set_bind_group(2, bind_group, &[]);
+            }
+        }
+    }
+}
+//}}}
+//{{{ enum: EdgeColorBuffers
+/// The buffers giving the edges of the triangle cells of a mesh their own color.
+enum EdgeColorBuffers {
+    /// A copy of the vertices of a mesh colored per vertex, with their line color replaced by the
+    /// edge color.
+    Vertices(wgpu::Buffer),
+    /// The color buffer and bind group of a mesh with a uniform color, holding the edge color in
+    /// place of the line color.
+    Uniform((wgpu::Buffer, wgpu::BindGroup)),
+}
+//}}}
+//{{{ constant: FALLBACK_EDGE_DEPTH_BIAS
+/// Depth bias of the fallback and thick triangle edges, the same as that of the triangle edge
+/// pipeline so edges show through the faces they border in the same way. The thick edges are
+/// drawn as triangles, which the pipeline biases, the fallback edges as lines, which the shader
+/// offsets, see [`dt::DepthMode::line_offset`].
+const FALLBACK_EDGE_DEPTH_BIAS: i32 = -2;
+//}}}
+//{{{ constant: CONTOUR_DEPTH_BIAS
+/// Depth bias of the contour lines, which lie on the surface they are drawn from, the same as
+/// that of the triangle edges so contours show through the faces in the same way.
+const CONTOUR_DEPTH_BIAS: i32 = FALLBACK_EDGE_DEPTH_BIAS;
+//}}}
+//{{{ constant: GIZMO_SIZE
+/// Width and height, in pixels, of the square in the bottom-left corner of a window the gizmo is
+/// drawn in.
+const GIZMO_SIZE: u32 = 120;
+//}}}
+//{{{ constant: PICK_TOLERANCE
+/// How far, in pixels, the cursor may move between pressing and releasing the left button for the
+/// click to still pick a mesh, rather than count as a drag of the view.
+const PICK_TOLERANCE: f32 = 3.0;
+//}}}
+//{{{ collection: WgpuContext
+//{{{ struct: WgpuContext
+/// The `WgpuContext` struct holds the part of the WGPU rendering system which is shared between
+/// all the windows of a state: the instance, adapter, device, queue, render pipelines and the
+/// mesh buffers. Each window only owns its surface, depth texture and camera uniform, see
+/// [`WgpuState`], so a mesh is uploaded to the GPU once however many windows display it.
+struct WgpuContext {
+    //................................. wgpu infrastructure
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface_format: wgpu::TextureFormat,
+    /// The layout of the depth textures of all the windows, which the pipelines are built for.
+    depth_mode: dt::DepthMode,
+    shader: wgpu::ShaderModule,
+    /// The pipelines drawing meshes colored per vertex.
+    pipelines: MeshPipelines,
+    /// The pipelines drawing meshes with a uniform color, from the compact layout of their
+    /// vertices.
+    uniform_color_pipelines: MeshPipelines,
+    mesh_model_bind_group_layout: wgpu::BindGroupLayout,
+    mesh_color_bind_group_layout: wgpu::BindGroupLayout,
+    thick_edge_render_pipeline: wgpu::RenderPipeline,
+    background_render_pipeline: wgpu::RenderPipeline,
+    /// Holds the top and bottom colors of the background gradient.
+    background_buffer: wgpu::Buffer,
+    background_bind_group: wgpu::BindGroup,
+    /// When set the background gradient is drawn over the clear color.
+    background_gradient: bool,
+    /// When set and the triangle edge pipeline is unsupported, triangle edges are drawn as line
+    /// cells built from the triangle indices instead.
+    wireframe_fallback: bool,
+    /// When set, triangle edges are drawn as quads of the edge width of each view instead of
+    /// single pixel lines.
+    thick_edges: bool,
+    /// The side of the triangle faces left undrawn.
+    face_culling: FaceCulling,
+    // ............................... mesh buffers
+    wgpu_mesh_buffers: HashMap<usize, MeshBuffers>,
+    /// The lines of the gizmo, drawn apart from the meshes in a corner of each window.
+    gizmo_buffers: Option<MeshBuffers>,
+}
+//}}}
+//{{{ impl: WgpuContext
+impl WgpuContext {
     //{{{ fun: new
-    /// Creates a new `WgpuState` instance with the given event loop, uniform buffer, vertex buffer
-    /// layout, and depth value.
+    /// Creates a new `WgpuContext` for the given window, along with the surface of that window.
     ///
-    /// This function sets up the necessary WGPU infrastructure, including the surface, device,
-    /// queue, configuration, depth texture, and render pipelines. It also creates the camera
-    /// buffer and bind group.
+    /// The adapter is chosen to be compatible with this first window's surface, and the render
+    /// pipelines are built for its preferred format. Windows launched later reuse the context.
+    /// The meshes colored per vertex are drawn from vertices of the layout `vert_buf_layout`,
+    /// those with a uniform color from vertices of the layout `compact_vert_buf_layout`.
     pub async fn new(
-        event_loop: &ActiveEventLoop,
-        uniform_buffer: &[u8],
-        vert_buf_layout: &[wgpu::VertexBufferLayout<'static>],
+        window: Arc<Window>,
+        vert_buf_layout: wgpu::VertexBufferLayout<'static>,
+        compact_vert_buf_layout: wgpu::VertexBufferLayout<'static>,
         d: usize,
-    ) -> Self {
-
-        //{{{ com: create window, find its size
-        //{{{ trace
-        info!("Creating window");
-        //}}}
-        let window = Arc::new(
-            event_loop
-                .create_window(Window::default_attributes())
-                .unwrap(),
-        );
-        let size = window.inner_size();
-        //}}}
+        depth_format: dt::DepthFormat,
+        reversed_z: bool,
+    ) -> (Self, wgpu::Surface<'static>) {
         //{{{ com: create instance
         //{{{ trace
         info!("Creating instance");
@@ -365,7 +1036,7 @@ impl<'a> WgpuState<'a> {
         //{{{ trace
         info!("Creating surface and adapter");
         //}}}
-        let surface = instance.create_surface(window.clone()).unwrap();
+        let surface = instance.create_surface(window).unwrap();
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptionsBase {
                 power_preference: wgpu::PowerPreference::default(),
@@ -379,7 +1050,8 @@ impl<'a> WgpuState<'a> {
         //{{{ trace
         info!("Compute the device and queue");
         //}}}
-        let features = wgpu::Features::POLYGON_MODE_LINE;
+        // line polygon mode is optional, without it triangle edges rely on the wireframe fallback
+        let features = adapter.features() & wgpu::Features::POLYGON_MODE_LINE;
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
@@ -403,276 +1075,765 @@ impl<'a> WgpuState<'a> {
             .copied()
             .find(|f| f.is_srgb())
             .unwrap_or(surface_capbilities.formats[0]);
-            //}}}
-        //{{{ com: create surface configuration, configure the surface
+        let depth_mode = dt::DepthMode {
+            format: dt::DepthTexture::supported_format(&adapter, depth_format),
+            reversed_z: reversed_z,
+        };
         //{{{ trace
-        info!("Create surface configuration, configure the surface");
+        info!("Using depth mode {:?}", depth_mode);
         //}}}
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width: size.width,
-            height: size.height,
-            present_mode: surface_capbilities.present_modes[0],
-            alpha_mode: surface_capbilities.alpha_modes[0],
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
-        surface.configure(&device, &config);
         //}}}
-        //{{{ com: create depth texture and the render pipelines
+        //{{{ com: create the render pipelines
         //{{{ trace
-        info!("Crate depth texture and the render pipelines");
-        //}}}
-        let depth_texture =
-            dt::DepthTexture::create_depth_texture(&device, &config, "Depth Texture");
-        let (lrp, terp, tfrp) =
-            create_render_pipelines(&device, &config, &depth_texture, vert_buf_layout, d);
+        info!("Create the render pipelines");
         //}}}
-        //{{{ com: create camera buffer
+        let shader = device.create_shader_module(shader_module_desc(d));
+        let mesh_model_bind_group_layout =
+            create_mesh_uniform_bind_group_layout(&device, "mesh_model_bind_group_layout");
+        let terp_thick = create_thick_edge_render_pipeline(
+            &device,
+            &shader,
+            &create_render_pipeline_layout(&device, &mesh_model_bind_group_layout, None),
+            surface_format,
+            depth_mode,
+            &vert_buf_layout,
+        );
+        let pipelines = MeshPipelines::new(
+            &device,
+            &shader,
+            create_render_pipeline_layout(&device, &mesh_model_bind_group_layout, None),
+            surface_format,
+            depth_mode,
+            vert_buf_layout,
+            "vs_main",
+            "vs_main_line",
+            "vs_main_edge",
+            "vs_main_highlight",
+        );
         //{{{ trace
-        info!("Create camera buffer");
+        info!("Create the render pipelines of meshes with a uniform color");
         //}}}
-        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Camera Buffer"),
-            contents: uniform_buffer,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
+        let mesh_color_bind_group_layout =
+            create_mesh_uniform_bind_group_layout(&device, "mesh_color_bind_group_layout");
+        let uniform_color_pipelines = MeshPipelines::new(
+            &device,
+            &shader,
+            create_render_pipeline_layout(
+                &device,
+                &mesh_model_bind_group_layout,
+                Some(&mesh_color_bind_group_layout),
+            ),
+            surface_format,
+            depth_mode,
+            compact_vert_buf_layout,
+            "vs_main_uniform",
+            "vs_main_uniform_line",
+            "vs_main_uniform_edge",
+            "vs_main_uniform_highlight",
+        );
         //}}}
-        //{{{ com: create camera bind group
+        //{{{ com: create the background pipeline, buffer and bind group
         //{{{ trace
-        info!("Create camera bind group");
+        info!("Create the background pipeline, buffer and bind group");
         //}}}
-        let camera_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-                label: Some("Camera Bind Group Layout"),
-            });
-
-        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &camera_bind_group_layout,
+        let (brp, background_bind_group_layout) =
+            create_background_render_pipeline(&device, surface_format, depth_mode);
+        let background_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Background Buffer"),
+            size: std::mem::size_of::<[[f32; 4]; 2]>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let background_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &background_bind_group_layout,
             entries: &[wgpu::BindGroupEntry {
                 binding: 0,
-                resource: camera_buffer.as_entire_binding(),
+                resource: background_buffer.as_entire_binding(),
             }],
-            label: Some("Camera Bind Group"),
+            label: Some("background_bind_group"),
         });
         //}}}
-        //{{{ com: yield the state
+        //{{{ com: yield the context
         //{{{ trace
-        info!("Yield the state object");
+        info!("Yield the context object");
         //}}}
-        Self {
-            surface: surface,
+        let context = Self {
+            instance: instance,
+            adapter: adapter,
             device: device,
             queue: queue,
-            config: config,
-            depth_texture: depth_texture,
-            line_render_pipeline: lrp,
-            tri_edge_render_pipeline: terp,
-            tri_face_render_pipeline: tfrp,
-            camera_bind_group: camera_bind_group,
-            camera_buffer: camera_buffer,
-            wgpu_line_buffers: HashMap::new(),
-            wgpu_tri_buffers: HashMap::new(),
-            window: window,
-        }
+            surface_format: surface_format,
+            depth_mode: depth_mode,
+            shader: shader,
+            pipelines: pipelines,
+            uniform_color_pipelines: uniform_color_pipelines,
+            mesh_model_bind_group_layout: mesh_model_bind_group_layout,
+            mesh_color_bind_group_layout: mesh_color_bind_group_layout,
+            thick_edge_render_pipeline: terp_thick,
+            background_render_pipeline: brp,
+            background_buffer: background_buffer,
+            background_bind_group: background_bind_group,
+            background_gradient: false,
+            wireframe_fallback: false,
+            thick_edges: false,
+            face_culling: FaceCulling::None,
+            wgpu_mesh_buffers: HashMap::new(),
+            gizmo_buffers: None,
+        };
+        (context, surface)
         //}}}
     }
     //}}}
     //{{{ fun: update
-    /// Takes an updated mesh state and updates the buffers used by the wgpu state.
+    /// Takes an updated mesh state and updates the buffers used by the wgpu context.
     ///
-    /// Meshes are static, therefore we only need to add or delete buffers as and
-    /// when meshes they are created or deleted. We will not need to edit existing
-    /// buffers as their corresponding meshes cannot be edited.
-    pub fn update<'b, V>(&mut self, mesh_state: &mut MeshState<'b, V>, uniform_buffer: &[u8])
+    /// Buffers are added or deleted as and when meshes are created or deleted. Existing meshes are
+    /// only edited by appending cells to them or replacing them outright, after which they are
+    /// marked dirty and their buffers are deleted and rebuilt like those of a new mesh. Each mesh
+    /// gets one vertex buffer, shared by an index buffer for its line cells and one for its
+    /// triangle cells. The vertex buffer of a mesh with a uniform color holds the compact layout of
+    /// its vertices, and the mesh gets a small uniform buffer holding its color. Every mesh also
+    /// gets a uniform buffer holding its model matrix, layer and line depth bias, which is
+    /// rewritten in place when the mesh is only moved. A triangle face pipeline is built for any
+    /// face culling not seen before. Meshes without any cell get no buffers at all, and so are
+    /// never drawn, and a mesh only gets an index buffer for the kinds of cell it has at least one
+    /// of.
+    ///
+    /// With the wireframe fallback in use, the triangle meshes also get an index buffer holding
+    /// their edges as line cells. These are built or dropped as the fallback is toggled. In the
+    /// same way, while edges are drawn thick, they get a buffer holding the vertices of each of
+    /// their edges.
+    pub fn update<'b, V>(&mut self, mesh_state: &mut MeshState<'b, V>)
     where
         V: VertexCore + Deserialize<'b> + Serialize,
     {
-        //{{{ com: delete line buffers corresponding to deleted meshes
-        {
-            // first, find the beffers which no longer exist
-            let deleted_mesh_set: Vec<usize> = self
-                .wgpu_line_buffers
-                .keys()
-                .filter(|mesh_uid| !mesh_state.meshes.contains_key(mesh_uid))
-                .cloned()
-                .collect();
-
-            // delete the buffers
-            for mesh_uid in deleted_mesh_set {
-                self.wgpu_line_buffers.remove(&mesh_uid);
-            }
-        }
-        //}}}
-        //{{{ com: delete triangle buffers corresponding to deleted meshes
+        //{{{ com: delete buffers corresponding to deleted or edited meshes
         {
-            // first, find the beffers which no longer exist
+            // first, find the beffers which no longer exist or are out of date
+            let dirty = mesh_state.take_dirty();
             let deleted_mesh_set: Vec<usize> = self
-                .wgpu_tri_buffers
+                .wgpu_mesh_buffers
                 .keys()
-                .filter(|mesh_uid| !mesh_state.meshes.contains_key(mesh_uid))
+                .filter(|mesh_uid| {
+                    !mesh_state.meshes.contains_key(mesh_uid) || dirty.contains(mesh_uid)
+                })
                 .cloned()
                 .collect();
 
             // delete the buffers
             for mesh_uid in deleted_mesh_set {
-                self.wgpu_tri_buffers.remove(&mesh_uid);
+                self.wgpu_mesh_buffers.remove(&mesh_uid);
             }
         }
         //}}}
-        //{{{ com: create buffers for new meshes, overwrite buffers for existing meshes
+        //{{{ com: create buffers for new meshes
         for (mesh_uid, mesh) in mesh_state.meshes.iter() {
-            if mesh.is_line() && !self.wgpu_line_buffers.contains_key(mesh_uid) {
-                let vertex_buffer =
+            if self.wgpu_mesh_buffers.contains_key(mesh_uid) {
+                continue;
+            }
+            if !mesh.has_cells() {
+                //{{{ trace
+                trace!("Mesh {} has no cells, skipping its buffers", mesh_uid);
+                //}}}
+                continue;
+            }
+
+            let dashed_mesh;
+            let mesh = match mesh.options().dash_pattern {
+                Some((dash, gap)) if mesh.has_lines() => {
+                    dashed_mesh = mesh.with_dashed_lines(dash, gap);
+                    &dashed_mesh
+                }
+                _ => mesh,
+            };
+            let uniform_color = match mesh.color_mode() {
+                MeshColorMode::PerVertex => None,
+                MeshColorMode::Uniform(color) => {
+                    Some(self.create_mesh_color(*mesh_uid, color, color))
+                }
+            };
+            let compact_vertices;
+            let vertices = if uniform_color.is_some() {
+                compact_vertices = mesh.compact_vertices();
+                &compact_vertices
+            } else {
+                mesh.vertex_slice()
+            };
+            let vertex_buffer = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(format!("Vertex Buffer {}", mesh_uid).as_str()),
+                    contents: bytemuck::cast_slice(vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+
+            let line_indices = (!mesh.line_cell_indices().is_empty()).then(|| {
+                let indices = mesh.line_cell_indices();
+                let index_buffer =
                     self.device
                         .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some(format!("Vertex Buffer {}", mesh_uid).as_str()),
-                            contents: bytemuck::cast_slice(mesh.vertex_slice()),
-                            usage: wgpu::BufferUsages::VERTEX,
+                            label: Some(format!("Line Index Buffer {}", mesh_uid).as_str()),
+                            contents: bytemuck::cast_slice(indices),
+                            usage: wgpu::BufferUsages::INDEX,
                         });
+                (indices.len() as u32, index_buffer)
+            });
 
+            let tri_indices = (!mesh.triangle_cell_indices().is_empty()).then(|| {
+                let indices = mesh.triangle_cell_indices();
                 let index_buffer =
                     self.device
                         .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some(format!("Index Buffer {}", mesh_uid).as_str()),
-                            contents: bytemuck::cast_slice(mesh.index_slice()),
+                            label: Some(format!("Triangle Index Buffer {}", mesh_uid).as_str()),
+                            contents: bytemuck::cast_slice(indices),
                             usage: wgpu::BufferUsages::INDEX,
                         });
+                (indices.len() as u32, index_buffer)
+            });
 
-                self.wgpu_line_buffers.insert(
-                    *mesh_uid,
-                    (mesh.num_indices() as u32, vertex_buffer, index_buffer),
-                );
-            }
-
-            if mesh.is_triangle() && !self.wgpu_tri_buffers.contains_key(mesh_uid) {
-                let vertex_buffer =
-                    self.device
+            let edge_color = match (mesh.options().edge_color, mesh.color_mode()) {
+                _ if tri_indices.is_none() => None,
+                (None, _) => None,
+                (Some(edge_color), MeshColorMode::PerVertex) => {
+                    let vertices = mesh.vertices_with_line_color(&edge_color);
+                    let vertex_buffer = self
+                        .device
                         .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some(format!("Vertex Buffer {}", mesh_uid).as_str()),
-                            contents: bytemuck::cast_slice(mesh.vertex_slice()),
+                            label: Some(format!("Edge Vertex Buffer {}", mesh_uid).as_str()),
+                            contents: bytemuck::cast_slice(&vertices),
                             usage: wgpu::BufferUsages::VERTEX,
                         });
+                    Some(EdgeColorBuffers::Vertices(vertex_buffer))
+                }
+                (Some(edge_color), MeshColorMode::Uniform(color)) => {
+                    let edge_color = self.create_mesh_color(*mesh_uid, edge_color, color);
+                    Some(EdgeColorBuffers::Uniform(edge_color))
+                }
+            };
 
-                let index_buffer =
-                    self.device
-                        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some(format!("Index Buffer {}", mesh_uid).as_str()),
-                            contents: bytemuck::cast_slice(mesh.index_slice()),
-                            usage: wgpu::BufferUsages::INDEX,
-                        });
+            let model = self.create_mesh_model(
+                &format!("Mesh {}", mesh_uid),
+                &mesh.model(),
+                mesh.layer(),
+                mesh.line_depth_bias(),
+            );
 
-                self.wgpu_tri_buffers.insert(
-                    *mesh_uid,
-                    (mesh.num_indices() as u32, vertex_buffer, index_buffer),
+            self.wgpu_mesh_buffers.insert(
+                *mesh_uid,
+                MeshBuffers {
+                    vertex_buffer,
+                    model,
+                    uniform_color,
+                    edge_color,
+                    line_indices,
+                    tri_indices,
+                    edge_indices: None,
+                    edge_vertices: None,
+                    overlay: mesh.is_overlay(),
+                    layer: mesh.layer(),
+                    front_face: mesh.front_face(),
+                },
+            );
+        }
+        //}}}
+        //{{{ com: upload the model matrices, layers and windings of moved meshes
+        for mesh_uid in mesh_state.take_moved() {
+            let buffers = self.wgpu_mesh_buffers.get_mut(&mesh_uid);
+            let (Some(buffers), Some(mesh)) = (buffers, mesh_state.meshes.get(&mesh_uid)) else {
+                continue;
+            };
+            let contents = model_uniform(
+                &mesh.model(),
+                mesh.layer(),
+                mesh.line_depth_bias(),
+                self.depth_mode,
+            );
+            self.queue.write_buffer(&buffers.model.0, 0, bytemuck::bytes_of(&contents));
+            buffers.layer = mesh.layer();
+            buffers.front_face = mesh.front_face();
+        }
+        //}}}
+        //{{{ com: build or drop the fallback edge buffers
+        let fallback = self.wireframe_fallback && self.pipelines.tri_edge.is_none();
+        for (mesh_uid, buffers) in self.wgpu_mesh_buffers.iter_mut() {
+            if !fallback {
+                buffers.edge_indices = None;
+                continue;
+            }
+            if buffers.edge_indices.is_some() || buffers.tri_indices.is_none() {
+                continue;
+            }
+            let Some(mesh) = mesh_state.meshes.get(mesh_uid) else {
+                continue;
+            };
+            let indices = mesh.triangle_edge_indices();
+            let index_buffer = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(format!("Edge Index Buffer {}", mesh_uid).as_str()),
+                    contents: bytemuck::cast_slice(&indices),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            buffers.edge_indices = Some((indices.len() as u32, index_buffer));
+        }
+        //}}}
+        //{{{ com: build the culled triangle face pipelines
+        // the pipelines wind triangles counterclockwise, so meshes wound clockwise are drawn
+        // with the pipeline culling the other side
+        let cullings: HashSet<FaceCulling> = self
+            .wgpu_mesh_buffers
+            .values()
+            .map(|buffers| buffers.front_face.ccw_culling(self.face_culling))
+            .collect();
+        for pipelines in [&mut self.pipelines, &mut self.uniform_color_pipelines] {
+            for &culling in cullings.iter() {
+                pipelines.build_culled_tri_face(
+                    &self.device,
+                    &self.shader,
+                    self.surface_format,
+                    self.depth_mode,
+                    culling,
                 );
             }
         }
         //}}}
-        //{{{ com: next update the uniforms
-        self.queue .write_buffer(&self.camera_buffer, 0, uniform_buffer);
+        //{{{ com: build or drop the thick edge buffers
+        for (mesh_uid, buffers) in self.wgpu_mesh_buffers.iter_mut() {
+            if !self.thick_edges {
+                buffers.edge_vertices = None;
+                continue;
+            }
+            if buffers.edge_vertices.is_some() || buffers.tri_indices.is_none() {
+                continue;
+            }
+            let Some(mesh) = mesh_state.meshes.get(mesh_uid) else {
+                continue;
+            };
+            let vertices = mesh.triangle_edge_vertices();
+            let vertex_buffer = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(format!("Edge Vertex Buffer {}", mesh_uid).as_str()),
+                    contents: bytemuck::cast_slice(&vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+            let num_edges = vertices.len() / (2 * V::len());
+            buffers.edge_vertices = Some((num_edges as u32, vertex_buffer));
+        }
         //}}}
     }
     //}}}
-    //{{{ fun: render
-    pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        //{{{ init: local variables
-        let output = self.surface.get_current_texture()?;
-
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self
+    //{{{ fun: mesh_pipelines
+    /// Returns the set of pipelines drawing the mesh with the given buffers.
+    fn mesh_pipelines(&self, buffers: &MeshBuffers) -> &MeshPipelines {
+        if buffers.uniform_color.is_some() {
+            &self.uniform_color_pipelines
+        } else {
+            &self.pipelines
+        }
+    }
+    //}}}
+    //{{{ fun: create_mesh_model
+    /// Creates the uniform buffer holding the model matrix, layer and line depth bias of a mesh,
+    /// see [`model_uniform`], along with the bind group binding it. Both are labelled after
+    /// `label`.
+    fn create_mesh_model(
+        &self,
+        label: &str,
+        model: &Mat4,
+        layer: i32,
+        line_depth_bias: i32,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let contents = model_uniform(model, layer, line_depth_bias, self.depth_mode);
+        let buffer = self
             .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(format!("{} Model Buffer", label).as_str()),
+                contents: bytemuck::bytes_of(&contents),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             });
-        //}}}
-        //{{{ com: perform render passes
-        {
-            //{{{ com: initialize render pass
-            //{{{ trace
-            trace!("Initialising render pass");
-            //}}}
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Line Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.5,
-                            g: 0.5,
-                            b: 0.5,
-                            a: 0.5,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: &self.depth_texture.view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: None,
-                }),
-                occlusion_query_set: None,
-                timestamp_writes: None,
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.mesh_model_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+            label: Some(format!("{} Model Bind Group", label).as_str()),
+        });
+        (buffer, bind_group)
+    }
+    //}}}
+    //{{{ fun: create_mesh_color
+    /// Creates the uniform buffer holding the colors of the lines and of the triangles of a mesh
+    /// with a uniform color, along with the bind group binding it.
+    fn create_mesh_color(
+        &self,
+        mesh_uid: usize,
+        line_color: Color,
+        tri_color: Color,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(format!("Mesh Color Buffer {}", mesh_uid).as_str()),
+                contents: bytemuck::cast_slice(&[line_color.to_rgba(), tri_color.to_rgba()]),
+                usage: wgpu::BufferUsages::UNIFORM,
             });
-            //}}}
-            //{{{ com: line render pass
-            {
-                //{{{ trace
-                trace!("Setting up line render pass");
-                //}}}
-                render_pass.set_pipeline(&self.line_render_pipeline);
-
-                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
-
-                for (uid, (num_indices, vertex_buffer, index_buffer)) in &self.wgpu_line_buffers {
-                    //{{{ trace
-                    trace!("Mesh {} has {} indices", uid, num_indices);
-                    trace!("Setting the vertex buffer");
-                    //}}}
-                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                    //{{{ trace
-                    trace!("Setting the index buffer");
-                    //}}}
-                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                    //{{{ trace
-                    trace!("Drawing the mesh");
-                    //}}}
-                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
-                }
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.mesh_color_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+            label: Some(format!("Mesh Color Bind Group {}", mesh_uid).as_str()),
+        });
+        (buffer, bind_group)
+    }
+    //}}}
+    //{{{ fun: set_background_gradient
+    /// Uploads the top and bottom colors of the background gradient, or stops drawing it when
+    /// `gradient` is `None`.
+    pub fn set_background_gradient(&mut self, gradient: Option<(Color, Color)>) {
+        self.background_gradient = gradient.is_some();
+        if let Some((top, bottom)) = gradient {
+            let [tr, tg, tb] = top.to_rgb();
+            let [br, bg, bb] = bottom.to_rgb();
+            let colors = [[tr, tg, tb, 1.0], [br, bg, bb, 1.0]];
+            self.queue
+                .write_buffer(&self.background_buffer, 0, bytemuck::cast_slice(&colors));
+        }
+    }
+    //}}}
+    //{{{ fun: set_gizmo
+    /// Uploads the line cells of the gizmo mesh, or drops them when `gizmo` is `None`.
+    pub fn set_gizmo<'b, V>(&mut self, gizmo: Option<&MeshCore<'b, V>>)
+    where
+        V: VertexCore + Deserialize<'b> + Serialize,
+    {
+        self.gizmo_buffers = gizmo.filter(|mesh| mesh.has_lines()).map(|mesh| {
+            let vertex_buffer = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Gizmo Vertex Buffer"),
+                    contents: bytemuck::cast_slice(mesh.vertex_slice()),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+            let indices = mesh.line_cell_indices();
+            let index_buffer = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Gizmo Line Index Buffer"),
+                    contents: bytemuck::cast_slice(indices),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            MeshBuffers {
+                vertex_buffer,
+                model: self.create_mesh_model("Gizmo", &Mat4::identity(), 0, 0),
+                uniform_color: None,
+                edge_color: None,
+                line_indices: Some((indices.len() as u32, index_buffer)),
+                tri_indices: None,
+                edge_indices: None,
+                edge_vertices: None,
+                overlay: true,
+                layer: 0,
+                front_face: FrontFace::Ccw,
             }
-            //}}}
-            //{{{ com: face render pass
-            {
-                //{{{ trace
-                trace!("Stting the face render pass");
-                //}}}
-                render_pass.set_pipeline(&self.tri_face_render_pipeline);
-                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
-
-                for (uid, (num_indices, vertex_buffer, index_buffer)) in &self.wgpu_tri_buffers {
-                    //{{{ trace
-                    trace!("Mesh {} has {} indices", uid, num_indices);
-                    trace!("Setting the vertex buffer");
-                    //}}} 
-                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        });
+    }
+    //}}}
+}
+//..................................................................................................
+//}}}
+//}}}
+//{{{ fun: is_toggle_key
+/// Returns whether the text of a key press is the single character `key`, in either case.
+fn is_toggle_key(key: Option<char>, text: &str) -> bool {
+    let mut chars = text.chars();
+    match (key, chars.next(), chars.next()) {
+        (Some(key), Some(c), None) => c.eq_ignore_ascii_case(&key),
+        _ => false,
+    }
+}
+//}}}
+//{{{ collection: WgpuState
+//{{{ struct: WgpuState
+/// The `WgpuState` struct represents the per-window state of the WGPU (WebGPU) rendering system.
+/// It contains the surface, configuration and depth texture of a single window, along with the
+/// camera buffer and bind group holding that window's view. The device, pipelines and mesh
+/// buffers it renders with are borrowed from the shared [`WgpuContext`].
+struct WgpuState<'a> {
+    //................................. wgpu infrastructure
+    surface: wgpu::Surface<'a>,
+    config: wgpu::SurfaceConfiguration,
+    depth_texture: dt::DepthTexture,
+    // ............................... uniforms
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    /// Holds the orientation-only view the gizmo is drawn with.
+    gizmo_camera_buffer: wgpu::Buffer,
+    gizmo_camera_bind_group: wgpu::BindGroup,
+    // ............................... Winit data
+    window: Arc<Window>,
+}
+//}}}
+//{{{ impl: WgpuState
+impl<'a> WgpuState<'a> {
+    //{{{ fun: new
+    /// Creates a new `WgpuState` instance for the given window and surface, with the camera
+    /// buffer initialised from the given uniform buffer.
+    ///
+    /// This function configures the surface against the shared context, and creates the depth
+    /// texture, the camera buffer and its bind group.
+    pub fn new(
+        context: &WgpuContext,
+        window: Arc<Window>,
+        surface: wgpu::Surface<'a>,
+        uniform_buffer: &[u8],
+    ) -> Self {
+        let size = window.inner_size();
+        //{{{ com: create surface configuration, configure the surface
+        //{{{ trace
+        info!("Create surface configuration, configure the surface");
+        //}}}
+        let surface_capbilities = surface.get_capabilities(&context.adapter);
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: context.surface_format,
+            width: size.width,
+            height: size.height,
+            present_mode: surface_capbilities.present_modes[0],
+            alpha_mode: surface_capbilities.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&context.device, &config);
+        //}}}
+        //{{{ com: create depth texture
+        //{{{ trace
+        info!("Crate depth texture");
+        //}}}
+        let depth_texture = dt::DepthTexture::create_depth_texture(
+            &context.device,
+            &config,
+            context.depth_mode.format,
+            "Depth Texture",
+        );
+        //}}}
+        //{{{ com: create camera buffer
+        //{{{ trace
+        info!("Create camera buffer");
+        //}}}
+        let camera_buffer = context
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Camera Buffer"),
+                contents: uniform_buffer,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+        //}}}
+        //{{{ com: create camera bind group
+        //{{{ trace
+        info!("Create camera bind group");
+        //}}}
+        let camera_bind_group_layout =
+            context
+                .device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    }],
+                    label: Some("Camera Bind Group Layout"),
+                });
+
+        let camera_bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &camera_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: camera_buffer.as_entire_binding(),
+                }],
+                label: Some("Camera Bind Group"),
+            });
+        //}}}
+        //{{{ com: create gizmo camera buffer and bind group
+        //{{{ trace
+        info!("Create gizmo camera buffer and bind group");
+        //}}}
+        let gizmo_camera_buffer = context
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Gizmo Camera Buffer"),
+                contents: uniform_buffer,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+
+        let gizmo_camera_bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &camera_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: gizmo_camera_buffer.as_entire_binding(),
+                }],
+                label: Some("Gizmo Camera Bind Group"),
+            });
+        //}}}
+        //{{{ com: yield the state
+        //{{{ trace
+        info!("Yield the state object");
+        //}}}
+        Self {
+            surface: surface,
+            config: config,
+            depth_texture: depth_texture,
+            camera_bind_group: camera_bind_group,
+            camera_buffer: camera_buffer,
+            gizmo_camera_buffer: gizmo_camera_buffer,
+            gizmo_camera_bind_group: gizmo_camera_bind_group,
+            window: window,
+        }
+        //}}}
+    }
+    //}}}
+    //{{{ fun: update_uniform
+    /// Writes the given view uniform into this window's camera buffer.
+    pub fn update_uniform(&mut self, context: &WgpuContext, uniform_buffer: &[u8]) {
+        context
+            .queue
+            .write_buffer(&self.camera_buffer, 0, uniform_buffer);
+    }
+    //}}}
+    //{{{ fun: update_gizmo_uniform
+    /// Writes the given view uniform into the camera buffer the gizmo is drawn with.
+    pub fn update_gizmo_uniform(&mut self, context: &WgpuContext, uniform_buffer: &[u8]) {
+        context
+            .queue
+            .write_buffer(&self.gizmo_camera_buffer, 0, uniform_buffer);
+    }
+    //}}}
+    //{{{ fun: render
+    pub fn render(
+        &mut self,
+        context: &WgpuContext,
+        highlight: Option<usize>,
+        show_gizmo: bool,
+    ) -> Result<(), wgpu::SurfaceError> {
+        //{{{ init: local variables
+        let output = self.surface.get_current_texture()?;
+
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        // every pass draws the meshes in order of their layers, see `MeshCore::set_layer`
+        let mut meshes: Vec<(&usize, &MeshBuffers)> = context.wgpu_mesh_buffers.iter().collect();
+        meshes.sort_by_key(|(uid, buffers)| (buffers.layer, **uid));
+        //}}}
+        //{{{ com: perform render passes
+        {
+            //{{{ com: initialize render pass
+            //{{{ trace
+            trace!("Initialising render pass");
+            //}}}
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Line Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.5,
+                            g: 0.5,
+                            b: 0.5,
+                            a: 0.5,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_texture.view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(context.depth_mode.clear_value()),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            //}}}
+            //{{{ com: background render pass
+            if context.background_gradient {
+                //{{{ trace
+                trace!("Performing background render pass");
+                //}}}
+                render_pass.set_pipeline(&context.background_render_pipeline);
+                render_pass.set_bind_group(0, &context.background_bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+            //}}}
+            //{{{ com: line render pass
+            {
+                //{{{ trace
+                trace!("Setting up line render pass");
+                //}}}
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+                for &(uid, buffers) in meshes.iter().filter(|(_, b)| !b.overlay) {
+                    let Some((num_indices, index_buffer)) = &buffers.line_indices else {
+                        continue;
+                    };
+                    render_pass.set_pipeline(&context.mesh_pipelines(buffers).line);
+                    //{{{ trace
+                    trace!("Mesh {} has {} indices", uid, num_indices);
+                    trace!("Setting the vertex buffer");
+                    //}}}
+                    buffers.bind(&mut render_pass);
+                    //{{{ trace
+                    trace!("Setting the index buffer");
+                    //}}}
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    //{{{ trace
+                    trace!("Drawing the mesh");
+                    //}}}
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                }
+            }
+            //}}}
+            //{{{ com: face render pass
+            {
+                //{{{ trace
+                trace!("Stting the face render pass");
+                //}}}
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+                for &(uid, buffers) in &meshes {
+                    let Some((num_indices, index_buffer)) = &buffers.tri_indices else {
+                        continue;
+                    };
+                    let pipelines = context.mesh_pipelines(buffers);
+                    let culling = buffers.front_face.ccw_culling(context.face_culling);
+                    render_pass.set_pipeline(pipelines.tri_face_pipeline(culling));
+                    //{{{ trace
+                    trace!("Mesh {} has {} indices", uid, num_indices);
+                    trace!("Setting the vertex buffer");
+                    //}}}
+                    buffers.bind(&mut render_pass);
                     //{{{ trace
                     trace!("Setting the index buffer");
                     //}}}
@@ -685,20 +1846,45 @@ impl<'a> WgpuState<'a> {
             }
             //}}}
             //{{{ com: edge render pass
-            if let Some(tri_edge_render_pipeline) = &self.tri_edge_render_pipeline {
+            if context.thick_edges {
                 //{{{ trace
-                trace!("Pergforming triangle edge render pass");
+                trace!("Performing thick triangle edge render pass");
                 //}}}
-                render_pass.set_pipeline(tri_edge_render_pipeline);
+                render_pass.set_pipeline(&context.thick_edge_render_pipeline);
+
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
 
+                for &(uid, buffers) in &meshes {
+                    let Some((num_edges, vertex_buffer)) = &buffers.edge_vertices else {
+                        continue;
+                    };
+                    //{{{ trace
+                    trace!("Mesh {} has {} edges", uid, num_edges);
+                    //}}}
+                    // one quad of two triangles per edge
+                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                    render_pass.set_bind_group(1, &buffers.model.1, &[]);
+                    render_pass.draw(0..6, 0..*num_edges)
+                }
+            } else if context.pipelines.tri_edge.is_some() {
+                //{{{ trace
+                trace!("Pergforming triangle edge render pass");
+                //}}}
                 render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
 
-                for (uid, (num_indices, vertex_buffer, index_buffer)) in &self.wgpu_tri_buffers {
+                for &(uid, buffers) in &meshes {
+                    let Some((num_indices, index_buffer)) = &buffers.tri_indices else {
+                        continue;
+                    };
+                    let Some(pipeline) = &context.mesh_pipelines(buffers).tri_edge else {
+                        continue;
+                    };
+                    render_pass.set_pipeline(pipeline);
                     //{{{ trace
                     trace!("Mesh {} has {} indices", uid, num_indices);
                     trace!("Setting the vertex buffer");
-                    //}}} 
-                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                    //}}}
+                    buffers.bind_edges(&mut render_pass);
                     //{{{ trace
                     trace!("Setting the index buffer");
                     //}}}
@@ -708,24 +1894,200 @@ impl<'a> WgpuState<'a> {
                     //}}}
                     render_pass.draw_indexed(0..*num_indices, 0, 0..1)
                 }
+            } else if context.wireframe_fallback && context.pipelines.fallback_edge.is_some() {
+                //{{{ trace
+                trace!("Performing fallback triangle edge render pass");
+                //}}}
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+                for &(uid, buffers) in &meshes {
+                    let Some((num_indices, index_buffer)) = &buffers.edge_indices else {
+                        continue;
+                    };
+                    let Some(pipeline) = &context.mesh_pipelines(buffers).fallback_edge else {
+                        continue;
+                    };
+                    render_pass.set_pipeline(pipeline);
+                    //{{{ trace
+                    trace!("Mesh {} has {} edge indices", uid, num_indices);
+                    //}}}
+                    buffers.bind_edges(&mut render_pass);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                }
+            }
+            //}}}
+            //{{{ com: overlay line render pass
+            {
+                //{{{ trace
+                trace!("Setting up overlay line render pass");
+                //}}}
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+                for &(uid, buffers) in meshes.iter().filter(|(_, b)| b.overlay) {
+                    let Some((num_indices, index_buffer)) = &buffers.line_indices else {
+                        continue;
+                    };
+                    render_pass.set_pipeline(&context.mesh_pipelines(buffers).overlay_line);
+                    //{{{ trace
+                    trace!("Mesh {} has {} overlay indices", uid, num_indices);
+                    //}}}
+                    buffers.bind(&mut render_pass);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                }
+            }
+            //}}}
+            //{{{ com: highlight render pass
+            if let Some(buffers) = highlight.and_then(|uid| context.wgpu_mesh_buffers.get(&uid)) {
+                //{{{ trace
+                trace!("Performing highlight render pass");
+                //}}}
+                let pipelines = context.mesh_pipelines(buffers);
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                buffers.bind(&mut render_pass);
+
+                if let Some((num_indices, index_buffer)) = &buffers.line_indices {
+                    render_pass.set_pipeline(&pipelines.highlight_line);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                }
+                if let (Some((num_indices, index_buffer)), Some(pipeline)) =
+                    (&buffers.tri_indices, &pipelines.highlight_edge)
+                {
+                    render_pass.set_pipeline(pipeline);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                } else if let Some((num_indices, index_buffer)) = &buffers.edge_indices {
+                    render_pass.set_pipeline(&pipelines.highlight_line);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..*num_indices, 0, 0..1)
+                }
+            }
+            //}}}
+            //{{{ com: gizmo render pass
+            let gizmo_lines = context
+                .gizmo_buffers
+                .as_ref()
+                .filter(|_| show_gizmo)
+                .and_then(|buffers| Some((buffers, buffers.line_indices.as_ref()?)));
+            if let Some((buffers, (num_indices, index_buffer))) = gizmo_lines {
+                //{{{ trace
+                trace!("Performing gizmo render pass");
+                //}}}
+                // drawn last, over the scene, in a square viewport of its own
+                let size = GIZMO_SIZE.min(self.config.width).min(self.config.height) as f32;
+                let y = self.config.height as f32 - size;
+                render_pass.set_viewport(0.0, y, size, size, 0.0, 1.0);
+                render_pass.set_pipeline(&context.pipelines.overlay_line);
+                render_pass.set_bind_group(0, &self.gizmo_camera_bind_group, &[]);
+                buffers.bind(&mut render_pass);
+                render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                render_pass.draw_indexed(0..*num_indices, 0, 0..1)
             }
             //}}}
         }
         //}}}
         //{{{ com: submit the render pass
-        self.queue.submit(Some(encoder.finish()));
+        context.queue.submit(Some(encoder.finish()));
         output.present();
         Ok(())
         //}}}
     }
     //}}}
+    //{{{ fun: read_depth
+    /// Copies the depth texture, as left by the last render, back to the CPU and returns the raw
+    /// depths row by row from the top-left corner of the window.
+    ///
+    /// This blocks until the GPU has finished the copy, and returns `None` if the depth format
+    /// cannot be copied or the readback buffer could not be mapped.
+    pub fn read_depth(&self, context: &WgpuContext) -> Option<Vec<f32>> {
+        // the 24-bit format has no fixed layout and cannot be copied out of the texture
+        let texel_len = match context.depth_mode.format {
+            wgpu::TextureFormat::Depth32Float => 4,
+            wgpu::TextureFormat::Depth16Unorm => 2,
+            format => {
+                //{{{ trace
+                error!("Depth format {:?} cannot be read back", format);
+                //}}}
+                return None;
+            }
+        };
+        let size = self.depth_texture.texture.size();
+        let row_len = size.width as usize * texel_len;
+        // rows copied out of a texture must be padded to the copy alignment
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+        let padded_row_len = row_len.div_ceil(align) * align;
+        //{{{ com: copy the depth texture into a mappable buffer
+        let buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Depth Readback Buffer"),
+            size: (padded_row_len * size.height as usize) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Depth Readback Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.depth_texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::DepthOnly,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_len as u32),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        context.queue.submit(Some(encoder.finish()));
+        //}}}
+        //{{{ com: map the buffer and strip the row padding
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        context.device.poll(wgpu::Maintain::Wait);
+        if let Err(e) = receiver.recv().ok()? {
+            //{{{ trace
+            error!("Failed to map the depth readback buffer: {}", e);
+            //}}}
+            return None;
+        }
+        let depths = slice
+            .get_mapped_range()
+            .chunks_exact(padded_row_len)
+            .flat_map(|row| row[..row_len].chunks_exact(texel_len))
+            .map(|bytes| match bytes {
+                [a, b, c, d] => f32::from_ne_bytes([*a, *b, *c, *d]),
+                [a, b] => u16::from_ne_bytes([*a, *b]) as f32 / u16::MAX as f32,
+                _ => unreachable!(),
+            })
+            .collect();
+        buffer.unmap();
+        //}}}
+        Some(depths)
+    }
+    //}}}
     //{{{ fun: resize
-    pub fn resize(&mut self, width: u32, height: u32) {
+    pub fn resize(&mut self, context: &WgpuContext, width: u32, height: u32) {
         self.config.width = width;
         self.config.height = height;
-        self.surface.configure(&self.device, &self.config);
-        self.depth_texture =
-            dt::DepthTexture::create_depth_texture(&self.device, &self.config, "Depth Texture");
+        self.surface.configure(&context.device, &self.config);
+        self.depth_texture = dt::DepthTexture::create_depth_texture(
+            &context.device,
+            &self.config,
+            context.depth_mode.format,
+            "Depth Texture",
+        );
     }
     //}}}
     //{{{ fun: update_camera
@@ -738,17 +2100,72 @@ impl<'a> WgpuState<'a> {
 //}}}
 //}}}
 //{{{ collection: MeshState
-//{{{ struct: MeshState
-/// Represents the state of a mesh in the application.
-///
-/// This struct contains the next unique identifier (UID) to be assigned to a new mesh,
+//{{{ enum: MeshEditError
+/// Raised when an existing mesh cannot be edited, see `MeshState::append_to_mesh` and
+/// `MeshState::replace_mesh`, or derived from, see `StateCore::add_contours`,
+/// `StateCore::add_bounding_box` and `StateCore::add_normals_visualization`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MeshEditError {
+    #[error("no mesh with the given uid")]
+    NoMesh,
+    #[error("cell types differ")]
+    CellTypeMismatch,
+    #[error("index out of range")]
+    IndexOutOfRange,
+    #[error("the number of values differs from the number of vertices")]
+    ValueCountMismatch,
+    #[error("the mesh has no normals")]
+    NoNormals,
+    #[error("the mesh has no cells to draw")]
+    EmptyMesh,
+    #[error(transparent)]
+    Budget(#[from] BudgetError),
+}
+//}}}
+//{{{ impl: From<AddMeshError> for MeshEditError
+impl From<AddMeshError> for MeshEditError {
+    fn from(e: AddMeshError) -> Self {
+        match e {
+            AddMeshError::Budget(e) => MeshEditError::Budget(e),
+            AddMeshError::Empty => MeshEditError::EmptyMesh,
+        }
+    }
+}
+//}}}
+//{{{ struct: MeshState
+/// Represents the state of a mesh in the application.
+///
+/// This struct contains the next unique identifier (UID) to be assigned to a new mesh,
 /// as well as a HashMap that stores all the existing meshes, indexed by their UIDs.
+///
+/// Names are unique: naming a mesh with a name already in use moves the name over to the new
+/// mesh, and the mesh which held it is left unnamed.
+///
+/// Meshes are only added, or grown, while they fit within the scene budget, unlimited unless set.
+///
+/// Uids are not monotonic. The uid of a removed mesh is recycled, the lowest free uid going to
+/// the next mesh added, and clearing the state frees every uid, so that the first mesh added
+/// afterwards gets the starting uid again. A uid thus only names a mesh until that mesh is
+/// removed or cleared.
 pub struct MeshState<'a, V>
 where
     V: VertexCore + Deserialize<'a> + Serialize,
 {
     pub next_uid: usize,
+    /// Uids below `next_uid` freed by removed meshes, handed out again before `next_uid`.
+    free_uids: BTreeSet<usize>,
     pub meshes: HashMap<usize, MeshCore<'a, V>>,
+    names: HashMap<String, usize>,
+    /// Uids of the meshes edited since the wgpu context last built their buffers.
+    dirty: HashSet<usize>,
+    /// Uids of the meshes whose model matrix changed since the wgpu context last uploaded it.
+    moved: HashSet<usize>,
+    budget: SceneBudget,
+    /// The number of vertices of all the meshes, kept up to date as they are added, edited and
+    /// removed, so that checking the budget does not walk every mesh.
+    num_vertices: usize,
+    /// Whether meshes without any cell are turned away rather than added, see `add_mesh`
+    reject_empty: bool,
 }
 //}}}
 //{{{ impl: MeshState
@@ -761,26 +2178,278 @@ where
     pub fn new() -> Self {
         Self {
             next_uid: Self::START_UID,
+            free_uids: BTreeSet::new(),
             meshes: HashMap::new(),
+            names: HashMap::new(),
+            dirty: HashSet::new(),
+            moved: HashSet::new(),
+            budget: SceneBudget::default(),
+            num_vertices: 0,
+            reject_empty: false,
         }
     }
 
-    pub fn add_mesh(&mut self, mut mesh: MeshCore<'a, V>) -> usize {
+    /// Adds a mesh, returning its uid, unless it does not fit within the budget in which case
+    /// nothing is added.
+    ///
+    /// A mesh without any cell is added like any other, though it has no buffers and is never
+    /// drawn, unless empty meshes are rejected, in which case a warning is logged and nothing is
+    /// added.
+    pub fn add_mesh(&mut self, mut mesh: MeshCore<'a, V>) -> Result<usize, AddMeshError> {
+        if self.reject_empty && !mesh.has_cells() {
+            //{{{ trace
+            warn!("Rejecting a mesh with no cells to draw");
+            //}}}
+            return Err(AddMeshError::Empty);
+        }
+        self.stats().check(1, mesh.num_vertices())?;
         let uid = self.next_uid();
 
         mesh.uid = uid;
+        let name = mesh.name.take();
 
+        self.num_vertices += mesh.num_vertices();
         self.meshes.insert(uid, mesh);
+        // the uid may be a recycled one, whose buffers the wgpu context has not dropped yet
+        self.dirty.insert(uid);
+        if let Some(name) = name {
+            self.set_name(uid, name);
+        }
+
+        Ok(uid)
+    }
+
+    pub fn set_budget(&mut self, budget: SceneBudget) {
+        self.budget = budget;
+    }
+
+    pub fn set_reject_empty(&mut self, reject_empty: bool) {
+        self.reject_empty = reject_empty;
+    }
+
+    /// Returns the number of meshes and vertices held, along with the budget.
+    pub fn stats(&self) -> SceneStats {
+        SceneStats {
+            num_meshes: self.meshes.len(),
+            num_vertices: self.num_vertices,
+            budget: self.budget,
+        }
+    }
+
+    /// Names the mesh with the given uid, taking the name away from any other mesh holding it.
+    /// Returns `false` if there is no such mesh.
+    pub fn set_name(&mut self, uid: usize, name: String) -> bool {
+        let Some(old_name) = self.meshes.get_mut(&uid).map(|mesh| mesh.name.take()) else {
+            return false;
+        };
+        if let Some(old_name) = old_name {
+            self.names.remove(&old_name);
+        }
+        if let Some(prev_uid) = self.names.insert(name.clone(), uid) {
+            if let Some(prev_mesh) = self.meshes.get_mut(&prev_uid) {
+                prev_mesh.name = None;
+            }
+        }
+        self.meshes.get_mut(&uid).unwrap().name = Some(name);
+        true
+    }
+
+    /// Appends the vertices and cells of `mesh` to the mesh with the given uid, offsetting the
+    /// appended indices by its current vertex count, and marks it dirty so that its buffers are
+    /// rebuilt. The indices of `mesh` refer to its own vertices.
+    ///
+    /// Fails, leaving the mesh untouched, if there is no such mesh, if the cell types differ, if
+    /// an index is out of range or if the appended vertices do not fit within the budget.
+    pub fn append_to_mesh(
+        &mut self,
+        uid: usize,
+        mesh: MeshCore<'a, V>,
+    ) -> Result<(), MeshEditError> {
+        let stats = self.stats();
+        let Some(existing) = self.meshes.get_mut(&uid) else {
+            return Err(MeshEditError::NoMesh);
+        };
+        if existing.cell_type != mesh.cell_type {
+            return Err(MeshEditError::CellTypeMismatch);
+        }
+        let nv = mesh.num_vertices() as u32;
+        if mesh.indices.iter().chain(&mesh.line_indices).any(|&index| index >= nv) {
+            return Err(MeshEditError::IndexOutOfRange);
+        }
+        stats.check(0, mesh.num_vertices())?;
+        self.num_vertices += mesh.num_vertices();
+        existing.merge(mesh);
+        self.dirty.insert(uid);
+        Ok(())
+    }
+
+    /// Replaces the mesh with the given uid by `mesh`, keeping its uid and name, and marks it
+    /// dirty so that its buffers are rebuilt.
+    ///
+    /// Fails, leaving the mesh untouched, if there is no such mesh or if the new mesh does not
+    /// fit within the budget in place of the old one.
+    pub fn replace_mesh(
+        &mut self,
+        uid: usize,
+        mut mesh: MeshCore<'a, V>,
+    ) -> Result<(), MeshEditError> {
+        let mut stats = self.stats();
+        let Some(existing) = self.meshes.get_mut(&uid) else {
+            return Err(MeshEditError::NoMesh);
+        };
+        stats.num_meshes -= 1;
+        stats.num_vertices -= existing.num_vertices();
+        stats.check(1, mesh.num_vertices())?;
+        mesh.uid = uid;
+        mesh.name = existing.name.take();
+        mesh.tag = existing.tag.take();
+        self.num_vertices = self.num_vertices - existing.num_vertices() + mesh.num_vertices();
+        *existing = mesh;
+        self.dirty.insert(uid);
+        Ok(())
+    }
+
+    /// Sets how the mesh with the given uid is colored, and marks it dirty so that its buffers
+    /// are rebuilt with the matching vertex layout. Returns `false` if there is no such mesh.
+    pub fn set_color_mode(&mut self, uid: usize, mode: MeshColorMode) -> bool {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_color_mode(mode);
+        self.dirty.insert(uid);
+        true
+    }
+
+    /// Edits the mesh with the given uid in place with `edit`, and marks it dirty so that its
+    /// buffers are rebuilt. Returns `false` if there is no such mesh.
+    pub fn edit_mesh<F>(&mut self, uid: usize, edit: F) -> bool
+    where
+        F: FnOnce(&mut MeshCore<'a, V>),
+    {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        let num_vertices = mesh.num_vertices();
+        edit(mesh);
+        self.num_vertices = self.num_vertices - num_vertices + mesh.num_vertices();
+        self.dirty.insert(uid);
+        true
+    }
+
+    /// Sets the options of the mesh with the given uid, marking it edited so its buffers are
+    /// rebuilt, and returns whether the mesh exists.
+    pub fn set_options(&mut self, uid: usize, options: MeshOptions) -> bool {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_options(options);
+        self.dirty.insert(uid);
+        true
+    }
+
+    /// Returns the uids of the meshes edited since the last call, clearing them.
+    pub fn take_dirty(&mut self) -> HashSet<usize> {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Sets the model matrix of the mesh with the given uid, marking it moved so that only the
+    /// uniform holding the matrix is uploaded again, rather than all its buffers rebuilt. Returns
+    /// `false` if there is no such mesh.
+    pub fn set_model(&mut self, uid: usize, model: Mat4) -> bool {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_model(model);
+        self.moved.insert(uid);
+        true
+    }
 
-        uid
+    /// Sets the layer of the mesh with the given uid, see `MeshCore::set_layer`, marking it
+    /// moved like `set_model`, as the layer is uploaded with the model matrix. Returns `false`
+    /// if there is no such mesh.
+    pub fn set_layer(&mut self, uid: usize, layer: i32) -> bool {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_layer(layer);
+        self.moved.insert(uid);
+        true
+    }
+
+    /// Sets which way the triangles of the mesh with the given uid are wound, marking it moved
+    /// like `set_model`, as only the side culled changes. Returns `false` if there is no such
+    /// mesh.
+    pub fn set_front_face(&mut self, uid: usize, front_face: FrontFace) -> bool {
+        let Some(mesh) = self.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_front_face(front_face);
+        self.moved.insert(uid);
+        true
+    }
+
+    /// Returns the uids of the meshes moved since the last call, clearing them.
+    pub fn take_moved(&mut self) -> HashSet<usize> {
+        std::mem::take(&mut self.moved)
+    }
+
+    /// Returns the uid of the mesh with the given name.
+    pub fn uid_by_name(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    /// Returns the uids of the meshes tagged with `tag`, in increasing order.
+    pub fn uids_by_tag(&self, tag: &str) -> Vec<usize> {
+        let mut uids: Vec<usize> = self
+            .meshes
+            .iter()
+            .filter(|(_, mesh)| mesh.tag() == Some(tag))
+            .map(|(uid, _)| *uid)
+            .collect();
+        uids.sort_unstable();
+        uids
+    }
+
+    /// Casts a ray against the triangle cells of every mesh and returns the uid of the mesh hit
+    /// nearest to the origin of the ray. When two meshes are hit at the same distance, as
+    /// overlapping 2D shapes are, the most recently added one wins.
+    pub fn pick(&self, origin: &Vec3, direction: &Vec3) -> Option<usize> {
+        self.meshes
+            .iter()
+            .filter_map(|(uid, mesh)| mesh.ray_intersection(origin, direction).map(|t| (t, *uid)))
+            .min_by(|(t1, uid1), (t2, uid2)| t1.total_cmp(t2).then(uid2.cmp(uid1)))
+            .map(|(_, uid)| uid)
+    }
+
+    /// Removes the mesh with the given uid, along with its name, and frees its uid for the next
+    /// mesh added. Returns the mesh, or `None` if there is no such mesh.
+    pub fn remove_mesh(&mut self, uid: usize) -> Option<MeshCore<'a, V>> {
+        let mesh = self.meshes.remove(&uid)?;
+        self.num_vertices -= mesh.num_vertices();
+        if let Some(name) = mesh.name.as_ref() {
+            self.names.remove(name);
+        }
+        self.dirty.remove(&uid);
+        self.moved.remove(&uid);
+        self.free_uids.insert(uid);
+        Some(mesh)
     }
 
     fn clear(&mut self) {
         self.meshes.clear();
+        self.names.clear();
+        self.dirty.clear();
+        self.moved.clear();
+        self.free_uids.clear();
+        self.num_vertices = 0;
         self.next_uid = Self::START_UID;
     }   
 
+    /// Returns the lowest free uid, recycling those of removed meshes first.
     fn next_uid(&mut self) -> usize {
+        if let Some(uid) = self.free_uids.pop_first() {
+            return uid;
+        }
         let out = self.next_uid;
 
         self.next_uid += 1;
@@ -814,19 +2483,134 @@ pub trait ViewStateCore {
     fn update(&mut self);
     fn view_controller(&mut self) -> &mut EventController;
     fn view_uniform_buffer(&self) -> &[u8];
+    /// Sets the size of the window in pixels, applying it to the view straight away rather than
+    /// on the next update.
+    fn set_viewport_size(&mut self, width: u32, height: u32);
+    /// Restores the camera to its default position, keeping the current window size.
+    fn reset_view(&mut self);
+    /// Returns the origin and direction, in world coordinates, of the ray passing through the
+    /// given pixel of the window. Used to pick meshes under the cursor.
+    fn pick_ray(&self, pixel: Vec2) -> (Vec3, Vec3);
+    /// Sets the color the highlighted mesh is drawn with in this view.
+    fn set_highlight_color(&mut self, color: &Color);
+    /// Sets the width, in pixels, the edges of triangle cells are drawn with in this view when
+    /// they are drawn as quads.
+    fn set_edge_width(&mut self, width: f32);
+    /// Returns how far the camera moves per key press and mouse wheel step.
+    fn sensitivity(&self) -> ViewSensitivity;
+    /// Sets how far the camera moves per key press and mouse wheel step.
+    fn set_sensitivity(&mut self, sensitivity: &ViewSensitivity);
+    /// Returns the world coordinates under the cursor. Only 2D views, whose meshes all lie in one
+    /// plane, have a single point under the cursor, other views return `None`.
+    fn cursor_world_position(&self) -> Option<Vec2> {
+        None
+    }
+    /// Returns whether the camera is in the middle of an animation, in which case the window
+    /// needs redrawing until it completes.
+    fn is_animating(&self) -> bool {
+        false
+    }
+    /// Maps values read from the depth buffer to distances in front of the camera. Views whose
+    /// depths are already linear leave them as they are.
+    fn linearize_depths(&self, _depths: &mut [f32]) {}
+    /// Switches the projection of the view to reversed-Z, to match the pipelines. Views which
+    /// do not use the depth buffer ignore it.
+    fn set_reversed_z(&mut self, _enabled: bool) {}
+    /// Places the camera in the given octant around its focus, which is also where resetting
+    /// the view returns it. Views without octants ignore it.
+    fn set_initial_octant(&mut self, _octant: i8) {}
+    /// Scales the camera, its clipping planes and how far it pans per key press to a scene with
+    /// the given number of world units in a meter. Views whose camera follows the extent of the
+    /// scene ignore it.
+    fn set_units_per_meter(&mut self, _units_per_meter: f32) {}
+    /// Keeps the proportions of world space whatever the shape of the window, or stretches the
+    /// view over the whole window. Views whose projection already follows the window ignore it.
+    fn set_keep_aspect(&mut self, _keep_aspect: bool) {}
+    /// Aims the camera at the axis-aligned box from `min` to `max` so that it fills the view,
+    /// moving smoothly if `animate` is set. Views without a camera to aim ignore it.
+    fn frame_box(&mut self, _min: Vec3, _max: Vec3, _animate: bool) {}
+    /// Clips the view by the planes, each given as a point and a normal pointing to the side it
+    /// hides, combined according to `mode`. Views without depth to cut through ignore it.
+    fn set_clip_planes(&mut self, _planes: &[(Vec3, Vec3)], _mode: ClipMode) {}
+    /// Returns the view uniform the gizmo is drawn with, which only follows the orientation of
+    /// the camera. Views without a gizmo return `None`.
+    fn gizmo_uniform_buffer(&self) -> Option<&[u8]> {
+        None
+    }
 }
 //..................................................................................................
 //}}}
+//{{{ collection: Viewport
+//{{{ struct: Viewport
+/// A single window of a state, pairing the window's GPU resources with its own view, so that
+/// every window can be navigated independently while displaying the same meshes.
+struct Viewport<'a, ViewState>
+where
+    ViewState: ViewStateCore + Default,
+{
+    /// The id of the window, which events are routed by
+    window_id: winit::window::WindowId,
+    view_state: ViewState,
+    /// The surface and buffers the window is drawn with, `None` for a view without a window to
+    /// draw in, which receives events but is never rendered
+    wgpu_state: Option<WgpuState<'a>>,
+    /// Where the left button was pressed, while it is held and the cursor has not moved away
+    pick_position: Option<[f32; 2]>,
+}
+//}}}
+//{{{ impl: Viewport
+impl<'a, ViewState> Viewport<'a, ViewState>
+where
+    ViewState: ViewStateCore + Default,
+{
+    /// Requests a redraw of the window, if the view has one.
+    fn window_request_redraw(&mut self) {
+        if let Some(wgpu_state) = self.wgpu_state.as_mut() {
+            wgpu_state.window_request_redraw();
+        }
+    }
+}
+//}}}
+//}}}
 //{{{ collection: StateCore
+//{{{ type: FrameCallback
+/// A function run on a state every frame, see [`StateCore::set_frame_callback`].
+pub type FrameCallback<'a, V, ViewState> = Box<dyn FnMut(&mut StateCore<'a, V, ViewState>) + Send>;
+//}}}
 //{{{ struct: StateCore
 pub struct StateCore<'a, V, ViewState>
 where
     V: VertexCore + Deserialize<'a> + Serialize,
     ViewState: ViewStateCore + Default,
 {
-    view_state: ViewState,
-    wgpu_state: Option<WgpuState<'a>>,
+    viewports: Vec<Viewport<'a, ViewState>>,
+    wgpu_context: Option<WgpuContext>,
     mesh_state: MeshState<'a, V>,
+    rendering_enabled: bool,
+    highlight: Option<usize>,
+    highlight_color: Color,
+    wireframe_fallback: bool,
+    edge_width: f32,
+    coordinate_readout: bool,
+    view_sensitivity: Option<ViewSensitivity>,
+    depth_format: dt::DepthFormat,
+    reversed_z: bool,
+    initial_octant: i8,
+    units_per_meter: f32,
+    keep_aspect: bool,
+    gizmo: Option<MeshCore<'a, V>>,
+    gizmo_visible: bool,
+    background_gradient: Option<(Color, Color)>,
+    face_culling: FaceCulling,
+    /// The key cycling through the face culling modes, see `set_culling_toggle_key`
+    culling_toggle_key: Option<char>,
+    clip_planes: Vec<(Vec3, Vec3)>,
+    clip_mode: ClipMode,
+    tessellation_quality: TessellationQuality,
+    draw_context: DrawContext,
+    frame_callback: Option<FrameCallback<'a, V, ViewState>>,
+    /// Notified by the next successful render pass, see `request_flush`
+    flush_waiters: Vec<oneshot::Sender<()>>,
 }
 //}}}
 //{{{ impl: StateCore
@@ -841,12 +2625,34 @@ where
         info!("Creating new StateCore");
         //}}}
 
-        let view_state = ViewState::default();
         let mesh_state = MeshState::new();
         let out = StateCore {
-            view_state: view_state,
-            wgpu_state: None,
+            viewports: Vec::new(),
+            wgpu_context: None,
             mesh_state: mesh_state,
+            rendering_enabled: true,
+            highlight: None,
+            highlight_color: Color::Yellow,
+            wireframe_fallback: false,
+            edge_width: 1.0,
+            coordinate_readout: false,
+            view_sensitivity: None,
+            depth_format: dt::DepthFormat::default(),
+            reversed_z: false,
+            initial_octant: 0,
+            units_per_meter: 1.0,
+            keep_aspect: true,
+            gizmo: None,
+            gizmo_visible: false,
+            background_gradient: None,
+            face_culling: FaceCulling::None,
+            culling_toggle_key: Some('c'),
+            clip_planes: Vec::new(),
+            clip_mode: ClipMode::default(),
+            tessellation_quality: TessellationQuality::default(),
+            draw_context: DrawContext::default(),
+            frame_callback: None,
+            flush_waiters: Vec::new(),
         };
         out
     }
@@ -857,18 +2663,79 @@ where
     }
     //}}}
     //{{{ fun: launch_window
-    pub async fn launch_window(&mut self, event_loop: &ActiveEventLoop) {
+    /// Opens a new window onto the state and returns its id.
+    ///
+    /// The first window creates the GPU device and render pipelines, later windows share them
+    /// along with the mesh buffers. Each window starts with its own default view.
+    pub async fn launch_window(&mut self, event_loop: &ActiveEventLoop) -> winit::window::WindowId {
         //{{{ trace
         info!("Launching window");
         //}}}
+        let window = Arc::new(
+            event_loop
+                .create_window(Window::default_attributes())
+                .unwrap(),
+        );
+        let (mut wgpu_context, surface) = match self.wgpu_context.take() {
+            Some(wgpu_context) => {
+                let surface = wgpu_context
+                    .instance
+                    .create_surface(window.clone())
+                    .unwrap();
+                (wgpu_context, surface)
+            }
+            None => {
+                // 2D meshes all lie at the same depth, which a reversed depth test would reject
+                let reversed_z = self.reversed_z && V::dim() == 3;
+                WgpuContext::new(
+                    window.clone(),
+                    V::desc(),
+                    V::compact_desc(),
+                    V::dim(),
+                    self.depth_format,
+                    reversed_z,
+                )
+                .await
+            }
+        };
+        wgpu_context.wireframe_fallback = self.wireframe_fallback;
+        wgpu_context.thick_edges = self.edge_width != 1.0;
+        wgpu_context.face_culling = self.face_culling;
+        if self.viewports.is_empty() {
+            wgpu_context.set_gizmo(self.gizmo.as_ref());
+            wgpu_context.set_background_gradient(self.background_gradient);
+        }
+
+        // not every platform sends a resize event before the first frame, so the view takes the
+        // size of the window now, before its uniform is uploaded
+        let size = window.inner_size();
+        let mut view_state = ViewState::default();
+        view_state.set_viewport_size(size.width, size.height);
+        view_state.set_highlight_color(&self.highlight_color);
+        view_state.set_edge_width(self.edge_width);
+        view_state.set_reversed_z(wgpu_context.depth_mode.reversed_z);
+        view_state.set_initial_octant(self.initial_octant);
+        view_state.set_units_per_meter(self.units_per_meter);
+        view_state.set_keep_aspect(self.keep_aspect);
+        view_state.set_clip_planes(&self.clip_planes, self.clip_mode);
+        if let Some(sensitivity) = self.view_sensitivity.as_ref() {
+            view_state.set_sensitivity(sensitivity);
+        }
         let wgpu_state = WgpuState::new(
-            event_loop,
-            self.view_state.view_uniform_buffer(),
-            &[V::desc()],
-            V::dim(),
-        )
-        .await;
-        self.wgpu_state = Some(wgpu_state);
+            &wgpu_context,
+            window.clone(),
+            surface,
+            view_state.view_uniform_buffer(),
+        );
+
+        self.wgpu_context = Some(wgpu_context);
+        self.viewports.push(Viewport {
+            window_id: window.id(),
+            view_state: view_state,
+            wgpu_state: Some(wgpu_state),
+            pick_position: None,
+        });
+        window.id()
     }
     //}}}
     //{{{ fun: handle_event
@@ -877,93 +2744,208 @@ where
         window_id: &winit::window::WindowId,
         event: &winit::event::WindowEvent,
     ) {
+        let Some(idx) = self.viewport_index(window_id) else {
+            return;
+        };
+        let viewport = &mut self.viewports[idx];
+
         match event {
             //{{{ case: MouseWheel
-            WindowEvent::MouseWheel { delta, .. } if self.has_window(window_id) => {
+            WindowEvent::MouseWheel { delta, .. } => {
                 //{{{ trace
                 debug!("Mouse wheel: {:?}", delta);
                 //}}}
-                self.view_state.view_controller().mouse_wheel_update(*delta);
+                viewport.view_state.view_controller().mouse_wheel_update(*delta);
 
-                self.window_request_redraw();
+                viewport.window_request_redraw();
             }
             //}}}
             //{{{ case: MouseInput
-            WindowEvent::MouseInput { state, button, .. } if self.has_window(window_id) => {
+            WindowEvent::MouseInput { state, button, .. } => {
                 //{{{ trace
                 debug!("Mouse input: {:?} {:?}", state, button);
                 //}}}
-                self.view_state
+                viewport
+                    .view_state
                     .view_controller()
                     .mouse_input_update(*state, *button);
 
-                self.window_request_redraw();
+                // a mesh is picked by releasing the left button where it was pressed, so that
+                // dragging the view does not change the highlight
+                let position = viewport.view_state.view_controller().mouse_position;
+                let clicked = match (*state, *button) {
+                    (winit::event::ElementState::Pressed, winit::event::MouseButton::Left) => {
+                        viewport.pick_position = Some(position);
+                        false
+                    }
+                    (winit::event::ElementState::Released, winit::event::MouseButton::Left) => {
+                        viewport.pick_position.take().is_some()
+                    }
+                    _ => false,
+                };
+                if clicked {
+                    let [x, y] = position;
+                    let (origin, direction) = viewport.view_state.pick_ray(Vec2::new(x, y));
+                    let picked = self.mesh_state.pick(&origin, &direction);
+                    match picked {
+                        Some(uid) => {
+                            //{{{ trace
+                            info!("Picked mesh {}", uid);
+                            //}}}
+                        }
+                        None => {
+                            //{{{ trace
+                            debug!("No mesh under the cursor");
+                            //}}}
+                        }
+                    }
+                    self.set_highlight(picked);
+                    return;
+                }
+
+                viewport.window_request_redraw();
             }
             //}}}
             //{{{ case: CursorMoved
-            WindowEvent::CursorMoved { position, .. } if self.has_window(window_id) => {
+            WindowEvent::CursorMoved { position, .. } => {
 
-                self.view_state
+                viewport
+                    .view_state
                     .view_controller()
                     .cursor_moved_update(*position);
+
+                if let Some([x, y]) = viewport.pick_position {
+                    let [cursor_x, cursor_y] = viewport.view_state.view_controller().mouse_position;
+                    if (cursor_x - x).hypot(cursor_y - y) > PICK_TOLERANCE {
+                        viewport.pick_position = None;
+                    }
+                }
+
+                if self.coordinate_readout {
+                    if let Some(world) = viewport.view_state.cursor_world_position() {
+                        //{{{ trace
+                        debug!("Cursor at world coordinates ({:.4}, {:.4})", world[0], world[1]);
+                        //}}}
+                    }
+                }
+            }
+            //}}}
+            //{{{ case: CursorLeft
+            WindowEvent::CursorLeft { .. } => {
+                viewport.view_state.view_controller().cursor_left_update();
             }
             //}}}
             //{{{ case: KeyboardInput
-            WindowEvent::KeyboardInput { event, .. } if self.has_window(window_id) => {
+            WindowEvent::KeyboardInput { event, .. } => {
                 //{{{ trace
                 debug!("Keyboard input: {:?}", event);
                 //}}}
                 match event.logical_key {
+                    winit::keyboard::Key::Named(winit::keyboard::NamedKey::Home)
+                        if event.state == winit::event::ElementState::Pressed =>
+                    {
+                        viewport.view_state.reset_view();
+                        viewport.window_request_redraw();
+                    }
+                    winit::keyboard::Key::Character(ref c)
+                        if c.as_str() == "r" && event.state == winit::event::ElementState::Pressed =>
+                    {
+                        viewport.view_state.reset_view();
+                        viewport.window_request_redraw();
+                    }
+                    winit::keyboard::Key::Character(ref c)
+                        if c.as_str() == "g"
+                            && event.state == winit::event::ElementState::Pressed
+                            && self.gizmo.is_some() =>
+                    {
+                        self.gizmo_visible = !self.gizmo_visible;
+                        self.window_request_redraw();
+                    }
+                    winit::keyboard::Key::Character(ref c)
+                        if is_toggle_key(self.culling_toggle_key, c.as_str())
+                            && event.state == winit::event::ElementState::Pressed =>
+                    {
+                        let culling = self.face_culling.next();
+                        //{{{ trace
+                        info!("Face culling set to {:?}", culling);
+                        //}}}
+                        self.set_face_culling(culling);
+                    }
                     winit::keyboard::Key::Named(key) => {
-                        self.view_state
+                        viewport
+                            .view_state
                             .view_controller()
                             .key_input_update(event.state, key);
 
-                        self.window_request_redraw();
+                        viewport.window_request_redraw();
                     }
                     _ => {}
                 }
             }
             //}}}
             //{{{ case: ModifiersChanged
-            WindowEvent::ModifiersChanged(ev) if self.has_window(window_id) => {
+            WindowEvent::ModifiersChanged(ev) => {
                 //{{{ trace
                 debug!("Modifiers changed: {:?}", ev);
                 //}}}
-                self.view_state
+                viewport
+                    .view_state
                     .view_controller()
                     .key_modifiers_update(ev.state());
-                self.window_request_redraw();
+                viewport.window_request_redraw();
             }
             //}}}
             //{{{ case: Resized
-            WindowEvent::Resized(size) if self.has_window(window_id) => {
+            WindowEvent::Resized(size) => {
                 //{{{ trace
                 debug!("Window resized: {:?}", size);
                 //}}}
-                self.view_state.view_controller().resize_update(*size);
-                self.wgpu_state
-                    .as_mut()
-                    .unwrap()
-                    .resize(size.width, size.height);
-                self.window_request_redraw();
+                viewport.view_state.view_controller().resize_update(*size);
+                if let (Some(wgpu_context), Some(wgpu_state)) =
+                    (self.wgpu_context.as_ref(), viewport.wgpu_state.as_mut())
+                {
+                    wgpu_state.resize(wgpu_context, size.width, size.height);
+                }
+                viewport.window_request_redraw();
             }
             //}}}
             //{{{ case: RedrawRequested
-            WindowEvent::RedrawRequested if self.has_window(window_id) => {
+            WindowEvent::RedrawRequested => {
                 //{{{ trace
                 debug!("Redraw requested");
                 //}}}
-                self.view_state.update();
-                self.wgpu_state
-                    .as_mut()
-                    .unwrap()
-                    .update(&mut self.mesh_state, self.view_state.view_uniform_buffer());
+                if !self.rendering_enabled {
+                    //{{{ trace
+                    debug!("Rendering disabled, skipping render pass");
+                    //}}}
+                    return;
+                }
+                // the callback may close windows, so the viewport is looked up again after it
+                self.run_frame_callback();
+                let Some(viewport) = self
+                    .viewport_index(window_id)
+                    .map(|idx| &mut self.viewports[idx])
+                else {
+                    return;
+                };
+                let Some(wgpu_context) = self.wgpu_context.as_mut() else {
+                    return;
+                };
+                let Some(wgpu_state) = viewport.wgpu_state.as_mut() else {
+                    return;
+                };
+                viewport.view_state.update();
+                wgpu_context.update(&mut self.mesh_state);
+                wgpu_state.update_uniform(wgpu_context, viewport.view_state.view_uniform_buffer());
+                if let Some(gizmo_uniform) = viewport.view_state.gizmo_uniform_buffer() {
+                    wgpu_state.update_gizmo_uniform(wgpu_context, gizmo_uniform);
+                }
 
-                match self.wgpu_state.as_mut().unwrap().render() {
+                let show_gizmo = self.gizmo_visible;
+                match wgpu_state.render(wgpu_context, self.highlight, show_gizmo) {
                     Ok(()) => {
                         //{{{ trace
-                        info!("Render successful");
+                        debug!("Render successful");
                         //}}}
                     }
                     Err(e) => {
@@ -972,6 +2954,20 @@ where
                         //}}}
                     }
                 }
+                // the pass drew every mesh added before the flush was requested, or failed, in
+                // which case waiting for the next one could block the waiters indefinitely
+                self.complete_flush_waiters();
+            }
+            //}}}
+            //{{{ case: Occluded
+            WindowEvent::Occluded(occluded) => {
+                //{{{ trace
+                debug!("Window occluded: {}", occluded);
+                //}}}
+                // an occluded window may not be redrawn until it is shown again
+                if *occluded {
+                    self.complete_flush_waiters();
+                }
             }
             //}}}
             //{{{ default
@@ -981,51 +2977,2141 @@ where
     }
     //..............................................................
     //}}}
+    //{{{ fun: viewport_index
+    fn viewport_index(&self, window_id: &winit::window::WindowId) -> Option<usize> {
+        self.viewports
+            .iter()
+            .position(|viewport| viewport.window_id == *window_id)
+    }
+    //..............................................................
+    //}}}
     //{{{ fun: has_window
     pub fn has_window(&mut self, window_id: &winit::window::WindowId) -> bool {
-        self.wgpu_state.as_mut().unwrap().window.id() == *window_id
+        self.viewport_index(window_id).is_some()
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: num_windows
+    /// Returns the number of windows currently open onto this state.
+    pub fn num_windows(&self) -> usize {
+        self.viewports.len()
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: is_animating
+    /// Returns whether the camera of any window is animating, in which case the windows need to
+    /// be redrawn until it completes. A state with a frame callback is always animating, so that
+    /// the callback runs every frame.
+    pub fn is_animating(&self) -> bool {
+        self.frame_callback.is_some()
+            || self
+                .viewports
+                .iter()
+                .any(|viewport| viewport.view_state.is_animating())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_frame_callback
+    /// Sets a function run on the state every frame, just before the frame is rendered, so that
+    /// an application embedding the viewer can edit meshes or move the camera through the public
+    /// API of the state, for instance to drive an animation. `None` removes the callback. An
+    /// application reaches the state of its viewer through [`crate::app::TopoViewer`], see
+    /// `get_state_2d_mut` and `get_state_3d_mut`.
+    ///
+    /// While a callback is set the windows are redrawn continuously, see `is_animating`.
+    ///
+    /// The callback is called with the state locked, from the thread running the event loop, so
+    /// it must not lock the mutex holding the state, which would deadlock, and should return
+    /// quickly, as RPC requests wait on the lock meanwhile. It is taken out of the state while it
+    /// runs: setting a new callback from within it replaces it from the next frame on.
+    pub fn set_frame_callback(&mut self, frame_callback: Option<FrameCallback<'a, V, ViewState>>) {
+        self.frame_callback = frame_callback;
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: run_frame_callback
+    /// Runs the frame callback, if any, on the state, as the event loop does before rendering
+    /// every frame.
+    pub fn run_frame_callback(&mut self) {
+        let Some(mut frame_callback) = self.frame_callback.take() else {
+            return;
+        };
+        frame_callback(self);
+        // a callback set by the callback itself takes over
+        if self.frame_callback.is_none() {
+            self.frame_callback = Some(frame_callback);
+        }
     }
     //..............................................................
     //}}}
     //{{{ fun: window_request_redraw
+    /// Requests a redraw of every window.
     pub fn window_request_redraw(&mut self) {
-        self.wgpu_state.as_mut().unwrap().window_request_redraw();
+        for viewport in self.viewports.iter_mut() {
+            viewport.window_request_redraw();
+        }
     }
     //..............................................................
     //}}}
-    //{{{ fun: add_mesh
-    pub fn add_mesh(&mut self, mesh: MeshCore<'a, V>) -> usize {
-        let uid = self.mesh_state.add_mesh(mesh);
-        self.window_request_redraw();
-        uid
+    //{{{ fun: close_window
+    /// Drops all the windows and all the GPU resources attached to them.
+    ///
+    /// The mesh state is left untouched. Once closed, events and redraw requests are ignored
+    /// until a new window is launched.
+    pub fn close_window(&mut self) {
+        //{{{ trace
+        info!("Closing all windows");
+        //}}}
+        self.viewports.clear();
+        self.wgpu_context = None;
+        // nothing will be rendered anymore, dropping the senders wakes up the waiters
+        self.flush_waiters.clear();
     }
     //..............................................................
     //}}}
-    //{{{ fun: get_mesh
-    pub fn get_mesh(&self, uid: usize) -> Option<&MeshCore<'a, V>> {
-        self.mesh_state.meshes.get(&uid)
+    //{{{ fun: close_window_with_id
+    /// Drops a single window along with its view, returning the number of windows left open.
+    ///
+    /// Closing the last window also releases the shared GPU resources, as in `close_window`.
+    pub fn close_window_with_id(&mut self, window_id: &winit::window::WindowId) -> usize {
+        if let Some(idx) = self.viewport_index(window_id) {
+            //{{{ trace
+            info!("Closing window {:?}", window_id);
+            //}}}
+            self.viewports.remove(idx);
+        }
+        if self.viewports.is_empty() {
+            self.wgpu_context = None;
+            self.flush_waiters.clear();
+        }
+        self.viewports.len()
     }
     //..............................................................
     //}}}
-    //{{{ fun: get_mesh_mut
-    pub fn get_mesh_mut(&mut self, uid: usize) -> Option<&mut MeshCore<'a, V>> {
-        self.mesh_state.meshes.get_mut(&uid)
+    //{{{ fun: view_state
+    /// Returns the view state of the first window opened, which holds that window's camera.
+    ///
+    /// Returns `None` if no window is open.
+    pub fn view_state(&self) -> Option<&ViewState> {
+        self.viewports.first().map(|viewport| &viewport.view_state)
     }
     //..............................................................
     //}}}
-    //{{{ fun: clear_meshes
-    pub fn clear(&mut self) {
-        self.mesh_state.clear();
+    //{{{ fun: view_state_of
+    /// Returns the view state of the given window, if it belongs to this state.
+    pub fn view_state_of(&self, window_id: &winit::window::WindowId) -> Option<&ViewState> {
+        self.viewport_index(window_id)
+            .map(|idx| &self.viewports[idx].view_state)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: read_depth_buffer
+    /// Reads back the depth buffer of the first window opened, as left by its last render, with
+    /// the depths linearized by the window's view.
+    ///
+    /// Returns `None` if no window is open or the readback fails. This stalls until the GPU is
+    /// idle, so it is meant for debugging rather than for every frame.
+    pub fn read_depth_buffer(&self) -> Option<DepthBuffer> {
+        let viewport = self.viewports.first()?;
+        let wgpu_context = self.wgpu_context.as_ref()?;
+        let wgpu_state = viewport.wgpu_state.as_ref()?;
+        let mut depths = wgpu_state.read_depth(wgpu_context)?;
+        viewport.view_state.linearize_depths(&mut depths);
+        let size = wgpu_state.depth_texture.texture.size();
+        Some(DepthBuffer {
+            width: size.width,
+            height: size.height,
+            depths: depths,
+        })
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: pick
+    /// Returns the uid of the mesh under the given pixel of the first window opened, if any.
+    ///
+    /// In 3D the nearest triangle mesh hit by the ray through the pixel is returned, in 2D the
+    /// filled mesh containing the point. Line cells are never picked.
+    pub fn pick(&self, pixel: Vec2) -> Option<usize> {
+        let viewport = self.viewports.first()?;
+        let (origin, direction) = viewport.view_state.pick_ray(pixel);
+        self.mesh_state.pick(&origin, &direction)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_highlight
+    /// Highlights the mesh with the given uid, drawing its edges and lines on top of everything
+    /// else in the highlight color. Passing `None` clears the highlight.
+    pub fn set_highlight(&mut self, uid: Option<usize>) {
+        self.highlight = uid;
         self.window_request_redraw();
     }
+    //..............................................................
     //}}}
-}
-//}}}
-//..................................................................................................
-//}}}
-
-//-------------------------------------------------------------------------------------------------
-//{{{ mod: tests
-#[cfg(test)]
-mod tests {}
+    //{{{ fun: highlight
+    /// Returns the uid of the highlighted mesh, if any.
+    pub fn highlight(&self) -> Option<usize> {
+        self.highlight
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_highlight_color
+    /// Sets the color the highlighted mesh is drawn with, in every window.
+    pub fn set_highlight_color(&mut self, color: Color) {
+        for viewport in self.viewports.iter_mut() {
+            viewport.view_state.set_highlight_color(&color);
+            viewport.window_request_redraw();
+        }
+        self.highlight_color = color;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_view_sensitivity
+    /// Sets how far the camera of every window, and of windows opened later, moves per key press
+    /// and mouse wheel step.
+    ///
+    /// Every value must be finite and strictly positive, otherwise the sensitivity is left
+    /// unchanged and the name of the offending field is returned.
+    pub fn set_view_sensitivity(
+        &mut self,
+        sensitivity: ViewSensitivity,
+    ) -> Result<(), &'static str> {
+        sensitivity.validate()?;
+        //{{{ trace
+        info!("Setting view sensitivity to {:?}", sensitivity);
+        //}}}
+        for viewport in self.viewports.iter_mut() {
+            viewport.view_state.set_sensitivity(&sensitivity);
+        }
+        self.view_sensitivity = Some(sensitivity);
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: view_sensitivity
+    /// Returns the sensitivity set with `set_view_sensitivity`, or that of the first window if
+    /// none was set. Returns `None` if neither exists.
+    pub fn view_sensitivity(&self) -> Option<ViewSensitivity> {
+        self.view_sensitivity
+            .or_else(|| self.view_state().map(|view_state| view_state.sensitivity()))
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: update_views
+    /// Applies `f` to the view state of every window and redraws them.
+    pub fn update_views<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut ViewState),
+    {
+        for viewport in self.viewports.iter_mut() {
+            f(&mut viewport.view_state);
+            viewport.window_request_redraw();
+        }
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: reset_view
+    /// Resets the camera of every window to its default state and redraws.
+    pub fn reset_view(&mut self) {
+        //{{{ trace
+        info!("Resetting view");
+        //}}}
+        for viewport in self.viewports.iter_mut() {
+            viewport.view_state.reset_view();
+            viewport.window_request_redraw();
+        }
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_rendering_enabled
+    /// Enables or disables rendering.
+    ///
+    /// While rendering is disabled redraw requests are ignored, but meshes can still be added,
+    /// modified and removed. Re-enabling rendering forces a redraw so the window catches up with
+    /// the current scene.
+    pub fn set_rendering_enabled(&mut self, enabled: bool) {
+        //{{{ trace
+        info!("Setting rendering enabled to {}", enabled);
+        //}}}
+        let was_enabled = self.rendering_enabled;
+        self.rendering_enabled = enabled;
+        if enabled && !was_enabled {
+            self.window_request_redraw();
+        }
+        if !enabled {
+            // no render pass is coming, so pending flushes complete straight away
+            self.complete_flush_waiters();
+        }
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: complete_flush_waiters
+    /// Completes every pending flush, see `request_flush`.
+    fn complete_flush_waiters(&mut self) {
+        for waiter in self.flush_waiters.drain(..) {
+            let _ = waiter.send(());
+        }
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: request_flush
+    /// Requests a redraw of every window and returns a receiver completed once a render pass
+    /// has drawn the scene as it stands, including every mesh added so far. Waiting on it lets
+    /// a client know its meshes are on screen, for instance before taking a screenshot.
+    ///
+    /// The receiver completes straight away when rendering is disabled or no window is open, as
+    /// there is nothing to wait for, and fails if the windows are closed before rendering. It
+    /// also completes when the render pass fails or a window is occluded, since the scene may
+    /// then not be drawn for a long time.
+    pub fn request_flush(&mut self) -> oneshot::Receiver<()> {
+        let (sender, receiver) = oneshot::channel();
+        if self.rendering_enabled && !self.viewports.is_empty() {
+            self.flush_waiters.push(sender);
+            self.window_request_redraw();
+        } else {
+            let _ = sender.send(());
+        }
+        receiver
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: rendering_enabled
+    /// Returns whether rendering is currently enabled.
+    pub fn rendering_enabled(&self) -> bool {
+        self.rendering_enabled
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_wireframe_fallback
+    /// Enables or disables the wireframe fallback and redraws.
+    ///
+    /// Backends without line polygon mode cannot draw the edges of triangle meshes. With the
+    /// fallback enabled, the edges are instead drawn as line cells generated from the triangles,
+    /// at the cost of an extra index buffer per triangle mesh. It has no effect on backends which
+    /// support line polygon mode, and is disabled by default.
+    pub fn set_wireframe_fallback(&mut self, enabled: bool) {
+        self.wireframe_fallback = enabled;
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.wireframe_fallback = enabled;
+        }
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: wireframe_fallback
+    /// Returns whether the wireframe fallback is enabled.
+    pub fn wireframe_fallback(&self) -> bool {
+        self.wireframe_fallback
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_face_culling
+    /// Sets which side of the triangle faces is left undrawn in every window, and redraws. Culling
+    /// the back or the front in turn shows which way the triangles of a mesh are wound. Nothing is
+    /// culled by default.
+    pub fn set_face_culling(&mut self, culling: FaceCulling) {
+        self.face_culling = culling;
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.face_culling = culling;
+        }
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: face_culling
+    /// Returns which side of the triangle faces is left undrawn.
+    pub fn face_culling(&self) -> FaceCulling {
+        self.face_culling
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_culling_toggle_key
+    /// Sets the key which, pressed in a window, cycles the face culling from none through the
+    /// back and the front, see [`FaceCulling::next`], logging the new mode. `None` disables the
+    /// toggle. The key is `c` by default.
+    pub fn set_culling_toggle_key(&mut self, key: Option<char>) {
+        self.culling_toggle_key = key;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_edge_width
+    /// Sets the width, in pixels, the edges of triangle meshes are drawn with in every window,
+    /// whether or not their faces are drawn, and redraws.
+    ///
+    /// The default of 1 draws the edges as lines. Any other width draws each edge as a quad
+    /// instead, at the cost of an extra vertex buffer per triangle mesh holding the vertices of
+    /// its edges. The width must be finite and strictly positive, otherwise it is left unchanged
+    /// and an error is returned.
+    pub fn set_edge_width(&mut self, width: f32) -> Result<(), &'static str> {
+        if !(width.is_finite() && width > 0.0) {
+            return Err("edge_width");
+        }
+        //{{{ trace
+        info!("Setting edge width to {}", width);
+        //}}}
+        self.edge_width = width;
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.thick_edges = width != 1.0;
+        }
+        self.update_views(|view_state| view_state.set_edge_width(width));
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: edge_width
+    /// Returns the width, in pixels, the edges of triangle meshes are drawn with.
+    pub fn edge_width(&self) -> f32 {
+        self.edge_width
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_draw_context
+    /// Sets the colors and cell type the `add_*_simple` methods draw shapes with. Shapes added
+    /// before are left as they are.
+    pub fn set_draw_context(&mut self, draw_context: DrawContext) {
+        self.draw_context = draw_context;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: draw_context
+    /// Returns the colors and cell type the `add_*_simple` methods draw shapes with.
+    pub fn draw_context(&self) -> DrawContext {
+        self.draw_context
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_depth_format
+    /// Sets the precision of the depth buffer. The format is fixed when the GPU context is
+    /// created, so this only takes effect for the first window launched after it is set, or after
+    /// every window has been closed. Formats the adapter cannot render to fall back to 32-bit
+    /// floating point depth.
+    pub fn set_depth_format(&mut self, depth_format: dt::DepthFormat) {
+        self.depth_format = depth_format;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: depth_format
+    /// Returns the requested precision of the depth buffer.
+    pub fn depth_format(&self) -> dt::DepthFormat {
+        self.depth_format
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_reversed_z
+    /// Enables reversed-Z depth, mapping the near plane to a depth of one and the far plane to
+    /// zero, for better depth precision far from the camera. Like the depth format it is fixed
+    /// when the GPU context is created, and it only applies to 3D views. Disabled by default.
+    pub fn set_reversed_z(&mut self, enabled: bool) {
+        self.reversed_z = enabled;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: reversed_z
+    /// Returns whether reversed-Z depth was requested.
+    pub fn reversed_z(&self) -> bool {
+        self.reversed_z
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_initial_octant
+    /// Sets the octant, 0 to 7, the camera of every window opened later starts in and returns to
+    /// when its view is reset. Only applies to 3D views, octant 0 by default. Any other octant
+    /// is left unchanged and an error is returned.
+    pub fn set_initial_octant(&mut self, octant: i8) -> Result<(), &'static str> {
+        if !(0..8).contains(&octant) {
+            return Err("octant");
+        }
+        self.initial_octant = octant;
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: initial_octant
+    pub fn initial_octant(&self) -> i8 {
+        self.initial_octant
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_units_per_meter
+    /// Sets the number of world units in a meter of the scene, 1 by default, and rescales the
+    /// camera of every window to it, along with those opened later. The default clipping planes,
+    /// camera distance and pan per key press suit scenes a few meters across, so scenes in
+    /// millimeters, say, are clipped or crawl unless this is set to 1000.
+    /// Only applies to 3D views. The number must be finite and strictly positive, otherwise it
+    /// is left unchanged and an error is returned.
+    pub fn set_units_per_meter(&mut self, units_per_meter: f32) -> Result<(), &'static str> {
+        if !(units_per_meter.is_finite() && units_per_meter > 0.0) {
+            return Err("units_per_meter");
+        }
+        //{{{ trace
+        info!("Setting units per meter to {}", units_per_meter);
+        //}}}
+        self.units_per_meter = units_per_meter;
+        self.update_views(|view_state| view_state.set_units_per_meter(units_per_meter));
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: units_per_meter
+    pub fn units_per_meter(&self) -> f32 {
+        self.units_per_meter
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_keep_aspect
+    /// Keeps the proportions of world space in every window, and those opened later, whatever
+    /// their shape, or stretches the view over the whole window. Only applies to 2D views, whose
+    /// proportions are kept by default.
+    pub fn set_keep_aspect(&mut self, keep_aspect: bool) {
+        self.keep_aspect = keep_aspect;
+        self.update_views(|view_state| view_state.set_keep_aspect(keep_aspect));
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: keep_aspect
+    /// Returns whether 2D views keep the proportions of world space.
+    pub fn keep_aspect(&self) -> bool {
+        self.keep_aspect
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_gizmo
+    /// Sets the mesh drawn as a gizmo in the bottom-left corner of every window, with the
+    /// orientation of the camera but none of its position or zoom. Only its line cells are
+    /// drawn, over the scene. `None` removes the gizmo.
+    pub fn set_gizmo(&mut self, gizmo: Option<MeshCore<'a, V>>) {
+        self.gizmo = gizmo;
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.set_gizmo(self.gizmo.as_ref());
+        }
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: has_gizmo
+    /// Returns whether a gizmo mesh has been set.
+    pub fn has_gizmo(&self) -> bool {
+        self.gizmo.is_some()
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_gizmo_visible
+    /// Shows or hides the gizmo and redraws. Pressing `g` in a window toggles it too.
+    pub fn set_gizmo_visible(&mut self, visible: bool) {
+        self.gizmo_visible = visible;
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: gizmo_visible
+    /// Returns whether the gizmo is shown, once one has been set.
+    pub fn gizmo_visible(&self) -> bool {
+        self.gizmo_visible
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_background_gradient
+    /// Fills the background of every window with a vertical gradient from the `top` color at the
+    /// top edge to the `bottom` color at the bottom edge, instead of the flat clear color.
+    pub fn set_background_gradient(&mut self, top: Color, bottom: Color) {
+        self.background_gradient = Some((top, bottom));
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.set_background_gradient(self.background_gradient);
+        }
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: clear_background_gradient
+    /// Removes the background gradient, restoring the flat clear color.
+    pub fn clear_background_gradient(&mut self) {
+        self.background_gradient = None;
+        if let Some(wgpu_context) = self.wgpu_context.as_mut() {
+            wgpu_context.set_background_gradient(None);
+        }
+        self.window_request_redraw();
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: background_gradient
+    /// Returns the top and bottom colors of the background gradient, if one is set.
+    pub fn background_gradient(&self) -> Option<(Color, Color)> {
+        self.background_gradient
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_clip_plane
+    /// Cuts every window through the plane given as a point and a normal, hiding everything on
+    /// the side the normal points to so the inside of solids shows, or removes every clipping
+    /// plane when `plane` is `None`. Only 3D views are cut.
+    ///
+    /// The point must be finite and the normal finite and non-zero, otherwise the planes are
+    /// left unchanged and the name of the offending field is returned.
+    pub fn set_clip_plane(&mut self, plane: Option<(Vec3, Vec3)>) -> Result<(), &'static str> {
+        self.set_clip_planes(plane.into_iter().collect(), ClipMode::InsideAll)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_clip_planes
+    /// Cuts every window through up to [`MAX_CLIP_PLANES`] planes, each given as a point and a
+    /// normal pointing to the side it hides. With [`ClipMode::InsideAll`] only what lies inside
+    /// every plane shows, with [`ClipMode::InsideAny`] what lies inside any of them. An empty
+    /// list removes the clipping. Only 3D views are cut.
+    ///
+    /// Every point must be finite and every normal finite and non-zero, otherwise the planes are
+    /// left unchanged and the name of the offending field is returned.
+    pub fn set_clip_planes(
+        &mut self,
+        planes: Vec<(Vec3, Vec3)>,
+        mode: ClipMode,
+    ) -> Result<(), &'static str> {
+        if planes.len() > MAX_CLIP_PLANES {
+            return Err("planes");
+        }
+        for (point, normal) in planes.iter() {
+            if !point.iter().all(|x| x.is_finite()) {
+                return Err("point");
+            }
+            if !normal.iter().all(|x| x.is_finite()) || normal.norm_squared() == 0.0 {
+                return Err("normal");
+            }
+        }
+        //{{{ trace
+        info!("Setting {} clip planes, {:?}", planes.len(), mode);
+        //}}}
+        self.update_views(|view_state| view_state.set_clip_planes(&planes, mode));
+        self.clip_planes = planes;
+        self.clip_mode = mode;
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_clip_box
+    /// Cuts every window down to the axis-aligned box from `min` to `max`, hiding everything
+    /// outside it. Both corners must be finite and `max` must not be below `min` on any axis,
+    /// otherwise the planes are left unchanged and the name of the offending corner is returned.
+    pub fn set_clip_box(&mut self, min: Vec3, max: Vec3) -> Result<(), &'static str> {
+        if !min.iter().all(|x| x.is_finite()) {
+            return Err("min");
+        }
+        if !max.iter().all(|x| x.is_finite()) || max.iter().zip(min.iter()).any(|(a, b)| a < b) {
+            return Err("max");
+        }
+        self.set_clip_planes(clip_box_planes(&min, &max), ClipMode::InsideAll)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: clip_planes
+    /// Returns the points and normals of the clipping planes, empty when nothing is clipped.
+    pub fn clip_planes(&self) -> &[(Vec3, Vec3)] {
+        &self.clip_planes
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: clip_mode
+    /// Returns how the clipping planes combine.
+    pub fn clip_mode(&self) -> ClipMode {
+        self.clip_mode
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_tessellation_quality
+    /// Sets the quality the shapes added from now on are tessellated at when their descriptor
+    /// leaves a subdivision count at zero, see [`TessellationQuality`] for the counts. Meshes
+    /// already added keep theirs. Fails, keeping the current quality, if an adaptive quality
+    /// has a chord error which is not finite and positive.
+    pub fn set_tessellation_quality(
+        &mut self,
+        quality: TessellationQuality,
+    ) -> Result<(), &'static str> {
+        if !quality.is_valid() {
+            return Err("chord_error");
+        }
+        //{{{ trace
+        info!("Setting tessellation quality to {:?}", quality);
+        //}}}
+        self.tessellation_quality = quality;
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: tessellation_quality
+    /// Returns the quality supplying the subdivision counts left at zero in descriptors.
+    pub fn tessellation_quality(&self) -> TessellationQuality {
+        self.tessellation_quality
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_coordinate_readout
+    /// Enables or disables logging, at debug level, the world coordinates under the cursor as it
+    /// moves. Only 2D views report coordinates. Disabled by default, as it logs on every move.
+    pub fn set_coordinate_readout(&mut self, enabled: bool) {
+        self.coordinate_readout = enabled;
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: coordinate_readout
+    /// Returns whether the world coordinates under the cursor are logged as it moves.
+    pub fn coordinate_readout(&self) -> bool {
+        self.coordinate_readout
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: add_mesh
+    /// Adds a mesh and returns its uid, or fails without adding it if it does not fit within
+    /// the scene budget or is empty while empty meshes are rejected. The uid may be one freed by
+    /// a removed mesh, see [`MeshState`].
+    pub fn add_mesh(&mut self, mesh: MeshCore<'a, V>) -> Result<usize, AddMeshError> {
+        let uid = self.mesh_state.add_mesh(mesh)?;
+        self.window_request_redraw();
+        Ok(uid)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: remove_mesh
+    /// Removes the mesh with the given uid, returning `false` if there is no such mesh. Its uid
+    /// is recycled, the next mesh added may get it, see [`MeshState`].
+    pub fn remove_mesh(&mut self, uid: usize) -> bool {
+        if self.mesh_state.remove_mesh(uid).is_none() {
+            return false;
+        }
+        if self.highlight == Some(uid) {
+            self.highlight = None;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_reject_empty_meshes
+    /// Turns away meshes without any line or triangle cell, such as the contours of a level no
+    /// value reaches, with a warning and an [`AddMeshError::Empty`], rather than adding them.
+    /// Off by default, in which case such meshes are added but never drawn.
+    pub fn set_reject_empty_meshes(&mut self, reject_empty: bool) {
+        self.mesh_state.set_reject_empty(reject_empty);
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_scene_budget
+    /// Limits the number of meshes and the total number of vertices the state holds. Meshes
+    /// already added are kept even if they exceed the new budget, but nothing more can be added
+    /// until they fit.
+    pub fn set_scene_budget(&mut self, budget: SceneBudget) {
+        //{{{ trace
+        info!("Setting scene budget to {:?}", budget);
+        //}}}
+        self.mesh_state.set_budget(budget);
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: scene_stats
+    /// Returns the number of meshes and vertices the state holds, along with its budget.
+    pub fn scene_stats(&self) -> SceneStats {
+        self.mesh_state.stats()
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: scene_bounds
+    /// Returns the smallest axis aligned box, as its minimum and maximum corners, enclosing the
+    /// bounding boxes of every mesh in the state, or `None` if there are no vertices.
+    pub fn scene_bounds(&self) -> Option<(Vec3, Vec3)> {
+        self.mesh_state
+            .meshes
+            .values()
+            .filter_map(|mesh| mesh.bounding_box())
+            .reduce(|(min1, max1), (min2, max2)| (min1.inf(&min2), max1.sup(&max2)))
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: contains_mesh
+    /// Returns whether the state holds a mesh with the given uid.
+    pub fn contains_mesh(&self, uid: usize) -> bool {
+        self.mesh_state.meshes.contains_key(&uid)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: get_mesh
+    pub fn get_mesh(&self, uid: usize) -> Option<&MeshCore<'a, V>> {
+        self.mesh_state.meshes.get(&uid)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: get_mesh_mut
+    /// Returns the mesh with the given uid for editing in place. The vertex count of the scene,
+    /// which the budget is checked against, is not updated, so vertices should be added through
+    /// `append_to_mesh` or `replace_mesh` instead.
+    pub fn get_mesh_mut(&mut self, uid: usize) -> Option<&mut MeshCore<'a, V>> {
+        self.mesh_state.meshes.get_mut(&uid)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: focus_on_mesh
+    /// Aims the camera of every window at the centre of the bounding box of the mesh with the
+    /// given uid, from far enough away to frame the whole mesh, moving smoothly if `animate` is
+    /// set. Only 3D views move. Returns `false` if there is no mesh with the given uid.
+    pub fn focus_on_mesh(&mut self, uid: usize, animate: bool) -> bool {
+        let Some(mesh) = self.mesh_state.meshes.get(&uid) else {
+            return false;
+        };
+        if let Some((min, max)) = mesh.bounding_box() {
+            //{{{ trace
+            info!("Focusing on mesh {} in box {} to {}", uid, min, max);
+            //}}}
+            self.update_views(|view_state| view_state.frame_box(min, max, animate));
+        }
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: append_to_mesh
+    /// Appends the vertices and cells of `mesh` to an existing mesh, see
+    /// `MeshState::append_to_mesh`, so that a mesh can be grown over time without replacing it.
+    pub fn append_to_mesh(
+        &mut self,
+        uid: usize,
+        mesh: MeshCore<'a, V>,
+    ) -> Result<(), MeshEditError> {
+        self.mesh_state.append_to_mesh(uid, mesh)?;
+        self.window_request_redraw();
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: replace_mesh
+    /// Swaps the geometry of an existing mesh for that of `mesh`, see `MeshState::replace_mesh`.
+    /// Unlike removing the mesh and adding a new one, this keeps its uid, so that references to
+    /// it stay valid.
+    pub fn replace_mesh(
+        &mut self,
+        uid: usize,
+        mesh: MeshCore<'a, V>,
+    ) -> Result<(), MeshEditError> {
+        self.mesh_state.replace_mesh(uid, mesh)?;
+        self.window_request_redraw();
+        Ok(())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_color_mode
+    /// Colors the mesh with the given uid per vertex, or with a single color, see
+    /// [`MeshColorMode`]. Returns `false` if there is no mesh with the given uid.
+    pub fn set_mesh_color_mode(&mut self, uid: usize, mode: MeshColorMode) -> bool {
+        if !self.mesh_state.set_color_mode(uid, mode) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_options
+    /// Sets the options controlling how the mesh with the given uid is drawn, such as the color
+    /// of the edges drawn over its triangle cells, returning whether the mesh exists.
+    pub fn set_mesh_options(&mut self, uid: usize, options: MeshOptions) -> bool {
+        if !self.mesh_state.set_options(uid, options) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_model
+    /// Places the mesh with the given uid in the world with the model matrix `model`, see
+    /// `MeshCore::set_model`. Only the matrix is uploaded again, so this is the cheap way of
+    /// moving a mesh around. Returns `false` if there is no mesh with the given uid.
+    pub fn set_mesh_model(&mut self, uid: usize, model: Mat4) -> bool {
+        if !self.mesh_state.set_model(uid, model) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_layer
+    /// Moves the mesh with the given uid to another layer, see `MeshCore::set_layer`, without
+    /// rebuilding its buffers. Returns `false` if there is no mesh with the given uid.
+    pub fn set_mesh_layer(&mut self, uid: usize, layer: i32) -> bool {
+        if !self.mesh_state.set_layer(uid, layer) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_front_face
+    /// Sets which way the triangles of the mesh with the given uid are wound seen from their
+    /// front, see `MeshCore::set_front_face`, so that face culling leaves the right side of it
+    /// undrawn. Returns `false` if there is no mesh with the given uid.
+    pub fn set_mesh_front_face(&mut self, uid: usize, front_face: FrontFace) -> bool {
+        if !self.mesh_state.set_front_face(uid, front_face) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: translate_mesh
+    /// Moves the mesh with the given uid by `offset`, see `MeshCore::translate`. Returns `false`
+    /// if there is no mesh with the given uid.
+    pub fn translate_mesh(&mut self, uid: usize, offset: &V::Vec) -> bool
+    where
+        V::Vec: Index<usize, Output = f32>,
+    {
+        if !self.mesh_state.edit_mesh(uid, |mesh| mesh.translate(offset)) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: scale_mesh
+    /// Scales the mesh with the given uid by `factor` about the point `about`, see
+    /// `MeshCore::scale_uniform`. Returns `false` if there is no mesh with the given uid.
+    pub fn scale_mesh(&mut self, uid: usize, factor: f32, about: &V::Vec) -> bool
+    where
+        V::Vec: Index<usize, Output = f32>,
+    {
+        if !self.mesh_state.edit_mesh(uid, |mesh| mesh.scale_uniform(factor, about)) {
+            return false;
+        }
+        self.window_request_redraw();
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: add_named_mesh
+    /// Adds a mesh under the given name, so that it can later be found with `get_mesh_by_name`
+    /// as well as by its uid, which is returned.
+    ///
+    /// Names are unique within a state. Adding a mesh under a name already in use moves the name
+    /// over to the new mesh, leaving the previous holder unnamed but otherwise untouched.
+    pub fn add_named_mesh(
+        &mut self,
+        name: &str,
+        mut mesh: MeshCore<'a, V>,
+    ) -> Result<usize, AddMeshError> {
+        mesh.name = Some(name.to_string());
+        self.add_mesh(mesh)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_name
+    /// Names an existing mesh, following the same policy as `add_named_mesh`. Returns `false`
+    /// if there is no mesh with the given uid.
+    pub fn set_mesh_name(&mut self, uid: usize, name: &str) -> bool {
+        self.mesh_state.set_name(uid, name.to_string())
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: mesh_uid_by_name
+    /// Returns the uid of the mesh currently holding the given name.
+    pub fn mesh_uid_by_name(&self, name: &str) -> Option<usize> {
+        self.mesh_state.uid_by_name(name)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: get_mesh_by_name
+    /// Returns the mesh currently holding the given name.
+    pub fn get_mesh_by_name(&self, name: &str) -> Option<&MeshCore<'a, V>> {
+        self.mesh_uid_by_name(name).and_then(|uid| self.get_mesh(uid))
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: set_mesh_tag
+    /// Puts the mesh with the given uid in the group with the given tag, or in no group with
+    /// `None`. Unlike names, any number of meshes share a tag. Returns `false` if there is no
+    /// mesh with the given uid.
+    pub fn set_mesh_tag(&mut self, uid: usize, tag: Option<&str>) -> bool {
+        let Some(mesh) = self.mesh_state.meshes.get_mut(&uid) else {
+            return false;
+        };
+        mesh.set_tag(tag);
+        true
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: mesh_uids_by_tag
+    /// Returns the uids of the meshes in the group with the given tag, in increasing order.
+    pub fn mesh_uids_by_tag(&self, tag: &str) -> Vec<usize> {
+        self.mesh_state.uids_by_tag(tag)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: transform_group
+    /// Moves every mesh in the group with the given tag by `transform`, applied after the model
+    /// matrix each mesh already has, so that the group moves as one. Like `set_mesh_model` only
+    /// the matrices are uploaded again. Returns how many meshes were moved.
+    pub fn transform_group(&mut self, tag: &str, transform: Mat4) -> usize {
+        let uids = self.mesh_state.uids_by_tag(tag);
+        for &uid in &uids {
+            let model = transform * self.mesh_state.meshes[&uid].model();
+            self.mesh_state.set_model(uid, model);
+        }
+        if !uids.is_empty() {
+            self.window_request_redraw();
+        }
+        uids.len()
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: add_contours
+    /// Adds a line mesh holding the contours of `values`, one per vertex of the triangle mesh
+    /// with the given uid, at each of `levels`, drawn in `color`, and returns the uid of the new
+    /// mesh. See `MeshCore::contours`.
+    ///
+    /// The contours get the depth bias of triangle edges, so they are drawn over the surface
+    /// they lie on but stay hidden behind it.
+    pub fn add_contours(
+        &mut self,
+        uid: usize,
+        values: &[f32],
+        levels: &[f32],
+        color: &Color,
+    ) -> Result<usize, MeshEditError> {
+        let Some(mesh) = self.mesh_state.meshes.get(&uid) else {
+            return Err(MeshEditError::NoMesh);
+        };
+        if !mesh.is_triangle() {
+            return Err(MeshEditError::CellTypeMismatch);
+        }
+        if values.len() != mesh.num_vertices() {
+            return Err(MeshEditError::ValueCountMismatch);
+        }
+        let mut contours = mesh.contours(values, levels, color);
+        contours.set_line_depth_bias(CONTOUR_DEPTH_BIAS);
+        //{{{ trace
+        info!("Adding {} contour lines of mesh {}", contours.num_indices() / 2, uid);
+        //}}}
+        Ok(self.add_mesh(contours)?)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: add_bounding_box
+    /// Adds a line mesh holding the edges of the bounding box of the mesh with the given uid,
+    /// see `MeshCore::bounding_box_edges`, drawn in `color`, and returns the uid of the new mesh.
+    /// Handy to check where a mesh lies and how far it extends.
+    pub fn add_bounding_box(&mut self, uid: usize, color: &Color) -> Result<usize, MeshEditError> {
+        let Some(mesh) = self.mesh_state.meshes.get(&uid) else {
+            return Err(MeshEditError::NoMesh);
+        };
+        let edges = mesh.bounding_box_edges(color);
+        //{{{ trace
+        info!("Adding {} bounding box edges of mesh {}", edges.num_indices() / 2, uid);
+        //}}}
+        Ok(self.add_mesh(edges)?)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: add_normals_visualization
+    /// Adds a line mesh holding the normals of the triangle mesh with the given uid, see
+    /// `MeshCore::normal_lines`, as segments `length` long drawn in `color`, and returns the uid
+    /// of the new mesh. Handy to track down shading which looks wrong. Line meshes and 2D
+    /// meshes have no normals to draw.
+    pub fn add_normals_visualization(
+        &mut self,
+        uid: usize,
+        length: f32,
+        color: &Color,
+    ) -> Result<usize, MeshEditError> {
+        let Some(mesh) = self.mesh_state.meshes.get(&uid) else {
+            return Err(MeshEditError::NoMesh);
+        };
+        let normals = mesh.normal_lines(length, color).ok_or(MeshEditError::NoNormals)?;
+        //{{{ trace
+        info!("Adding {} normals of mesh {}", normals.num_indices() / 2, uid);
+        //}}}
+        Ok(self.add_mesh(normals)?)
+    }
+    //..............................................................
+    //}}}
+    //{{{ fun: clear_meshes
+    pub fn clear(&mut self) {
+        self.mesh_state.clear();
+        // uids are reused once cleared, so the highlight must not carry over to a new mesh
+        self.highlight = None;
+        self.window_request_redraw();
+    }
+    //}}}
+}
+//}}}
+//..................................................................................................
+//}}}
+
+//-------------------------------------------------------------------------------------------------
+//{{{ mod: tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Color;
+    use crate::d2::{Mesh as Mesh2, Mesh2D};
+
+    #[test]
+    fn pick_test() {
+        let mut mesh_state = MeshState::new();
+        let mut lower = Mesh2::from_num_triangles(1);
+        lower.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(2.0, 0.0),
+            &Vec2::new(0.0, 2.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let lower_uid = mesh_state.add_mesh(lower).unwrap();
+        let mut upper = Mesh2::from_num_triangles(1);
+        upper.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(1.0, 0.0),
+            &Vec2::new(0.0, 1.0),
+            &Color::Black,
+            &Color::Blue,
+        );
+        let upper_uid = mesh_state.add_mesh(upper).unwrap();
+
+        let pick_at = |x: f32, y: f32| mesh_state.pick(&Vec3::new(x, y, -1.0), &Vec3::z());
+        assert_eq!(pick_at(0.25, 0.25), Some(upper_uid));
+        assert_eq!(pick_at(1.25, 0.25), Some(lower_uid));
+        assert_eq!(pick_at(1.5, 1.5), None);
+    }
+
+    #[test]
+    fn num_vertices_test() {
+        let mut mesh_state = MeshState::new();
+        let triangle = || {
+            let mut mesh = Mesh2::from_num_triangles(1);
+            mesh.add_triangle(
+                &Vec2::new(0.0, 0.0),
+                &Vec2::new(1.0, 0.0),
+                &Vec2::new(0.0, 1.0),
+                &Color::Black,
+                &Color::Red,
+            );
+            mesh
+        };
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::zeros(), &Vec2::x(), &Color::Black, &Color::Black);
+        // the running total always matches a count over every mesh
+        let check = |mesh_state: &MeshState<_>, expected: usize| {
+            let counted: usize = mesh_state.meshes.values().map(|mesh| mesh.num_vertices()).sum();
+            assert_eq!(counted, expected);
+            assert_eq!(mesh_state.stats().num_vertices, expected);
+        };
+
+        let first = mesh_state.add_mesh(triangle()).unwrap();
+        let second = mesh_state.add_mesh(line.clone()).unwrap();
+        check(&mesh_state, 5);
+        assert!(mesh_state.append_to_mesh(first, triangle()).is_ok());
+        check(&mesh_state, 8);
+        // a failed append leaves the total alone
+        assert!(mesh_state.append_to_mesh(first, line.clone()).is_err());
+        check(&mesh_state, 8);
+        assert!(mesh_state.replace_mesh(first, triangle()).is_ok());
+        check(&mesh_state, 5);
+        assert!(mesh_state.edit_mesh(second, |mesh| mesh.merge(line.clone())));
+        check(&mesh_state, 7);
+        assert!(mesh_state.remove_mesh(second).is_some());
+        check(&mesh_state, 3);
+        mesh_state.clear();
+        check(&mesh_state, 0);
+        mesh_state.add_mesh(triangle()).unwrap();
+        check(&mesh_state, 3);
+    }
+
+    #[test]
+    fn set_model_test() {
+        let mut mesh_state = MeshState::new();
+        let mut mesh = Mesh2::from_num_triangles(1);
+        mesh.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(1.0, 0.0),
+            &Vec2::new(0.0, 1.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let vertices = mesh.vertex_slice().to_vec();
+        let uid = mesh_state.add_mesh(mesh).unwrap();
+        mesh_state.take_dirty();
+
+        // moving a mesh leaves its vertices, and so its buffers, as they are
+        let model = Mat4::new_translation(&Vec3::new(5.0, 0.0, 0.0));
+        assert!(mesh_state.set_model(uid, model));
+        assert!(!mesh_state.set_model(uid + 1, model));
+        assert!(mesh_state.take_dirty().is_empty());
+        assert_eq!(mesh_state.take_moved(), HashSet::from([uid]));
+        assert!(mesh_state.take_moved().is_empty());
+        assert_eq!(mesh_state.meshes[&uid].vertex_slice(), vertices.as_slice());
+
+        // but picking and the bounds follow it
+        let pick_at = |x: f32, y: f32| mesh_state.pick(&Vec3::new(x, y, -1.0), &Vec3::z());
+        assert_eq!(pick_at(0.25, 0.25), None);
+        assert_eq!(pick_at(5.25, 0.25), Some(uid));
+        let (min, max) = mesh_state.meshes[&uid].bounding_box().unwrap();
+        assert_eq!((min, max), (Vec3::new(5.0, 0.0, 0.0), Vec3::new(6.0, 1.0, 0.0)));
+
+        mesh_state.remove_mesh(uid);
+        assert!(mesh_state.take_moved().is_empty());
+    }
+
+    #[test]
+    fn face_culling_test() {
+        let mut state = crate::d2::State::new();
+        assert_eq!(state.face_culling(), FaceCulling::None);
+        let mut culling = state.face_culling();
+        let cycle: Vec<FaceCulling> = (0..3)
+            .map(|_| {
+                culling = culling.next();
+                culling
+            })
+            .collect();
+        assert_eq!(cycle, [FaceCulling::Back, FaceCulling::Front, FaceCulling::None]);
+        state.set_face_culling(FaceCulling::Front);
+        assert_eq!(state.face_culling(), FaceCulling::Front);
+
+        // meshes wound clockwise are culled with the pipeline culling the other side
+        assert_eq!(FrontFace::Ccw.ccw_culling(FaceCulling::Back), FaceCulling::Back);
+        assert_eq!(FrontFace::Cw.ccw_culling(FaceCulling::Back), FaceCulling::Front);
+        assert_eq!(FrontFace::Cw.ccw_culling(FaceCulling::Front), FaceCulling::Back);
+        assert_eq!(FrontFace::Cw.ccw_culling(FaceCulling::None), FaceCulling::None);
+        let mut mesh = Mesh2::from_num_triangles(1);
+        mesh.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(0.0, 1.0),
+            &Vec2::new(1.0, 0.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let uid = state.add_mesh(mesh).unwrap();
+        assert!(state.set_mesh_front_face(uid, FrontFace::Cw));
+        assert_eq!(state.get_mesh(uid).unwrap().front_face(), FrontFace::Cw);
+        assert!(!state.set_mesh_front_face(uid + 1, FrontFace::Cw));
+
+        assert!(is_toggle_key(Some('c'), "c"));
+        assert!(is_toggle_key(Some('c'), "C"));
+        assert!(!is_toggle_key(Some('c'), "cc"));
+        assert!(!is_toggle_key(None, "c"));
+    }
+
+    /// The default depth mode of the windows.
+    const DEPTH_MODE: dt::DepthMode = dt::DepthMode {
+        format: wgpu::TextureFormat::Depth32Float,
+        reversed_z: false,
+    };
+
+    #[test]
+    fn model_uniform_test() {
+        // a stretch along x shrinks the x component of the normals
+        let model = Mat4::new_nonuniform_scaling(&Vec3::new(2.0, 1.0, 1.0));
+        let uniform = model_uniform(&model, 0, 0, DEPTH_MODE);
+        assert_eq!(Mat4::from(uniform.model), model);
+        let normal = Mat4::from(uniform.normal_matrix).transform_vector(&Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(normal, Vec3::new(0.5, 1.0, 0.0));
+        // a singular matrix leaves the normals alone
+        let uniform = model_uniform(&Mat4::zeros(), 0, 0, DEPTH_MODE);
+        assert_eq!(Mat4::from(uniform.normal_matrix), Mat4::identity());
+        // the uniform fills whole 16 byte rows, as uniform structs do
+        assert_eq!(std::mem::size_of::<ModelUniform>() % 16, 0);
+    }
+
+    #[test]
+    fn line_depth_offset_test() {
+        // the depth a fragment is written with: the clip space depth output by the vertex shader
+        // and offset as by its `offset_depth`, divided by w and rounded to the depth format
+        let written = |depth: f32, w: f32, offset: f32, format: wgpu::TextureFormat| {
+            let depth = (depth * w + offset * w) / w;
+            match format {
+                wgpu::TextureFormat::Depth16Unorm => (depth * 65535.0).round() / 65535.0,
+                wgpu::TextureFormat::Depth24Plus => (depth * 16777215.0).round() / 16777215.0,
+                _ => depth,
+            }
+        };
+        let formats = [
+            wgpu::TextureFormat::Depth16Unorm,
+            wgpu::TextureFormat::Depth24Plus,
+            wgpu::TextureFormat::Depth32Float,
+        ];
+        for format in formats {
+            for reversed_z in [false, true] {
+                let depth_mode = dt::DepthMode {
+                    format: format,
+                    reversed_z: reversed_z,
+                };
+                let nearer = |a: f32, b: f32| match depth_mode.compare() {
+                    wgpu::CompareFunction::Less => a < b,
+                    _ => a > b,
+                };
+                // contours and fallback edges lying on a triangle pass the depth test against it,
+                // even near the far plane, where the depth is least precise
+                let uniform = model_uniform(&Mat4::identity(), 0, CONTOUR_DEPTH_BIAS, depth_mode);
+                for depth in [0.5, 0.9, 0.999, 0.99999] {
+                    let depth = if reversed_z { 1.0 - depth } else { depth };
+                    for w in [0.1, 1.0, 100.0] {
+                        let triangle = written(depth, w, 0.0, format);
+                        let line = written(depth, w, uniform.line_depth_offset, format);
+                        let edge = written(depth, w, uniform.edge_depth_offset, format);
+                        assert!(nearer(line, triangle), "{:?} {} {}", format, depth, w);
+                        assert!(nearer(edge, triangle), "{:?} {} {}", format, depth, w);
+                    }
+                }
+                // lines without a bias are left at the depth of the triangles
+                let uniform = model_uniform(&Mat4::identity(), 0, 0, depth_mode);
+                assert_eq!(uniform.line_depth_offset, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn layer_depth_test() {
+        // higher layers are nearer the camera, and every layer stays within the depth range,
+        // short of the depth the buffer is cleared to
+        assert_eq!(layer_depth(0), 0.5);
+        let depths: Vec<f32> = (-MAX_LAYER..=MAX_LAYER).map(layer_depth).collect();
+        assert!(depths.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(depths[0] < 1.0 && depths[depths.len() - 1] > 0.0);
+        // neighbouring layers are told apart by the 16-bit depth format
+        assert!(depths.windows(2).all(|pair| pair[0] - pair[1] > 1.0 / 65535.0));
+        assert_eq!(layer_depth(i32::MAX), layer_depth(MAX_LAYER));
+        assert_eq!(layer_depth(i32::MIN), layer_depth(-MAX_LAYER));
+
+        // changing the layer of a mesh only uploads its model uniform again
+        let mut mesh_state = MeshState::new();
+        let uid = mesh_state.add_mesh(Mesh2::from_num_triangles(1)).unwrap();
+        mesh_state.take_dirty();
+        assert!(mesh_state.set_layer(uid, 3));
+        assert!(!mesh_state.set_layer(uid + 1, 3));
+        assert_eq!(mesh_state.meshes[&uid].layer(), 3);
+        assert!(mesh_state.take_dirty().is_empty());
+        assert_eq!(mesh_state.take_moved(), HashSet::from([uid]));
+    }
+
+    #[test]
+    fn append_to_mesh_test() {
+        let triangle = |offset: f32| {
+            let mut mesh = Mesh2::from_num_triangles(1);
+            mesh.add_triangle(
+                &Vec2::new(offset, 0.0),
+                &Vec2::new(offset + 1.0, 0.0),
+                &Vec2::new(offset, 1.0),
+                &Color::Black,
+                &Color::Red,
+            );
+            mesh
+        };
+        let mut mesh_state = MeshState::new();
+        let uid = mesh_state.add_mesh(triangle(0.0)).unwrap();
+
+        assert!(mesh_state.append_to_mesh(uid, triangle(2.0)).is_ok());
+        let mesh = &mesh_state.meshes[&uid];
+        assert_eq!(mesh.num_vertices(), 6);
+        assert_eq!(mesh.num_triangles(), 2);
+        assert_eq!(mesh.index_slice(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(mesh_state.take_dirty(), HashSet::from([uid]));
+        assert!(mesh_state.take_dirty().is_empty());
+
+        assert_eq!(mesh_state.append_to_mesh(uid + 1, triangle(2.0)), Err(MeshEditError::NoMesh));
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::zeros(), &Vec2::x(), &Color::Black, &Color::Black);
+        assert_eq!(mesh_state.append_to_mesh(uid, line), Err(MeshEditError::CellTypeMismatch));
+        assert_eq!(mesh_state.meshes[&uid].num_vertices(), 6);
+        assert!(mesh_state.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn recycle_uid_test() {
+        let mut state = crate::d2::State::new();
+        let uids: Vec<usize> = (0..3)
+            .map(|_| state.add_mesh(Mesh2::from_num_lines(1)).unwrap())
+            .collect();
+        assert_eq!(uids, vec![5, 6, 7]);
+        state.set_mesh_name(6, "middle");
+
+        assert!(state.contains_mesh(6));
+        assert!(state.remove_mesh(6));
+        assert!(!state.remove_mesh(6));
+        assert!(!state.contains_mesh(6));
+        assert!(state.contains_mesh(7));
+        assert_eq!(state.mesh_uid_by_name("middle"), None);
+        assert!(state.remove_mesh(5));
+
+        // the lowest freed uid goes first, and a reused uid has its buffers rebuilt
+        state.mesh_state.take_dirty();
+        assert_eq!(state.add_mesh(Mesh2::from_num_lines(1)).unwrap(), 5);
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([5]));
+        assert_eq!(state.add_mesh(Mesh2::from_num_lines(1)).unwrap(), 6);
+        assert_eq!(state.add_mesh(Mesh2::from_num_lines(1)).unwrap(), 8);
+
+        state.clear();
+        assert!(!state.contains_mesh(8));
+        assert_eq!(state.add_mesh(Mesh2::from_num_lines(1)).unwrap(), 5);
+    }
+
+    #[test]
+    fn add_bounding_box_test() {
+        let mut state = crate::d2::State::new();
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::new(0.0, 1.0), &Vec2::new(2.0, 4.0), &Color::Black, &Color::Black);
+        let uid = state.add_mesh(line).unwrap();
+
+        let box_uid = state.add_bounding_box(uid, &Color::Red).unwrap();
+        let edges = state.get_mesh(box_uid).unwrap();
+        assert!(edges.is_line());
+        assert_eq!(edges.num_indices(), 8);
+        assert_eq!(edges.bounding_box(), state.get_mesh(uid).unwrap().bounding_box());
+        assert_eq!(state.add_bounding_box(box_uid + 1, &Color::Red), Err(MeshEditError::NoMesh));
+    }
+
+    #[test]
+    fn scene_bounds_test() {
+        let mut state = crate::d2::State::new();
+        assert_eq!(state.scene_bounds(), None);
+
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::new(0.0, 1.0), &Vec2::new(2.0, 4.0), &Color::Black, &Color::Black);
+        state.add_mesh(line).unwrap();
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::new(-1.0, 2.0), &Vec2::new(1.0, 3.0), &Color::Black, &Color::Black);
+        state.add_mesh(line).unwrap();
+        assert_eq!(
+            state.scene_bounds(),
+            Some((Vec3::new(-1.0, 1.0, 0.0), Vec3::new(2.0, 4.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn add_contours_test() {
+        let mut state = crate::d2::State::new();
+        let mut triangle = Mesh2::from_num_triangles(1);
+        triangle.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(2.0, 0.0),
+            &Vec2::new(0.0, 2.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let uid = state.add_mesh(triangle).unwrap();
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::new(0.0, 0.0), &Vec2::new(1.0, 0.0), &Color::Black, &Color::Black);
+        let line_uid = state.add_mesh(line).unwrap();
+
+        let values = [0.0, 2.0, 0.0];
+        let contour_uid = state.add_contours(uid, &values, &[1.0], &Color::Blue).unwrap();
+        let contours = state.get_mesh(contour_uid).unwrap();
+        assert_eq!(contours.num_indices(), 2);
+        assert_eq!(contours.line_depth_bias(), CONTOUR_DEPTH_BIAS);
+
+        let add = |state: &mut crate::d2::State, uid, values: &[f32]| {
+            state.add_contours(uid, values, &[1.0], &Color::Blue)
+        };
+        assert_eq!(add(&mut state, 42, &values), Err(MeshEditError::NoMesh));
+        assert_eq!(add(&mut state, line_uid, &[0.0, 2.0]), Err(MeshEditError::CellTypeMismatch));
+        assert_eq!(add(&mut state, uid, &[0.0, 2.0]), Err(MeshEditError::ValueCountMismatch));
+
+        // contours of a level no value reaches are empty, and only turned away when asked to
+        let unreached = [3.0, 4.0, 5.0];
+        assert!(add(&mut state, uid, &unreached).is_ok());
+        state.set_reject_empty_meshes(true);
+        assert_eq!(add(&mut state, uid, &unreached), Err(MeshEditError::EmptyMesh));
+    }
+
+    #[test]
+    fn replace_mesh_test() {
+        let mut state = crate::d2::State::new();
+        let uid = state.add_named_mesh("shape", Mesh2::from_num_lines(1)).unwrap();
+        let mut triangle = Mesh2::from_num_triangles(1);
+        triangle.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(1.0, 0.0),
+            &Vec2::new(0.0, 1.0),
+            &Color::Black,
+            &Color::Red,
+        );
+
+        assert!(state.replace_mesh(uid, triangle.clone()).is_ok());
+        let mesh = state.get_mesh(uid).unwrap();
+        assert_eq!(mesh.uid, uid);
+        assert!(mesh.is_triangle());
+        assert_eq!(mesh.num_vertices(), 3);
+        assert_eq!(state.mesh_uid_by_name("shape"), Some(uid));
+        assert_eq!(state.get_mesh_by_name("shape").unwrap().name(), Some("shape"));
+        assert_eq!(state.scene_stats().num_meshes, 1);
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([uid]));
+        assert_eq!(state.replace_mesh(uid + 1, triangle), Err(MeshEditError::NoMesh));
+    }
+
+    #[test]
+    fn set_mesh_color_mode_test() {
+        let mut state = crate::d2::State::new();
+        let uid = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        state.mesh_state.take_dirty();
+
+        let mode = MeshColorMode::Uniform(Color::Green);
+        assert!(state.set_mesh_color_mode(uid, mode));
+        assert_eq!(state.get_mesh(uid).unwrap().color_mode(), mode);
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([uid]));
+        assert!(!state.set_mesh_color_mode(uid + 1, mode));
+        assert!(state.mesh_state.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn translate_scale_mesh_test() {
+        let mut state = crate::d2::State::new();
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::new(1.0, 1.0), &Vec2::new(3.0, 1.0), &Color::Black, &Color::Black);
+        let uid = state.add_mesh(line).unwrap();
+        state.mesh_state.take_dirty();
+
+        assert!(state.translate_mesh(uid, &Vec2::new(-1.0, 2.0)));
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([uid]));
+        assert!(state.scale_mesh(uid, 2.0, &Vec2::new(0.0, 3.0)));
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([uid]));
+        let mesh = state.get_mesh(uid).unwrap();
+        assert_eq!(mesh.vertex_position(0), Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!(mesh.vertex_position(1), Vec3::new(4.0, 3.0, 0.0));
+
+        assert!(!state.translate_mesh(uid + 1, &Vec2::zeros()));
+        assert!(!state.scale_mesh(uid + 1, 2.0, &Vec2::zeros()));
+        assert!(state.mesh_state.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn set_mesh_options_test() {
+        let mut state = crate::d2::State::new();
+        let uid = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        state.mesh_state.take_dirty();
+        assert_eq!(state.get_mesh(uid).unwrap().options(), MeshOptions::default());
+
+        let options = MeshOptions {
+            edge_color: Some(Color::Black),
+            dash_pattern: Some((0.2, 0.1)),
+        };
+        assert!(state.set_mesh_options(uid, options));
+        assert_eq!(state.get_mesh(uid).unwrap().options(), options);
+        assert_eq!(state.mesh_state.take_dirty(), HashSet::from([uid]));
+        assert!(!state.set_mesh_options(uid + 1, options));
+        assert!(state.mesh_state.take_dirty().is_empty());
+    }
+
+    #[test]
+    fn empty_mesh_test() {
+        let mut state = crate::d2::State::new();
+        let empty = Mesh2::from_num_lines(1);
+        assert!(!empty.has_cells());
+
+        // empty meshes are added by default, but can be rejected
+        assert!(state.add_mesh(empty.clone()).is_ok());
+        state.set_reject_empty_meshes(true);
+        assert_eq!(state.add_mesh(empty), Err(AddMeshError::Empty));
+        assert_eq!(state.scene_stats().num_meshes, 1);
+
+        // a triangle mesh whose vertices no cell uses is just as empty
+        let mut unused = Mesh2::from_num_triangles(1);
+        unused.add_vertex(&Vec2::zeros(), &Color::Black, &Color::Black);
+        assert!(!unused.is_empty());
+        assert!(!unused.has_cells());
+        assert_eq!(state.add_mesh(unused), Err(AddMeshError::Empty));
+    }
+
+    #[test]
+    fn scene_budget_test() {
+        let mut state = crate::d2::State::new();
+        state.set_scene_budget(SceneBudget {
+            max_meshes: Some(2),
+            max_vertices: Some(5),
+        });
+        let mut triangle = Mesh2::from_num_triangles(1);
+        triangle.add_triangle(
+            &Vec2::new(0.0, 0.0),
+            &Vec2::new(1.0, 0.0),
+            &Vec2::new(0.0, 1.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let mut line = Mesh2::from_num_lines(1);
+        line.add_line(&Vec2::zeros(), &Vec2::x(), &Color::Black, &Color::Black);
+
+        let uid = state.add_mesh(triangle.clone()).unwrap();
+        let err = BudgetError::Vertices { used: 3, added: 3, max: 5 };
+        assert_eq!(state.add_mesh(triangle.clone()), Err(AddMeshError::Budget(err)));
+        assert_eq!(state.append_to_mesh(uid, triangle.clone()), Err(MeshEditError::Budget(err)));
+        assert!(state.add_mesh(line.clone()).is_ok());
+        let err = BudgetError::Meshes { used: 2, added: 1, max: 2 };
+        assert_eq!(state.add_mesh(Mesh2::from_num_lines(1)), Err(AddMeshError::Budget(err)));
+
+        let stats = state.scene_stats();
+        assert_eq!((stats.num_meshes, stats.num_vertices), (2, 5));
+        assert_eq!(state.get_mesh(uid).unwrap().num_vertices(), 3);
+    }
+
+    #[test]
+    fn mesh_name_test() {
+        let mut state = crate::d2::State::new();
+        let first = state.add_named_mesh("grid", Mesh2::from_num_lines(1)).unwrap();
+        let unnamed = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        assert_eq!(state.mesh_uid_by_name("grid"), Some(first));
+        assert_eq!(state.get_mesh_by_name("grid").unwrap().name(), Some("grid"));
+        assert_eq!(state.get_mesh(unnamed).unwrap().name(), None);
+        assert!(state.get_mesh_by_name("missing").is_none());
+
+        // reusing a name moves it over to the new mesh
+        let second = state.add_named_mesh("grid", Mesh2::from_num_lines(1)).unwrap();
+        assert_eq!(state.mesh_uid_by_name("grid"), Some(second));
+        assert_eq!(state.get_mesh(first).unwrap().name(), None);
+
+        // renaming a mesh releases its old name
+        assert!(state.set_mesh_name(second, "lattice"));
+        assert!(state.get_mesh_by_name("grid").is_none());
+        assert_eq!(state.mesh_uid_by_name("lattice"), Some(second));
+        assert!(!state.set_mesh_name(100, "missing"));
+
+        state.clear();
+        assert!(state.get_mesh_by_name("lattice").is_none());
+    }
+
+    #[test]
+    fn transform_group_test() {
+        let mut state = crate::d2::State::new();
+        let arm = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        let hand = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        let base = state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        assert!(state.set_mesh_tag(arm, Some("arm")));
+        assert!(state.set_mesh_tag(hand, Some("arm")));
+        assert!(!state.set_mesh_tag(100, Some("arm")));
+        assert_eq!(state.mesh_uids_by_tag("arm"), vec![arm, hand]);
+        assert_eq!(state.get_mesh(hand).unwrap().tag(), Some("arm"));
+        assert_eq!(state.get_mesh(base).unwrap().tag(), None);
+
+        // the transform is applied after the model each mesh already has
+        let offset = Mat4::new_translation(&Vec3::new(0.0, 1.0, 0.0));
+        assert!(state.set_mesh_model(hand, offset));
+        state.mesh_state.take_moved();
+        let shift = Mat4::new_translation(&Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(state.transform_group("arm", shift), 2);
+        assert_eq!(state.get_mesh(arm).unwrap().model(), shift);
+        assert_eq!(state.get_mesh(hand).unwrap().model(), shift * offset);
+        assert_eq!(state.get_mesh(base).unwrap().model(), Mat4::identity());
+        assert_eq!(state.mesh_state.take_moved(), HashSet::from([arm, hand]));
+
+        // untagging a mesh takes it out of the group
+        assert!(state.set_mesh_tag(arm, None));
+        assert_eq!(state.transform_group("arm", shift), 1);
+        assert_eq!(state.get_mesh(arm).unwrap().model(), shift);
+        assert_eq!(state.transform_group("missing", shift), 0);
+    }
+
+    #[test]
+    fn view_sensitivity_test() {
+        let sensitivity = ViewSensitivity {
+            key_pan_delta: 0.5,
+            key_rotate_delta: 0.1,
+            zoom_speed: 0.01,
+        };
+        let mut state = crate::d3::State::new();
+        assert!(state.view_sensitivity().is_none());
+        assert!(state.set_view_sensitivity(sensitivity).is_ok());
+        assert_eq!(state.view_sensitivity(), Some(sensitivity));
+
+        // invalid values are rejected and leave the sensitivity untouched
+        let zero_zoom = ViewSensitivity { zoom_speed: 0.0, ..sensitivity };
+        assert_eq!(state.set_view_sensitivity(zero_zoom), Err("zoom_speed"));
+        let negative_pan = ViewSensitivity { key_pan_delta: -1.0, ..sensitivity };
+        assert_eq!(state.set_view_sensitivity(negative_pan), Err("key_pan_delta"));
+        let nan_rotate = ViewSensitivity { key_rotate_delta: f32::NAN, ..sensitivity };
+        assert_eq!(state.set_view_sensitivity(nan_rotate), Err("key_rotate_delta"));
+        assert_eq!(state.view_sensitivity(), Some(sensitivity));
+    }
+
+    #[test]
+    fn draw_context_test() {
+        use crate::common::CellType;
+
+        let mut state = crate::d3::State::new();
+        assert_eq!(state.draw_context(), DrawContext::default());
+        let uid = state.add_cuboid_simple(Vec3::zeros(), 1.0, 2.0, 3.0).unwrap();
+        assert!(state.get_mesh(uid).unwrap().is_triangle());
+        let (min, max) = state.get_mesh(uid).unwrap().bounding_box().unwrap();
+        assert_eq!((min, max), (Vec3::zeros(), Vec3::new(1.0, 2.0, 3.0)));
+
+        // a cuboid whose axes its policy rejects is an error rather than a panic
+        let left_handed = crate::d3::CuboidDescriptor {
+            origin: Vec3::zeros(),
+            x_axis: Vec3::x(),
+            y_axis: Vec3::y(),
+            z_axis: -Vec3::z(),
+            lenx: 1.0,
+            leny: 1.0,
+            lenz: 1.0,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            axes_policy: crate::d3::AxesPolicy::RequireRightHanded,
+        };
+        let num_meshes = state.scene_stats().num_meshes;
+        let res = crate::d3::State3D::add_cuboid(&mut state, &left_handed);
+        assert!(matches!(res, Err(crate::d3::AddShapeError::Mesh(_))));
+        assert_eq!(state.scene_stats().num_meshes, num_meshes);
+
+        // shapes added after the context changes are drawn with it
+        state.set_draw_context(DrawContext {
+            default_cell_type: CellType::Line,
+            ..DrawContext::default()
+        });
+        let uid = state.add_sphere_simple(Vec3::zeros(), 1.0).unwrap();
+        assert!(state.get_mesh(uid).unwrap().is_line());
+        let uid = state.add_triangle_simple(Vec3::zeros(), Vec3::x(), Vec3::y()).unwrap();
+        assert!(state.get_mesh(uid).unwrap().is_line());
+
+        let mut state = crate::d2::State::new();
+        let uid = state.add_circle_simple(Vec2::new(1.0, 1.0), 0.5).unwrap();
+        assert!(state.get_mesh(uid).unwrap().is_triangle());
+        let uid = state.add_rectangle_simple(Vec2::zeros(), 2.0, 1.0).unwrap();
+        let (min, max) = state.get_mesh(uid).unwrap().bounding_box().unwrap();
+        assert_eq!((min, max), (Vec3::new(-1.0, -0.5, 0.0), Vec3::new(1.0, 0.5, 0.0)));
+    }
+
+    #[test]
+    fn frame_callback_test() {
+        let mut state = crate::d2::State::new();
+        assert!(!state.is_animating());
+
+        // the callback edits the state, here adding a mesh every frame
+        state.set_frame_callback(Some(Box::new(|state| {
+            state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        })));
+        assert!(state.is_animating());
+        state.run_frame_callback();
+        state.run_frame_callback();
+        assert_eq!(state.scene_stats().num_meshes, 2);
+
+        // one which replaces itself only runs once
+        state.set_frame_callback(Some(Box::new(|state| {
+            state.clear();
+            state.set_frame_callback(Some(Box::new(|_| {})));
+        })));
+        state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        state.run_frame_callback();
+        state.add_mesh(Mesh2::from_num_lines(1)).unwrap();
+        state.run_frame_callback();
+        assert_eq!(state.scene_stats().num_meshes, 1);
+
+        state.set_frame_callback(None);
+        assert!(!state.is_animating());
+    }
+
+    /// Returns the adapter and device of any adapter, requested without a surface, or `None` on
+    /// machines without one so that the tests building pipelines are skipped there.
+    fn headless_device() -> Option<(wgpu::Adapter, wgpu::Device)> {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions::default())
+                .await?;
+            let features = adapter.features() & wgpu::Features::POLYGON_MODE_LINE;
+            let (device, _queue) = adapter
+                .request_device(
+                    &wgpu::DeviceDescriptor {
+                        label: Some("Test Device"),
+                        required_features: features,
+                        required_limits: wgpu::Limits::default(),
+                    },
+                    None,
+                )
+                .await
+                .ok()?;
+            Some((adapter, device))
+        })
+    }
+
+    #[test]
+    fn pipeline_validation_test() {
+        use crate::d3::Vertex as Vertex3;
+        let Some((adapter, device)) = headless_device() else {
+            return;
+        };
+        let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let depth_mode = dt::DepthMode {
+            format: dt::DepthTexture::supported_format(&adapter, dt::DepthFormat::default()),
+            reversed_z: false,
+        };
+
+        // the fragment stage of the 3D shader reads the clipping planes and clip mode of the view
+        // uniform, so every pipeline, whatever its vertex layout, is only valid if the camera
+        // binding is visible to it
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(shader_module_desc(3));
+        let mesh_model_layout =
+            create_mesh_uniform_bind_group_layout(&device, "mesh_model_bind_group_layout");
+        let mut pipelines = MeshPipelines::new(
+            &device,
+            &shader,
+            create_render_pipeline_layout(&device, &mesh_model_layout, None),
+            format,
+            depth_mode,
+            Vertex3::desc(),
+            "vs_main",
+            "vs_main_line",
+            "vs_main_edge",
+            "vs_main_highlight",
+        );
+        pipelines.build_culled_tri_face(&device, &shader, format, depth_mode, FaceCulling::Back);
+        let mesh_color_layout =
+            create_mesh_uniform_bind_group_layout(&device, "mesh_color_bind_group_layout");
+        let mut uniform_color_pipelines = MeshPipelines::new(
+            &device,
+            &shader,
+            create_render_pipeline_layout(&device, &mesh_model_layout, Some(&mesh_color_layout)),
+            format,
+            depth_mode,
+            Vertex3::compact_desc(),
+            "vs_main_uniform",
+            "vs_main_uniform_line",
+            "vs_main_uniform_edge",
+            "vs_main_uniform_highlight",
+        );
+        uniform_color_pipelines.build_culled_tri_face(
+            &device,
+            &shader,
+            format,
+            depth_mode,
+            FaceCulling::Front,
+        );
+        create_thick_edge_render_pipeline(
+            &device,
+            &shader,
+            &create_render_pipeline_layout(&device, &mesh_model_layout, None),
+            format,
+            depth_mode,
+            &Vertex3::desc(),
+        );
+        let error = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(device.pop_error_scope());
+        assert!(error.is_none(), "{:?}", error);
+    }
+
+    /// Adds a view without a window to the state, which receives the events sent to `window_id`.
+    fn add_headless_view<'a, V, ViewState>(
+        state: &mut StateCore<'a, V, ViewState>,
+        window_id: u64,
+    ) -> winit::window::WindowId
+    where
+        V: VertexCore + Deserialize<'a> + Serialize,
+        ViewState: ViewStateCore + Default,
+    {
+        let window_id = winit::window::WindowId::from(window_id);
+        state.viewports.push(Viewport {
+            window_id: window_id,
+            view_state: ViewState::default(),
+            wgpu_state: None,
+            pick_position: None,
+        });
+        window_id
+    }
+
+    fn cursor_moved(x: f64, y: f64) -> WindowEvent {
+        WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        }
+    }
+
+    #[test]
+    fn handle_event_test() {
+        let mut state = crate::d2::State::new();
+        let first = add_headless_view(&mut state, 1);
+        let second = add_headless_view(&mut state, 2);
+        assert_eq!(state.num_windows(), 2);
+        assert!(state.has_window(&second));
+
+        // an event only reaches the view of the window it was sent to
+        state.handle_event(&second, &cursor_moved(10.0, 20.0));
+        let mouse_position = |state: &mut crate::d2::State, idx: usize| {
+            state.viewports[idx].view_state.view_controller().mouse_position
+        };
+        assert_eq!(mouse_position(&mut state, 0), [0.0, 0.0]);
+        assert_eq!(mouse_position(&mut state, 1), [10.0, 20.0]);
+        state.handle_event(&first, &cursor_moved(5.0, 5.0));
+        assert_eq!(mouse_position(&mut state, 0), [5.0, 5.0]);
+        assert_eq!(mouse_position(&mut state, 1), [10.0, 20.0]);
+
+        // events of unknown windows are ignored
+        state.handle_event(&winit::window::WindowId::from(3), &cursor_moved(1.0, 1.0));
+        assert_eq!(mouse_position(&mut state, 0), [5.0, 5.0]);
+        assert_eq!(mouse_position(&mut state, 1), [10.0, 20.0]);
+
+        // closing a window leaves the others routed to their views
+        assert_eq!(state.close_window_with_id(&first), 1);
+        state.handle_event(&second, &cursor_moved(30.0, 40.0));
+        assert_eq!(mouse_position(&mut state, 0), [30.0, 40.0]);
+    }
+
+    fn left_mouse_input(state: winit::event::ElementState) -> WindowEvent {
+        WindowEvent::MouseInput {
+            device_id: winit::event::DeviceId::dummy(),
+            state: state,
+            button: winit::event::MouseButton::Left,
+        }
+    }
+
+    #[test]
+    fn pick_on_click_test() {
+        let mut state = crate::d2::State::new();
+        let window = add_headless_view(&mut state, 1);
+        state.viewports[0].view_state.set_viewport_size(800, 600);
+        state.viewports[0].view_state.update();
+        // a triangle about the origin, which the centre pixel of the window looks at
+        let mut mesh = Mesh2::from_num_triangles(1);
+        mesh.add_triangle(
+            &Vec2::new(-1.0, -1.0),
+            &Vec2::new(1.0, -1.0),
+            &Vec2::new(0.0, 1.0),
+            &Color::Black,
+            &Color::Red,
+        );
+        let uid = state.add_mesh(mesh).unwrap();
+        let press = left_mouse_input(winit::event::ElementState::Pressed);
+        let release = left_mouse_input(winit::event::ElementState::Released);
+
+        // pressing alone does not pick, releasing where it was pressed does
+        state.handle_event(&window, &cursor_moved(400.0, 300.0));
+        state.handle_event(&window, &press);
+        assert_eq!(state.highlight(), None);
+        state.handle_event(&window, &cursor_moved(401.0, 301.0));
+        state.handle_event(&window, &release);
+        assert_eq!(state.highlight(), Some(uid));
+
+        // a drag leaves the highlight alone, even when it ends where it started
+        state.set_highlight(None);
+        state.handle_event(&window, &press);
+        state.handle_event(&window, &cursor_moved(700.0, 300.0));
+        state.handle_event(&window, &cursor_moved(401.0, 301.0));
+        state.handle_event(&window, &release);
+        assert_eq!(state.highlight(), None);
+
+        // a click off every mesh clears the highlight
+        state.set_highlight(Some(uid));
+        state.handle_event(&window, &cursor_moved(790.0, 10.0));
+        state.handle_event(&window, &press);
+        state.handle_event(&window, &release);
+        assert_eq!(state.highlight(), None);
+    }
+
+    #[test]
+    fn set_highlight_test() {
+        let mut state = crate::d2::State::new();
+        let add_triangle = |state: &mut crate::d2::State| {
+            let mut mesh = Mesh2::from_num_triangles(1);
+            mesh.add_triangle(
+                &Vec2::new(0.0, 0.0),
+                &Vec2::new(1.0, 0.0),
+                &Vec2::new(0.0, 1.0),
+                &Color::Black,
+                &Color::Red,
+            );
+            state.add_mesh(mesh).unwrap()
+        };
+        let first = add_triangle(&mut state);
+        let second = add_triangle(&mut state);
+        assert_eq!(state.highlight(), None);
+
+        state.set_highlight(Some(first));
+        assert_eq!(state.highlight(), Some(first));
+        state.set_highlight(None);
+        assert_eq!(state.highlight(), None);
+
+        // removing another mesh keeps the highlight, removing the highlighted one clears it
+        state.set_highlight(Some(second));
+        assert!(state.remove_mesh(first));
+        assert_eq!(state.highlight(), Some(second));
+        assert!(state.remove_mesh(second));
+        assert_eq!(state.highlight(), None);
+
+        // so does clearing the state, whose uids are reused
+        let third = add_triangle(&mut state);
+        state.set_highlight(Some(third));
+        state.clear();
+        assert_eq!(state.highlight(), None);
+    }
+
+    #[test]
+    fn edge_width_test() {
+        let mut state = crate::d3::State::new();
+        assert_eq!(state.edge_width(), 1.0);
+        assert!(state.set_edge_width(2.5).is_ok());
+        assert_eq!(state.edge_width(), 2.5);
+
+        // invalid widths are rejected and leave the width untouched
+        assert_eq!(state.set_edge_width(0.0), Err("edge_width"));
+        assert_eq!(state.set_edge_width(f32::NAN), Err("edge_width"));
+        assert_eq!(state.edge_width(), 2.5);
+    }
+
+    #[test]
+    fn initial_octant_test() {
+        let mut state = crate::d3::State::new();
+        assert_eq!(state.initial_octant(), 0);
+        assert!(state.set_initial_octant(7).is_ok());
+        assert_eq!(state.initial_octant(), 7);
+
+        // octants outside 0 to 7 are rejected and leave the octant untouched
+        assert_eq!(state.set_initial_octant(8), Err("octant"));
+        assert_eq!(state.set_initial_octant(-1), Err("octant"));
+        assert_eq!(state.initial_octant(), 7);
+    }
+
+    #[test]
+    fn tessellation_quality_test() {
+        use crate::common::CellType;
+        use crate::d3::{ColorBy, SphereDescriptor, State3D};
+
+        let mut state = crate::d3::State::new();
+        let quality = TessellationQuality::Adaptive { chord_error: 0.01 };
+        assert!(state.set_tessellation_quality(quality).is_ok());
+        let mut add_sphere = |radius: f32| {
+            let uid = state
+                .add_sphere(&SphereDescriptor {
+                    origin: Vec3::zeros(),
+                    axis: Vec3::z(),
+                    radius: radius,
+                    n_lat: 0,
+                    n_long: 0,
+                    line_color: Color::Black,
+                    tri_color: Color::Red,
+                    cell_type: CellType::Triangle,
+                    color_by: ColorBy::None,
+                })
+                .unwrap();
+            state.get_mesh(uid).unwrap().num_triangles()
+        };
+        // at the same chord error a tiny sphere needs far fewer triangles than a huge one
+        let tiny = add_sphere(0.02);
+        let huge = add_sphere(100.0);
+        assert!(100 * tiny < huge);
+
+        // a chord error which is not positive is rejected and leaves the quality untouched
+        let invalid = TessellationQuality::Adaptive { chord_error: -1.0 };
+        assert_eq!(state.set_tessellation_quality(invalid), Err("chord_error"));
+        assert_eq!(state.tessellation_quality(), quality);
+    }
+
+    #[test]
+    fn request_flush_test() {
+        // without a window there is nothing to render, so the flush completes at once
+        let mut state = crate::d3::State::new();
+        assert_eq!(state.request_flush().try_recv(), Ok(()));
+        state.set_rendering_enabled(false);
+        assert_eq!(state.request_flush().try_recv(), Ok(()));
+
+        // with a window the flush waits for a render pass, or for the window to be occluded
+        state.set_rendering_enabled(true);
+        let window = add_headless_view(&mut state, 1);
+        let mut rendered = state.request_flush();
+        assert!(rendered.try_recv().is_err());
+        state.handle_event(&window, &WindowEvent::Occluded(false));
+        assert!(rendered.try_recv().is_err());
+        state.handle_event(&window, &WindowEvent::Occluded(true));
+        assert_eq!(rendered.try_recv(), Ok(()));
+    }
+
+    #[test]
+    fn clip_plane_test() {
+        let mut state = crate::d3::State::new();
+        assert!(state.clip_planes().is_empty());
+        let plane = (Vec3::new(1.0, 2.0, 3.0), Vec3::x());
+        assert!(state.set_clip_plane(Some(plane)).is_ok());
+        assert_eq!(state.clip_planes(), &[plane]);
+
+        // invalid planes are rejected and leave the planes untouched
+        assert_eq!(state.set_clip_plane(Some((Vec3::zeros(), Vec3::zeros()))), Err("normal"));
+        let infinite = Vec3::new(f32::INFINITY, 0.0, 0.0);
+        assert_eq!(state.set_clip_plane(Some((infinite, Vec3::x()))), Err("point"));
+        assert_eq!(state.set_clip_planes(vec![plane; 7], ClipMode::InsideAll), Err("planes"));
+        assert_eq!(state.clip_planes(), &[plane]);
+
+        assert!(state.set_clip_planes(vec![plane; 2], ClipMode::InsideAny).is_ok());
+        assert_eq!(state.clip_planes().len(), 2);
+        assert_eq!(state.clip_mode(), ClipMode::InsideAny);
+
+        assert!(state.set_clip_plane(None).is_ok());
+        assert!(state.clip_planes().is_empty());
+        assert_eq!(state.clip_mode(), ClipMode::InsideAll);
+    }
+
+    #[test]
+    fn clip_box_test() {
+        let mut state = crate::d3::State::new();
+        let min = Vec3::new(-1.0, -2.0, -3.0);
+        let max = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(state.set_clip_box(max, min), Err("max"));
+        assert!(state.set_clip_box(min, max).is_ok());
+        assert_eq!(state.clip_mode(), ClipMode::InsideAll);
+
+        // the centre is inside every plane, and each corner pushed outwards is outside one
+        let outside = |p: &Vec3| {
+            state
+                .clip_planes()
+                .iter()
+                .filter(|(point, normal)| (p - point).dot(normal) > 0.0)
+                .count()
+        };
+        assert_eq!(state.clip_planes().len(), 6);
+        assert_eq!(outside(&Vec3::zeros()), 0);
+        assert_eq!(outside(&(max + Vec3::x())), 1);
+        assert_eq!(outside(&(min - Vec3::new(0.5, 0.5, 0.5))), 3);
+    }
+
+    #[test]
+    fn focus_on_mesh_test() {
+        use crate::d3::Mesh3D;
+        let mut state = crate::d3::State::new();
+        let mut mesh = crate::d3::Mesh::from_num_lines(1);
+        mesh.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+        let uid = state.add_mesh(mesh).unwrap();
+        assert!(state.focus_on_mesh(uid, false));
+        assert!(!state.focus_on_mesh(uid + 1, true));
+    }
+
+    #[test]
+    fn add_normals_visualization_test() {
+        use crate::d3::Mesh3D;
+        let mut state = crate::d3::State::new();
+        let mut triangle = crate::d3::Mesh::from_num_triangles(1);
+        triangle.add_triangle(&Vec3::zeros(), &Vec3::x(), &Vec3::y(), &Color::Black, &Color::Red);
+        let uid = state.add_mesh(triangle).unwrap();
+        let mut line = crate::d3::Mesh::from_num_lines(1);
+        line.add_line(&Vec3::zeros(), &Vec3::x(), &Color::Black, &Color::Black);
+        let line_uid = state.add_mesh(line).unwrap();
+
+        // one segment per vertex, along the +z normal of the triangle
+        let normals_uid = state.add_normals_visualization(uid, 0.5, &Color::Blue).unwrap();
+        let normals = state.get_mesh(normals_uid).unwrap();
+        assert!(normals.is_line());
+        assert_eq!(normals.num_indices(), 6);
+        for i in (0..normals.num_vertices()).step_by(2) {
+            let d = normals.vertex_position(i + 1) - normals.vertex_position(i);
+            assert!((d - Vec3::new(0.0, 0.0, 0.5)).norm() < 1e-6);
+        }
+        let add = |state: &mut crate::d3::State, uid| {
+            state.add_normals_visualization(uid, 0.5, &Color::Blue)
+        };
+        assert_eq!(add(&mut state, line_uid), Err(MeshEditError::NoNormals));
+        assert_eq!(add(&mut state, 42), Err(MeshEditError::NoMesh));
+    }
+
+    #[test]
+    fn background_gradient_test() {
+        let mut state = crate::d3::State::new();
+        assert!(state.background_gradient().is_none());
+
+        state.set_background_gradient(Color::Navy, Color::Other((0.1, 0.2, 0.3)));
+        let (top, bottom) = state.background_gradient().unwrap();
+        assert_eq!(top.to_rgb(), Color::Navy.to_rgb());
+        assert_eq!(bottom.to_rgb(), [0.1, 0.2, 0.3]);
+
+        state.clear_background_gradient();
+        assert!(state.background_gradient().is_none());
+    }
+}
 //}}}
@@ -32,6 +32,9 @@ pub struct Camera
     pub rotation: f32,
     /// Zoom level of the camera, where 1.0 is the default zoom
     pub zoom: f32,
+    /// Rectangle in world coordinates, given by its minimum and maximum corners, which the point
+    /// at the centre of the view is kept within, see [`ViewOptions::pan_bounds`]
+    pub pan_bounds: Option<(Vec2, Vec2)>,
 
     x_axis: Vec2,
     y_axis: Vec2,
@@ -73,6 +76,20 @@ impl Camera
         self.y_axis[1] = c;
     }
     //}}}
+    //{{{ fun: rotate_about
+    /// Rotates the camera by `delta` about the world point `pivot`, which stays at the same place
+    /// on screen while the rest of the scene turns around it.
+    pub fn rotate_about(&mut self, delta: f32, pivot: Vec2)
+    {
+        // the view matrix sends a point p to R(rotation) * (zoom * p + position), so the pivot
+        // keeps its place if R(delta) * (zoom * pivot + position) is unchanged
+        let anchor = self.zoom * pivot + self.position;
+        let (s, c) = delta.sin_cos();
+        let inverse_rotation = Mat2::new(c, s, -s, c);
+        self.rotate(delta);
+        self.position = inverse_rotation * anchor - self.zoom * pivot;
+    }
+    //}}}
     //{{{ fun: pan
     pub fn pan(&mut self, delta_x: f32, delta_y: f32)   
     {
@@ -81,6 +98,34 @@ impl Camera
         //}}}
         self.position[0] += delta_x * self.x_axis[0] + delta_y * self.y_axis[0];
         self.position[1] += delta_x * self.x_axis[1] + delta_y * self.y_axis[1];
+        self.clamp_to_bounds();
+    }
+    //}}}
+    //{{{ fun: centre
+    /// Returns the world point at the centre of the view.
+    pub fn centre(&self) -> Vec2
+    {
+        // the view matrix sends the centre to the origin, so zoom * centre + position = 0
+        -self.position / self.zoom
+    }
+    //}}}
+    //{{{ fun: clamp_to_bounds
+    /// Moves the camera so that the point at the centre of the view lies within the pan bounds,
+    /// if any, keeping its rotation and zoom.
+    pub fn clamp_to_bounds(&mut self)
+    {
+        let Some((min, max)) = self.pan_bounds else {
+            return;
+        };
+        let centre = self.centre();
+        let clamped = centre.sup(&min).inf(&max);
+        if clamped != centre
+        {
+            //{{{ trace
+            debug!("clamping centre {} to {}", centre, clamped);
+            //}}}
+            self.position = -self.zoom * clamped;
+        }
     }
     //}}}
 }
@@ -95,6 +140,7 @@ impl Default for Camera
             position: Vec2::zeros(),
             rotation: 0.0,
             zoom: 1.0,
+            pan_bounds: None,
             x_axis: Vec2::new(1.0, 0.0),
             y_axis: Vec2::new(0.0, 1.0),
         }
@@ -104,6 +150,17 @@ impl Default for Camera
 //..................................................................................................
 //}}}
 //}}}
+//{{{ struct: CameraDescriptor
+/// A snapshot of the camera of a 2D view, used to report the viewpoint to clients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraDescriptor
+{
+    pub position: Vec2,
+    pub rotation: f32,
+    pub zoom: f32,
+}
+//..................................................................................................
+//}}}
 //{{{ collection: ViewOptions
 //{{{ struct: ViewOptions
 /// Short Description
@@ -122,7 +179,14 @@ pub struct ViewOptions
     pub rotate_delta: f32,
     /// This is the sensitivety of the mouse wheel when moving forward and backward
     pub zoom_speed: f32,
-   
+    /// Scales the view to the aspect ratio of the window so that world space keeps its
+    /// proportions, with the shorter side of the window spanning the unit square. When unset the
+    /// unit square is stretched over the whole window.
+    pub keep_aspect: bool,
+    /// Rectangle in world coordinates, given by its minimum and maximum corners, the camera may
+    /// not pan out of: the point at the centre of the view is clamped to it on every update, so
+    /// that the scene cannot be lost in empty space. Unbounded when `None`.
+    pub pan_bounds: Option<(Vec2, Vec2)>,
 }
 //}}}
 //{{{ impl Default for ViewOptions
@@ -134,6 +198,8 @@ impl Default for ViewOptions
             key_pan_delta: 0.25,
             rotate_delta: rad(2.5),
             zoom_speed: 0.001,
+            keep_aspect: true,
+            pan_bounds: None,
         }
     }
 }
@@ -168,20 +234,31 @@ impl View
         let rot_matrix = Mat4::new(  c,  -s, 0.0, 0.0, 
                                      s,   c, 0.0, 0.0,  
                                    0.0, 0.0, 1.0, 0.0, 
-                                   0.0, 0.0, 0.0, 0.0 );
+                                   0.0, 0.0, 0.0, 1.0 );
         let rho = self.camera.zoom;
         let dx = self.camera.position[0];
         let dy = self.camera.position[1];   
         //{{{ trace
         debug!("theta: {}, rho: {}, dx: {}, dy: {}", theta, rho, dx, dy);
         //}}}
-        let zoom_pan_matrix = Mat4::new(  rho, 0.0, dx, 0.0,
-                                          0.0, rho, dy, 0.0,              
+        // points are (x, y, 0, 1), so the translation goes in the last column
+        let zoom_pan_matrix = Mat4::new(  rho, 0.0, 0.0,  dx,
+                                          0.0, rho, 0.0,  dy,
                                           0.0, 0.0, 1.0, 0.0,
-                                          0.0, 0.0, 0.0, 0.0 );
+                                          0.0, 0.0, 0.0, 1.0 );
 
 
-        let view_matrix = rot_matrix * zoom_pan_matrix; 
+        let [width, height] = self.uniform.viewport;
+        let aspect = width / height;
+        let (sx, sy) = match self.options.keep_aspect && aspect.is_finite() && aspect > 0.0
+        {
+            true if aspect > 1.0 => (1.0 / aspect, 1.0),
+            true => (1.0, aspect),
+            false => (1.0, 1.0),
+        };
+        let aspect_matrix = Mat4::new_nonuniform_scaling(&Vec3::new(sx, sy, 1.0));
+
+        let view_matrix = aspect_matrix * rot_matrix * zoom_pan_matrix; 
         //{{{ trace
         trace!(target: "update_uniform", "rot_matrix: {}", rot_matrix);
         trace!(target: "update_uniform", "zoom_pan_matrix: {}", zoom_pan_matrix);
@@ -189,6 +266,102 @@ impl View
         //}}}
         self.uniform.view_matrix = view_matrix.into();
     }
+
+    /// Restores the default camera, the view options and the viewport are kept.
+    pub fn reset(&mut self)
+    {
+        self.camera = Camera::default();
+        self.camera.pan_bounds = self.options.pan_bounds;
+        self.camera.clamp_to_bounds();
+        self.update_uniform();
+    }
+
+    /// Keeps the point at the centre of the view within the given rectangle of world space,
+    /// given by its minimum and maximum corners, or lets the camera pan freely when `None`, see
+    /// [`ViewOptions::pan_bounds`].
+    pub fn set_pan_bounds(&mut self, pan_bounds: Option<(Vec2, Vec2)>)
+    {
+        self.options.pan_bounds = pan_bounds;
+        self.camera.pan_bounds = pan_bounds;
+        self.camera.clamp_to_bounds();
+        self.update_uniform();
+    }
+
+    /// Sets the width and height of the framebuffer in pixels.
+    pub fn set_viewport_size(&mut self, width: f32, height: f32)
+    {
+        self.uniform.viewport = [width, height];
+        self.update_uniform();
+    }
+
+    /// Keeps the proportions of world space whatever the shape of the window, or stretches the
+    /// unit square over the whole window, see [`ViewOptions::keep_aspect`].
+    pub fn set_keep_aspect(&mut self, keep_aspect: bool)
+    {
+        self.options.keep_aspect = keep_aspect;
+        self.update_uniform();
+    }
+
+    /// Returns the width and height of the framebuffer in pixels.
+    pub fn viewport(&self) -> [f32; 2]
+    {
+        self.uniform.viewport
+    }
+
+    pub fn camera(&self) -> &Camera
+    {
+        &self.camera
+    }
+
+    /// Sets the color the highlighted mesh is drawn with.
+    pub fn set_highlight_color(&mut self, color: &Color)
+    {
+        let [r, g, b] = color.to_rgb();
+        self.uniform.highlight_color = [r, g, b, 1.0];
+    }
+
+    /// Sets the width, in pixels, the edges of triangle cells are drawn with when they are drawn
+    /// as quads rather than as lines.
+    pub fn set_edge_width(&mut self, width: f32)
+    {
+        self.uniform.edge_width = width;
+    }
+
+    /// Converts a pixel position on screen into the world coordinates of the scene, by inverting
+    /// the view matrix.
+    ///
+    /// # Arguments
+    /// * `pixel` - Position in pixels, measured from the top-left corner of the framebuffer with
+    ///   the y-axis pointing down, as reported by the cursor.
+    /// * `viewport` - Width and height of the framebuffer in pixels.
+    pub fn screen_to_world(&self, pixel: Vec2, viewport: Vec2) -> Vec2
+    {
+        let ndc = Vec2::new(
+            2.0 * pixel[0] / viewport[0] - 1.0,
+            1.0 - 2.0 * pixel[1] / viewport[1],
+        );
+        let view_matrix = Mat4::from(self.uniform.view_matrix);
+        let linear: Mat2 = view_matrix.fixed_view::<2, 2>(0, 0).into();
+        let translation: Vec2 = view_matrix.fixed_view::<2, 1>(0, 3).into();
+        //{{{ trace
+        trace!(target: "screen_to_world", "ndc: {}, view_matrix: {}", ndc, view_matrix);
+        //}}}
+        match linear.try_inverse()
+        {
+            Some(inv) => inv * (ndc - translation),
+            None => Vec2::zeros(),
+        }
+    }
+
+    /// Returns a snapshot of the current camera position, rotation and zoom.
+    pub fn camera_descriptor(&self) -> CameraDescriptor
+    {
+        CameraDescriptor {
+            position: self.camera.position,
+            rotation: self.camera.rotation,
+            zoom: self.camera.zoom,
+        }
+    }
 }
 //}}}
 //{{{ impl Default for View
@@ -205,6 +378,19 @@ impl Default for View
 //..................................................................................................
 //}}}
 //}}}
+//{{{ fun: snap_to_grid
+/// Snaps a point in world coordinates to the nearest node of a square grid with the given
+/// spacing, anchored at the origin. A non-positive spacing leaves the point unchanged.
+pub fn snap_to_grid(world: Vec2, spacing: f32) -> Vec2
+{
+    if spacing <= 0.0
+    {
+        return world;
+    }
+    world.map(|x| (x / spacing).round() * spacing)
+}
+//..................................................................................................
+//}}}
 //{{{ struct: ViewUniform
 /// Short Description
 ///
@@ -214,6 +400,13 @@ impl Default for View
 pub struct  ViewUniform
 {
     view_matrix: [[f32; 4]; 4],
+    /// Width and height of the framebuffer in pixels
+    viewport: [f32; 2],
+    /// Width in pixels of the edges of triangle cells, when drawn as quads
+    edge_width: f32,
+    _padding: f32,
+    /// Color the edges of the highlighted mesh are drawn with
+    highlight_color: [f32; 4],
 }
 
 impl Default for ViewUniform
@@ -224,8 +417,12 @@ impl Default for ViewUniform
                 [1.0, 0.0, 0.0, 0.0],
                 [0.0, 1.0, 0.0, 0.0],
                 [0.0, 0.0, 1.0, 0.0],
-                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
             ], 
+            viewport: [1.0, 1.0],
+            edge_width: 1.0,
+            _padding: 0.0,
+            highlight_color: [1.0, 1.0, 0.0, 1.0],
         }
     }
 }
@@ -238,8 +435,9 @@ impl EventController
     {
 
         // handle resizing
-        if let ResizedState::Resized(_) = self.resized_state
+        if let ResizedState::Resized((width, height)) = self.resized_state
         {
+            view.set_viewport_size(width, height);
             self.resized_state = ResizedState::NotResized;
         }
 
@@ -254,7 +452,8 @@ impl EventController
                     KeyStrokeState::Right => view.options.rotate_delta,
                     _ => 0.0
                 };
-                view.camera.rotate(delta_angle);
+                let pivot = self.rotation_pivot_2d(view);
+                view.camera.rotate_about(delta_angle, pivot);
             }
             else
             {
@@ -281,9 +480,199 @@ impl EventController
             self.mouse_wheel_delta = None;
         }
 
+        // the options may have been changed directly since the last update
+        view.camera.pan_bounds = view.options.pan_bounds;
+        view.camera.clamp_to_bounds();
         view.update_uniform();   
     }
+
+    /// Returns the world point a 2D view rotates about, the one under the cursor or, when the
+    /// cursor is outside the window, the one at the centre of the viewport.
+    fn rotation_pivot_2d(&self, view: &View) -> Vec2
+    {
+        if self.cursor_in_window
+        {
+            return self.mouse_world_position_2d(view);
+        }
+        let viewport = Vec2::from(view.viewport());
+        view.screen_to_world(0.5 * viewport, viewport)
+    }
+
+    /// Returns the world coordinates of the cursor in the given 2D view.
+    pub fn mouse_world_position_2d(&self, view: &View) -> Vec2
+    {
+        let viewport = view.viewport();
+        view.screen_to_world(
+            Vec2::new(self.mouse_position[0], self.mouse_position[1]),
+            Vec2::new(viewport[0], viewport[1]),
+        )
+    }
 }
 //..................................................................................................
 //}}}
 
+//-------------------------------------------------------------------------------------------------
+//{{{ mod: tests
+#[cfg(test)]
+mod tests
+{
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+
+    use super::*;
+
+    #[test]
+    fn screen_to_world_test()
+    {
+        let mut view = View::default();
+        let viewport = Vec2::new(800.0, 600.0);
+        view.update_uniform();
+
+        // with the default camera the centre pixel is the origin and the corners are at unit
+        // distance, with the y-axis pointing up
+        let centre = view.screen_to_world(Vec2::new(400.0, 300.0), viewport);
+        assert!(centre.norm() < 1e-6);
+        let top_right = view.screen_to_world(Vec2::new(800.0, 0.0), viewport);
+        assert!((top_right - Vec2::new(1.0, 1.0)).norm() < 1e-6);
+
+        // after moving the camera the centre pixel maps onto the point the camera looks at,
+        // which the view matrix sends back to the centre of the screen
+        view.camera.pan(0.5, -0.25);
+        view.camera.rotate(rad(30.0));
+        view.camera.zoom(1.0);
+        view.update_uniform();
+        let centre = view.screen_to_world(Vec2::new(400.0, 300.0), viewport);
+        let clip = Mat4::from(view.uniform.view_matrix) * Vec4::new(centre[0], centre[1], 0.0, 1.0);
+        assert!(clip.xy().norm() < 1e-6);
+
+        // and the cursor reported by the event controller is converted the same way
+        let mut event_controller = EventController::default();
+        event_controller.resize_update(PhysicalSize::new(800, 600));
+        event_controller.cursor_moved_update(PhysicalPosition::new(400.0, 300.0));
+        event_controller.update_view_2d(&mut view);
+        assert!((event_controller.mouse_world_position_2d(&view) - centre).norm() < 1e-6);
+    }
+
+    #[test]
+    fn view_matrix_test()
+    {
+        // the default matrix is the identity, leaving the homogeneous coordinate at one
+        let mut view = View::default();
+        let point = Vec4::new(0.25, -0.5, 0.0, 1.0);
+        assert_eq!(Mat4::from(view.uniform.view_matrix) * point, point);
+
+        // panning by (0.5, -0.25) and zooming to 2 sends (0.25, -0.5) to 2 * (0.25, -0.5) +
+        // (0.5, -0.25), while a quarter turn maps (x, y) to (-y, x)
+        view.camera.pan(0.5, -0.25);
+        view.camera.zoom(1.0);
+        view.update_uniform();
+        let rho = view.camera.zoom;
+        let ndc = Mat4::from(view.uniform.view_matrix) * point;
+        let expected = Vec2::new(rho * 0.25 + 0.5, -rho * 0.5 - 0.25);
+        assert!((ndc.xy() - expected).norm() < 1e-6, "{} != {}", ndc.xy(), expected);
+        assert_eq!(ndc[3], 1.0);
+
+        view.camera.rotate(rad(90.0));
+        view.update_uniform();
+        let ndc = Mat4::from(view.uniform.view_matrix) * point;
+        let expected = Vec2::new(-expected[1], expected[0]);
+        assert!((ndc.xy() - expected).norm() < 1e-6, "{} != {}", ndc.xy(), expected);
+        assert_eq!(ndc[3], 1.0);
+    }
+
+    #[test]
+    fn rotate_about_cursor_test()
+    {
+        let mut view = View::default();
+        let viewport = Vec2::new(800.0, 600.0);
+        view.camera.pan(0.5, -0.25);
+        view.camera.zoom(1.0);
+        view.update_uniform();
+        let mut event_controller = EventController::default();
+        event_controller.resize_update(PhysicalSize::new(800, 600));
+
+        // the point under the cursor stays put while the camera turns around it
+        let cursor = Vec2::new(600.0, 150.0);
+        event_controller.cursor_moved_update(PhysicalPosition::new(600.0, 150.0));
+        event_controller.update_view_2d(&mut view);
+        let pivot = view.screen_to_world(cursor, viewport);
+        event_controller.key_modifier_state = winit::keyboard::ModifiersState::SHIFT;
+        event_controller.key_stroke_state = KeyStrokeState::Right;
+        event_controller.update_view_2d(&mut view);
+        assert!((view.camera().rotation - view.options.rotate_delta).abs() < 1e-6);
+        assert!((view.screen_to_world(cursor, viewport) - pivot).norm() < 1e-5);
+
+        // without a cursor it turns around the centre of the viewport
+        event_controller.cursor_left_update();
+        let centre = view.screen_to_world(0.5 * viewport, viewport);
+        event_controller.key_stroke_state = KeyStrokeState::Left;
+        event_controller.update_view_2d(&mut view);
+        assert!(view.camera().rotation.abs() < 1e-6);
+        assert!((view.screen_to_world(0.5 * viewport, viewport) - centre).norm() < 1e-5);
+    }
+
+    #[test]
+    fn keep_aspect_test()
+    {
+        // the size in pixels of the unit square centred on the origin
+        let square_size = |view: &View| {
+            let [width, height] = view.viewport();
+            let ndc = Mat4::from(view.uniform.view_matrix) * Vec4::new(0.5, 0.5, 0.0, 1.0);
+            Vec2::new(ndc[0] * width, ndc[1] * height)
+        };
+
+        // a wide window shows the unit square as a square whose height spans the window
+        let mut view = View::default();
+        view.set_viewport_size(1600.0, 900.0);
+        let size = square_size(&view);
+        assert!((size[0] - size[1]).abs() < 1e-3, "{} is not square", size);
+        assert!((size[1] - 450.0).abs() < 1e-3);
+
+        // as does a tall window, whose width spans the square
+        view.set_viewport_size(900.0, 1600.0);
+        let size = square_size(&view);
+        assert!((size[0] - size[1]).abs() < 1e-3, "{} is not square", size);
+        assert!((size[0] - 450.0).abs() < 1e-3);
+
+        // stretching maps the unit square onto the whole window
+        view.set_viewport_size(1600.0, 900.0);
+        view.set_keep_aspect(false);
+        assert_eq!(square_size(&view), Vec2::new(800.0, 450.0));
+    }
+
+    #[test]
+    fn pan_bounds_test()
+    {
+        let mut view = View::default();
+        let (min, max) = (Vec2::new(-1.0, -2.0), Vec2::new(1.0, 2.0));
+        view.set_pan_bounds(Some((min, max)));
+
+        // panning far past the bounds leaves the centre of the view on them
+        view.camera.pan(10.0, -10.0);
+        assert!((view.camera().centre() - Vec2::new(-1.0, 2.0)).norm() < 1e-6);
+        view.camera.pan(0.5, 0.0);
+        assert!((view.camera().centre() - Vec2::new(-1.0, 2.0)).norm() < 1e-6);
+        view.camera.pan(-1.0, 1.0);
+        assert!((view.camera().centre() - Vec2::new(0.0, 1.0)).norm() < 1e-6);
+
+        // zooming out moves the centre, which the next update clamps back
+        let mut event_controller = EventController::default();
+        view.camera.position = Vec2::new(0.0, -1.5);
+        view.camera.zoom = 0.5;
+        event_controller.update_view_2d(&mut view);
+        assert!((view.camera().centre() - Vec2::new(0.0, 2.0)).norm() < 1e-6);
+
+        // without bounds the camera pans freely
+        view.set_pan_bounds(None);
+        view.camera.pan(10.0, 0.0);
+        assert!((view.camera().centre() - Vec2::new(-20.0, 2.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn snap_to_grid_test()
+    {
+        assert_eq!(snap_to_grid(Vec2::new(0.26, -0.74), 0.5), Vec2::new(0.5, -0.5));
+        assert_eq!(snap_to_grid(Vec2::new(1.2, 3.9), 1.0), Vec2::new(1.0, 4.0));
+        assert_eq!(snap_to_grid(Vec2::new(1.2, 3.9), 0.0), Vec2::new(1.2, 3.9));
+    }
+}
+//}}}
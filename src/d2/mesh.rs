@@ -26,10 +26,20 @@ pub enum Error
 {
     #[error("Indices out of bounds")]
     IndexOutOfBounds,
+    #[error("Vertex attribute or index lengths do not agree")]
+    LengthMismatch,
+    #[error("Invalid cell type")]
+    InvalidCellType,
+    #[error("Non-finite value in {0}")]
+    NonFinite(&'static str),
+    #[error("Invalid JSON descriptor: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid descriptor: {0}")]
+    InvalidDescriptor(String),
 }
 //}}}
 //{{{ struct: AxesDescriptor
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct AxesDescriptor {
     pub origin: Vec2,
     pub x_axis: Vec2,
@@ -40,15 +50,18 @@ pub struct AxesDescriptor {
 //..................................................................................................
 //}}}
 //{{{ struct LineDescriptor
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct LineDescriptor {
     pub v1: Vec2, 
     pub v2: Vec2, 
     pub color: Color,
+    /// If set the line is dashed, with dashes and gaps of these lengths in world units
+    #[serde(default)]
+    pub dash_pattern: Option<(f32, f32)>,
 }
 //}}}
 //{{{ struct: SquareDescriptor
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct SquareDescriptor {
     pub origin: Vec2,
     pub x_axis: Vec2,
@@ -58,18 +71,174 @@ pub struct SquareDescriptor {
     pub line_color: Color,
     pub tri_color: Color,
     pub cell_type: CellType,
+    /// If set the shape is anchored at its origin and its lengths are measured in pixels, so it
+    /// keeps the same size on screen regardless of zoom
+    #[serde(default)]
+    pub screen_space: bool,
+    /// If set the square is filled with `tri_color` and outlined with `line_color` in a single
+    /// mesh, whatever the `cell_type`
+    #[serde(default)]
+    pub fill_and_outline: bool,
 }
+//}}}
+//{{{ impl: SquareDescriptor
+impl SquareDescriptor {
+    /// Returns the thick outline of the square, `width` wide and drawn in its `line_color`,
+    /// whose corners are joined with `join_style` so that skewed axes give clean sharp corners.
+    pub fn to_outline(
+        &self,
+        width: f32,
+        join_style: JoinStyle,
+        miter_limit: f32,
+    ) -> OutlineDescriptor {
+        let (x, y) = (self.lenx * self.x_axis, self.leny * self.y_axis);
+        OutlineDescriptor {
+            points: vec![self.origin, self.origin + x, self.origin + x + y, self.origin + y],
+            width: width,
+            color: self.line_color,
+            join_style: join_style,
+            miter_limit: miter_limit,
+        }
+    }
+}
+//..................................................................................................
+//}}}
+//{{{ collection: RectangleDescriptor
+//{{{ struct: RectangleDescriptor
+/// A rectangle given by its center, its side lengths and the angle, in radians, it is rotated
+/// anticlockwise by about its center. Unlike a `SquareDescriptor` its axes can never be skewed,
+/// as they are computed from the single rotation angle.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RectangleDescriptor {
+    pub center: Vec2,
+    pub width: f32,
+    pub height: f32,
+    pub rotation: f32,
+    pub line_color: Color,
+    pub tri_color: Color,
+    pub cell_type: CellType,
+}
+//}}}
+//{{{ impl: RectangleDescriptor
+impl RectangleDescriptor {
+    /// Returns the equivalent square descriptor, with orthonormal axes rotated by `rotation` and
+    /// its origin at the bottom left corner of the rotated rectangle.
+    pub fn to_square(&self) -> SquareDescriptor {
+        let (sin, cos) = self.rotation.sin_cos();
+        let x_axis = Vec2::new(cos, sin);
+        let y_axis = Vec2::new(-sin, cos);
+        SquareDescriptor {
+            origin: self.center - 0.5 * self.width * x_axis - 0.5 * self.height * y_axis,
+            x_axis: x_axis,
+            y_axis: y_axis,
+            lenx: self.width,
+            leny: self.height,
+            line_color: self.line_color,
+            tri_color: self.tri_color,
+            cell_type: self.cell_type,
+            screen_space: false,
+            fill_and_outline: false,
+        }
+    }
+
+    /// Returns the thick outline of the rotated rectangle, see [`SquareDescriptor::to_outline`].
+    pub fn to_outline(
+        &self,
+        width: f32,
+        join_style: JoinStyle,
+        miter_limit: f32,
+    ) -> OutlineDescriptor {
+        self.to_square().to_outline(width, join_style, miter_limit)
+    }
+}
+//}}}
 //..................................................................................................
 //}}}
 //{{{ struct: CircleDescriptor
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct CircleDescriptor {
     pub center: Vec2,
     pub radius: f32,
+    /// Number of sides approximating the circle, zero to leave it to the tessellation quality
     pub num_sides: u32,
     pub line_color: Color,
     pub tri_color: Color,
     pub cell_type: CellType,
+    /// If set the shape is anchored at its center and its radius is measured in pixels, so it
+    /// keeps the same size on screen regardless of zoom
+    #[serde(default)]
+    pub screen_space: bool,
+    /// If set the circle is filled with `tri_color` and outlined with `line_color` in a single
+    /// mesh, whatever the `cell_type`
+    #[serde(default)]
+    pub fill_and_outline: bool,
+}
+//}}}
+//{{{ impl: CircleDescriptor
+impl CircleDescriptor {
+    /// Replaces the number of sides, if left at zero, by that of `quality`.
+    pub fn apply_quality(&mut self, quality: TessellationQuality) {
+        self.num_sides = quality.sides(self.num_sides as usize, self.radius) as u32;
+    }
+}
+//..................................................................................................
+//}}}
+//{{{ collection: OutlineDescriptor
+//{{{ struct: OutlineDescriptor
+/// A closed polygon drawn as a band of triangles `width` wide, centred on its sides, so that its
+/// outline keeps a thickness in world units. The last point joins back to the first. The width
+/// must be strictly positive, a negative one would turn the band inside out.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OutlineDescriptor {
+    pub points: Vec<Vec2>,
+    pub width: f32,
+    pub color: Color,
+    /// How the sides of the outline meet at the corners
+    #[serde(default)]
+    pub join_style: JoinStyle,
+    /// The longest a miter join may be, as a multiple of half the width, beyond which the corner
+    /// is bevelled instead. A right angle needs a limit of at least `sqrt(2)`.
+    #[serde(default = "OutlineDescriptor::default_miter_limit")]
+    pub miter_limit: f32,
+}
+//}}}
+//{{{ impl: OutlineDescriptor
+impl OutlineDescriptor {
+    /// The miter limit outlines get when none is given, the same as that of SVG strokes.
+    pub const DEFAULT_MITER_LIMIT: f32 = 4.0;
+
+    fn default_miter_limit() -> f32 {
+        Self::DEFAULT_MITER_LIMIT
+    }
+}
+//}}}
+//{{{ enum: JoinStyle
+/// How two sides of a thick outline meet at a corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinStyle {
+    /// The outer edges of both sides are extended until they meet in a point, unless that point
+    /// lies beyond the miter limit, in which case the corner is bevelled.
+    #[default]
+    Miter,
+    /// The outer edges of both sides are joined by a straight cut across the corner.
+    Bevel,
+}
+//}}}
+//..................................................................................................
+//}}}
+//{{{ enum: Descriptor
+/// Any of the shape descriptors, tagged by the kind of shape so that a scene can be read from a
+/// file or script, e.g. `{"type": "circle", "center": [0, 0], ...}`.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Descriptor {
+    Axes(AxesDescriptor),
+    Line(LineDescriptor),
+    Square(SquareDescriptor),
+    Rectangle(RectangleDescriptor),
+    Circle(CircleDescriptor),
+    Outline(OutlineDescriptor),
 }
 //..................................................................................................
 //}}}
@@ -80,12 +249,24 @@ pub struct CircleDescriptor {
 ///
 /// - `create_axes`: Creates a mesh representing a set of coordinate axes.
 /// - `create_square`: Creates a mesh representing a 2D square.
+/// - `create_rectangle`: Creates a mesh representing a 2D rectangle rotated about its center.
 /// - `create_circle`: Creates a mesh representing a 2D circle.
+/// - `create_outline`: Creates a mesh representing the thick outline of a closed polygon.
+/// - `from_vertices_indices`: Creates a mesh from per-vertex positions and colors and an index
+///    list, packing the vertex layout on the caller's behalf.
+/// - `from_descriptor`: Creates a mesh from any of the shape descriptors.
+/// - `from_json_descriptor`: Creates a mesh from a shape descriptor serialized as JSON.
+/// - `append_primitive`: Appends the mesh of a shape descriptor to the mesh, building composite
+///    objects as one mesh.
 /// - `add_vertex`: Adds a single vertex to the mesh.
 /// - `add_line_indices`: Adds a line segment to the mesh using indices which refer to vertices 
 ///    already in the mesh.
 /// - `add_line`: Adds a line segment to the mesh, both the vertices and the indices are created.
 /// - `add_triangle`: Adds a triangle to the mesh, both the vertices and the indices are created.
+/// - `add_screen_line`: Adds a line segment whose endpoints are pixel offsets from a world-space
+///    anchor.
+/// - `add_screen_triangle`: Adds a triangle whose corners are pixel offsets from a world-space
+///    anchor.
 ///
 /// Implementations of this trait can be used to generate and manipulate 2D meshes for various 
 /// purposes, such as rendering or visualization.
@@ -93,7 +274,22 @@ pub trait Mesh2D<'a> {
     fn create_axes(axes: &AxesDescriptor) -> Self;
     fn create_line(line: &LineDescriptor) -> Self;
     fn create_square(square: &SquareDescriptor) -> Self;
+    fn create_rectangle(rectangle: &RectangleDescriptor) -> Self;
     fn create_circle(circle: &CircleDescriptor) -> Self;
+    fn create_outline(outline: &OutlineDescriptor) -> Self;
+    fn from_vertices_indices(
+        positions: &[Vec2],
+        colors: &[Color],
+        indices: &[u32],
+        cell_type: CellType,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn from_descriptor(descriptor: &Descriptor) -> Self;
+    fn from_json_descriptor(json: &str) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn append_primitive(&mut self, descriptor: &Descriptor) -> Result<(), Error>;
     fn add_vertex(&mut self, v: &Vec2, lin_color: &Color, tri_color: &Color);
     fn add_line_indices(&mut self, i1: u32, i2: u32) -> Result<(), Error>;
     fn add_triangle_indices(&mut self, i1: u32, i2: u32, i3: u32) -> Result<(), Error> ;
@@ -106,6 +302,31 @@ pub trait Mesh2D<'a> {
         line_color: &Color,
         tri_color: &Color,
     );
+    fn add_screen_line(
+        &mut self,
+        anchor: &Vec2,
+        o1: &Vec2,
+        o2: &Vec2,
+        line_color: &Color,
+        tri_color: &Color,
+    );
+    fn add_screen_triangle(
+        &mut self,
+        anchor: &Vec2,
+        o1: &Vec2,
+        o2: &Vec2,
+        o3: &Vec2,
+        line_color: &Color,
+        tri_color: &Color,
+    );
+    fn add_triangle_fan(
+        &mut self,
+        center: &Vec2,
+        rim: &[Vec2],
+        line_color: &Color,
+        tri_color: &Color,
+    );
+    fn add_triangle_strip(&mut self, points: &[Vec2], line_color: &Color, tri_color: &Color);
 }
 //..................................................................................................
 //}}}
@@ -128,6 +349,7 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
     {
         let mut mesh = Mesh::from_num_lines(1);
         mesh.add_line(&line.v1, &line.v2, &line.color, &Color::default());
+        mesh.set_options(MeshOptions { dash_pattern: line.dash_pattern, ..MeshOptions::default() });
         mesh
     }
     //}}}
@@ -141,9 +363,18 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
     ///
     /// The function calculates the vertices of the square based on the `origin`, `x_axis`, `y_axis`, `lenx`, and `leny` fields of the `SquareDescriptor`. It then adds the lines or triangles to the mesh using the `add_line` and `add_triangle` methods.
     ///
-    /// If the `cell_type` field of the `SquareDescriptor` is not `CellType::Line` or `CellType::Triangle`, the function will panic with the message "Unknown cell type".
+    /// If `fill_and_outline` is set, the triangles are created whatever the cell type and the
+    /// boundary of the square is added to them as line cells.
+    ///
+    /// If the `cell_type` field of the `SquareDescriptor` is `CellType::None`, and
+    /// `fill_and_outline` is not set, the mesh is empty.
     fn create_square(square_disc: &SquareDescriptor) -> Self {
-        match (square_disc.cell_type) {
+        let cell_type = if square_disc.fill_and_outline {
+            CellType::Triangle
+        } else {
+            square_disc.cell_type
+        };
+        match cell_type {
             //{{{ case: CellType::Line
             CellType::Line => {
                 let mut mesh = Mesh::from_num_lines(4);
@@ -152,6 +383,20 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
                 let dx = square_disc.x_axis * square_disc.lenx;
                 let dy = square_disc.y_axis * square_disc.leny;
 
+                if square_disc.screen_space {
+                    let v0 = Vec2::zeros();
+                    let v1 = dx;
+                    let v2 = dx + dy;
+                    let v3 = dy;
+                    let lc = &square_disc.line_color;
+                    let tc = &square_disc.tri_color;
+                    mesh.add_screen_line(&o, &v0, &v1, lc, tc);
+                    mesh.add_screen_line(&o, &v1, &v2, lc, tc);
+                    mesh.add_screen_line(&o, &v2, &v3, lc, tc);
+                    mesh.add_screen_line(&o, &v3, &v0, lc, tc);
+                    return mesh;
+                }
+
                 let v0 = o;
                 let v1 = o + dx;
                 let v2 = o + dx + dy;
@@ -171,37 +416,72 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
                 let dx = square_disc.x_axis * square_disc.lenx;
                 let dy = square_disc.y_axis * square_disc.leny;
 
-                let v0 = o;
-                let v1 = o + dx;
-                let v2 = o + dx + dy;
-                let v3 = o + dy;
-                mesh.add_triangle(
-                    &v0,
-                    &v1,
-                    &v2,
-                    &square_disc.line_color,
-                    &square_disc.tri_color,
-                );
-                mesh.add_triangle(
-                    &v0,
-                    &v2,
-                    &v3,
-                    &square_disc.line_color,
-                    &square_disc.tri_color,
-                );
+                if square_disc.screen_space {
+                    let v0 = Vec2::zeros();
+                    let v1 = dx;
+                    let v2 = dx + dy;
+                    let v3 = dy;
+                    let lc = &square_disc.line_color;
+                    let tc = &square_disc.tri_color;
+                    mesh.add_screen_triangle(&o, &v0, &v1, &v2, lc, tc);
+                    mesh.add_screen_triangle(&o, &v0, &v2, &v3, lc, tc);
+                } else {
+                    let v0 = o;
+                    let v1 = o + dx;
+                    let v2 = o + dx + dy;
+                    let v3 = o + dy;
+                    mesh.add_triangle(
+                        &v0,
+                        &v1,
+                        &v2,
+                        &square_disc.line_color,
+                        &square_disc.tri_color,
+                    );
+                    mesh.add_triangle(
+                        &v0,
+                        &v2,
+                        &v3,
+                        &square_disc.line_color,
+                        &square_disc.tri_color,
+                    );
+                }
+
+                if square_disc.fill_and_outline {
+                    // corners v0, v1, v2 are vertices 0, 1, 2 of the first triangle and v3 is
+                    // vertex 5 of the second
+                    mesh.append_line_indices(&[0, 1, 1, 2, 2, 5, 5, 0]);
+                }
                 mesh
             }
             //}}}
-            //{{{ default
-            _ => {
-                panic!("Unknown cell type");
-            } //}}}
+            //{{{ case: CellType::None
+            CellType::None => Mesh::from_num_triangles(0),
+            //}}}
         }
     }
     //}}}
+    //{{{ fun: create_rectangle
+    /// Creates the same cells as `create_square` does for the equivalent square.
+    fn create_rectangle(rectangle: &RectangleDescriptor) -> Self {
+        Self::create_square(&rectangle.to_square())
+    }
+    //}}}
     //{{{ fun: create_circle
+    /// If `fill_and_outline` is set, the triangles are created whatever the cell type and the rim
+    /// of the circle is added to them as line cells. Otherwise `CellType::None` gives an empty
+    /// mesh.
     fn create_circle(circle: &CircleDescriptor) -> Self {
-        match circle.cell_type {
+        if circle.num_sides == 0 {
+            let mut circle = circle.clone();
+            circle.apply_quality(TessellationQuality::default());
+            return Self::create_circle(&circle);
+        }
+        let cell_type = if circle.fill_and_outline {
+            CellType::Triangle
+        } else {
+            circle.cell_type
+        };
+        match cell_type {
             //{{{ case: CellType::Line
             CellType::Line => {
                 let mut mesh = Mesh::from_num_lines(circle.num_sides as usize);
@@ -215,9 +495,13 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
                     let angle2 = (ii as f32 / circle.num_sides as f32) * 2.0 * pi;
                     let (sin_theta1, cos_theta1) = angle1.sin_cos();
                     let (sin_theta2, cos_theta2) = angle2.sin_cos();
-                    let p1 = c + r * Vec2::new(cos_theta1, sin_theta1);
-                    let p2 = c + r * Vec2::new(cos_theta2, sin_theta2);
-                    mesh.add_line(&p1, &p2, &circle.line_color, &circle.tri_color);
+                    let d1 = r * Vec2::new(cos_theta1, sin_theta1);
+                    let d2 = r * Vec2::new(cos_theta2, sin_theta2);
+                    if circle.screen_space {
+                        mesh.add_screen_line(&c, &d1, &d2, &circle.line_color, &circle.tri_color);
+                    } else {
+                        mesh.add_line(&(c + d1), &(c + d2), &circle.line_color, &circle.tri_color);
+                    }
                 }
                 mesh
             }
@@ -235,17 +519,185 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
                     let angle2 = (ii as f32 / circle.num_sides as f32) * 2.0 * pi;
                     let (sin_theta1, cos_theta1) = angle1.sin_cos();
                     let (sin_theta2, cos_theta2) = angle2.sin_cos();
-                    let p1 = c + r * Vec2::new(cos_theta1, sin_theta1);
-                    let p2 = c + r * Vec2::new(cos_theta2, sin_theta2);
-                    mesh.add_triangle(&c, &p1, &p2, &circle.line_color, &circle.tri_color);
+                    let d1 = r * Vec2::new(cos_theta1, sin_theta1);
+                    let d2 = r * Vec2::new(cos_theta2, sin_theta2);
+                    if circle.screen_space {
+                        let d0 = Vec2::zeros();
+                        let (lc, tc) = (&circle.line_color, &circle.tri_color);
+                        mesh.add_screen_triangle(&c, &d0, &d1, &d2, lc, tc);
+                    } else {
+                        let (p1, p2) = (c + d1, c + d2);
+                        mesh.add_triangle(&c, &p1, &p2, &circle.line_color, &circle.tri_color);
+                    }
+                    if circle.fill_and_outline {
+                        // the rim is the edge opposite the center of each triangle
+                        mesh.append_line_indices(&[3 * i + 1, 3 * i + 2]);
+                    }
                 }
                 mesh
             }
             //}}}
-            _ => {
-                panic!("Unknown cell type");
+            CellType::None => Mesh::from_num_triangles(0),
+        }
+    }
+    //}}}
+    //{{{ fun: create_outline
+    /// Each side of the polygon is a quad of two triangles, offset by half the width on either
+    /// side of it. At each corner the quads share the point where their inner edges meet, and on
+    /// the outer side either meet at the miter point or, for a bevel, are joined by one more
+    /// triangle, so the band has neither gaps nor overlaps. Repeated points are skipped.
+    fn create_outline(outline: &OutlineDescriptor) -> Self {
+        //{{{ locals
+        let mut points = outline.points.clone();
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        let n = points.len();
+        let half_width = 0.5 * outline.width;
+        let color = &outline.color;
+        let mut mesh = Mesh::from_num_triangles(3 * n);
+        if n < 2 {
+            return mesh;
+        }
+        //}}}
+        //{{{ com: compute where the sides end and start at each corner
+        // the left normal of the side from point i to point i + 1
+        let normal = |i: usize| {
+            let d = (points[(i + 1) % n] - points[i]).normalize();
+            Vec2::new(-d.y, d.x)
+        };
+        // the (left, right) ends of the side arriving at, and leaving from, each corner
+        let mut arrive = Vec::with_capacity(n);
+        let mut leave = Vec::with_capacity(n);
+        for i in 0..n {
+            let p = points[i];
+            let (n0, n1) = (normal((i + n - 1) % n), normal(i));
+            let bisector = n0 + n1;
+            // the miter point lies along the bisector of the normals, 1 / cos of half the angle
+            // between them further out than half the width
+            let ratio = if bisector.norm() > f32::EPSILON {
+                1.0 / bisector.normalize().dot(&n1)
+            } else {
+                f32::INFINITY
+            };
+            let miter = if ratio.is_finite() {
+                bisector.normalize() * half_width * ratio
+            } else {
+                Vec2::zeros()
+            };
+            if outline.join_style == JoinStyle::Miter && ratio <= outline.miter_limit {
+                arrive.push((p + miter, p - miter));
+                leave.push((p + miter, p - miter));
+                continue;
+            }
+            // a left turn has its outer corner on the right
+            let left_turn = n0.perp(&n1) > 0.0;
+            if left_turn {
+                arrive.push((p + miter, p - n0 * half_width));
+                leave.push((p + miter, p - n1 * half_width));
+            } else {
+                arrive.push((p + n0 * half_width, p - miter));
+                leave.push((p + n1 * half_width, p - miter));
+            }
+            // the bevel, between the outer ends of both sides and their shared inner point
+            let (a, b) = (arrive[i], leave[i]);
+            if left_turn {
+                mesh.add_triangle(&a.0, &a.1, &b.1, color, color);
+            } else {
+                mesh.add_triangle(&a.1, &b.0, &a.0, color, color);
+            }
+        }
+        //}}}
+        //{{{ com: add a quad for each side
+        for i in 0..n {
+            let (a, b) = (leave[i], arrive[(i + 1) % n]);
+            mesh.add_triangle(&a.1, &b.1, &b.0, color, color);
+            mesh.add_triangle(&a.1, &b.0, &a.0, color, color);
+        }
+        //}}}
+        mesh
+    }
+    //}}}
+    //{{{ fun: from_descriptor
+    /// Creates the mesh of whichever shape the descriptor holds.
+    fn from_descriptor(descriptor: &Descriptor) -> Self {
+        match descriptor {
+            Descriptor::Axes(axes) => Self::create_axes(axes),
+            Descriptor::Line(line) => Self::create_line(line),
+            Descriptor::Square(square) => Self::create_square(square),
+            Descriptor::Rectangle(rectangle) => Self::create_rectangle(rectangle),
+            Descriptor::Circle(circle) => Self::create_circle(circle),
+            Descriptor::Outline(outline) => Self::create_outline(outline),
+        }
+    }
+    //}}}
+    //{{{ fun: from_json_descriptor
+    /// Parses a JSON [`Descriptor`], tagged with the kind of shape in its `type` field, and
+    /// creates the mesh of that shape. The descriptor is held to the checks the RPC server
+    /// applies to the matching add request, see `validate_descriptor`.
+    fn from_json_descriptor(json: &str) -> Result<Self, Error> {
+        let descriptor: Descriptor = serde_json::from_str(json)?;
+        crate::d2::validate_descriptor(&descriptor).map_err(Error::InvalidDescriptor)?;
+        Ok(Self::from_descriptor(&descriptor))
+    }
+    //}}}
+    //{{{ fun: append_primitive
+    /// Appends the mesh of whichever shape the descriptor holds, see [`Self::from_descriptor`],
+    /// to this one, so that a composite object such as an arrow is one mesh with a single uid.
+    /// Fails, leaving the mesh untouched, unless the shape has the same cell type as the mesh.
+    /// Shapes without any vertex are skipped.
+    fn append_primitive(&mut self, descriptor: &Descriptor) -> Result<(), Error> {
+        let primitive = Self::from_descriptor(descriptor);
+        if primitive.num_vertices() == 0 {
+            return Ok(());
+        }
+        if primitive.cell_type != self.cell_type {
+            return Err(Error::InvalidCellType);
+        }
+        self.merge(primitive);
+        Ok(())
+    }
+    //}}}
+    //{{{ fun: from_vertices_indices
+    /// Each vertex uses its color for both lines and triangles. The number of colors must match
+    /// the number of positions, the number of indices must be a multiple of the cell size, every
+    /// index must refer to one of the given positions and all values must be finite.
+    fn from_vertices_indices(
+        positions: &[Vec2],
+        colors: &[Color],
+        indices: &[u32],
+        cell_type: CellType,
+    ) -> Result<Self, Error> {
+        //{{{ com: validate the input
+        if colors.len() != positions.len() {
+            return Err(Error::LengthMismatch);
+        }
+        let mut mesh = match cell_type {
+            CellType::Line if indices.len() % 2 == 0 => Mesh::from_num_lines(indices.len() / 2),
+            CellType::Triangle if indices.len() % 3 == 0 => {
+                Mesh::from_num_triangles(indices.len() / 3)
             }
+            CellType::Line | CellType::Triangle => return Err(Error::LengthMismatch),
+            _ => return Err(Error::InvalidCellType),
+        };
+        if indices.iter().any(|&i| i as usize >= positions.len()) {
+            return Err(Error::IndexOutOfBounds);
+        }
+        if !positions.iter().all(|v| v.iter().all(|x| x.is_finite())) {
+            return Err(Error::NonFinite("positions"));
+        }
+        if !colors.iter().all(|c| c.to_rgba().iter().all(|x| x.is_finite())) {
+            return Err(Error::NonFinite("colors"));
+        }
+        //}}}
+        //{{{ com: pack the vertices and indices
+        for (v, c) in positions.iter().zip(colors) {
+            mesh.add_vertex(v, c, c);
         }
+        mesh.append_indices(indices);
+        //}}}
+        Ok(mesh)
     }
     //}}}
     //{{{ fun: add_vertex
@@ -254,6 +706,7 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
             position: *v,
             line_color: *lin_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
     }
     //}}}
@@ -291,11 +744,13 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
             position: *v1,
             line_color: *line_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
         self.append_vertex(&Vertex::new(&VertexDescriptor {
             position: *v2,
             line_color: *line_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
     }
     //}}}
@@ -317,19 +772,109 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
             position: *v1,
             line_color: *line_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
         self.append_vertex(&Vertex::new(&VertexDescriptor {
             position: *v2,
             line_color: *line_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
         self.append_vertex(&Vertex::new(&VertexDescriptor {
             position: *v3,
             line_color: *line_color,
             triangle_color: *tri_color,
+            screen_offset: Vec2::zeros(),
         }));
     }
     //}}}
+    //{{{ fun: add_screen_line
+    fn add_screen_line(
+        &mut self,
+        anchor: &Vec2,
+        o1: &Vec2,
+        o2: &Vec2,
+        line_color: &Color,
+        tri_color: &Color,
+    ) {
+        assert!(self.is_line());
+
+        let nv = self.num_vertices() as u32;
+        let indices = [nv, nv + 1];
+        self.append_indices(&indices);
+
+        for o in [o1, o2] {
+            self.append_vertex(&Vertex::new(&VertexDescriptor {
+                position: *anchor,
+                line_color: *line_color,
+                triangle_color: *tri_color,
+                screen_offset: *o,
+            }));
+        }
+    }
+    //}}}
+    //{{{ fun: add_screen_triangle
+    fn add_screen_triangle(
+        &mut self,
+        anchor: &Vec2,
+        o1: &Vec2,
+        o2: &Vec2,
+        o3: &Vec2,
+        line_color: &Color,
+        tri_color: &Color,
+    ) {
+        assert!(self.is_triangle());
+        let nv = self.num_vertices() as u32;
+        let indices = [nv, nv + 1, nv + 2];
+        self.append_indices(&indices);
+
+        for o in [o1, o2, o3] {
+            self.append_vertex(&Vertex::new(&VertexDescriptor {
+                position: *anchor,
+                line_color: *line_color,
+                triangle_color: *tri_color,
+                screen_offset: *o,
+            }));
+        }
+    }
+    //}}}
+    //{{{ fun: add_triangle_fan
+    /// Appends an open fan of triangles from `center` to each consecutive pair of `rim` points,
+    /// so `n` rim points give `n - 1` triangles. Repeat the first rim point at the end to close
+    /// the fan.
+    fn add_triangle_fan(
+        &mut self,
+        center: &Vec2,
+        rim: &[Vec2],
+        line_color: &Color,
+        tri_color: &Color,
+    ) {
+        assert!(self.is_triangle());
+        if rim.len() < 2 {
+            return;
+        }
+        let nv = self.num_vertices() as u32;
+        self.append_fan_indices(nv, nv + 1, rim.len());
+        for v in std::iter::once(center).chain(rim) {
+            self.add_vertex(v, line_color, tri_color);
+        }
+    }
+    //}}}
+    //{{{ fun: add_triangle_strip
+    /// Appends a strip of triangles over consecutive triples of `points`, so `n` points give
+    /// `n - 2` triangles all wound the same way as the first.
+    fn add_triangle_strip(&mut self, points: &[Vec2], line_color: &Color, tri_color: &Color) {
+        assert!(self.is_triangle());
+        if points.len() < 3 {
+            return;
+        }
+        let nv = self.num_vertices() as u32;
+        self.append_strip_indices(nv, points.len());
+        for v in points {
+            self.add_vertex(v, line_color, tri_color);
+        }
+    }
+    //}}}
 }
 //..................................................................................................
 //}}}
@@ -337,9 +882,24 @@ impl<'a> Mesh2D<'a> for Mesh<'a> {
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 
     use super::*;
+    use crate::core::VertexCore;
+
+    /// Checks the vertex and index counts of a mesh, that its cells are whole and that every index
+    /// refers to a vertex of the mesh.
+    fn assert_topology(mesh: &Mesh, num_vertices: usize, num_indices: usize) {
+        assert_eq!(mesh.num_vertices(), num_vertices);
+        assert_eq!(mesh.num_indices(), num_indices);
+        assert_eq!(mesh.triangle_cell_indices().len() % 3, 0);
+        assert_eq!(mesh.line_cell_indices().len() % 2, 0);
+        assert!(mesh
+            .index_slice()
+            .iter()
+            .chain(mesh.line_cell_indices())
+            .all(|&i| (i as usize) < num_vertices));
+    }
 
     #[test]
     fn create_axes_test() {
@@ -351,6 +911,8 @@ mod tests {
             pos_len: 1.0,
         };
         let mesh = Mesh::create_axes(&axes_disc);
+        assert!(mesh.is_line());
+        assert_topology(&mesh, 4, 4);
     }
 
     #[test]
@@ -364,8 +926,440 @@ mod tests {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            screen_space: false,
+            fill_and_outline: false,
         };
         let mesh = Mesh::create_square(&square_disc);
+        assert!(mesh.is_triangle());
+        assert_topology(&mesh, 6, 6);
+
+        let mesh = Mesh::create_square(&SquareDescriptor {
+            cell_type: CellType::Line,
+            ..square_disc
+        });
+        assert!(mesh.is_line());
+        assert_topology(&mesh, 8, 8);
+
+        // 2D vertices decode to 2D positions, without normals
+        let positions = mesh.positions();
+        assert_eq!(positions.len(), mesh.num_vertices());
+        assert!(positions.contains(&Vec2::new(10.0, 1.0)));
+        assert!(mesh.normals().is_none());
+        assert!(mesh.colors().iter().all(|c| *c == Color::Red.to_rgba()));
+    }
+
+    #[test]
+    fn default_cell_type_test() {
+        // shapes whose cell type is left at its default have no cells, instead of panicking
+        let square = SquareDescriptor {
+            origin: Vec2::zeros(),
+            x_axis: Vec2::x(),
+            y_axis: Vec2::y(),
+            lenx: 1.0,
+            leny: 1.0,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::default(),
+            screen_space: false,
+            fill_and_outline: false,
+        };
+        let rectangle = RectangleDescriptor {
+            center: Vec2::zeros(),
+            width: 2.0,
+            height: 1.0,
+            rotation: 0.5,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::default(),
+        };
+        let circle = CircleDescriptor {
+            center: Vec2::zeros(),
+            radius: 1.0,
+            num_sides: 8,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::default(),
+            screen_space: false,
+            fill_and_outline: false,
+        };
+        for mesh in [
+            Mesh::create_square(&square),
+            Mesh::create_rectangle(&rectangle),
+            Mesh::create_circle(&circle),
+        ] {
+            assert!(mesh.is_empty());
+            assert!(!mesh.has_cells());
+        }
+
+        // filling and outlining a shape does not depend on its cell type
+        let circle = CircleDescriptor { fill_and_outline: true, ..circle };
+        assert!(Mesh::create_circle(&circle).has_triangles());
+    }
+
+    #[test]
+    fn create_outline_test() {
+        let mut outline = OutlineDescriptor {
+            points: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+                Vec2::new(0.0, 2.0),
+            ],
+            width: 0.2,
+            color: Color::Black,
+            join_style: JoinStyle::Miter,
+            miter_limit: OutlineDescriptor::DEFAULT_MITER_LIMIT,
+        };
+        // the summed area of the triangles only matches that of the band if they neither leave
+        // gaps nor overlap
+        let area = |mesh: &Mesh| -> f32 {
+            let positions: Vec<Vec2> = (0..mesh.num_vertices())
+                .map(|i| mesh.vertex_position(i).xy())
+                .collect();
+            positions
+                .chunks_exact(3)
+                .map(|t| 0.5 * (t[1] - t[0]).perp(&(t[2] - t[0])).abs())
+                .sum()
+        };
+
+        // the right angle corners are mitered to a point half the width out along each side
+        let mesh = Mesh::create_outline(&outline);
+        assert_eq!(mesh.num_triangles(), 8);
+        let has_vertex = |mesh: &Mesh, v: Vec2| {
+            (0..mesh.num_vertices()).any(|i| (mesh.vertex_position(i).xy() - v).norm() < 1e-6)
+        };
+        assert!(has_vertex(&mesh, Vec2::new(-0.1, -0.1)));
+        assert!(has_vertex(&mesh, Vec2::new(2.1, 2.1)));
+        assert!(has_vertex(&mesh, Vec2::new(0.1, 1.9)));
+        assert!((area(&mesh) - (2.2 * 2.2 - 1.8 * 1.8)).abs() < 1e-5);
+
+        // a right angle miter is sqrt(2) times half the width, past a limit of 1.2
+        outline.miter_limit = 1.2;
+        let bevelled = Mesh::create_outline(&outline);
+        assert_eq!(bevelled.num_triangles(), 12);
+        assert!(!has_vertex(&bevelled, Vec2::new(-0.1, -0.1)));
+        assert!(has_vertex(&bevelled, Vec2::new(-0.1, 0.0)));
+        assert!(has_vertex(&bevelled, Vec2::new(0.0, -0.1)));
+        let cut = 4.0 * 0.5 * 0.1 * 0.1;
+        assert!((area(&bevelled) - (2.2 * 2.2 - 1.8 * 1.8 - cut)).abs() < 1e-5);
+
+        // bevel joins, and the same polygon clockwise, closed by repeating its first point
+        outline.miter_limit = OutlineDescriptor::DEFAULT_MITER_LIMIT;
+        outline.join_style = JoinStyle::Bevel;
+        outline.points.reverse();
+        outline.points.push(outline.points[0]);
+        let bevelled = Mesh::create_outline(&outline);
+        assert_eq!(bevelled.num_triangles(), 12);
+        assert!((area(&bevelled) - (2.2 * 2.2 - 1.8 * 1.8 - cut)).abs() < 1e-5);
+
+        // the outline of a rectangle goes around its corners, mitered at right angles
+        let rectangle = RectangleDescriptor {
+            center: Vec2::new(1.0, 1.0),
+            width: 2.0,
+            height: 2.0,
+            rotation: 0.0,
+            line_color: Color::Black,
+            tri_color: Color::Green,
+            cell_type: CellType::Line,
+        };
+        let limit = OutlineDescriptor::DEFAULT_MITER_LIMIT;
+        let mesh = Mesh::create_outline(&rectangle.to_outline(0.2, JoinStyle::Miter, limit));
+        assert_eq!(mesh.num_triangles(), 8);
+        assert!(has_vertex(&mesh, Vec2::new(-0.1, -0.1)));
+        assert!((area(&mesh) - (2.2 * 2.2 - 1.8 * 1.8)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn create_rectangle_test() {
+        let rectangle = RectangleDescriptor {
+            center: Vec2::new(1.0, 2.0),
+            width: 4.0,
+            height: 2.0,
+            rotation: std::f32::consts::FRAC_PI_2,
+            line_color: Color::Red,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+        };
+        let square = rectangle.to_square();
+        assert!(square.x_axis.dot(&square.y_axis).abs() < 1e-6);
+        assert!((square.x_axis - Vec2::new(0.0, 1.0)).norm() < 1e-6);
+        assert!((square.origin - Vec2::new(2.0, 0.0)).norm() < 1e-6);
+
+        let mesh = Mesh::create_rectangle(&rectangle);
+        assert!(mesh.is_triangle());
+        assert_topology(&mesh, 6, 6);
+        // rotated by a quarter turn the width runs along y and the height along x
+        let corners: Vec<Vec2> =
+            (0..mesh.num_vertices()).map(|i| mesh.vertex_position(i).xy()).collect();
+        let (min, max) = corners.iter().fold(
+            (Vec2::repeat(f32::MAX), Vec2::repeat(f32::MIN)),
+            |(min, max), c| (min.inf(c), max.sup(c)),
+        );
+        assert!((min - Vec2::new(0.0, 0.0)).norm() < 1e-5);
+        assert!((max - Vec2::new(2.0, 4.0)).norm() < 1e-5);
+
+        let mesh = Mesh::create_rectangle(&RectangleDescriptor {
+            cell_type: CellType::Line,
+            ..rectangle
+        });
+        assert!(mesh.is_line());
+        assert_topology(&mesh, 8, 8);
+    }
+
+    #[test]
+    fn triangle_fan_strip_test() {
+        let signed_area = |[v1, v2, v3]: [Vec3; 3]| (v2 - v1).cross(&(v3 - v1)).z;
+
+        // five rim points around a half circle give four triangles, all wound the same way
+        let rim: Vec<Vec2> = (0..5)
+            .map(|i| {
+                let theta = i as f32 * std::f32::consts::FRAC_PI_4;
+                Vec2::new(theta.cos(), theta.sin())
+            })
+            .collect();
+        let mut fan = Mesh::from_num_triangles(4);
+        fan.add_triangle_fan(&Vec2::zeros(), &rim, &Color::Black, &Color::Red);
+        assert_topology(&fan, 6, 12);
+        assert!(fan.triangles().all(|tri| signed_area(tri) > 0.0));
+
+        // a zig-zag strip alternates the orientation of its triples, but not of its triangles
+        let points: Vec<Vec2> = (0..6).map(|i| Vec2::new(i as f32, (i % 2) as f32)).collect();
+        let mut strip = Mesh::from_num_triangles(4);
+        strip.add_triangle_strip(&points, &Color::Black, &Color::Red);
+        assert_topology(&strip, 6, 12);
+        assert!(strip.triangles().all(|tri| signed_area(tri) < 0.0));
+
+        // too few points add nothing
+        strip.add_triangle_strip(&points[..2], &Color::Black, &Color::Red);
+        fan.add_triangle_fan(&Vec2::zeros(), &rim[..1], &Color::Black, &Color::Red);
+        assert_topology(&strip, 6, 12);
+        assert_topology(&fan, 6, 12);
+    }
+
+    #[test]
+    fn primitive_topology_test() {
+        let line = Mesh::create_line(&LineDescriptor {
+            v1: Vec2::zeros(),
+            v2: Vec2::x(),
+            color: Color::Red,
+            dash_pattern: None,
+        });
+        assert_topology(&line, 2, 2);
+
+        let n = 8;
+        for screen_space in [false, true] {
+            let circle_disc = CircleDescriptor {
+                center: Vec2::new(1.0, 2.0),
+                radius: 5.0,
+                num_sides: n as u32,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                screen_space: screen_space,
+                fill_and_outline: false,
+            };
+            let circle = Mesh::create_circle(&circle_disc);
+            assert!(circle.is_triangle());
+            assert_topology(&circle, 3 * n, 3 * n);
+
+            let circle = Mesh::create_circle(&CircleDescriptor {
+                cell_type: CellType::Line,
+                ..circle_disc.clone()
+            });
+            assert!(circle.is_line());
+            assert_topology(&circle, 2 * n, 2 * n);
+
+            let circle = Mesh::create_circle(&CircleDescriptor {
+                fill_and_outline: true,
+                ..circle_disc
+            });
+            assert_topology(&circle, 3 * n, 3 * n);
+        }
+    }
+
+    #[test]
+    fn fill_and_outline_test() {
+        let square_disc = SquareDescriptor {
+            origin: Vec2::new(0.0, 0.0),
+            x_axis: Vec2::new(1.0, 0.0),
+            y_axis: Vec2::new(0.0, 1.0),
+            lenx: 2.0,
+            leny: 1.0,
+            line_color: Color::Black,
+            tri_color: Color::Green,
+            cell_type: CellType::Line,
+            screen_space: false,
+            fill_and_outline: true,
+        };
+        let square = Mesh::create_square(&square_disc);
+        assert!(square.has_triangles() && square.has_lines());
+        assert_eq!(square.num_triangles(), 2);
+        // the outline visits the four corners in turn
+        let corners: Vec<Vec2> = square
+            .line_cell_indices()
+            .chunks_exact(2)
+            .map(|line| square.vertex_position(line[0] as usize).xy())
+            .collect();
+        assert_eq!(
+            corners,
+            vec![Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(0.0, 1.0)]
+        );
+
+        let circle_disc = CircleDescriptor {
+            center: Vec2::new(1.0, 2.0),
+            radius: 5.0,
+            num_sides: 8,
+            line_color: Color::Black,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+            screen_space: false,
+            fill_and_outline: true,
+        };
+        let circle = Mesh::create_circle(&circle_disc);
+        assert_eq!(circle.line_cell_indices().len(), 16);
+        for &i in circle.line_cell_indices() {
+            let r = (circle.vertex_position(i as usize).xy() - circle_disc.center).norm();
+            assert!((r - 5.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn create_screen_space_circle_test() {
+        let circle_disc = CircleDescriptor {
+            center: Vec2::new(1.0, 2.0),
+            radius: 5.0,
+            num_sides: 8,
+            line_color: Color::Red,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+            screen_space: true,
+            fill_and_outline: false,
+        };
+        let mesh = Mesh::create_circle(&circle_disc);
+        let n = Vertex::len();
+        let p = Vertex::position_offset();
+        let o = Vertex::screen_offset_offset();
+        for i in 0..mesh.num_vertices() {
+            // every vertex sits on the anchor, the shape lives in the pixel offsets
+            let vert = &mesh.vertex_slice()[i * n..(i + 1) * n];
+            assert_eq!(&vert[p..p + 2], &[1.0, 2.0]);
+            let r = (vert[o] * vert[o] + vert[o + 1] * vert[o + 1]).sqrt();
+            assert!(r < 1e-6 || (r - 5.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn from_vertices_indices_test() {
+        let positions = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)];
+        let colors = [Color::Red, Color::Green, Color::Rgba((0.0, 0.0, 1.0, 0.5))];
+        let mesh =
+            Mesh::from_vertices_indices(&positions, &colors, &[0, 1, 2], CellType::Triangle)
+                .unwrap();
+        assert_eq!(mesh.num_vertices(), 3);
+        assert_eq!(mesh.index_slice(), &[0, 1, 2]);
+        let n = Vertex::len();
+        let p = Vertex::position_offset();
+        let c = Vertex::triangle_color_offset();
+        let vert = &mesh.vertex_slice()[n..2 * n];
+        assert_eq!(&vert[p..p + 2], &[1.0, 0.0]);
+        assert_eq!(&vert[c..c + 4], &Color::Green.to_rgba());
+        // the alpha of a translucent color is carried into the vertex
+        let vert = &mesh.vertex_slice()[2 * n..3 * n];
+        assert_eq!(&vert[c..c + 4], &[0.0, 0.0, 1.0, 0.5]);
+
+        let res = Mesh::from_vertices_indices(&positions, &colors, &[0, 3], CellType::Line);
+        assert!(matches!(res, Err(Error::IndexOutOfBounds)));
+        let res = Mesh::from_vertices_indices(&positions, &colors[..2], &[0, 1], CellType::Line);
+        assert!(matches!(res, Err(Error::LengthMismatch)));
+        let res = Mesh::from_vertices_indices(&positions, &colors, &[0, 1], CellType::Triangle);
+        assert!(matches!(res, Err(Error::LengthMismatch)));
+        let nan_positions = [Vec2::new(0.0, 0.0), Vec2::new(f32::NAN, 0.0), Vec2::new(0.0, 1.0)];
+        let res = Mesh::from_vertices_indices(&nan_positions, &colors, &[0, 1], CellType::Line);
+        assert!(matches!(res, Err(Error::NonFinite("positions"))));
+    }
+
+    /// One descriptor of each kind of shape.
+    pub(crate) fn sample_descriptors() -> Vec<Descriptor> {
+        vec![
+            Descriptor::Axes(AxesDescriptor {
+                origin: Vec2::zeros(),
+                x_axis: Vec2::x(),
+                y_axis: Vec2::y(),
+                neg_len: 1.0,
+                pos_len: 2.0,
+            }),
+            Descriptor::Line(LineDescriptor {
+                v1: Vec2::zeros(),
+                v2: Vec2::x(),
+                color: Color::Other((0.2, 0.4, 0.6)),
+                dash_pattern: None,
+            }),
+            Descriptor::Square(SquareDescriptor {
+                origin: Vec2::zeros(),
+                x_axis: Vec2::x(),
+                y_axis: Vec2::y(),
+                lenx: 2.0,
+                leny: 1.0,
+                line_color: Color::Black,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                screen_space: false,
+                fill_and_outline: true,
+            }),
+            Descriptor::Rectangle(RectangleDescriptor {
+                center: Vec2::new(-1.0, 0.5),
+                width: 2.0,
+                height: 1.0,
+                rotation: 0.5,
+                line_color: Color::Black,
+                tri_color: Color::Green,
+                cell_type: CellType::Line,
+            }),
+            Descriptor::Circle(CircleDescriptor {
+                center: Vec2::new(1.0, 2.0),
+                radius: 5.0,
+                num_sides: 8,
+                line_color: Color::Black,
+                tri_color: Color::Green,
+                cell_type: CellType::Line,
+                screen_space: true,
+                fill_and_outline: false,
+            }),
+            Descriptor::Outline(OutlineDescriptor {
+                points: vec![Vec2::zeros(), Vec2::new(2.0, 0.0), Vec2::new(1.0, 3.0)],
+                width: 0.25,
+                color: Color::Red,
+                join_style: JoinStyle::Bevel,
+                miter_limit: 2.0,
+            }),
+        ]
+    }
+
+    #[test]
+    fn json_descriptor_round_trip_test() {
+        for descriptor in &sample_descriptors() {
+            let json = serde_json::to_string(descriptor).unwrap();
+            let parsed: Descriptor = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+            let expected = Mesh::from_descriptor(descriptor);
+            let mesh = Mesh::from_json_descriptor(&json).unwrap();
+            assert_eq!(mesh.vertex_slice(), expected.vertex_slice());
+            assert_eq!(mesh.index_slice(), expected.index_slice());
+            assert_eq!(mesh.line_cell_indices(), expected.line_cell_indices());
+        }
+
+        // the flags added after the first release may be left out
+        let json = r#"{"type": "circle", "center": [0.0, 0.0], "radius": 1.0, "num_sides": 4,
+            "line_color": "Red", "tri_color": "Blue", "cell_type": "Triangle"}"#;
+        assert_eq!(Mesh::from_json_descriptor(json).unwrap().num_triangles(), 4);
+        assert!(matches!(Mesh::from_json_descriptor("{}"), Err(Error::Json(_))));
+
+        // well-formed JSON of an invalid shape is rejected as the RPC server would
+        let json = r#"{"type": "circle", "center": [0.0, 0.0], "radius": 0.0, "num_sides": 4,
+            "line_color": "Red", "tri_color": "Blue", "cell_type": "Triangle"}"#;
+        let res = Mesh::from_json_descriptor(json);
+        assert!(matches!(res, Err(Error::InvalidDescriptor(_))));
     }
 }
 //}}}
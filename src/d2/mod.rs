@@ -18,8 +18,12 @@ mod mesh;
 mod state;
 mod rpc;
 
-pub(crate) use state::State;
-pub(crate) use rpc::run_server;
+pub use state::{State, State2D};
+pub(crate) use rpc::{run_server, validate_descriptor};
 
-pub use mesh::{AxesDescriptor,LineDescriptor, SquareDescriptor, CircleDescriptor, Mesh, Mesh2D};
-pub use rpc::Client2D;
\ No newline at end of file
+pub use mesh::{
+    AxesDescriptor, LineDescriptor, SquareDescriptor, RectangleDescriptor, CircleDescriptor,
+    OutlineDescriptor, JoinStyle, Descriptor, Mesh, Mesh2D, Error as MeshError,
+};
+pub use camera::{CameraDescriptor, snap_to_grid};
+pub use rpc::{Client2D, Error as ClientError, RPC_SCHEMA};
\ No newline at end of file
@@ -5,9 +5,17 @@
 
 
 //{{{ crate imports 
-use crate::common::{Vec2, Color, CellType, Validated};
+use crate::common::{
+    first_degenerate, first_non_finite, model_from_values, model_to_values, Vec2, Color,
+    FrontFace, Mat4, MeshColorMode, MeshOptions, MeshDiagnostics, SceneBudget, SceneStats,
+    Validated, ViewSensitivity,
+};
 use super::d2rpc;
-use super::super::mesh::{AxesDescriptor, LineDescriptor, SquareDescriptor, CircleDescriptor, Mesh};
+use super::super::mesh::{
+    AxesDescriptor, LineDescriptor, SquareDescriptor, RectangleDescriptor, CircleDescriptor,
+    OutlineDescriptor, Descriptor, Mesh,
+};
+use super::super::camera::CameraDescriptor;
 //}}}
 //{{{ std imports 
 use std::marker::PhantomData;
@@ -40,7 +48,10 @@ impl From<d2rpc::Color> for Color
 {
     fn from(c: d2rpc::Color) -> Self
     {
-        Color::Other((c.r, c.g, c.b,))
+        match c.a {
+            Some(a) => Color::Rgba((c.r, c.g, c.b, a)),
+            None => Color::Other((c.r, c.g, c.b,)),
+        }
     }
 }
 //}}}
@@ -49,12 +60,155 @@ impl From<Color> for d2rpc::Color
 {
     fn from(c: Color) -> Self
     {
-        let rgp = c.to_rgb();
+        let rgba = c.to_rgba();
         Self {
-            r: rgp[0],
-            g: rgp[1],
-            b: rgp[2],
+            r: rgba[0],
+            g: rgba[1],
+            b: rgba[2],
+            a: matches!(c, Color::Rgba(_)).then_some(rgba[3]),
+        }
+    }
+}
+//}}}
+
+//{{{ fun: vec2_is_finite
+/// Returns `true` if the vector is absent or both of its components are finite.
+fn vec2_is_finite(v: &Option<d2rpc::Vec2>) -> bool
+{
+    v.as_ref().map_or(true, |v| v.x.is_finite() && v.y.is_finite())
+}
+//}}}
+//{{{ fun: color_is_finite
+/// Returns `true` if the color is absent or all of its channels are finite.
+fn color_is_finite(c: &Option<d2rpc::Color>) -> bool
+{
+    c.as_ref().map_or(true, |c| {
+        c.r.is_finite() && c.g.is_finite() && c.b.is_finite() && c.a.map_or(true, f32::is_finite)
+    })
+}
+//}}}
+
+//{{{ fun: color_mode_from_rpc
+/// Returns the color mode of a mesh sent with the given uniform color, drawn with the colors of
+/// its vertices when there is none.
+pub fn color_mode_from_rpc(uniform_color: Option<d2rpc::Color>) -> MeshColorMode
+{
+    uniform_color.map_or(MeshColorMode::PerVertex, |c| MeshColorMode::Uniform(c.into()))
+}
+//}}}
+//{{{ fun: color_mode_to_rpc
+/// Returns the uniform color a mesh with the given color mode is sent with, if any.
+pub fn color_mode_to_rpc(mode: MeshColorMode) -> Option<d2rpc::Color>
+{
+    match mode
+    {
+        MeshColorMode::PerVertex => None,
+        MeshColorMode::Uniform(color) => Some(color.into()),
+    }
+}
+//}}}
+//{{{ fun: options_are_finite
+/// Returns `true` if the mesh options are absent or all of their values are finite.
+fn options_are_finite(o: &Option<d2rpc::MeshOptions>) -> bool
+{
+    o.as_ref().map_or(true, |o| {
+        color_is_finite(&o.edge_color) && o.dash_length.is_finite() && o.gap_length.is_finite()
+    })
+}
+//}}}
+
+//{{{ fun: to_vec2
+fn to_vec2(v: &Option<d2rpc::Vec2>) -> Option<Vec2>
+{
+    v.clone().map(Vec2::from)
+}
+//}}}
+//{{{ fun: is_zero
+/// Returns `true` if the vector is too short to define a direction.
+fn is_zero(v: &Vec2) -> bool
+{
+    v.norm() <= f32::EPSILON
+}
+//}}}
+//{{{ fun: are_parallel
+/// Returns `true` if the two vectors are parallel, or either is zero.
+fn are_parallel(a: &Vec2, b: &Vec2) -> bool
+{
+    a.perp(b).abs() <= f32::EPSILON * a.norm() * b.norm()
+}
+//}}}
+
+//{{{ fun: validate_descriptor
+/// Validates a shape descriptor with the same checks the RPC server applies to the matching add
+/// request, so that shapes from other sources, such as scene files, are held to the same rules.
+pub(crate) fn validate_descriptor(descriptor: &Descriptor) -> Result<(), String>
+{
+    let client_name = String::new();
+    match descriptor.clone()
+    {
+        Descriptor::Axes(axes) => d2rpc::AddAxesRequest {
+            client_name,
+            name: String::new(),
+            axes_descriptor: Some(axes.into()),
+        }
+        .validate(),
+        Descriptor::Line(line) => d2rpc::AddLineRequest {
+            client_name,
+            name: String::new(),
+            line_descriptor: Some(line.into()),
         }
+        .validate(),
+        Descriptor::Square(square) => d2rpc::AddSquareRequest {
+            client_name,
+            name: String::new(),
+            square_descriptor: Some(square.into()),
+        }
+        .validate(),
+        Descriptor::Rectangle(rectangle) => d2rpc::AddRectangleRequest {
+            client_name,
+            name: String::new(),
+            rectangle_descriptor: Some(rectangle.into()),
+        }
+        .validate(),
+        Descriptor::Circle(circle) => d2rpc::AddCircleRequest {
+            client_name,
+            name: String::new(),
+            circle_descriptor: Some(circle.into()),
+        }
+        .validate(),
+        // outlines have no add request of their own, so are checked directly
+        Descriptor::Outline(outline) => outline.validate(),
+    }
+}
+//}}}
+
+//{{{ impl: Validated for OutlineDescriptor
+impl Validated for OutlineDescriptor
+{
+    fn is_valid(&self) -> bool
+    {
+        self.width > 0.0
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let points_finite = self.points.iter().all(|p| p.x.is_finite() && p.y.is_finite());
+        let color_finite = self.color.to_rgba().iter().all(|x| x.is_finite());
+        first_non_finite(&[
+            ("points", points_finite),
+            ("width", self.width.is_finite()),
+            ("color", color_finite),
+            ("miter_limit", self.miter_limit.is_finite()),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str>
+    {
+        let points = &self.points;
+        first_degenerate(&[(
+            points.iter().all(|p| *p == points[0]),
+            "outline has fewer than two distinct points",
+        )])
     }
 }
 //}}}
@@ -81,6 +235,28 @@ impl Validated for d2rpc::AddAxesRequest
         }
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let ad = self.axes_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec2_is_finite(&ad.origin)),
+            ("x_axis", vec2_is_finite(&ad.x_axis)),
+            ("y_axis", vec2_is_finite(&ad.y_axis)),
+            ("pos_len", ad.pos_len.is_finite()),
+            ("neg_len", ad.neg_len.is_finite()),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str>
+    {
+        let ad = self.axes_descriptor.as_ref()?;
+        let (x_axis, y_axis) = (to_vec2(&ad.x_axis)?, to_vec2(&ad.y_axis)?);
+        first_degenerate(&[
+            (is_zero(&x_axis), "axes x_axis has zero length"),
+            (is_zero(&y_axis), "axes y_axis has zero length"),
+        ])
+    }
 }
 //}}}
 //{{{ impl: From<d2rpc::AxesDescriptor> for AxesDescriptor
@@ -128,6 +304,7 @@ impl Validated for d2rpc::AddLineRequest
             {
                 is_val &= line_descriptor.v1.is_some();
                 is_val &= line_descriptor.v2.is_some();
+                is_val &= line_descriptor.color.is_some();
             }
             None =>
             {
@@ -136,6 +313,31 @@ impl Validated for d2rpc::AddLineRequest
         }
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let ld = self.line_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("v1", vec2_is_finite(&ld.v1)),
+            ("v2", vec2_is_finite(&ld.v2)),
+            ("color", color_is_finite(&ld.color)),
+            ("dash_length", ld.dash_length.is_finite()),
+            ("gap_length", ld.gap_length.is_finite()),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str>
+    {
+        let ld = self.line_descriptor.as_ref()?;
+        let (v1, v2) = (to_vec2(&ld.v1)?, to_vec2(&ld.v2)?);
+        first_degenerate(&[
+            (is_zero(&(v2 - v1)), "line endpoints v1 and v2 coincide"),
+            (
+                ld.dash_length < 0.0 || ld.gap_length < 0.0,
+                "line dash_length and gap_length must not be negative",
+            ),
+        ])
+    }
 }
 //}}}
 //{{{  impl: From<d2rpc::LineDescriptor> for LineDescriptor
@@ -147,6 +349,8 @@ impl From<d2rpc::LineDescriptor> for LineDescriptor
             v1: line_desc.v1.unwrap().into(),
             v2: line_desc.v2.unwrap().into(),
             color: line_desc.color.unwrap().into(),
+            dash_pattern: (line_desc.dash_length > 0.0)
+                .then_some((line_desc.dash_length, line_desc.gap_length)),
         };
         line_desc_out
     }
@@ -157,10 +361,13 @@ impl From<LineDescriptor> for d2rpc::LineDescriptor
 {
     fn from(line_desc: LineDescriptor) -> Self
     {
+        let (dash_length, gap_length) = line_desc.dash_pattern.unwrap_or((0.0, 0.0));
         let line_desc_out = d2rpc::LineDescriptor {
             v1: Some(line_desc.v1.into()),
             v2: Some(line_desc.v2.into()),
             color: Some(line_desc.color.into()),
+            dash_length: dash_length,
+            gap_length: gap_length,
         };
         line_desc_out
     }
@@ -189,6 +396,31 @@ impl Validated for d2rpc::AddSquareRequest
         }
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let sd = self.square_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec2_is_finite(&sd.origin)),
+            ("x_axis", vec2_is_finite(&sd.x_axis)),
+            ("y_axis", vec2_is_finite(&sd.y_axis)),
+            ("lenx", sd.lenx.is_finite()),
+            ("leny", sd.leny.is_finite()),
+            ("line_color", color_is_finite(&sd.line_color)),
+            ("tri_color", color_is_finite(&sd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str>
+    {
+        let sd = self.square_descriptor.as_ref()?;
+        let (x_axis, y_axis) = (to_vec2(&sd.x_axis)?, to_vec2(&sd.y_axis)?);
+        first_degenerate(&[
+            (is_zero(&x_axis), "square x_axis has zero length"),
+            (is_zero(&y_axis), "square y_axis has zero length"),
+            (are_parallel(&x_axis, &y_axis), "square x_axis and y_axis are parallel"),
+        ])
+    }
 }
 //}}}
 //{{{ impl: From<d2rpc::SquareDescriptor> for SquareDescriptor
@@ -204,7 +436,9 @@ impl From<d2rpc::SquareDescriptor> for SquareDescriptor
             leny: square_desc.leny,
             line_color: square_desc.line_color.unwrap().into(),
             tri_color: square_desc.tri_color.unwrap().into(),
-            cell_type: (square_desc.cell_type as i32).into()
+            cell_type: (square_desc.cell_type as i32).into(),
+            screen_space: square_desc.screen_space,
+            fill_and_outline: square_desc.fill_and_outline,
         };
         square_desc_out
     }
@@ -223,13 +457,76 @@ impl From<SquareDescriptor> for d2rpc::SquareDescriptor
             leny: square_desc.leny,
             line_color: Some(square_desc.line_color.into()),
             tri_color: Some(square_desc.tri_color.into()),
-            cell_type: (square_desc.cell_type as i32).into()
+            cell_type: (square_desc.cell_type as i32).into(),
+            screen_space: square_desc.screen_space,
+            fill_and_outline: square_desc.fill_and_outline,
         };
         square_desc_out
     }
 }
 //}}}
 
+//{{{ impl: Validated for d2rpc::AddRectangleRequest
+impl Validated for d2rpc::AddRectangleRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        match self.rectangle_descriptor
+        {
+            Some(ref rd) => rd.center.is_some() && rd.width > 0.0 && rd.height > 0.0,
+            None => false,
+        }
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let rd = self.rectangle_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("center", vec2_is_finite(&rd.center)),
+            ("width", rd.width.is_finite()),
+            ("height", rd.height.is_finite()),
+            ("rotation", rd.rotation.is_finite()),
+            ("line_color", color_is_finite(&rd.line_color)),
+            ("tri_color", color_is_finite(&rd.tri_color)),
+        ])
+    }
+}
+//}}}
+//{{{ impl: From<d2rpc::RectangleDescriptor> for RectangleDescriptor
+impl From<d2rpc::RectangleDescriptor> for RectangleDescriptor
+{
+    fn from(rectangle_desc: d2rpc::RectangleDescriptor) -> Self
+    {
+        RectangleDescriptor {
+            center: rectangle_desc.center.unwrap().into(),
+            width: rectangle_desc.width,
+            height: rectangle_desc.height,
+            rotation: rectangle_desc.rotation,
+            line_color: rectangle_desc.line_color.unwrap().into(),
+            tri_color: rectangle_desc.tri_color.unwrap().into(),
+            cell_type: rectangle_desc.cell_type.into(),
+        }
+    }
+}
+//}}}
+//{{{ impl: From<RectangleDescriptor> for d2rpc::RectangleDescriptor
+impl From<RectangleDescriptor> for d2rpc::RectangleDescriptor
+{
+    fn from(rectangle_desc: RectangleDescriptor) -> Self
+    {
+        d2rpc::RectangleDescriptor {
+            center: Some(rectangle_desc.center.into()),
+            width: rectangle_desc.width,
+            height: rectangle_desc.height,
+            rotation: rectangle_desc.rotation,
+            line_color: Some(rectangle_desc.line_color.into()),
+            tri_color: Some(rectangle_desc.tri_color.into()),
+            cell_type: rectangle_desc.cell_type.into(),
+        }
+    }
+}
+//}}}
+
 //{{{ impl: Validated for d2rpc::AddCircleRequest
 impl Validated for d2rpc::AddCircleRequest
 {
@@ -241,7 +538,6 @@ impl Validated for d2rpc::AddCircleRequest
             {
                 is_val &= circle_descriptor.center.is_some();
                 is_val &= circle_descriptor.radius > 0.0;
-                is_val &= circle_descriptor.num_sides > 0;
             }
             None =>
             {
@@ -250,6 +546,17 @@ impl Validated for d2rpc::AddCircleRequest
         }
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let cd = self.circle_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("center", vec2_is_finite(&cd.center)),
+            ("radius", cd.radius.is_finite()),
+            ("line_color", color_is_finite(&cd.line_color)),
+            ("tri_color", color_is_finite(&cd.tri_color)),
+        ])
+    }
 }
 
 //}}}
@@ -264,7 +571,9 @@ impl From<d2rpc::CircleDescriptor> for CircleDescriptor
             num_sides: circle_desc.num_sides,
             line_color: circle_desc.line_color.unwrap().into(),
             tri_color: circle_desc.tri_color.unwrap().into(),
-            cell_type: (circle_desc.cell_type as i32).into()
+            cell_type: (circle_desc.cell_type as i32).into(),
+            screen_space: circle_desc.screen_space,
+            fill_and_outline: circle_desc.fill_and_outline,
         };
         circle_desc_out
     }
@@ -281,7 +590,9 @@ impl From<CircleDescriptor> for d2rpc::CircleDescriptor
             num_sides: circle_desc.num_sides,
             line_color: Some(circle_desc.line_color.into()),
             tri_color: Some(circle_desc.tri_color.into()),
-            cell_type: (circle_desc.cell_type as i32).into()
+            cell_type: (circle_desc.cell_type as i32).into(),
+            screen_space: circle_desc.screen_space,
+            fill_and_outline: circle_desc.fill_and_outline,
         };
         circle_desc_out
     }
@@ -300,6 +611,41 @@ impl Validated for d2rpc::AddMeshRequest
             {
                 is_val &= mesh_descriptor.vertices.len() > 0;
                 is_val &= mesh_descriptor.indices.len() > 0;
+                is_val &= model_from_values(&mesh_descriptor.model).is_some();
+            }
+            None =>
+            {
+                is_val = false;
+            }
+        }
+        is_val
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::AppendToMeshRequest
+impl Validated for d2rpc::AppendToMeshRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        let mut is_val = true;
+        match self.mesh_descriptor
+        {
+            Some(ref mesh_descriptor) =>
+            {
+                is_val &= mesh_descriptor.vertices.len() > 0;
+                is_val &= mesh_descriptor.indices.len() > 0;
+                is_val &= model_from_values(&mesh_descriptor.model).is_some();
             }
             None =>
             {
@@ -308,6 +654,163 @@ impl Validated for d2rpc::AddMeshRequest
         }
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::ValidateMeshRequest
+impl Validated for d2rpc::ValidateMeshRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        self.mesh_descriptor.is_some()
+    }
+
+    /// Non-finite vertices are what the request reports on, so they are let through.
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        None
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::SetMeshModelRequest
+impl Validated for d2rpc::SetMeshModelRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        model_from_values(&self.model).is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        first_non_finite(&[("model", self.model.iter().all(|x| x.is_finite()))])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::SetMeshTagRequest
+impl Validated for d2rpc::SetMeshTagRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        true
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        None
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::TransformGroupRequest
+impl Validated for d2rpc::TransformGroupRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        !self.tag.is_empty() && model_from_values(&self.transform).is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        first_non_finite(&[("transform", self.transform.iter().all(|x| x.is_finite()))])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::SetMeshColorModeRequest
+impl Validated for d2rpc::SetMeshColorModeRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        true
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        first_non_finite(&[("uniform_color", color_is_finite(&self.uniform_color))])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::SetMeshOptionsRequest
+impl Validated for d2rpc::SetMeshOptionsRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        self.options.is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        first_non_finite(&[("options", options_are_finite(&self.options))])
+    }
+}
+//}}}
+//{{{ impl: Validated for d2rpc::ReplaceMeshRequest
+impl Validated for d2rpc::ReplaceMeshRequest
+{
+    fn is_valid(&self) -> bool
+    {
+        let mut is_val = true;
+        match self.mesh_descriptor
+        {
+            Some(ref mesh_descriptor) =>
+            {
+                is_val &= mesh_descriptor.vertices.len() > 0;
+                is_val &= mesh_descriptor.indices.len() > 0;
+                is_val &= model_from_values(&mesh_descriptor.model).is_some();
+            }
+            None =>
+            {
+                is_val = false;
+            }
+        }
+        is_val
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl: From<d2rpc::MeshOptions> for MeshOptions
+impl From<d2rpc::MeshOptions> for MeshOptions
+{
+    fn from(options: d2rpc::MeshOptions) -> Self
+    {
+        MeshOptions {
+            edge_color: options.edge_color.map(Color::from),
+            dash_pattern: (options.dash_length > 0.0)
+                .then_some((options.dash_length, options.gap_length)),
+        }
+    }
+}
+//}}}
+//{{{ impl: From<MeshOptions> for d2rpc::MeshOptions
+impl From<MeshOptions> for d2rpc::MeshOptions
+{
+    fn from(options: MeshOptions) -> Self
+    {
+        let (dash_length, gap_length) = options.dash_pattern.unwrap_or((0.0, 0.0));
+        d2rpc::MeshOptions {
+            edge_color: options.edge_color.map(d2rpc::Color::from),
+            dash_length: dash_length,
+            gap_length: gap_length,
+        }
+    }
 }
 //}}}
 //{{{ impl From<d2rpc::MeshDescriptor> for Mesh
@@ -319,6 +822,16 @@ impl<'a> From<d2rpc::MeshDescriptor> for Mesh<'a>
             vertices: mesh_desc.vertices,
             indices: mesh_desc.indices,
             cell_type: (mesh_desc.cell_type as i32).into(),
+            line_indices: mesh_desc.line_indices,
+            overlay: mesh_desc.overlay,
+            line_depth_bias: mesh_desc.line_depth_bias,
+            name: None,
+            tag: None,
+            color_mode: color_mode_from_rpc(mesh_desc.uniform_color),
+            options: mesh_desc.options.map(MeshOptions::from).unwrap_or_default(),
+            model: model_from_values(&mesh_desc.model).unwrap_or_else(Mat4::identity),
+            layer: mesh_desc.layer,
+            front_face: if mesh_desc.clockwise { FrontFace::Cw } else { FrontFace::Ccw },
             uid: 0,
             phant: PhantomData,
         };
@@ -335,16 +848,410 @@ impl<'a> From<Mesh<'a>> for d2rpc::MeshDescriptor
             vertices: mesh.vertices,
             indices: mesh.indices,
             cell_type: (mesh.cell_type as i32).into(),
+            line_indices: mesh.line_indices,
+            overlay: mesh.overlay,
+            line_depth_bias: mesh.line_depth_bias,
+            layer: mesh.layer,
+            model: model_to_values(&mesh.model),
+            uniform_color: color_mode_to_rpc(mesh.color_mode),
+            options: Some(mesh.options.into()),
+            clockwise: mesh.front_face == FrontFace::Cw,
         };
         mesh_desc
     }
 }
 //}}}
+
+//{{{ impl: From<CameraDescriptor> for d2rpc::GetCameraResponse
+impl From<CameraDescriptor> for d2rpc::GetCameraResponse
+{
+    fn from(camera: CameraDescriptor) -> Self
+    {
+        d2rpc::GetCameraResponse {
+            position: Some(camera.position.into()),
+            rotation: camera.rotation,
+            zoom: camera.zoom,
+        }
+    }
+}
+//}}}
+//{{{ impl: From<d2rpc::GetCameraResponse> for CameraDescriptor
+impl From<d2rpc::GetCameraResponse> for CameraDescriptor
+{
+    fn from(camera: d2rpc::GetCameraResponse) -> Self
+    {
+        CameraDescriptor {
+            position: camera.position.unwrap_or_default().into(),
+            rotation: camera.rotation,
+            zoom: camera.zoom,
+        }
+    }
+}
+//}}}
+//{{{ impl: From<SceneStats> for d2rpc::GetSceneStatsResponse
+/// An unlimited quantity is sent as a maximum of zero.
+impl From<SceneStats> for d2rpc::GetSceneStatsResponse
+{
+    fn from(stats: SceneStats) -> Self
+    {
+        d2rpc::GetSceneStatsResponse {
+            num_meshes: stats.num_meshes as u64,
+            num_vertices: stats.num_vertices as u64,
+            max_meshes: stats.budget.max_meshes.unwrap_or(0) as u64,
+            max_vertices: stats.budget.max_vertices.unwrap_or(0) as u64,
+        }
+    }
+}
+//}}}
+//{{{ impl: From<d2rpc::GetSceneStatsResponse> for SceneStats
+impl From<d2rpc::GetSceneStatsResponse> for SceneStats
+{
+    fn from(stats: d2rpc::GetSceneStatsResponse) -> Self
+    {
+        SceneStats {
+            num_meshes: stats.num_meshes as usize,
+            num_vertices: stats.num_vertices as usize,
+            budget: SceneBudget {
+                max_meshes: (stats.max_meshes > 0).then_some(stats.max_meshes as usize),
+                max_vertices: (stats.max_vertices > 0).then_some(stats.max_vertices as usize),
+            },
+        }
+    }
+}
+//}}}
+//{{{ impl: From<MeshDiagnostics> for d2rpc::MeshStatsResponse
+/// The bounds are sent without their z-component, which is zero for 2D meshes.
+impl From<MeshDiagnostics> for d2rpc::MeshStatsResponse
+{
+    fn from(diagnostics: MeshDiagnostics) -> Self
+    {
+        d2rpc::MeshStatsResponse {
+            num_vertices: diagnostics.num_vertices as u64,
+            num_triangles: diagnostics.num_triangles as u64,
+            min: diagnostics.bounds.map(|(min, _)| min.xy().into()),
+            max: diagnostics.bounds.map(|(_, max)| max.xy().into()),
+            is_manifold: diagnostics.is_manifold,
+            has_degenerate: diagnostics.has_degenerate,
+            non_finite_count: diagnostics.non_finite_count as u64,
+        }
+    }
+}
+//}}}
+//{{{ impl: From<d2rpc::MeshStatsResponse> for MeshDiagnostics
+impl From<d2rpc::MeshStatsResponse> for MeshDiagnostics
+{
+    fn from(stats: d2rpc::MeshStatsResponse) -> Self
+    {
+        let to_vec3 = |v: d2rpc::Vec2| Vec2::from(v).push(0.0);
+        MeshDiagnostics {
+            num_vertices: stats.num_vertices as usize,
+            num_triangles: stats.num_triangles as usize,
+            bounds: stats.min.zip(stats.max).map(|(min, max)| (to_vec3(min), to_vec3(max))),
+            is_manifold: stats.is_manifold,
+            has_degenerate: stats.has_degenerate,
+            non_finite_count: stats.non_finite_count as usize,
+        }
+    }
+}
+//}}}
+//{{{ impl From<d2rpc::SetViewSensitivityRequest> for ViewSensitivity
+impl From<d2rpc::SetViewSensitivityRequest> for ViewSensitivity
+{
+    fn from(msg: d2rpc::SetViewSensitivityRequest) -> Self
+    {
+        ViewSensitivity {
+            key_pan_delta: msg.key_pan_delta,
+            key_rotate_delta: msg.key_rotate_delta,
+            zoom_speed: msg.zoom_speed,
+        }
+    }
+}
+//}}}
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
 mod tests
 {
-  
+    use super::*;
+    use crate::common::{CellType, Vec3};
+    use crate::d2::mesh::{tests::sample_descriptors, JoinStyle};
+    use crate::d2::Mesh2D;
+
+    #[test]
+    fn non_finite_validation_test()
+    {
+        let valid = d2rpc::AddCircleRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            circle_descriptor: Some(d2rpc::CircleDescriptor {
+                center: Some(Vec2::new(0.0, 0.0).into()),
+                radius: 1.0,
+                num_sides: 8,
+                line_color: Some(Color::Red.into()),
+                tri_color: Some(Color::Green.into()),
+                cell_type: 2,
+                screen_space: false,
+                fill_and_outline: false,
+            }),
+        };
+        assert!(valid.validate().is_ok());
+
+        let mut nan_center = valid.clone();
+        nan_center.circle_descriptor.as_mut().unwrap().center = Some(Vec2::new(f32::NAN, 0.0).into());
+        assert_eq!(nan_center.non_finite_field(), Some("center"));
+        assert!(nan_center.validate().unwrap_err().contains("center"));
+
+        let mut inf_color = valid.clone();
+        inf_color.circle_descriptor.as_mut().unwrap().tri_color =
+            Some(d2rpc::Color { r: 0.0, g: f32::INFINITY, b: 0.0, a: None });
+        assert_eq!(inf_color.non_finite_field(), Some("tri_color"));
+
+        let nan_mesh = d2rpc::AddMeshRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            mesh_descriptor: Some(d2rpc::MeshDescriptor {
+                vertices: vec![0.0, f32::NEG_INFINITY],
+                indices: vec![0],
+                cell_type: 1,
+                line_indices: vec![],
+                overlay: false,
+                line_depth_bias: 0,
+                layer: 0,
+                model: vec![],
+                uniform_color: None,
+                options: None,
+                clockwise: false,
+            }),
+        };
+        assert!(nan_mesh.validate().unwrap_err().contains("vertices"));
+
+        // the model matrix has 16 finite entries, or none for the identity
+        let mut bad_model = nan_mesh.clone();
+        let md = bad_model.mesh_descriptor.as_mut().unwrap();
+        md.vertices = vec![0.0, 0.0];
+        md.model = vec![1.0; 15];
+        assert!(bad_model.validate().is_err());
+        bad_model.mesh_descriptor.as_mut().unwrap().model = vec![f32::NAN; 16];
+        assert!(bad_model.validate().unwrap_err().contains("model"));
+        bad_model.mesh_descriptor.as_mut().unwrap().model = vec![1.0; 16];
+        assert!(bad_model.validate().is_ok());
+        let nan_color = Color::Other((f32::NAN, 0.0, 0.0));
+        bad_model.mesh_descriptor.as_mut().unwrap().uniform_color = Some(nan_color.into());
+        assert!(bad_model.validate().unwrap_err().contains("uniform_color"));
+        let md = bad_model.mesh_descriptor.as_mut().unwrap();
+        md.uniform_color = None;
+        md.options = Some(d2rpc::MeshOptions { dash_length: f32::NAN, ..Default::default() });
+        assert!(bad_model.validate().unwrap_err().contains("options"));
+    }
+
+    #[test]
+    fn line_validation_test()
+    {
+        let line = d2rpc::AddLineRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            line_descriptor: Some(LineDescriptor {
+                v1: Vec2::new(0.0, 0.0),
+                v2: Vec2::new(1.0, 0.0),
+                color: Color::Red,
+                dash_pattern: None,
+            }.into()),
+        };
+        assert!(line.validate().is_ok());
+
+        // the conversion unwraps every field, so a line without a color must not get through
+        let mut no_color = line.clone();
+        no_color.line_descriptor.as_mut().unwrap().color = None;
+        assert!(!no_color.is_valid());
+        assert!(no_color.validate().is_err());
+
+        // a zero dash length leaves the line solid, a negative one is rejected
+        let mut dashed = line.clone();
+        dashed.line_descriptor.as_mut().unwrap().dash_length = 0.2;
+        dashed.line_descriptor.as_mut().unwrap().gap_length = 0.1;
+        let round_trip: LineDescriptor = dashed.line_descriptor.clone().unwrap().into();
+        assert_eq!(round_trip.dash_pattern, Some((0.2, 0.1)));
+        dashed.line_descriptor.as_mut().unwrap().dash_length = -0.2;
+        assert!(dashed.validate().is_err());
+
+        let round_trip: LineDescriptor = line.line_descriptor.unwrap().into();
+        assert_eq!(round_trip.v2, Vec2::new(1.0, 0.0));
+        assert_eq!(round_trip.dash_pattern, None);
+    }
+
+    #[test]
+    fn rectangle_validation_test()
+    {
+        let rectangle = |width: f32, rotation: f32| d2rpc::AddRectangleRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            rectangle_descriptor: Some(RectangleDescriptor {
+                center: Vec2::new(1.0, 1.0),
+                width,
+                height: 2.0,
+                rotation,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Line,
+            }.into()),
+        };
+        assert!(rectangle(1.0, 0.3).validate().is_ok());
+        assert!(rectangle(0.0, 0.3).validate().is_err());
+        assert!(rectangle(-1.0, 0.3).validate().is_err());
+        assert_eq!(rectangle(1.0, f32::NAN).non_finite_field(), Some("rotation"));
+
+        let round_trip: RectangleDescriptor =
+            rectangle(1.0, 0.3).rectangle_descriptor.unwrap().into();
+        assert_eq!(round_trip.rotation, 0.3);
+        assert_eq!(round_trip.cell_type, CellType::Line);
+    }
+
+    #[test]
+    fn degenerate_validation_test()
+    {
+        let line = d2rpc::AddLineRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            line_descriptor: Some(LineDescriptor {
+                v1: Vec2::new(0.5, 0.5),
+                v2: Vec2::new(0.5, 0.5),
+                color: Color::Red,
+                dash_pattern: None,
+            }.into()),
+        };
+        assert_eq!(line.validate(), Err("line endpoints v1 and v2 coincide".to_string()));
+
+        let square = |x_axis: Vec2, y_axis: Vec2| d2rpc::AddSquareRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            square_descriptor: Some(SquareDescriptor {
+                origin: Vec2::zeros(),
+                x_axis,
+                y_axis,
+                lenx: 1.0,
+                leny: 1.0,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                screen_space: false,
+                fill_and_outline: false,
+            }.into()),
+        };
+        assert!(square(Vec2::x(), Vec2::y()).validate().is_ok());
+        assert_eq!(square(Vec2::zeros(), Vec2::y()).degenerate_reason(), Some("square x_axis has zero length"));
+        assert_eq!(square(Vec2::x(), Vec2::zeros()).degenerate_reason(), Some("square y_axis has zero length"));
+        assert_eq!(
+            square(Vec2::x(), Vec2::new(-3.0, 0.0)).degenerate_reason(),
+            Some("square x_axis and y_axis are parallel")
+        );
+
+        let axes = d2rpc::AddAxesRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            axes_descriptor: Some(AxesDescriptor {
+                origin: Vec2::zeros(),
+                x_axis: Vec2::zeros(),
+                y_axis: Vec2::y(),
+                neg_len: 1.0,
+                pos_len: 1.0,
+            }.into()),
+        };
+        assert_eq!(axes.validate(), Err("axes x_axis has zero length".to_string()));
+    }
+
+    #[test]
+    fn outline_validation_test()
+    {
+        let outline = |width: f32, miter_limit: f32| {
+            Descriptor::Outline(OutlineDescriptor {
+                points: vec![Vec2::zeros(), Vec2::x(), Vec2::y()],
+                width,
+                color: Color::Red,
+                join_style: JoinStyle::Miter,
+                miter_limit,
+            })
+        };
+        assert!(validate_descriptor(&outline(0.1, 4.0)).is_ok());
+        assert_eq!(
+            validate_descriptor(&outline(f32::NAN, 4.0)),
+            Err("non-finite value in width".to_string())
+        );
+        assert_eq!(
+            validate_descriptor(&outline(0.1, f32::INFINITY)),
+            Err("non-finite value in miter_limit".to_string())
+        );
+        for width in [0.0, -0.1] {
+            assert_eq!(
+                validate_descriptor(&outline(width, 4.0)),
+                Err("missing or out of range field".to_string())
+            );
+        }
+
+        let mut point = outline(0.1, 4.0);
+        if let Descriptor::Outline(ref mut outline) = point {
+            outline.points = vec![Vec2::x(); 3];
+        }
+        assert_eq!(
+            validate_descriptor(&point),
+            Err("outline has fewer than two distinct points".to_string())
+        );
+    }
+
+    #[test]
+    fn mesh_descriptor_round_trip_test()
+    {
+        // the name of a mesh stays behind
+        let check = |mesh: Mesh| {
+            let expected = mesh.clone();
+            let mesh_descriptor: d2rpc::MeshDescriptor = mesh.into();
+            let mesh: Mesh = mesh_descriptor.into();
+            assert_eq!(mesh.vertex_slice(), expected.vertex_slice());
+            assert_eq!(mesh.index_slice(), expected.index_slice());
+            assert_eq!(mesh.line_cell_indices(), expected.line_cell_indices());
+            assert_eq!(mesh.cell_type, expected.cell_type);
+            assert_eq!(mesh.is_overlay(), expected.is_overlay());
+            assert_eq!(mesh.line_depth_bias(), expected.line_depth_bias());
+            assert_eq!(mesh.layer(), expected.layer());
+            assert_eq!(mesh.model(), expected.model());
+            assert_eq!(mesh.color_mode(), expected.color_mode());
+            assert_eq!(mesh.options(), expected.options());
+            assert_eq!(mesh.front_face(), expected.front_face());
+        };
+        for descriptor in sample_descriptors() {
+            check(Mesh::from_descriptor(&descriptor));
+        }
+        let mut overlay = Mesh::from_descriptor(&sample_descriptors()[2]);
+        overlay.set_overlay(true);
+        overlay.set_line_depth_bias(-2);
+        overlay.set_layer(4);
+        overlay.set_model(Mat4::new_translation(&Vec3::new(1.0, 2.0, 0.0)));
+        overlay.set_color_mode(MeshColorMode::Uniform(Color::Other((0.25, 0.5, 0.75))));
+        overlay.set_options(MeshOptions {
+            edge_color: Some(Color::Other((0.0, 0.0, 0.0))),
+            dash_pattern: Some((0.1, 0.05)),
+        });
+        overlay.set_front_face(FrontFace::Cw);
+        check(overlay);
+    }
+
+    #[test]
+    fn mesh_stats_test()
+    {
+        let diagnostics = MeshDiagnostics {
+            num_vertices: 4,
+            num_triangles: 2,
+            bounds: Some((Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 0.0))),
+            is_manifold: true,
+            has_degenerate: false,
+            non_finite_count: 1,
+        };
+        let response: d2rpc::MeshStatsResponse = diagnostics.into();
+        assert_eq!(response.max, Some(Vec2::new(1.0, 2.0).into()));
+        assert_eq!(MeshDiagnostics::from(response), diagnostics);
+
+        // a mesh without finite vertices has no bounds
+        let empty = MeshDiagnostics::default();
+        assert_eq!(MeshDiagnostics::from(d2rpc::MeshStatsResponse::from(empty)), empty);
+    }
 }
 //}}}
\ No newline at end of file
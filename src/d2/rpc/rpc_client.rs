@@ -4,17 +4,27 @@
 //--------------------------------------------------------------------------------------------------
 
 //{{{ crate imports
-use super::super::mesh::{AxesDescriptor, LineDescriptor, CircleDescriptor, Mesh, SquareDescriptor};
+use super::super::mesh::{
+    AxesDescriptor, LineDescriptor, CircleDescriptor, Mesh, RectangleDescriptor, SquareDescriptor,
+};
+use super::super::camera::CameraDescriptor;
+use super::super::state::{simple_circle, simple_rectangle};
+use crate::common::{
+    model_to_values, Color, DrawContext, Mat4, MeshColorMode, MeshDiagnostics, MeshOptions,
+    SceneStats, TessellationQuality, Vec2, ViewSensitivity,
+};
+use super::common::color_mode_to_rpc;
 use super::d2rpc;
 use super::d2rpc::state_service_client::StateServiceClient;
+use crate::connection::{Connection, Resend};
 use std::result::Result;
 //}}}
 //{{{ std imports
+use std::future::Future;
+use std::time::{Duration, Instant};
 //}}}
 //{{{ dep imports
 use thiserror::Error;
-use tokio::runtime;
-use tokio::runtime::Runtime;
 use tonic::Request;
 use topohedral_tracing::*;
 //}}}
@@ -31,6 +41,9 @@ type RpcClient = StateServiceClient<tonic::transport::Channel>;
 /// - `IoError`: Represents an I/O error that occurred during the RPC call.
 /// - `TonicError`: Represents a transport-level error that occurred during the RPC call.
 /// - `StatusError`: Represents an error returned by the RPC server in the form of a Tonic status.
+/// - `Validation`: Represents a request the server rejected as invalid, such as a descriptor
+///    with a negative radius, along with the client method which sent it and the server's
+///    message.
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("IO error: {0}")]
@@ -39,22 +52,36 @@ pub enum Error {
     TonicError(#[from] tonic::transport::Error),
     #[error("Status error: {0}")]
     StatusError(#[from] tonic::Status),
+    #[error("Invalid {method} request: {message}")]
+    Validation { method: &'static str, message: String },
 }
 //..............................................................................
 //}}}
+//{{{ fun: status_error
+/// Returns the conversion of the status the server answered `method` with into an [`Error`],
+/// telling the requests it rejected as invalid apart from the other failures.
+fn status_error(method: &'static str) -> impl FnOnce(tonic::Status) -> Error {
+    move |status| match status.code() {
+        tonic::Code::InvalidArgument => Error::Validation {
+            method: method,
+            message: status.message().to_string(),
+        },
+        _ => Error::StatusError(status),
+    }
+}
+//}}}
 //{{{ struct: ClientD2
 /// The `Client2D` struct represents a client for the 2D viewer's RPC API.
 ///
 /// It contains the following fields:
 /// - `client_name`: A `String` representing the name of the client.
-/// - `stub`: An `RpcClient` instance, which is an alias for the `StateServiceClient` from
-///    the `tonic` crate, providing the client for the 2D viewer's state service RPC API.
-/// - `tokio_runtime`: A `Runtime` instance from the `tokio` crate, which is used to run the
-///    RPC calls asynchronously.
+/// - `connection`: The connection to the server, through an `RpcClient` stub, which is an alias
+///    for the `StateServiceClient` from the `tonic` crate.
+/// - `draw_context`: The colors and cell type the `add_*_simple` methods draw shapes with.
 pub struct Client2D {
     client_name: String,
-    stub: RpcClient,
-    tokio_runtime: Runtime,
+    connection: Connection<RpcClient>,
+    draw_context: DrawContext,
 }
 //..............................................................................
 //}}}
@@ -66,94 +93,576 @@ impl Client2D {
         info!("Starting 2D client");
         //}}}
         let client_name = "client2d";
-        let tokio_runtime = runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap();
-
-        let stub =
-            tokio_runtime.block_on(RpcClient::connect(format!("http://127.0.0.1:{}", port)))?;
+        let connection = Connection::new(port, RpcClient::new)?;
         Ok(Self {
             client_name: client_name.to_string(),
-            stub: stub,
-            tokio_runtime: tokio_runtime,
+            connection: connection,
+            draw_context: DrawContext::default(),
         })
     }
     //}}}
+    //{{{ fun: set_reconnect_attempts
+    /// Sets how many times a call whose connection to the server has broken, for instance
+    /// because the server was restarted, reconnects before failing. A call spends one attempt
+    /// per try at reconnecting, waiting a quarter of a second before each, and is sent again
+    /// once reconnected. Zero, the default, fails such calls straight away.
+    ///
+    /// Only calls which are safe to send twice are sent again: the calls adding meshes,
+    /// `append_to_mesh`, `open_window` and `kill_server` may have reached the server before the
+    /// connection broke, so they fail on a broken connection whatever the attempts.
+    pub fn set_reconnect_attempts(&mut self, attempts: u32) {
+        self.connection.set_reconnect_attempts(attempts);
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: call
+    /// Sends `message`, which is safe to send twice, to the server through `send`, sending it
+    /// again if the connection breaks, see [`Self::set_reconnect_attempts`].
+    fn call<M, R, F, Fut>(
+        &mut self,
+        method: &'static str,
+        message: M,
+        send: F,
+    ) -> Result<tonic::Response<R>, Error>
+    where
+        M: Clone + Default,
+        F: Fn(RpcClient, Request<M>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        self.connection
+            .call(method, message, Resend::Idempotent, send)
+            .map_err(status_error(method))
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: call_once
+    /// Sends `message` to the server through `send`, never sending it twice, for the calls
+    /// which change the scene each time the server receives them.
+    fn call_once<M, R, F, Fut>(
+        &mut self,
+        method: &'static str,
+        message: M,
+        send: F,
+    ) -> Result<tonic::Response<R>, Error>
+    where
+        M: Clone + Default,
+        F: Fn(RpcClient, Request<M>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        self.connection
+            .call(method, message, Resend::Never, send)
+            .map_err(status_error(method))
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: add_axes
     pub fn add_axes(&mut self, axes_desc: AxesDescriptor) -> Result<usize, Error> {
         let axes_desc_rpc: d2rpc::AxesDescriptor = axes_desc.into();
-        let request = Request::new(d2rpc::AddAxesRequest {
+        let request = d2rpc::AddAxesRequest {
             client_name: self.client_name.clone(),
+            name: String::new(),
             axes_descriptor: Some(axes_desc_rpc),
-        });
+        };
 
-        let response = self.tokio_runtime.block_on(self.stub.add_axes(request))?;
+        let response = self.call_once("add_axes", request, |mut stub, request| async move {
+            stub.add_axes(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //}}}
     //{{{ fun: add_line
     pub fn add_line(&mut self, line_desc: LineDescriptor) -> Result<usize, Error> {
         let line_desc_rpc: d2rpc::LineDescriptor = line_desc.into();
-        let request = Request::new(d2rpc::AddLineRequest {
+        let request = d2rpc::AddLineRequest {
             client_name: self.client_name.clone(),
+            name: String::new(),
             line_descriptor: Some(line_desc_rpc),
-        });
-        let response = self.tokio_runtime.block_on(self.stub.add_line(request))?;
+        };
+        let response = self.call_once("add_line", request, |mut stub, request| async move {
+            stub.add_line(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //}}}
     //{{{ fun: add_square
     pub fn add_square(&mut self, square_desc: SquareDescriptor) -> Result<usize, Error> {
         let square_desc_rpc: d2rpc::SquareDescriptor = square_desc.into();
-        let request = Request::new(d2rpc::AddSquareRequest {
+        let request = d2rpc::AddSquareRequest {
             client_name: self.client_name.clone(),
+            name: String::new(),
             square_descriptor: Some(square_desc_rpc),
-        });
-        let response = self.tokio_runtime.block_on(self.stub.add_square(request))?;
+        };
+        let response = self.call_once("add_square", request, |mut stub, request| async move {
+            stub.add_square(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //}}}
+    //{{{ fun: add_rectangle
+    pub fn add_rectangle(&mut self, rectangle_desc: RectangleDescriptor) -> Result<usize, Error> {
+        let rectangle_desc_rpc: d2rpc::RectangleDescriptor = rectangle_desc.into();
+        let request = d2rpc::AddRectangleRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            rectangle_descriptor: Some(rectangle_desc_rpc),
+        };
+        let response = self.call_once("add_rectangle", request, |mut stub, request| async move {
+            stub.add_rectangle(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //}}}
     //{{{ fun: add_circle
     pub fn add_circle(&mut self, circle_desc: CircleDescriptor) -> Result<usize, Error> {
         let circle_desc_rpc: d2rpc::CircleDescriptor = circle_desc.into();
-        let request = Request::new(d2rpc::AddCircleRequest {
+        let request = d2rpc::AddCircleRequest {
             client_name: self.client_name.clone(),
+            name: String::new(),
             circle_descriptor: Some(circle_desc_rpc),
-        });
-        let response = self.tokio_runtime.block_on(self.stub.add_circle(request))?;
+        };
+        let response = self.call_once("add_circle", request, |mut stub, request| async move {
+            stub.add_circle(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //}}}
+    //{{{ fun: set_draw_context
+    /// Sets the colors and cell type the `add_*_simple` methods draw shapes with. Shapes added
+    /// before keep their colors.
+    pub fn set_draw_context(&mut self, draw_context: DrawContext) {
+        self.draw_context = draw_context;
+    }
+    //}}}
+    //{{{ fun: draw_context
+    /// Returns the colors and cell type the `add_*_simple` methods draw shapes with.
+    pub fn draw_context(&self) -> DrawContext {
+        self.draw_context
+    }
+    //}}}
+    //{{{ fun: add_rectangle_simple
+    /// Adds the axis-aligned rectangle with the given center and side lengths, drawn with the
+    /// draw context of the client.
+    pub fn add_rectangle_simple(
+        &mut self,
+        center: Vec2,
+        width: f32,
+        height: f32,
+    ) -> Result<usize, Error> {
+        self.add_rectangle(simple_rectangle(&self.draw_context, center, width, height))
+    }
+    //}}}
+    //{{{ fun: add_circle_simple
+    /// Adds the circle with the given center and radius, drawn with the draw context of the
+    /// client.
+    pub fn add_circle_simple(&mut self, center: Vec2, radius: f32) -> Result<usize, Error> {
+        self.add_circle(simple_circle(&self.draw_context, center, radius))
+    }
+    //}}}
     //{{{ fun: add_mesh
     pub fn add_mesh<'a>(&mut self, mesh: Mesh<'a>) -> Result<usize, Error> {
+        self.add_named_mesh("", mesh)
+    }
+    //}}}
+    //{{{ fun: add_named_mesh
+    /// Adds a mesh tagged with the given name, which the server can look the mesh up by. An empty
+    /// name adds the mesh unnamed.
+    pub fn add_named_mesh<'a>(&mut self, name: &str, mesh: Mesh<'a>) -> Result<usize, Error> {
         let mesh_desc_rpc: d2rpc::MeshDescriptor = mesh.clone().into();
-        let request = Request::new(d2rpc::AddMeshRequest {
+        let request = d2rpc::AddMeshRequest {
             client_name: self.client_name.clone(),
+            name: name.to_string(),
             mesh_descriptor: Some(mesh_desc_rpc),
-        });
-        let response = self.tokio_runtime.block_on(self.stub.add_mesh(request))?;
+        };
+        let response = self.call_once("add_mesh", request, |mut stub, request| async move {
+            stub.add_mesh(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //}}}
+    //{{{ fun: append_to_mesh
+    /// Appends the vertices and cells of `mesh` to the mesh with the given id, which must have
+    /// the same cell type. The indices of `mesh` refer to its own vertices, the server offsets
+    /// them past those already in the mesh.
+    pub fn append_to_mesh<'a>(&mut self, id: usize, mesh: Mesh<'a>) -> Result<(), Error> {
+        let mesh_desc_rpc: d2rpc::MeshDescriptor = mesh.into();
+        let request = d2rpc::AppendToMeshRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let _ = self.call_once("append_to_mesh", request, |mut stub, request| async move {
+            stub.append_to_mesh(request).await
+        })?;
+        Ok(())
+    }
+    //}}}
+    //{{{ fun: replace_mesh
+    /// Replaces the geometry of the mesh with the given id by that of `mesh`, keeping the id, so
+    /// that a mesh can be animated without the references to it breaking.
+    pub fn replace_mesh<'a>(&mut self, id: usize, mesh: Mesh<'a>) -> Result<(), Error> {
+        let mesh_desc_rpc: d2rpc::MeshDescriptor = mesh.into();
+        let request = d2rpc::ReplaceMeshRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let _ = self.call("replace_mesh", request, |mut stub, request| async move {
+            stub.replace_mesh(request).await
+        })?;
+        Ok(())
+    }
+    //}}}
+    //{{{ fun: validate_mesh
+    /// Asks the server to check the geometry of `mesh`, without adding it to the scene, and
+    /// returns its report.
+    pub fn validate_mesh<'a>(&mut self, mesh: Mesh<'a>) -> Result<MeshDiagnostics, Error> {
+        let mesh_desc_rpc: d2rpc::MeshDescriptor = mesh.into();
+        let request = d2rpc::ValidateMeshRequest {
+            client_name: self.client_name.clone(),
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let response = self.call("validate_mesh", request, |mut stub, request| async move {
+            stub.validate_mesh(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //}}}
+    //{{{ fun: add_contours
+    /// Adds a line mesh holding the contours of `values`, one per vertex of the triangle mesh
+    /// with the given id, at each of `levels`, and returns the id of the new mesh.
+    pub fn add_contours(
+        &mut self,
+        id: usize,
+        values: &[f32],
+        levels: &[f32],
+        color: &Color,
+    ) -> Result<usize, Error> {
+        let request = d2rpc::AddContoursRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            values: values.to_vec(),
+            levels: levels.to_vec(),
+            color: Some((*color).into()),
+        };
+        let response = self.call_once("add_contours", request, |mut stub, request| async move {
+            stub.add_contours(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_bounding_box
+    /// Adds a line mesh holding the edges of the bounding box of the mesh with the given id, and
+    /// returns the id of the new mesh.
+    pub fn add_bounding_box(&mut self, id: usize, color: &Color) -> Result<usize, Error> {
+        let request = d2rpc::AddBoundingBoxRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            color: Some((*color).into()),
+        };
+        let response = self.call_once("add_bounding_box", request, |mut stub, request| async move {
+            stub.add_bounding_box(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_model
+    /// Sets the model matrix placing the mesh with the given id in the world, moving it without
+    /// resending its vertices.
+    pub fn set_mesh_model(&mut self, id: usize, model: &Mat4) -> Result<(), Error> {
+        let request = d2rpc::SetMeshModelRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            model: model_to_values(model),
+        };
+        let _ = self.call("set_mesh_model", request, |mut stub, request| async move {
+            stub.set_mesh_model(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_tag
+    /// Puts the mesh with the given id in the group with the given tag, or in no group with
+    /// `None`, so that the group can be moved at once with [`Self::transform_group`].
+    pub fn set_mesh_tag(&mut self, id: usize, tag: Option<&str>) -> Result<(), Error> {
+        let request = d2rpc::SetMeshTagRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            tag: tag.unwrap_or_default().to_string(),
+        };
+        let _ = self.call("set_mesh_tag", request, |mut stub, request| async move {
+            stub.set_mesh_tag(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: transform_group
+    /// Moves every mesh in the group with the given tag by `transform`, applied after the model
+    /// of each mesh, returning how many meshes were moved.
+    pub fn transform_group(&mut self, tag: &str, transform: &Mat4) -> Result<usize, Error> {
+        let request = d2rpc::TransformGroupRequest {
+            client_name: self.client_name.clone(),
+            tag: tag.to_string(),
+            transform: model_to_values(transform),
+        };
+        // each call moves the group further, so it is never sent twice
+        let response = self.call_once("transform_group", request, |mut stub, request| async move {
+            stub.transform_group(request).await
+        })?;
+        Ok(response.into_inner().num_meshes as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_color_mode
+    /// Draws the mesh with the given id with the colors of its vertices, or with a single color,
+    /// see [`MeshColorMode`].
+    pub fn set_mesh_color_mode(&mut self, id: usize, mode: MeshColorMode) -> Result<(), Error> {
+        let request = d2rpc::SetMeshColorModeRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            uniform_color: color_mode_to_rpc(mode),
+        };
+        let _ = self.call("set_mesh_color_mode", request, |mut stub, request| async move {
+            stub.set_mesh_color_mode(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_options
+    /// Sets the options controlling how the mesh with the given id is drawn, such as the color of
+    /// the edges drawn over its triangle cells, see [`MeshOptions`].
+    pub fn set_mesh_options(&mut self, id: usize, options: &MeshOptions) -> Result<(), Error> {
+        let request = d2rpc::SetMeshOptionsRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            options: Some((*options).into()),
+        };
+        let _ = self.call("set_mesh_options", request, |mut stub, request| async move {
+            stub.set_mesh_options(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: clear
     pub fn clear(&mut self) -> Result<(), Error> {
-        let request = Request::new(d2rpc::ClearRequest {
+        let request = d2rpc::ClearRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call("clear", request, |mut stub, request| async move {
+            stub.clear(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: get_camera
+    pub fn get_camera(&mut self) -> Result<CameraDescriptor, Error> {
+        let request = d2rpc::GetCameraRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self.call("get_camera", request, |mut stub, request| async move {
+            stub.get_camera(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_rendering_enabled
+    pub fn set_rendering_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+        let request = d2rpc::SetRenderingEnabledRequest {
+            client_name: self.client_name.clone(),
+            enabled: enabled,
+        };
+        let _ = self.call("set_rendering_enabled", request, |mut stub, request| async move {
+            stub.set_rendering_enabled(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: flush
+    /// Blocks until the viewer has rendered every mesh added so far, so that a screenshot taken
+    /// next shows them. Returns at once if rendering is disabled or no window is open, and fails
+    /// if the viewer has not rendered within five seconds.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        let request = d2rpc::FlushRequest {
             client_name: self.client_name.clone(),
-        });
-        let _ = self.tokio_runtime.block_on(self.stub.clear(request))?;
+        };
+        let _ = self.call("flush", request, |mut stub, request| async move {
+            stub.flush(request).await
+        })?;
         Ok(())
     }
     //..............................................................................
     //}}}
+    //{{{ fun: open_window
+    /// Opens another window onto the scene, with a camera of its own. The window appears once the
+    /// viewer's event loop handles the request, shortly after this returns.
+    pub fn open_window(&mut self) -> Result<(), Error> {
+        let request = d2rpc::OpenWindowRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call_once("open_window", request, |mut stub, request| async move {
+            stub.open_window(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_view_sensitivity
+    /// Sets how far the camera moves per key press and mouse wheel step, every value must be
+    /// finite and strictly positive.
+    pub fn set_view_sensitivity(&mut self, sensitivity: &ViewSensitivity) -> Result<(), Error> {
+        let request = d2rpc::SetViewSensitivityRequest {
+            client_name: self.client_name.clone(),
+            key_pan_delta: sensitivity.key_pan_delta,
+            key_rotate_delta: sensitivity.key_rotate_delta,
+            zoom_speed: sensitivity.zoom_speed,
+        };
+        let _ = self.call("set_view_sensitivity", request, |mut stub, request| async move {
+            stub.set_view_sensitivity(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_background_gradient
+    /// Fills the background with a vertical gradient from `top` to `bottom`.
+    pub fn set_background_gradient(&mut self, top: &Color, bottom: &Color) -> Result<(), Error> {
+        let request = d2rpc::SetBackgroundGradientRequest {
+            client_name: self.client_name.clone(),
+            top: Some((*top).into()),
+            bottom: Some((*bottom).into()),
+        };
+        let _ = self.call("set_background_gradient", request, |mut stub, request| async move {
+            stub.set_background_gradient(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: clear_background_gradient
+    /// Removes the background gradient, restoring the flat background.
+    pub fn clear_background_gradient(&mut self) -> Result<(), Error> {
+        let request = d2rpc::SetBackgroundGradientRequest {
+            client_name: self.client_name.clone(),
+            top: None,
+            bottom: None,
+        };
+        let _ = self.call("set_background_gradient", request, |mut stub, request| async move {
+            stub.set_background_gradient(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_tessellation_quality
+    /// Sets the quality circles added from now on are tessellated at when their number of sides
+    /// is left at zero.
+    pub fn set_tessellation_quality(&mut self, quality: TessellationQuality) -> Result<(), Error> {
+        let (quality, chord_error) = quality.into();
+        let request = d2rpc::SetTessellationQualityRequest {
+            client_name: self.client_name.clone(),
+            quality: quality,
+            chord_error: chord_error,
+        };
+        let _ = self.call("set_tessellation_quality", request, |mut stub, request| async move {
+            stub.set_tessellation_quality(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: scene_stats
+    /// Returns the number of meshes and vertices held by the server, along with its budget.
+    pub fn scene_stats(&mut self) -> Result<SceneStats, Error> {
+        let request = d2rpc::GetSceneStatsRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self.call("get_scene_stats", request, |mut stub, request| async move {
+            stub.get_scene_stats(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: scene_bounds
+    /// Returns the minimum and maximum corners of the smallest box enclosing every mesh held by
+    /// the server, or `None` if it holds no vertices.
+    pub fn scene_bounds(&mut self) -> Result<Option<(Vec2, Vec2)>, Error> {
+        let request = d2rpc::SceneBoundsRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self
+            .call("scene_bounds", request, |mut stub, request| async move {
+                stub.scene_bounds(request).await
+            })?
+            .into_inner();
+        match (response.empty, response.min, response.max) {
+            (false, Some(min), Some(max)) => Ok(Some((min.into(), max.into()))),
+            _ => Ok(None),
+        }
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: exists
+    /// Returns whether the server holds a mesh with the given id, for checking a mesh is still
+    /// there before operating on it.
+    pub fn exists(&mut self, id: usize) -> Result<bool, Error> {
+
+        let request = d2rpc::ExistsRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+        };
+        let response = self.call("exists", request, |mut stub, request| async move {
+            stub.exists(request).await
+        })?;
+        Ok(response.into_inner().exists)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: disconnect
+    /// Tells the server this client is done with it. A server started with `--exit-on-idle`
+    /// shuts down once every client has disconnected, or gone quiet, for the idle timeout.
+    pub fn disconnect(&mut self) -> Result<(), Error> {
+        let request = d2rpc::DisconnectRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call("disconnect", request, |mut stub, request| async move {
+            stub.disconnect(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: ping
+    /// Checks the server is alive, returning the round-trip time of the request. This also keeps
+    /// the client active on a server started with `--exit-on-idle`.
+    pub fn ping(&mut self) -> Result<Duration, Error> {
+        let request = d2rpc::PingRequest {
+            client_name: self.client_name.clone(),
+        };
+        let start = Instant::now();
+        let _ = self.call("ping", request, |mut stub, request| async move {
+            stub.ping(request).await
+        })?;
+        Ok(start.elapsed())
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: kill_server
     pub fn kill_server(&mut self) -> Result<(), Error> {
-        let request = Request::new(d2rpc::KillServerRequest {
+        let request = d2rpc::KillServerRequest {
             client_name: self.client_name.clone(),
-        });
-        let _ = self
-            .tokio_runtime
-            .block_on(self.stub.kill_server(request))?;
+        };
+        let _ = self.call_once("kill_server", request, |mut stub, request| async move {
+            stub.kill_server(request).await
+        })?;
         Ok(())
     }
     //..............................................................................
@@ -164,5 +673,56 @@ impl Client2D {
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::connection::{is_broken_connection, RECONNECT_DELAY};
+
+    #[test]
+    fn status_error_test() {
+        let error = status_error("add_circle")(tonic::Status::invalid_argument("Invalid circle"));
+        assert!(matches!(
+            error,
+            Error::Validation { method: "add_circle", ref message } if message == "Invalid circle"
+        ));
+        let error = status_error("add_circle")(tonic::Status::unavailable("connection refused"));
+        assert!(matches!(error, Error::StatusError(_)));
+    }
+
+    #[test]
+    fn reconnect_test() {
+        // a port nothing listens on, so that every connection is refused
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port() as usize;
+        let mut connection = Connection::new_lazy(port, RpcClient::new);
+        connection.set_reconnect_attempts(2);
+        let mut client = Client2D {
+            client_name: "test".to_string(),
+            connection: connection,
+            draw_context: DrawContext::default(),
+        };
+        // the call tries reconnecting twice before giving up with the transport error
+        let start = Instant::now();
+        match client.ping() {
+            Err(Error::StatusError(status)) => assert!(is_broken_connection(&status)),
+            other => panic!("expected a broken connection, got {:?}", other),
+        }
+        assert!(start.elapsed() >= 2 * RECONNECT_DELAY);
+
+        // adding a mesh is never sent twice, so it fails without reconnecting
+        let start = Instant::now();
+        match client.add_line(LineDescriptor {
+            v1: Vec2::new(0.0, 0.0),
+            v2: Vec2::new(1.0, 1.0),
+            color: Color::Black,
+            dash_pattern: None,
+        }) {
+            Err(Error::StatusError(status)) => assert!(is_broken_connection(&status)),
+            other => panic!("expected a broken connection, got {:?}", other),
+        }
+        assert!(start.elapsed() < RECONNECT_DELAY);
+    }
+}
 //}}}
@@ -12,15 +12,20 @@
 //{{{ crate imports 
 use super::d2rpc;
 use super::common::*;
-use super::super::mesh::{AxesDescriptor, SquareDescriptor, CircleDescriptor, Mesh};
+use super::super::mesh::{
+    AxesDescriptor, SquareDescriptor, RectangleDescriptor, CircleDescriptor, Mesh,
+};
 use super::super::state::{State, State2D};
-use crate::common::Validated;
+use crate::common::{model_from_values, AddMeshError, Color, Validated};
+use crate::core::MeshEditError;
 use crate::app::TopoHedralEvent;
+use crate::clients::{shutdown_when_idle, ClientTracker};
 //}}}
 //{{{ std imports 
 use core::net::SocketAddr;
 use std::result::Result;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 //}}}
 //{{{ dep imports 
 use topohedral_tracing::{error, info, topo_log};
@@ -30,6 +35,24 @@ use winit::event_loop::EventLoopProxy;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
+//{{{ collection: constants
+/// The longest a flush waits for the scene to be rendered, in case no render pass is coming, for
+/// instance because the window is minimized.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+//}}}
+//{{{ fun: add_mesh_status
+/// Returns the status of an add request whose mesh the state turned away: resource exhausted if it
+/// does not fit within the scene budget, an invalid argument if it is empty.
+fn add_mesh_status(e: AddMeshError) -> Status
+{
+    match e
+    {
+        AddMeshError::Budget(e) => Status::resource_exhausted(e.to_string()),
+        AddMeshError::Empty => Status::invalid_argument(e.to_string()),
+    }
+}
+//}}}
+
 //{{{ struct: StateServer
 /// The `StateServer` struct is responsible for managing the state of the 2D rendering system.
 /// It holds a reference to the shared state object (`State<'static>`), which is protected by a mutex,
@@ -40,6 +63,20 @@ pub struct StateServer
 {
     state: Arc<Mutex<State<'static>>>,
     shutdown_sender: mpsc::Sender<()>,
+    clients: Arc<Mutex<ClientTracker>>,
+    event_loop_proxy: EventLoopProxy<TopoHedralEvent>,
+}
+//}}}
+//{{{ impl StateServer
+impl StateServer
+{
+    //{{{ fun: touch
+    /// Registers the client making a request, or records that it is still active.
+    fn touch(&self, client_name: &str)
+    {
+        self.clients.lock().unwrap().touch(client_name, Instant::now());
+    }
+    //}}}
 }
 //}}}
 //{{{ impl d2rpc::state_service_server::StateService for StateServer
@@ -54,24 +91,32 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_axes request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out:  Result<Response<d2rpc::AddItemResponse>, Status> = if msg.is_valid() 
-        {
-            let axes_desc = msg.axes_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_axes(&axes_desc);
-            let add_axes_result = d2rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_axes_result))
-        }
-        else {
-            Err(Status::invalid_argument("Invalid axes descriptor"))
+        let out:  Result<Response<d2rpc::AddItemResponse>, Status> = match msg.validate() {
+            Ok(()) => {
+                let axes_desc = msg.axes_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_axes(&axes_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_axes_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_axes_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid axes descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -83,21 +128,29 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!("Received add_line request from {} on port {:?}", msg.client_name, addr);
         //}}}
-        let out: Result<Response<d2rpc::AddItemResponse>, Status> = if msg.is_valid()   
-        {
-            let line_desc = msg.line_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_line(&line_desc);
-            let add_line_result = d2rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_line_result))
-        }
-        else {
-            Err(Status::invalid_argument("Invalid line descriptor"))
+        let out: Result<Response<d2rpc::AddItemResponse>, Status> = match msg.validate() {
+            Ok(()) => {
+                let line_desc = msg.line_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_line(&line_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_line_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_line_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid line descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -109,6 +162,7 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
 
         //{{{ trace
         info!(
@@ -117,19 +171,64 @@ impl d2rpc::state_service_server::StateService for StateServer
         );
         //}}}
         
-        let out: Result<Response<d2rpc::AddItemResponse>, Status> = if msg.is_valid() 
-        {
-            let square_desc = msg.square_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_square(&square_desc);
-            let add_square_result = d2rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_square_result))
-        }
-        else 
-        {
-            Err(Status::invalid_argument("Invalid square descriptor"))
+        let out: Result<Response<d2rpc::AddItemResponse>, Status> = match msg.validate() {
+            Ok(()) => {
+                let square_desc = msg.square_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_square(&square_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_square_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_square_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid square descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: add_rectangle
+    async fn add_rectangle(
+        &self,
+        request: Request<d2rpc::AddRectangleRequest>,
+    ) -> Result<Response<d2rpc::AddItemResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_rectangle request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+
+        let out: Result<Response<d2rpc::AddItemResponse>, Status> = match msg.validate() {
+            Ok(()) => {
+                let rectangle_desc: RectangleDescriptor = msg.rectangle_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_rectangle(&rectangle_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_rectangle_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_rectangle_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid rectangle descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -142,6 +241,7 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_axes request from {} on port {:?}",
@@ -149,19 +249,25 @@ impl d2rpc::state_service_server::StateService for StateServer
         );
         //}}}
 
-        let out = if msg.is_valid()
-        {
-            let circle_desc = msg.circle_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_circle(&circle_desc);
-            let add_circle_result = d2rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_circle_result))
-        } 
-        else 
-        {
-            Err(Status::invalid_argument("Invalid circle descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let circle_desc = msg.circle_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_circle(&circle_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_circle_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_circle_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid circle descriptor: {}",
+                e
+            ))),
         };
         out
     }   
@@ -174,29 +280,333 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_axes request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid()
-        {
-            let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_mesh(mesh);
-            let add_mesh_result = d2rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_mesh_result))
-        }
-        else 
-        {
-            Err(Status::invalid_argument("Invalid mesh descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_mesh(mesh)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_mesh_result = d2rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_mesh_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: append_to_mesh
+    async fn append_to_mesh(
+        &self,
+        request: Request<d2rpc::AppendToMeshRequest>,
+    ) -> Result<Response<d2rpc::AppendToMeshResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received append_to_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                match state.append_to_mesh(msg.id as usize, mesh) {
+                    Ok(()) => Ok(Response::new(d2rpc::AppendToMeshResponse {})),
+                    Err(MeshEditError::NoMesh) => {
+                        Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+                    }
+                    Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+                    Err(e) => Err(Status::invalid_argument(format!(
+                        "Cannot append to mesh {}: {}",
+                        msg.id, e
+                    ))),
+                }
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: replace_mesh
+    async fn replace_mesh(
+        &self,
+        request: Request<d2rpc::ReplaceMeshRequest>,
+    ) -> Result<Response<d2rpc::ReplaceMeshResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received replace_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                match state.replace_mesh(msg.id as usize, mesh) {
+                    Ok(()) => Ok(Response::new(d2rpc::ReplaceMeshResponse {})),
+                    Err(MeshEditError::NoMesh) => {
+                        Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+                    }
+                    Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+                    Err(e) => Err(Status::internal(e.to_string())),
+                }
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
         };
         out
     }
     //}}}
+    //{{{ fun: validate_mesh
+    /// Reports on the geometry of the mesh without adding it to the scene.
+    async fn validate_mesh(
+        &self,
+        request: Request<d2rpc::ValidateMeshRequest>,
+    ) -> Result<Response<d2rpc::MeshStatsResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received validate_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                Ok(Response::new(mesh.diagnostics().into()))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: add_contours
+    async fn add_contours(
+        &self,
+        request: Request<d2rpc::AddContoursRequest>,
+    ) -> Result<Response<d2rpc::AddItemResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_contours request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let color = msg.color.map(Color::from).unwrap_or(Color::Black);
+        let mut state = self.state.lock().unwrap();
+        match state.add_contours(msg.id as usize, &msg.values, &msg.levels, &color) {
+            Ok(id) => Ok(Response::new(d2rpc::AddItemResponse { id: id as u64 })),
+            Err(MeshEditError::NoMesh) => {
+                Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+            }
+            Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Cannot add contours of mesh {}: {}",
+                msg.id, e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: add_bounding_box
+    async fn add_bounding_box(
+        &self,
+        request: Request<d2rpc::AddBoundingBoxRequest>,
+    ) -> Result<Response<d2rpc::AddItemResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_bounding_box request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let color = msg.color.map(Color::from).unwrap_or(Color::Black);
+        let mut state = self.state.lock().unwrap();
+        match state.add_bounding_box(msg.id as usize, &color) {
+            Ok(id) => Ok(Response::new(d2rpc::AddItemResponse { id: id as u64 })),
+            Err(MeshEditError::NoMesh) => {
+                Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+            }
+            Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Cannot add the bounding box of mesh {}: {}",
+                msg.id, e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_model
+    async fn set_mesh_model(
+        &self,
+        request: Request<d2rpc::SetMeshModelRequest>,
+    ) -> Result<Response<d2rpc::SetMeshModelResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_model request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let model = model_from_values(&msg.model).unwrap();
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_model(msg.id as usize, model) {
+            Ok(Response::new(d2rpc::SetMeshModelResponse {}))
+        }
+        else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_tag
+    async fn set_mesh_tag(
+        &self,
+        request: Request<d2rpc::SetMeshTagRequest>,
+    ) -> Result<Response<d2rpc::SetMeshTagResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_tag request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let tag = Some(msg.tag.as_str()).filter(|tag| !tag.is_empty());
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_tag(msg.id as usize, tag) {
+            Ok(Response::new(d2rpc::SetMeshTagResponse {}))
+        }
+        else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: transform_group
+    async fn transform_group(
+        &self,
+        request: Request<d2rpc::TransformGroupRequest>,
+    ) -> Result<Response<d2rpc::TransformGroupResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received transform_group request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let transform = model_from_values(&msg.transform).unwrap();
+        let num_meshes = self.state.lock().unwrap().transform_group(&msg.tag, transform);
+        Ok(Response::new(d2rpc::TransformGroupResponse {
+            num_meshes: num_meshes as u64,
+        }))
+    }
+    //}}}
+    //{{{ fun: set_mesh_color_mode
+    async fn set_mesh_color_mode(
+        &self,
+        request: Request<d2rpc::SetMeshColorModeRequest>,
+    ) -> Result<Response<d2rpc::SetMeshColorModeResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_color_mode request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let mode = color_mode_from_rpc(msg.uniform_color);
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_color_mode(msg.id as usize, mode) {
+            Ok(Response::new(d2rpc::SetMeshColorModeResponse {}))
+        }
+        else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_options
+    async fn set_mesh_options(
+        &self,
+        request: Request<d2rpc::SetMeshOptionsRequest>,
+    ) -> Result<Response<d2rpc::SetMeshOptionsResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_options request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let options = msg.options.unwrap().into();
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_options(msg.id as usize, options) {
+            Ok(Response::new(d2rpc::SetMeshOptionsResponse {}))
+        }
+        else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
     //{{{ fun: clear
     async fn clear(
         &self,
@@ -205,6 +615,7 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received clear_all request from {} on port {:?}",
@@ -216,6 +627,275 @@ impl d2rpc::state_service_server::StateService for StateServer
         Ok(Response::new(d2rpc::ClearResponse {}))
     }
     //}}}
+    //{{{ fun: get_camera
+    async fn get_camera(
+        &self,
+        request: Request<d2rpc::GetCameraRequest>,
+    ) -> Result<Response<d2rpc::GetCameraResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received get_camera request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(state.camera().into()))
+    }
+    //}}}
+    //{{{ fun: set_rendering_enabled
+    async fn set_rendering_enabled(
+        &self,
+        request: Request<d2rpc::SetRenderingEnabledRequest>,
+    ) -> Result<Response<d2rpc::SetRenderingEnabledResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_rendering_enabled request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        state.set_rendering_enabled(msg.enabled);
+        Ok(Response::new(d2rpc::SetRenderingEnabledResponse {}))
+    }
+    //}}}
+    //{{{ fun: flush
+    async fn flush(
+        &self,
+        request: Request<d2rpc::FlushRequest>,
+    ) -> Result<Response<d2rpc::FlushResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received flush request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // the lock must be released for the render loop to draw the frame waited on
+        let rendered = self.state.lock().unwrap().request_flush();
+        match tokio::time::timeout(FLUSH_TIMEOUT, rendered).await {
+            Ok(Ok(())) => Ok(Response::new(d2rpc::FlushResponse {})),
+            Ok(Err(_)) => Err(Status::unavailable("The window closed before rendering")),
+            Err(_) => Err(Status::deadline_exceeded("The scene was not rendered in time")),
+        }
+    }
+    //}}}
+    //{{{ fun: open_window
+    async fn open_window(
+        &self,
+        request: Request<d2rpc::OpenWindowRequest>,
+    ) -> Result<Response<d2rpc::OpenWindowResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received open_window request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // windows can only be created on the thread running the event loop
+        match self.event_loop_proxy.send_event(TopoHedralEvent::OpenWindow) {
+            Ok(()) => Ok(Response::new(d2rpc::OpenWindowResponse {})),
+            Err(_) => Err(Status::unavailable("The event loop has closed")),
+        }
+    }
+    //}}}
+    //{{{ fun: set_view_sensitivity
+    async fn set_view_sensitivity(
+        &self,
+        request: Request<d2rpc::SetViewSensitivityRequest>,
+    ) -> Result<Response<d2rpc::SetViewSensitivityResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_view_sensitivity request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        match state.set_view_sensitivity(msg.into()) {
+            Ok(()) => Ok(Response::new(d2rpc::SetViewSensitivityResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid view sensitivity: {} must be finite and positive",
+                field
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_background_gradient
+    async fn set_background_gradient(
+        &self,
+        request: Request<d2rpc::SetBackgroundGradientRequest>,
+    ) -> Result<Response<d2rpc::SetBackgroundGradientResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_background_gradient request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        // a missing color removes the gradient
+        match (msg.top, msg.bottom)
+        {
+            (Some(top), Some(bottom)) => state.set_background_gradient(top.into(), bottom.into()),
+            _ => state.clear_background_gradient(),
+        }
+        Ok(Response::new(d2rpc::SetBackgroundGradientResponse {}))
+    }
+    //}}}
+    //{{{ fun: set_tessellation_quality
+    async fn set_tessellation_quality(
+        &self,
+        request: Request<d2rpc::SetTessellationQualityRequest>,
+    ) -> Result<Response<d2rpc::SetTessellationQualityResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_tessellation_quality request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        match state.set_tessellation_quality((msg.quality, msg.chord_error).into())
+        {
+            Ok(()) => Ok(Response::new(d2rpc::SetTessellationQualityResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid tessellation quality: bad {}, it must be finite and positive",
+                field
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: get_scene_stats
+    async fn get_scene_stats(
+        &self,
+        request: Request<d2rpc::GetSceneStatsRequest>,
+    ) -> Result<Response<d2rpc::GetSceneStatsResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received get_scene_stats request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(state.scene_stats().into()))
+    }
+    //}}}
+    //{{{ fun: scene_bounds
+    async fn scene_bounds(
+        &self,
+        request: Request<d2rpc::SceneBoundsRequest>,
+    ) -> Result<Response<d2rpc::SceneBoundsResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received scene_bounds request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        let response = match state.scene_bounds() {
+            Some((min, max)) => d2rpc::SceneBoundsResponse {
+                min: Some(min.xy().into()),
+                max: Some(max.xy().into()),
+                empty: false,
+            },
+            None => d2rpc::SceneBoundsResponse {
+                min: None,
+                max: None,
+                empty: true,
+            },
+        };
+        Ok(Response::new(response))
+    }
+    //}}}
+    //{{{ fun: exists
+    async fn exists(
+        &self,
+        request: Request<d2rpc::ExistsRequest>,
+    ) -> Result<Response<d2rpc::ExistsResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received exists request for mesh {} from {} on port {:?}",
+            msg.id, msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(d2rpc::ExistsResponse {
+            exists: state.contains_mesh(msg.id as usize),
+        }))
+    }
+    //}}}
+    //{{{ fun: disconnect
+    async fn disconnect(
+        &self,
+        request: Request<d2rpc::DisconnectRequest>,
+    ) -> Result<Response<d2rpc::DisconnectResponse>, Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        //{{{ trace
+        info!(
+            "Received disconnect request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        self.clients
+            .lock()
+            .unwrap()
+            .disconnect(&msg.client_name, Instant::now());
+        Ok(Response::new(d2rpc::DisconnectResponse {}))
+    }
+    //}}}
+    //{{{ fun: ping
+    async fn ping(
+        &self,
+        request: Request<d2rpc::PingRequest>,
+    ) -> Result<Response<d2rpc::PongResponse>, Status>
+    {
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        let server_time_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Ok(Response::new(d2rpc::PongResponse {
+            server_time_millis: server_time_millis,
+        }))
+    }
+    //}}}
     //{{{ fun: kill_server
     async fn kill_server(
         &self,
@@ -224,6 +904,7 @@ impl d2rpc::state_service_server::StateService for StateServer
     {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{  trace
         info!(
             "Received kill_server request from {} on port {:?}",
@@ -249,14 +930,25 @@ pub async fn run_server(
     shutdown_sender: mpsc::Sender<()>,
     mut shutdown_receiver: mpsc::Receiver<()>,
     event_loop_proxy: EventLoopProxy<TopoHedralEvent>,
+    exit_on_idle: Option<Duration>,
 )
 {
     info!("Starting RPC server on port {}", rpc_address);
 
 
+    let clients = ClientTracker::new_arc_mutex();
+    if let Some(timeout) = exit_on_idle {
+        //{{{ trace
+        info!("Shutting down after {:?} without active clients", timeout);
+        //}}}
+        tokio::spawn(shutdown_when_idle(clients.clone(), timeout, shutdown_sender.clone()));
+    }
+
     let state_server = StateServer {
         state: state,
         shutdown_sender: shutdown_sender,
+        clients: clients,
+        event_loop_proxy: event_loop_proxy.clone(),
     };
 
     let server = Server::builder()
@@ -277,7 +969,12 @@ pub async fn run_server(
     //{{{ trace
     info!("Sending Shutdown Event");
     //}}}
-    event_loop_proxy.send_event(TopoHedralEvent::RcpShutdown).unwrap();
+    if let Err(e) = event_loop_proxy.send_event(TopoHedralEvent::RcpShutdown)
+    {
+        //{{{ trace
+        info!("Event loop already closed, shutdown event not delivered: {}", e);
+        //}}}
+    }
 }
 //}}}
 
@@ -1,5 +1,9 @@
 struct ViewUniform {
     view_matrix: mat4x4<f32>,
+    viewport: vec2<f32>,
+    edge_width: f32,
+    _padding: f32,
+    highlight_color: vec4<f32>,
 };
 
 @group(0) @binding(0)
@@ -8,34 +12,172 @@ var<uniform> view: ViewUniform;
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
-    @location(1) line_color: vec3<f32>,
-    @location(2) tri_color: vec3<f32>,
+    @location(1) line_color: vec4<f32>,
+    @location(2) tri_color: vec4<f32>,
+    @location(3) screen_offset: vec2<f32>,
 }
 
+// The far end of an edge drawn with `vs_main_thick_edge`, the near end is a `VertexInput`
+struct EdgeEndInput {
+    @location(4) position: vec2<f32>,
+    @location(5) line_color: vec4<f32>,
+    @location(6) tri_color: vec4<f32>,
+    @location(7) screen_offset: vec2<f32>,
+}
+
+// The vertex of a mesh with a uniform color, which keeps only the attributes of `VertexInput`
+// that are not colors
+struct CompactVertexInput {
+    @location(0) position: vec2<f32>,
+    @location(3) screen_offset: vec2<f32>,
+}
+
+// Places the vertices of a mesh in the world, before the view, at the depth of the layer of the
+// mesh, so that meshes in higher layers are drawn on top. The normal matrix is only of use to 3D
+// meshes, and ignored here. The depth offsets pull the line cells of the mesh, and the edges of
+// its triangle cells drawn as lines, in front of its triangles, within the layer.
+struct MeshModel {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+    layer_depth: f32,
+    line_depth_offset: f32,
+    edge_depth_offset: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> mesh_model: MeshModel;
+
+// The colors of a mesh with a uniform color, bound along with its compact vertices
+struct MeshColor {
+    line_color: vec4<f32>,
+    tri_color: vec4<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> mesh_color: MeshColor;
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
-    @location(0) line_color: vec3<f32>,
-    @location(1) tri_color: vec3<f32>,
+    @location(0) line_color: vec4<f32>,
+    @location(1) tri_color: vec4<f32>,
 }
 
 
-@vertex
-fn vs_main( model: VertexInput) -> VertexOutput {
+fn transform( model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
-    let pos =  view.view_matrix * vec4<f32>(model.position, 1.0, 0.0);
-    out.position = vec4<f32>(pos.xy, 0.0, 1.0);
+    let pos =  view.view_matrix * mesh_model.model * vec4<f32>(model.position, 0.0, 1.0);
+    // screen-space offsets are in pixels, clip space spans 2 units across the viewport
+    let offset = 2.0 * model.screen_offset / view.viewport;
+    out.position = vec4<f32>(pos.xy + offset * pos.w, mesh_model.layer_depth * pos.w, pos.w);
     // out.position = vec4<f32>(model.position, 0.0, 1.0);
     out.line_color = model.line_color;
     out.tri_color = model.tri_color;
     return out;
 }
 
+// Offsets the depth of a vertex by `offset`, scaled by w so that it survives the division by w
+fn offset_depth(out: VertexOutput, offset: f32) -> VertexOutput {
+    var offset_out = out;
+    offset_out.position.z += offset * out.position.w;
+    return offset_out;
+}
+
+@vertex
+fn vs_main( model: VertexInput) -> VertexOutput {
+    return transform(model);
+}
+
+// Draws the line cells of a mesh, offset in depth by its line depth bias
+@vertex
+fn vs_main_line( model: VertexInput) -> VertexOutput {
+    return offset_depth(transform(model), mesh_model.line_depth_offset);
+}
+
+// Draws the edges of the triangle cells of a mesh as line cells, offset in depth like the edges
+// drawn by line polygon mode
+@vertex
+fn vs_main_edge( model: VertexInput) -> VertexOutput {
+    return offset_depth(transform(model), mesh_model.edge_depth_offset);
+}
+
+// Draws the selected mesh with the highlight color in place of its own line color
+@vertex
+fn vs_main_highlight( model: VertexInput) -> VertexOutput {
+    var out = transform(model);
+    out.line_color = view.highlight_color;
+    return out;
+}
+
+fn with_mesh_color(model: CompactVertexInput) -> VertexInput {
+    return VertexInput(
+        model.position,
+        mesh_color.line_color,
+        mesh_color.tri_color,
+        model.screen_offset,
+    );
+}
+
+// Draws a mesh with a uniform color, read from `mesh_color` rather than from its vertices
+@vertex
+fn vs_main_uniform( model: CompactVertexInput) -> VertexOutput {
+    return transform(with_mesh_color(model));
+}
+
+@vertex
+fn vs_main_uniform_line( model: CompactVertexInput) -> VertexOutput {
+    return offset_depth(transform(with_mesh_color(model)), mesh_model.line_depth_offset);
+}
+
+@vertex
+fn vs_main_uniform_edge( model: CompactVertexInput) -> VertexOutput {
+    return offset_depth(transform(with_mesh_color(model)), mesh_model.edge_depth_offset);
+}
+
+@vertex
+fn vs_main_uniform_highlight( model: CompactVertexInput) -> VertexOutput {
+    var out = transform(with_mesh_color(model));
+    out.line_color = view.highlight_color;
+    return out;
+}
+
+// Expands the segment between the clip space positions `a` and `b` into a quad `width` pixels
+// wide and returns the clip space position of its corner `corner`, from 0 to 5. The corners make
+// up the triangles (a-, a+, b-) and (b-, a+, b+), where - and + are the two sides of the segment.
+fn expand_segment(a: vec4<f32>, b: vec4<f32>, corner: u32, width: f32) -> vec4<f32> {
+    // corners 2, 3 and 5 lie at the end b, corners 1, 4 and 5 on the + side
+    let at_b = ((0x2Cu >> corner) & 1u) == 1u;
+    let side = f32((0x32u >> corner) & 1u) * 2.0 - 1.0;
+    // the direction of the segment on the screen, in pixels
+    let along = (b.xy / b.w - a.xy / a.w) * view.viewport;
+    var dir = vec2<f32>(1.0, 0.0);
+    if (dot(along, along) > 0.0) {
+        dir = normalize(along);
+    }
+    // half the width in pixels, where clip space spans 2 units across the viewport
+    let offset = side * width * vec2<f32>(-dir.y, dir.x) / view.viewport;
+    let end = select(a, b, at_b);
+    return vec4<f32>(end.xy + offset * end.w, end.z, end.w);
+}
+
+// Draws the edge from `a` to `b`, one per instance, as a quad `view.edge_width` pixels wide
+@vertex
+fn vs_main_thick_edge(
+    @builtin(vertex_index) corner: u32,
+    a: VertexInput,
+    b: EdgeEndInput,
+) -> VertexOutput {
+    var out = transform(a);
+    let far = transform(VertexInput(b.position, b.line_color, b.tri_color, b.screen_offset));
+    out.position = expand_segment(out.position, far.position, corner, view.edge_width);
+    return out;
+}
+
 @fragment 
 fn fs_main_line(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(in.line_color, 1.0);
+    return in.line_color;
 }
 
 @fragment 
 fn fs_main_triangle(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(in.tri_color, 1.0);  
+    return in.tri_color;
 }
\ No newline at end of file
@@ -5,10 +5,14 @@
 
 //{{{ crate imports 
 use super::{
-    camera::View,
-    mesh::{AxesDescriptor, CircleDescriptor, Mesh, Mesh2D, SquareDescriptor, LineDescriptor},
+    camera::{CameraDescriptor, View},
+    mesh::{
+        AxesDescriptor, CircleDescriptor, Descriptor, Mesh, Mesh2D, RectangleDescriptor,
+        SquareDescriptor, LineDescriptor, OutlineDescriptor,
+    },
     vertex::Vertex 
 };
+use crate::common::{AddMeshError, Color, DrawContext, Vec2, Vec3, ViewSensitivity};
 use crate::core::{StateCore, ViewStateCore};
 use crate::events::EventController;
 //}}}
@@ -18,6 +22,48 @@ use crate::events::EventController;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
+/// The number of sides of the circles added by `add_circle_simple`.
+const SIMPLE_CIRCLE_SIDES: u32 = 64;
+
+//{{{ fun: simple_rectangle
+/// Returns the axis-aligned rectangle with the given center and side lengths drawn with
+/// `context`, as added by the `add_rectangle_simple` methods of the state and client.
+pub(crate) fn simple_rectangle(
+    context: &DrawContext,
+    center: Vec2,
+    width: f32,
+    height: f32,
+) -> RectangleDescriptor
+{
+    RectangleDescriptor {
+        center: center,
+        width: width,
+        height: height,
+        rotation: 0.0,
+        line_color: context.default_line_color,
+        tri_color: context.default_tri_color,
+        cell_type: context.default_cell_type,
+    }
+}
+//}}}
+//{{{ fun: simple_circle
+/// Returns the circle with the given center and radius drawn with `context`, as added by the
+/// `add_circle_simple` methods of the state and client.
+pub(crate) fn simple_circle(context: &DrawContext, center: Vec2, radius: f32) -> CircleDescriptor
+{
+    CircleDescriptor {
+        center: center,
+        radius: radius,
+        num_sides: SIMPLE_CIRCLE_SIDES,
+        line_color: context.default_line_color,
+        tri_color: context.default_tri_color,
+        cell_type: context.default_cell_type,
+        screen_space: false,
+        fill_and_outline: false,
+    }
+}
+//}}}
+
 //{{{ collection: ViewState
 //{{{ struct: VeiwState
 #[derive(Default, Debug)]
@@ -38,6 +84,11 @@ impl ViewState
             view_controller: EventController::default(),
         }
     }
+
+    pub fn view(&self) -> &View
+    {
+        &self.view
+    }
 }
 //}}}
 //{{{ impl: ViewStateCore for ViewState
@@ -57,6 +108,61 @@ impl ViewStateCore for ViewState
     {
         bytemuck::bytes_of(&self.view.uniform)
     }
+
+    fn set_viewport_size(&mut self, width: u32, height: u32)
+    {
+        self.view.set_viewport_size(width as f32, height as f32);
+    }
+
+    fn set_keep_aspect(&mut self, keep_aspect: bool)
+    {
+        self.view.set_keep_aspect(keep_aspect);
+    }
+
+    fn reset_view(&mut self)
+    {
+        self.view.reset();
+    }
+
+    fn set_highlight_color(&mut self, color: &Color)
+    {
+        self.view.set_highlight_color(color);
+    }
+
+    fn set_edge_width(&mut self, width: f32)
+    {
+        self.view.set_edge_width(width);
+    }
+
+    fn sensitivity(&self) -> ViewSensitivity
+    {
+        ViewSensitivity {
+            key_pan_delta: self.view.options.key_pan_delta,
+            key_rotate_delta: self.view.options.rotate_delta,
+            zoom_speed: self.view.options.zoom_speed,
+        }
+    }
+
+    fn set_sensitivity(&mut self, sensitivity: &ViewSensitivity)
+    {
+        self.view.options.key_pan_delta = sensitivity.key_pan_delta;
+        self.view.options.rotate_delta = sensitivity.key_rotate_delta;
+        self.view.options.zoom_speed = sensitivity.zoom_speed;
+    }
+
+    fn pick_ray(&self, pixel: Vec2) -> (Vec3, Vec3)
+    {
+        // 2D meshes lie in the plane z = 0, so a ray along the z-axis through the world position
+        // of the pixel hits exactly the filled shapes containing it
+        let viewport = self.view.viewport();
+        let world = self.view.screen_to_world(pixel, Vec2::new(viewport[0], viewport[1]));
+        (Vec3::new(world[0], world[1], -1.0), Vec3::z())
+    }
+
+    fn cursor_world_position(&self) -> Option<Vec2>
+    {
+        Some(self.view_controller.mouse_world_position_2d(&self.view))
+    }
 }
 //..................................................................................................
 //}}}
@@ -70,22 +176,34 @@ pub trait State2D<'a>
     fn add_axes(
         &mut self,
         axes_desc: &AxesDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
 
     fn add_line(
         &mut self, 
         line_desc: &LineDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
 
     fn add_square(
         &mut self,
         square_desc: &SquareDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
+
+    fn add_rectangle(
+        &mut self,
+        rectangle_desc: &RectangleDescriptor,
+    ) -> Result<usize, AddMeshError>;
 
     fn add_circle(
         &mut self,
         circle_desc: &CircleDescriptor,
-    ) -> usize; 
+    ) -> Result<usize, AddMeshError>; 
+
+    fn add_outline(
+        &mut self,
+        outline_desc: &OutlineDescriptor,
+    ) -> Result<usize, AddMeshError>;
+
+    fn camera(&self) -> CameraDescriptor;
 }
 //}}}
 //{{{ impl: State2D for State
@@ -94,7 +212,7 @@ impl<'a> State2D<'a> for State<'a>
     fn add_axes(
         &mut self,
         axes_desc: &AxesDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let axes_mesh = Mesh::create_axes(axes_desc);
         self.add_mesh(axes_mesh)
@@ -103,7 +221,7 @@ impl<'a> State2D<'a> for State<'a>
     fn add_line(
         &mut self, 
         line_desc: &LineDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let line_mesh = Mesh::create_line(line_desc);
         self.add_mesh(line_mesh)
@@ -112,20 +230,94 @@ impl<'a> State2D<'a> for State<'a>
     fn add_square(
         &mut self,
         square_desc: &SquareDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let square_mesh = Mesh::create_square(square_desc);
         self.add_mesh(square_mesh)
     }
 
+    fn add_rectangle(
+        &mut self,
+        rectangle_desc: &RectangleDescriptor,
+    ) -> Result<usize, AddMeshError>
+    {
+        let rectangle_mesh = Mesh::create_rectangle(rectangle_desc);
+        self.add_mesh(rectangle_mesh)
+    }
+
     fn add_circle(
         &mut self,
         circle_desc: &CircleDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
-        let circle_mesh = Mesh::create_circle(circle_desc);
+        let mut circle_desc = circle_desc.clone();
+        circle_desc.apply_quality(self.tessellation_quality());
+        let circle_mesh = Mesh::create_circle(&circle_desc);
         self.add_mesh(circle_mesh)
     }
+
+    fn add_outline(
+        &mut self,
+        outline_desc: &OutlineDescriptor,
+    ) -> Result<usize, AddMeshError>
+    {
+        let outline_mesh = Mesh::create_outline(outline_desc);
+        self.add_mesh(outline_mesh)
+    }
+
+    fn camera(&self) -> CameraDescriptor
+    {
+        self.view_state()
+            .map_or_else(|| View::default().camera_descriptor(), |vs| vs.view().camera_descriptor())
+    }
+}
+//}}}
+//{{{ impl: State
+/// Simplified versions of the `add_*` methods of [`State2D`], taking only the geometry of the
+/// shapes and drawing them with the draw context of the state.
+impl<'a> State<'a>
+{
+    /// Adds the axis-aligned rectangle with the given center and side lengths, drawn with the
+    /// draw context of the state.
+    pub fn add_rectangle_simple(
+        &mut self,
+        center: Vec2,
+        width: f32,
+        height: f32,
+    ) -> Result<usize, AddMeshError>
+    {
+        let rectangle_desc = simple_rectangle(&self.draw_context(), center, width, height);
+        self.add_rectangle(&rectangle_desc)
+    }
+
+    /// Adds the circle with the given center and radius, drawn with the draw context of the
+    /// state.
+    pub fn add_circle_simple(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+    ) -> Result<usize, AddMeshError>
+    {
+        let circle_desc = simple_circle(&self.draw_context(), center, radius);
+        self.add_circle(&circle_desc)
+    }
+
+    /// Adds the shape the descriptor holds through the matching `add_*` method of [`State2D`],
+    /// so that subdivision counts left at zero come from the tessellation quality of the state.
+    pub fn add_descriptor(
+        &mut self,
+        descriptor: &Descriptor,
+    ) -> Result<usize, AddMeshError>
+    {
+        match descriptor {
+            Descriptor::Axes(axes) => self.add_axes(axes),
+            Descriptor::Line(line) => self.add_line(line),
+            Descriptor::Square(square) => self.add_square(square),
+            Descriptor::Rectangle(rectangle) => self.add_rectangle(rectangle),
+            Descriptor::Circle(circle) => self.add_circle(circle),
+            Descriptor::Outline(outline) => self.add_outline(outline),
+        }
+    }
 }
 //}}}
 
@@ -134,6 +326,24 @@ impl<'a> State2D<'a> for State<'a>
 #[cfg(test)]
 mod tests
 {
-  
+    use super::*;
+    use winit::dpi::PhysicalPosition;
+    use winit::event::MouseScrollDelta;
+
+    #[test]
+    fn cursor_world_position_test()
+    {
+        let mut view_state = ViewState::default();
+        view_state.set_viewport_size(800, 600);
+        // zoom in, so that the world coordinates differ from the normalized device coordinates
+        view_state.view_controller().mouse_wheel_update(MouseScrollDelta::LineDelta(0.0, 2.0));
+        view_state.update();
+
+        let pixel = Vec2::new(200.0, 450.0);
+        view_state.view_controller().cursor_moved_update(PhysicalPosition::new(200.0, 450.0));
+        let expected = view_state.view().screen_to_world(pixel, Vec2::new(800.0, 600.0));
+        assert_eq!(view_state.cursor_world_position(), Some(expected));
+        assert!((expected - Vec2::new(-0.5, -0.5)).norm() > 1e-3);
+    }
 }
 //}}}
\ No newline at end of file
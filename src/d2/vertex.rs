@@ -9,6 +9,8 @@ pub struct VertexDescriptor
     pub position: Vec2,
     pub line_color: Color,
     pub triangle_color: Color,
+    /// Offset in pixels applied after the view transform, zero for world-space geometry
+    pub screen_offset: Vec2,
 }
 
 #[repr(C)]
@@ -17,21 +19,28 @@ pub struct Vertex
 {
     /// The internal data has the layout:
     /// - position: [x, y]
-    /// - line_color : [r, g, b]
-    /// - triangle_color : [r, g, b]
-    data: [f32; 8],
+    /// - line_color : [r, g, b, a]
+    /// - triangle_color : [r, g, b, a]
+    /// - screen_offset : [dx, dy]
+    data: [f32; 12],
 }
 
 impl Vertex
 {
     pub fn new(vert_desc: &VertexDescriptor) -> Vertex
     {
-        let mut data = [0.0; 8];
+        let mut data = [0.0; 12];
         data[0..2].copy_from_slice(vert_desc.position.as_slice());
-        data[2..5].copy_from_slice(&vert_desc.line_color.to_rgb());
-        data[5..8].copy_from_slice(&vert_desc.triangle_color.to_rgb());
+        data[2..6].copy_from_slice(&vert_desc.line_color.to_rgba());
+        data[6..10].copy_from_slice(&vert_desc.triangle_color.to_rgba());
+        data[10..12].copy_from_slice(vert_desc.screen_offset.as_slice());
         Vertex { data }
     }
+
+    pub fn screen_offset_offset() -> usize
+    {
+        10
+    }
 }
 
 impl VertexCore for Vertex
@@ -59,17 +68,53 @@ impl VertexCore for Vertex
                 wgpu::VertexAttribute {
                     offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
                     shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x3,
+                    format: wgpu::VertexFormat::Float32x4,
                 },
                 wgpu::VertexAttribute {
-                    offset: mem::size_of::<[f32; 5]>() as wgpu::BufferAddress,
+                    offset: mem::size_of::<[f32; 6]>() as wgpu::BufferAddress,
                     shader_location: 2,
-                    format: wgpu::VertexFormat::Float32x3,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 10]>() as wgpu::BufferAddress,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+            ],
+        }
+    }
+
+    fn compact_desc() -> wgpu::VertexBufferLayout<'static>
+    {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x2,
                 },
             ],
         }
     }
 
+    fn write_compact(
+        vertex: &[f32],
+        out: &mut Vec<f32>,
+    )
+    {
+        let offset = Vertex::screen_offset_offset();
+        out.extend_from_slice(&vertex[0..2]);
+        out.extend_from_slice(&vertex[offset..offset + 2]);
+    }
+
     fn position_offset() -> usize
     {
         0
@@ -82,12 +127,18 @@ impl VertexCore for Vertex
 
     fn triangle_color_offset() -> usize
     {
-        5
+        6
     }
 
     fn len() -> usize
     {
-        8
+        12
+    }
+
+    fn position(vertex: &[f32]) -> Vec2
+    {
+        let start = Vertex::position_offset();
+        Vec2::from_column_slice(&vertex[start..start + 2])
     }
 
     fn dim() -> usize
@@ -133,38 +184,38 @@ impl<'a> VertexViewCore<'a> for VertexView<'a>
 
     fn set_line_color(
         &mut self,
-        color: &[f32; 3],
+        color: &[f32; 4],
     )
     {
         let start = Vertex::line_color_offset();
-        let end = Vertex::line_color_offset() + 3;
+        let end = Vertex::line_color_offset() + 4;
         self.vertex_data[start..end].copy_from_slice(color);
     }
 
-    fn get_line_color(&self) -> [f32; 3]
+    fn get_line_color(&self) -> [f32; 4]
     {
         let start = Vertex::line_color_offset();
-        let end = Vertex::line_color_offset() + 3;
-        let mut color = [0.0; 3];
+        let end = Vertex::line_color_offset() + 4;
+        let mut color = [0.0; 4];
         color.copy_from_slice(&self.vertex_data[start..end]);
         color
     }
 
     fn set_triangle_color(
         &mut self,
-        color: &[f32; 3],
+        color: &[f32; 4],
     )
     {
         let start = Vertex::triangle_color_offset();
-        let end = Vertex::triangle_color_offset() + 3;
+        let end = Vertex::triangle_color_offset() + 4;
         self.vertex_data[start..end].copy_from_slice(color);
     }
 
-    fn get_triangle_color(&self) -> [f32; 3]
+    fn get_triangle_color(&self) -> [f32; 4]
     {
         let start = Vertex::triangle_color_offset();
-        let end = Vertex::triangle_color_offset() + 3;
-        let mut color = [0.0; 3];
+        let end = Vertex::triangle_color_offset() + 4;
+        let mut color = [0.0; 4];
         color.copy_from_slice(&self.vertex_data[start..end]);
         color
     }
@@ -9,6 +9,7 @@ use crate::common::*;
 use crate::events::*;
 //}}}
 //{{{ std imports 
+use std::time::Instant;
 //}}}
 //{{{ dep imports 
 use bytemuck::{Pod, Zeroable};
@@ -22,6 +23,27 @@ use winit::keyboard::ModifiersKeyState;
 const GLOBAL_UP: Vec3 = Vec3::new(0.0, 0.0, 1.0);
 /// The maximum angle the camera can pitch up or down.
 const PITCH_SAFE: f32 = 1.0e-2;
+/// The smallest field of view angle reachable with CTRL + mouse wheel, 1 degree.
+const FOV_MIN: f32 = 0.017453292;
+/// The largest field of view angle reachable with CTRL + mouse wheel, 170 degrees.
+const FOV_MAX: f32 = 2.9670597;
+/// The smallest half-height of the orthographic view volume.
+const ORTHO_EXTENT_MIN: f32 = 1.0e-4;
+/// The smallest factor a single mouse wheel step scales the distance of the camera or the view
+/// volume by, so that a large step cannot flip them through the focus.
+const ZOOM_FACTOR_MIN: f32 = 0.1;
+/// Maps the depth of a projection from `[-1, 1]`, near to far, onto `[1, 0]` for reversed-Z.
+const REVERSE_Z: Mat4 = Mat4::new(
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, -0.5, 0.5,
+    0.0, 0.0, 0.0, 1.0,
+);
+/// Distance of the eye from the origin of the gizmo, whose axes are of unit length.
+const GIZMO_DISTANCE: f32 = 2.0;
+/// Half-width of the square orthographic view volume of the gizmo, leaving a margin around axes
+/// of unit length.
+const GIZMO_EXTENT: f32 = 1.2;
 /// The initial distance of the camer
 const INV_SQRT_3: f32 = 0.5773502691896258;
 /// Positions of the 8 octants of the sphere.
@@ -65,19 +87,54 @@ pub struct Camera
     /// Current quadrant the camera resides in. Note this is only the exact octant of the camera
     /// when the user presses <SHIFT + UP/DOWN/LEFT/RIGHT>
     octant: i8,
+    /// roll angle of the camera about its direction vector $\mbf{d}$. A positive roll turns the
+    /// up vector clockwise as seen looking along $\mbf{d}$. It is defined on the range $[0, 2\pi]$
+    roll: f32,
 }
 //..................................................................................................
 //}}}
 //{{{ impl: Camera
 impl Camera
 {
+    /// Creates a camera at unit distance from the origin in the given octant, looking at the
+    /// origin. Its pitch and yaw are those of its position.
+    pub fn from_octant(octant: i8) -> Self
+    {
+        let position = DIAGONAL_POSITIONS[octant as usize];
+        let (pitch, yaw) = pitch_and_yaw(&position);
+        Self {
+            position: position,
+            focus: Vec3::zeros(),
+            pitch: pitch,
+            yaw: yaw,
+            octant: octant,
+            roll: 0.0,
+        }
+    }
+
     pub fn calc_matrix(&self) -> Mat4
     {
         let position: Poi3 = self.position.into();
 
         let target: Poi3 = self.focus.into();
 
-        Mat4::look_at_rh(&position, &target, &GLOBAL_UP)
+        Mat4::look_at_rh(&position, &target, &self.up())
+    }
+
+    /// Returns the up vector of the camera, that is the global up vector rotated about the camera
+    /// direction by the roll angle.
+    pub fn up(&self) -> Vec3
+    {
+        let axis = nalgebra::Unit::new_normalize(self.direction());
+        nalgebra::Rotation3::from_axis_angle(&axis, self.roll) * GLOBAL_UP
+    }
+
+    pub fn roll(
+        &mut self,
+        delta: f32,
+    )
+    {
+        self.roll = mod_angle(self.roll + delta);
     }
 
     pub fn set_octant(
@@ -85,7 +142,7 @@ impl Camera
         new_oct: i8,
     )
     {
-        assert!(new_oct < 8);
+        assert!((0..8).contains(&new_oct));
 
         self.octant = new_oct;
 
@@ -134,22 +191,65 @@ impl Camera
     )
     {
         let forward = (self.focus - self.position).normalize();
-        let up = GLOBAL_UP;
-        let right = forward.cross(&GLOBAL_UP).normalize();
+        let right = forward.cross(&self.up()).normalize();
+        let up = right.cross(&forward);
         let displacement = up * delta_y + right * delta_x;
         self.position += displacement;
         self.focus += displacement;
     }
 
+    /// Places the camera at `position` looking at `focus`, keeping its roll.
+    fn look_from(
+        &mut self,
+        position: Vec3,
+        focus: Vec3,
+    )
+    {
+        self.position = position;
+        self.focus = focus;
+        let (pitch, yaw) = pitch_and_yaw(&(position - focus).normalize());
+        self.pitch = pitch;
+        self.yaw = yaw;
+    }
+
     fn dist(&self) -> f32
     {
         (self.position - self.focus).norm()
     }
 
+    /// Scales the distance of the camera from its focus by `factor`, keeping its direction.
+    fn scale_dist(
+        &mut self,
+        factor: f32,
+    )
+    {
+        self.position = self.focus + (self.position - self.focus) * factor;
+    }
+
     fn direction(&self) -> Vec3
     {
         (self.focus - self.position).normalize()
     }
+
+    pub fn position(&self) -> Vec3
+    {
+        self.position
+    }
+
+    pub fn focus(&self) -> Vec3
+    {
+        self.focus
+    }
+
+    pub fn pitch(&self) -> f32
+    {
+        self.pitch
+    }
+
+    pub fn yaw(&self) -> f32
+    {
+        self.yaw
+    }
 }
 //}}}
 //{{{ impl: Default for Camera
@@ -157,27 +257,37 @@ impl Default for Camera
 {
     fn default() -> Self
     {
-        Self {
-            position: DIAGONAL_POSITIONS[0],
-            focus: Vec3::zeros(),
-            pitch: 2.186276,
-            yaw: -2.3561945,
-            octant: 0,
-        }
+        Self::from_octant(0)
     }
 }
 //..................................................................................................
 //}}}
 //}}}
 //{{{ col: Projection
+//{{{ enum: ProjectionKind
+/// The kind of projection used to map view coordinates to clip coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectionKind
+{
+    /// Objects shrink with distance from the camera, controlled by the field of view.
+    Perspective,
+    /// Objects keep their size regardless of distance, controlled by the extent of the view volume.
+    Orthographic,
+}
+//}}}
 //{{{ struct: Projection
 #[derive(Debug)]
 pub struct Projection
 {
+    kind: ProjectionKind,
     fov: f32,
+    /// Half-height of the view volume when using an orthographic projection
+    ortho_extent: f32,
     near: f32,
     far: f32,
     aspect: f32,
+    /// Maps the near plane to a depth of one and the far plane to zero
+    reversed_z: bool,
 }
 //}}}
 //{{{ impl: Projection
@@ -185,9 +295,79 @@ impl Projection
 {
     pub fn calc_matrix(&self) -> Mat4
     { 
-        Mat4::new_perspective(self.aspect, self.fov, self.near, self.far)
+        let proj = match self.kind
+        {
+            ProjectionKind::Perspective => 
+            {
+                Mat4::new_perspective(self.aspect, self.fov, self.near, self.far)
+            }
+            ProjectionKind::Orthographic =>
+            {
+                let h = self.ortho_extent;
+                let w = h * self.aspect;
+                Mat4::new_orthographic(-w, w, -h, h, self.near, self.far)
+            }
+        };
+        if self.reversed_z { REVERSE_Z * proj } else { proj }
         // OPENGL_TO_WGPU_MATRIX * Mat4::new_perspective(self.aspect, self.fov, self.near, self.far)
     }
+
+    /// Changes the field of view by `delta` radians, clamped to a sensible range.
+    pub fn change_fov(&mut self, delta: f32)
+    {
+        self.fov = (self.fov + delta).clamp(FOV_MIN, FOV_MAX);
+    }
+
+    pub fn fov(&self) -> f32
+    {
+        self.fov
+    }
+
+    /// Scales the orthographic view volume, a factor less than one zooms in.
+    pub fn scale_extent(&mut self, factor: f32)
+    {
+        self.ortho_extent = (self.ortho_extent * factor).max(ORTHO_EXTENT_MIN);
+    }
+
+    /// Scales the near and far planes and the orthographic view volume by `factor`.
+    fn scale(&mut self, factor: f32)
+    {
+        self.near *= factor;
+        self.far *= factor;
+        self.ortho_extent = (self.ortho_extent * factor).max(ORTHO_EXTENT_MIN);
+    }
+
+    /// Returns the normalized device depths of the near and far planes.
+    pub fn depth_range(&self) -> (f32, f32)
+    {
+        if self.reversed_z { (1.0, 0.0) } else { (-1.0, 1.0) }
+    }
+
+    pub fn set_reversed_z(&mut self, enabled: bool)
+    {
+        self.reversed_z = enabled;
+    }
+
+    pub fn reversed_z(&self) -> bool
+    {
+        self.reversed_z
+    }
+
+    /// Maps values read from the depth buffer back to the distance in front of the camera, by
+    /// unprojecting them with the inverse of the projection matrix.
+    pub fn linearize_depths(&self, depths: &mut [f32])
+    {
+        let Some(inv_proj) = self.calc_matrix().try_inverse()
+        else
+        {
+            return;
+        };
+        for depth in depths.iter_mut()
+        {
+            let p = inv_proj * Vec4::new(0.0, 0.0, *depth, 1.0);
+            *depth = -p[2] / p[3];
+        }
+    }
 }
 //}}}
 //{{{ impl: Default for Projection
@@ -196,16 +376,32 @@ impl Default for Projection
     fn default() -> Self
     {
         Self {
+            kind: ProjectionKind::Perspective,
             fov: rad(45.0),
+            ortho_extent: 1.0,
             near: 0.1,
             far: 100.0,
             aspect: 2.0,
+            reversed_z: false,
         }
     }
 }
 //..................................................................................................
 //}}}
 //}}}
+//{{{ struct: CameraDescriptor
+/// A snapshot of the camera of a 3D view, used to report the viewpoint to clients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraDescriptor
+{
+    pub position: Vec3,
+    pub focus: Vec3,
+    pub pitch: f32,
+    pub yaw: f32,
+    pub fov: f32,
+}
+//..................................................................................................
+//}}}
 //{{{ col: ViewOptions
 //{{{ struct: ViewOptions
 /// This struct contains options for how the internal state of View changes in response to
@@ -221,8 +417,26 @@ pub struct ViewOptions
     /// This is the radian delta in each either the pitch or yaw angles the camera will move
     /// in reponse to arrow key presses with no modifiers
     pub key_orbit_delta: f32,
-    /// This is the sensitivety of the mouse wheel when moving forward and backward
+    /// This is the radian delta in the roll angle the camera will move in response to left/right
+    /// arrow key presses with the control key held down
+    pub key_roll_delta: f32,
+    /// This is the sensitivety of the mouse wheel when moving forward and backward, as the
+    /// fraction of the distance of the camera from its focus moved per unit of wheel delta, so
+    /// that zooming feels the same at any scale
     pub zoom_speed: f32,
+    /// This is the sensitivety of the mouse wheel when changing the field of view with the
+    /// control key held down
+    pub fov_speed: f32,
+    /// The time in seconds the camera takes to move to a new octant when SHIFT + arrow keys are
+    /// pressed, zero jumps straight to the new viewpoint
+    pub animation_duration: f32,
+    /// The octant the camera starts in, and returns to when the view is reset, only set through
+    /// `View::set_initial_octant` which keeps it in range
+    initial_octant: i8,
+    /// The number of world units in a meter. The pan delta, the near and far planes and the
+    /// distance the camera starts at are made for one, and are scaled with it by
+    /// `View::set_units_per_meter`, so that scenes in millimeters, say, use a thousand.
+    pub units_per_meter: f32,
 }
 //}}}
 //{{{ impl: Default for ViewOptions
@@ -233,11 +447,26 @@ impl Default for ViewOptions
         Self {
             key_pan_delta: 0.1,
             key_orbit_delta: rad(2.5),
+            key_roll_delta: rad(2.5),
             zoom_speed: 0.001,
+            fov_speed: 0.001,
+            animation_duration: 0.25,
+            initial_octant: 0,
+            units_per_meter: 1.0,
         }
     }
 }
 //}}}
+//{{{ impl: ViewOptions
+impl ViewOptions
+{
+    /// Returns the octant the camera starts in, and returns to when the view is reset.
+    pub fn initial_octant(&self) -> i8
+    {
+        self.initial_octant
+    }
+}
+//}}}
 //..................................................................................................
 //}}}
 //{{{ col: View
@@ -255,6 +484,9 @@ pub struct View
     camera: Camera,
     projection: Projection,
     pub uniform: ViewUniform,
+    /// The uniform of the gizmo, whose view only follows the orientation of the camera
+    gizmo_uniform: ViewUniform,
+    animation: Option<CameraAnimation>,
 }
 //}}}
 //{{{ impl: View
@@ -265,6 +497,101 @@ impl View
         self.projection.calc_matrix() * self.camera.calc_matrix()
     }
 
+    pub fn camera(&self) -> &Camera
+    {
+        &self.camera
+    }
+
+    pub fn projection(&self) -> &Projection
+    {
+        &self.projection
+    }
+
+    /// Returns a snapshot of the current camera position, orientation and field of view.
+    pub fn camera_descriptor(&self) -> CameraDescriptor
+    {
+        CameraDescriptor {
+            position: self.camera.position(),
+            focus: self.camera.focus(),
+            pitch: self.camera.pitch(),
+            yaw: self.camera.yaw(),
+            fov: self.projection.fov(),
+        }
+    }
+
+    /// Switches between perspective and orthographic projection.
+    ///
+    /// When switching to orthographic the extent of the view volume is chosen so that objects at
+    /// the focus of the camera keep their size on screen.
+    pub fn set_projection_kind(&mut self, kind: ProjectionKind)
+    {
+        if kind == ProjectionKind::Orthographic && self.projection.kind != kind
+        {
+            self.projection.ortho_extent = self.camera.dist() * (0.5 * self.projection.fov).tan();
+        }
+        self.projection.kind = kind;
+    }
+
+    /// Returns the kind of projection currently in use.
+    pub fn projection_kind(&self) -> ProjectionKind
+    {
+        self.projection.kind
+    }
+
+    /// Switches the projection to reversed-Z, which must match the depth test of the pipelines
+    /// the view is rendered with.
+    pub fn set_reversed_z(&mut self, enabled: bool)
+    {
+        self.projection.set_reversed_z(enabled);
+        self.update_uniform();
+    }
+
+    /// Moves the camera to look at its focus from the given octant, 0 to 7 as numbered by
+    /// `octant`, and makes it the octant the view returns to when reset. Any other octant leaves
+    /// the view unchanged and an error is returned.
+    pub fn set_initial_octant(&mut self, octant: i8) -> Result<(), &'static str>
+    {
+        if !(0..8).contains(&octant)
+        {
+            return Err("octant");
+        }
+        self.options.initial_octant = octant;
+        self.camera.set_octant(octant);
+        self.update_uniform();
+        Ok(())
+    }
+
+    /// Scales the camera to a scene with `units_per_meter` world units in a meter, moving it
+    /// away from its focus and scaling the clipping planes and the pan delta by the change from
+    /// the current number of units. The zoom is already relative to the distance of the camera.
+    pub fn set_units_per_meter(&mut self, units_per_meter: f32)
+    {
+        let factor = units_per_meter / self.options.units_per_meter;
+        self.options.units_per_meter = units_per_meter;
+        self.options.key_pan_delta *= factor;
+        self.camera.scale_dist(factor);
+        self.projection.scale(factor);
+        self.update_uniform();
+    }
+
+    /// Sets the size of the framebuffer in pixels and the aspect ratio of the projection to
+    /// match. A zero size, as reported for a minimised window, leaves the aspect ratio as it is.
+    pub fn set_viewport_size(&mut self, width: f32, height: f32)
+    {
+        self.uniform.viewport = [width, height];
+        if width > 0.0 && height > 0.0
+        {
+            self.projection.aspect = width / height;
+        }
+        self.update_uniform();
+    }
+
+    /// Rolls the camera about its view direction by `delta` radians.
+    pub fn roll(&mut self, delta: f32)
+    {
+        self.camera.roll(delta);
+    }
+
     pub fn update_uniform(&mut self)
     {
         self.uniform.view_position[0..3].copy_from_slice(self.camera.position.as_slice());
@@ -276,11 +603,253 @@ impl View
 
         let view_proj: Mat4 = self.calc_matrix();
         self.uniform.view_proj = view_proj.into();
+
+        self.gizmo_uniform = self.uniform;
+        self.gizmo_uniform.view_proj = self.gizmo_matrix().into();
+        // the gizmo lives in a space of its own, which the clipping plane does not cut
+        self.gizmo_uniform.clip_plane_count = 0;
+    }
+
+    /// Returns the view-projection matrix of the gizmo. It looks at the origin along the camera
+    /// direction with the camera's up vector, so the gizmo turns with the camera but ignores its
+    /// position, zoom and projection.
+    pub fn gizmo_matrix(&self) -> Mat4
+    {
+        let eye: Poi3 = (self.camera.direction() * -GIZMO_DISTANCE).into();
+        let view = Mat4::look_at_rh(&eye, &Poi3::origin(), &self.camera.up());
+        let e = GIZMO_EXTENT;
+        let proj = Mat4::new_orthographic(-e, e, -e, e, GIZMO_DISTANCE - e, GIZMO_DISTANCE + e);
+        // the gizmo is not depth tested, but the depths must still land in [0, 1] to avoid clipping
+        REVERSE_Z * proj * view
+    }
+
+    /// Returns the uniform the gizmo is drawn with, kept up to date by `update_uniform`.
+    pub fn gizmo_uniform(&self) -> &ViewUniform
+    {
+        &self.gizmo_uniform
+    }
+
+    /// Restores the default camera and projection, the view options, the aspect ratio of the
+    /// window and the depth mode are kept.
+    pub fn reset(&mut self)
+    {
+        let aspect = self.projection.aspect;
+        let reversed_z = self.projection.reversed_z;
+        self.camera = Camera::from_octant(self.options.initial_octant);
+        self.camera.scale_dist(self.options.units_per_meter);
+        self.projection = Projection::default();
+        self.projection.scale(self.options.units_per_meter);
+        self.projection.aspect = aspect;
+        self.projection.reversed_z = reversed_z;
+        self.animation = None;
+        self.update_uniform();
+    }
+
+    /// Moves the camera so that it looks at `target_focus` from `target_position`, smoothly over
+    /// `duration` seconds.
+    ///
+    /// The animation is advanced by `advance_animation`, which is called every time the view is
+    /// updated. A duration of zero jumps straight to the target.
+    pub fn animate_to(
+        &mut self,
+        target_position: Vec3,
+        target_focus: Vec3,
+        duration: f32,
+    )
+    {
+        self.camera.octant = octant(&(target_position - target_focus));
+        if duration > 0.0
+        {
+            self.animation = Some(CameraAnimation {
+                start_position: self.camera.position,
+                start_focus: self.camera.focus,
+                target_position: target_position,
+                target_focus: target_focus,
+                start: Instant::now(),
+                duration: duration,
+            });
+        }
+        else
+        {
+            self.animation = None;
+            self.camera.look_from(target_position, target_focus);
+            self.update_uniform();
+        }
+    }
+
+    /// Points the camera at the centre of the box from `min` to `max` and moves it along its
+    /// current view direction until the whole box is in view, smoothly over `duration` seconds.
+    ///
+    /// The box is framed by its bounding sphere, fitted to the narrower of the vertical and
+    /// horizontal fields of view. With an orthographic projection the extent of the view volume
+    /// is fitted to the sphere as well. A box of a single point keeps the distance of the camera.
+    pub fn frame_box(
+        &mut self,
+        min: Vec3,
+        max: Vec3,
+        duration: f32,
+    )
+    {
+        let centre = 0.5 * (min + max);
+        let radius = 0.5 * (max - min).norm();
+        let mut dist = self.camera.dist();
+        if radius > 0.0
+        {
+            let half_fov = 0.5 * self.projection.fov;
+            let half_angle = half_fov.min((self.projection.aspect * half_fov.tan()).atan());
+            dist = radius / half_angle.sin();
+            if self.projection.kind == ProjectionKind::Orthographic
+            {
+                let extent = radius * (1.0 / self.projection.aspect).max(1.0);
+                self.projection.ortho_extent = extent.max(ORTHO_EXTENT_MIN);
+            }
+        }
+        let position = centre - dist * self.camera.direction();
+        self.animate_to(position, centre, duration);
+    }
+
+    /// Moves the camera along the current animation to where it should be at time `now`,
+    /// returning whether the animation is still running.
+    pub fn advance_animation(&mut self, now: Instant) -> bool
+    {
+        let Some(animation) = self.animation
+        else
+        {
+            return false;
+        };
+        let t = animation.progress(now);
+        if t >= 1.0
+        {
+            self.camera.look_from(animation.target_position, animation.target_focus);
+            self.animation = None;
+        }
+        else
+        {
+            let (position, focus) = animation.interpolate(smoothstep(t));
+            self.camera.look_from(position, focus);
+        }
+        self.animation.is_some()
+    }
+
+    /// Returns whether the camera is moving towards a target set with `animate_to`.
+    pub fn is_animating(&self) -> bool
+    {
+        self.animation.is_some()
+    }
+
+    /// Returns the width and height of the framebuffer in pixels.
+    pub fn viewport(&self) -> [f32; 2]
+    {
+        self.uniform.viewport
+    }
+
+    /// Sets the color the highlighted mesh is drawn with.
+    pub fn set_highlight_color(&mut self, color: &Color)
+    {
+        let [r, g, b] = color.to_rgb();
+        self.uniform.highlight_color = [r, g, b, 1.0];
+    }
+
+    /// Sets the width, in pixels, the edges of triangle cells are drawn with when they are drawn
+    /// as quads rather than as lines.
+    pub fn set_edge_width(&mut self, width: f32)
+    {
+        self.uniform.edge_width = width;
+    }
+
+    /// Sets the clipping planes, each given as a point and a normal pointing to the side it
+    /// hides, and how they combine. Planes with a zero normal, and any past the first
+    /// [`MAX_CLIP_PLANES`], are ignored. An empty slice clips nothing.
+    pub fn set_clip_planes(&mut self, planes: &[(Vec3, Vec3)], mode: ClipMode)
+    {
+        let planes = planes
+            .iter()
+            .filter_map(|(point, normal)| normal.try_normalize(0.0).map(|normal| (point, normal)))
+            .take(MAX_CLIP_PLANES);
+        self.uniform.clip_planes = [[0.0; 4]; MAX_CLIP_PLANES];
+        let mut count = 0;
+        for (clip_plane, (point, normal)) in self.uniform.clip_planes.iter_mut().zip(planes)
+        {
+            *clip_plane = [normal[0], normal[1], normal[2], -normal.dot(point)];
+            count += 1;
+        }
+        self.uniform.clip_plane_count = count;
+        self.uniform.clip_mode = match mode
+        {
+            ClipMode::InsideAll => 0,
+            ClipMode::InsideAny => 1,
+        };
+    }
+
+    /// Returns the origin and unit direction of the ray through the given pixel, measured from
+    /// the top-left corner of the framebuffer, by unprojecting it with the inverse of the
+    /// view-projection matrix.
+    ///
+    /// The ray starts on the near plane, which for a perspective projection lies on the line from
+    /// the camera position through the pixel.
+    pub fn screen_to_ray(&self, pixel: Vec2) -> (Vec3, Vec3)
+    {
+        let viewport = self.viewport();
+        let x = 2.0 * pixel[0] / viewport[0] - 1.0;
+        let y = 1.0 - 2.0 * pixel[1] / viewport[1];
+
+        let Some(inv_view_proj) = Mat4::from(self.uniform.view_proj).try_inverse()
+        else
+        {
+            return (self.camera.position, self.camera.direction());
+        };
+        let unproject = |z: f32| {
+            let p = inv_view_proj * Vec4::new(x, y, z, 1.0);
+            p.xyz() / p[3]
+        };
+        let (near_depth, far_depth) = self.projection.depth_range();
+        let near = unproject(near_depth);
+        let far = unproject(far_depth);
+        (near, (far - near).normalize())
     }
 }
 //..................................................................................................
 //}}}
 //}}}
+//{{{ col: CameraAnimation
+//{{{ struct: CameraAnimation
+/// The camera moving from one viewpoint to another. The direction from the focus to the camera
+/// is slerped, while the distance between them and the focus itself are lerped.
+#[derive(Debug, Clone, Copy)]
+struct CameraAnimation
+{
+    start_position: Vec3,
+    start_focus: Vec3,
+    target_position: Vec3,
+    target_focus: Vec3,
+    start: Instant,
+    /// Length of the animation in seconds
+    duration: f32,
+}
+//}}}
+//{{{ impl: CameraAnimation
+impl CameraAnimation
+{
+    /// Returns the fraction of the animation elapsed at time `now`, clamped to $[0, 1]$.
+    fn progress(&self, now: Instant) -> f32
+    {
+        let elapsed = now.saturating_duration_since(self.start).as_secs_f32();
+        (elapsed / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Returns the camera position and focus a fraction `s` of the way along the animation.
+    fn interpolate(&self, s: f32) -> (Vec3, Vec3)
+    {
+        let start_offset = self.start_position - self.start_focus;
+        let target_offset = self.target_position - self.target_focus;
+        let focus = self.start_focus.lerp(&self.target_focus, s);
+        let dist = (1.0 - s) * start_offset.norm() + s * target_offset.norm();
+        let dir = slerp_direction(&start_offset.normalize(), &target_offset.normalize(), s);
+        (focus + dist * dir, focus)
+    }
+}
+//}}}
+//}}}
 //{{{ col: ViewUniform
 //{{{ struct: ViewUniform   
 #[repr(C)]
@@ -290,6 +859,21 @@ pub struct ViewUniform
     view_position: [f32; 4],
     view_direction: [f32; 4],
     view_proj: [[f32; 4]; 4],
+    /// Width and height of the framebuffer in pixels
+    viewport: [f32; 2],
+    /// Width in pixels of the edges of triangle cells, when drawn as quads
+    edge_width: f32,
+    _padding: f32,
+    /// Color the edges of the highlighted mesh are drawn with
+    highlight_color: [f32; 4],
+    /// Unit normals and offsets of the clipping planes, fragments on the side a normal points to
+    /// are outside its plane
+    clip_planes: [[f32; 4]; MAX_CLIP_PLANES],
+    /// Number of clipping planes in use, from the start of `clip_planes`
+    clip_plane_count: u32,
+    /// 0 to keep fragments inside every plane, 1 to keep those inside any of them
+    clip_mode: u32,
+    _clip_padding: [u32; 2],
 }
 //}}}
 //{{{ impl: Default for ViewUniform
@@ -301,6 +885,14 @@ impl Default for ViewUniform
             view_position: [0.0; 4],
             view_direction: [0.0; 4],
             view_proj: Mat4::identity().into(),
+            viewport: [1.0, 1.0],
+            edge_width: 1.0,
+            _padding: 0.0,
+            highlight_color: [1.0, 1.0, 0.0, 1.0],
+            clip_planes: [[0.0; 4]; MAX_CLIP_PLANES],
+            clip_plane_count: 0,
+            clip_mode: 0,
+            _clip_padding: [0; 2],
         }
     }
 }
@@ -317,16 +909,23 @@ impl EventController
     )
     {
         // handle resizing
-        if let ResizedState::Resized(new_size) = self.resized_state
+        if let ResizedState::Resized((width, height)) = self.resized_state
         {
-            view.projection.aspect = new_size.0 as f32 / new_size.1 as f32;
+            view.set_viewport_size(width, height);
 
             self.resized_state = ResizedState::NotResized;
         }
 
-        // handle key stroke to change octant of camera
+        view.advance_animation(Instant::now());
+
+        // handle key stroke to change octant of camera, any other movement of the camera stops
+        // it animating
         if self.key_stroke_state != KeyStrokeState::None
         {
+            if self.key_modifier_state != winit::keyboard::ModifiersState::SHIFT
+            {
+                view.animation = None;
+            }
             match (self.key_modifier_state)
             {
                 winit::keyboard::ModifiersState::SHIFT => {
@@ -334,7 +933,10 @@ impl EventController
                     let new_octant = octant_change(old_octant, self.key_stroke_state);
                     if new_octant != old_octant
                     {
-                        view.camera.set_octant(new_octant);
+                        let focus = view.camera.focus;
+                        let position =
+                            focus + view.camera.dist() * DIAGONAL_POSITIONS[new_octant as usize];
+                        view.animate_to(position, focus, view.options.animation_duration);
                     }
                 },
                 winit::keyboard::ModifiersState::ALT => {
@@ -349,6 +951,16 @@ impl EventController
                     };
                     view.camera.pan(del_x, del_y);
                 }
+                winit::keyboard::ModifiersState::CONTROL => {
+                    let delta_roll = view.options.key_roll_delta;
+                    let roll = match self.key_stroke_state
+                    {
+                        KeyStrokeState::Left => -delta_roll,
+                        KeyStrokeState::Right => delta_roll,
+                        _ => 0.0,
+                    };
+                    view.roll(roll);
+                }
                 _ => {
                     let delta_angle = view.options.key_orbit_delta;
                     let (pitch_delta, yaw_delta) = match self.key_stroke_state
@@ -366,10 +978,31 @@ impl EventController
             self.key_stroke_state = KeyStrokeState::None;
         }
 
-        // handle mouse wheel
+        // handle mouse wheel, CTRL changes the field of view, otherwise we dolly the camera in
+        // perspective or scale the view volume in orthographic, both by the same fraction per
+        // step whatever the scale of the scene
         if let Some(mwd) = self.mouse_wheel_delta
         {
-            view.camera.zoom(mwd * view.options.zoom_speed);
+            if self.key_modifier_state == winit::keyboard::ModifiersState::CONTROL
+            {
+                view.projection.change_fov(-mwd * view.options.fov_speed);
+            }
+            else
+            {
+                let factor = (1.0 - mwd * view.options.zoom_speed).max(ZOOM_FACTOR_MIN);
+                match view.projection.kind
+                {
+                    ProjectionKind::Perspective =>
+                    {
+                        view.animation = None;
+                        view.camera.scale_dist(factor);
+                    }
+                    ProjectionKind::Orthographic =>
+                    {
+                        view.projection.scale_extent(factor);
+                    }
+                }
+            }
             self.mouse_wheel_delta = None;
         }
 
@@ -378,6 +1011,44 @@ impl EventController
     //}}}
 }
 //}}}
+//{{{ fun: smoothstep
+/// Eases the fraction `t` in and out, so that animations start and stop gently.
+fn smoothstep(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+//..................................................................................................
+//}}}
+//{{{ fun: slerp_direction
+/// Spherically interpolates between the unit vectors `a` and `b`.
+///
+/// When the vectors are opposite the arc between them is not unique, in which case it is taken
+/// about an arbitrary axis orthogonal to `a`.
+fn slerp_direction(
+    a: &Vec3,
+    b: &Vec3,
+    t: f32,
+) -> Vec3
+{
+    let cos_angle = a.dot(b).clamp(-1.0, 1.0);
+    if cos_angle > 1.0 - 1.0e-6
+    {
+        a.lerp(b, t).normalize()
+    }
+    else if cos_angle < -1.0 + 1.0e-6
+    {
+        let axis = nalgebra::Unit::new_normalize(orthogonal_vector(a));
+        nalgebra::Rotation3::from_axis_angle(&axis, std::f32::consts::PI * t) * a
+    }
+    else
+    {
+        let angle = cos_angle.acos();
+        (a * ((1.0 - t) * angle).sin() + b * (t * angle).sin()) / angle.sin()
+    }
+}
+//..................................................................................................
+//}}}
 //{{{ fun: octant_change
 fn octant_change(
     octant: i8,
@@ -475,14 +1146,20 @@ mod tests
                 pitch: pitch,
                 yaw: yaw,
                 octant: octant(&(pos - focus)),
+                roll: 0.0,
             },
             projection: Projection {
+                kind: ProjectionKind::Perspective,
                 fov,
+                ortho_extent: 1.0,
                 near,
                 far,
                 aspect,
+                reversed_z: false,
             },
             uniform: ViewUniform::default(),
+            gizmo_uniform: ViewUniform::default(),
+            animation: None,
         };
 
         view.update_uniform();
@@ -503,10 +1180,13 @@ mod tests
     fn projection_calc_matrix_test()
     {
         let proj = Projection {
+            kind: ProjectionKind::Perspective,
+            ortho_extent: 1.0,
             fov: rad(45.0),
             near: 0.1,
             far: 100.0,
             aspect: 2.0,
+            reversed_z: false,
         };
 
         let mat = proj.calc_matrix();
@@ -535,6 +1215,34 @@ mod tests
         }
     }
 
+    #[test]
+    fn screen_to_ray_test()
+    {
+        for kind in [ProjectionKind::Perspective, ProjectionKind::Orthographic]
+        {
+            let mut view = build_view();
+            view.set_projection_kind(kind);
+            view.uniform.viewport = [800.0, 400.0];
+            view.update_uniform();
+
+            // the ray through the centre pixel runs along the view direction through the focus
+            let (origin, direction) = view.screen_to_ray(vector![400.0, 200.0]);
+            assert!((direction - view.camera.direction()).norm() < 1e-4);
+            let to_focus = view.camera.focus - origin;
+            assert!(to_focus.cross(&direction).norm() < 1e-4);
+        }
+
+        // off-centre pixels, with the y-axis pointing down, project back onto the same pixel
+        let mut view = build_view();
+        view.uniform.viewport = [800.0, 400.0];
+        view.update_uniform();
+        let (origin, direction) = view.screen_to_ray(vector![600.0, 100.0]);
+        let p = origin + 5.0 * direction;
+        let clip = view.calc_matrix() * vector![p[0], p[1], p[2], 1.0];
+        assert!((clip[0] / clip[3] - 0.5).abs() < 1e-4);
+        assert!((clip[1] / clip[3] - 0.5).abs() < 1e-4);
+    }
+
     //{{{ test: left_key_stroke_test
     #[test]
     fn left_key_stroke_test()
@@ -573,5 +1281,427 @@ mod tests
         assert_eq!(view.camera.octant, 1);
     }
     //}}}
+    //{{{ test: roll_test
+    #[test]
+    fn roll_test()
+    {
+        let mut view = build_view();
+
+        // a point directly above the focus on screen lies on the +y axis in view coordinates
+        let dir = view.camera.direction();
+        let up = view.camera.up();
+        let screen_up = (up - up.dot(&dir) * dir).normalize();
+        let p = view.camera.focus + screen_up;
+        let q = view.camera.calc_matrix() * Vec4::new(p[0], p[1], p[2], 1.0);
+        assert!(q[0].abs() < 1e-5);
+        assert!((q[1] - 1.0).abs() < 1e-5);
+
+        // after a quarter turn of roll the same point lies on the -x axis
+        view.roll(rad(90.0));
+        let q = view.camera.calc_matrix() * Vec4::new(p[0], p[1], p[2], 1.0);
+        assert!((q[0] + 1.0).abs() < 1e-5);
+        assert!(q[1].abs() < 1e-5);
+
+        // panning up moves the camera along the rolled screen up direction
+        let old_focus = view.camera.focus;
+        view.camera.pan(0.0, 1.0);
+        let displ = view.camera.focus - old_focus;
+        let q = view.camera.calc_matrix() * Vec4::new(p[0], p[1], p[2], 1.0);
+        assert!(displ.dot(&dir).abs() < 1e-5);
+        assert!((q[0] + 1.0).abs() < 1e-5);
+        assert!((q[1] + 1.0).abs() < 1e-5);
+    }
+    //}}}
+    //{{{ test: mouse_wheel_test
+    #[test]
+    fn mouse_wheel_test()
+    {
+        let mut view = build_view();
+        let mut view_controller = EventController::default();
+        let wheel = winit::event::MouseScrollDelta::LineDelta(0.0, 100.0);
+
+        // perspective: the wheel dollies the camera towards the focus
+        let dist = view.camera.dist();
+        view_controller.mouse_wheel_update(wheel);
+        view_controller.update_view_3d(&mut view);
+        assert!(view.camera.dist() < dist);
+
+        // CTRL + wheel narrows the field of view and leaves the camera where it is
+        let dist = view.camera.dist();
+        let fov = view.projection.fov;
+        view_controller.key_modifiers_update(winit::keyboard::ModifiersState::CONTROL);
+        view_controller.mouse_wheel_update(wheel);
+        view_controller.update_view_3d(&mut view);
+        assert!(view.projection.fov < fov);
+        assert_eq!(view.camera.dist(), dist);
+
+        // orthographic: the wheel shrinks the view volume and leaves the camera where it is
+        view_controller.key_modifiers_update(winit::keyboard::ModifiersState::empty());
+        view.set_projection_kind(ProjectionKind::Orthographic);
+        let extent = view.projection.ortho_extent;
+        view_controller.mouse_wheel_update(wheel);
+        view_controller.update_view_3d(&mut view);
+        assert!(view.projection.ortho_extent < extent);
+        assert_eq!(view.camera.dist(), dist);
+    }
+    //}}}
+    //{{{ test: zoom_fraction_test
+    #[test]
+    fn zoom_fraction_test()
+    {
+        // each wheel step moves the camera the same fraction of its distance at any scale
+        let wheel = winit::event::MouseScrollDelta::LineDelta(0.0, 10.0);
+        for units_per_meter in [0.01, 1.0, 1000.0]
+        {
+            let mut view = build_view();
+            view.set_units_per_meter(units_per_meter);
+            let mut view_controller = EventController::default();
+            let expected = 1.0 - 10.0 * view.options.zoom_speed;
+            for _ in 0..3
+            {
+                let dist = view.camera.dist();
+                view_controller.mouse_wheel_update(wheel);
+                view_controller.update_view_3d(&mut view);
+                assert!((view.camera.dist() / dist - expected).abs() < 1e-5);
+            }
+        }
+
+        // a huge step cannot take the camera through its focus
+        let mut view = build_view();
+        let mut view_controller = EventController::default();
+        let dist = view.camera.dist();
+        view_controller.mouse_wheel_update(winit::event::MouseScrollDelta::LineDelta(0.0, 1.0e6));
+        view_controller.update_view_3d(&mut view);
+        assert!((view.camera.dist() - ZOOM_FACTOR_MIN * dist).abs() < 1e-5);
+    }
+    //}}}
+    //{{{ test: reset_test
+    #[test]
+    fn reset_test()
+    {
+        let mut view = View::default();
+        let mut view_controller = EventController::default();
+        view_controller.resize_update(winit::dpi::PhysicalSize::new(800, 400));
+        view_controller.update_view_3d(&mut view);
+        let default_camera = view.camera_descriptor();
+
+        view.roll(rad(30.0));
+        view.camera.orbit(rad(10.0), rad(20.0));
+        view.projection.change_fov(rad(10.0));
+        assert_ne!(view.camera_descriptor(), default_camera);
+
+        view.reset();
+        assert_eq!(view.camera_descriptor(), default_camera);
+        assert_eq!(view.camera.roll, 0.0);
+        assert_eq!(view.projection.aspect, 2.0);
+        assert_eq!(view.viewport(), [800.0, 400.0]);
+    }
+    //}}}
+    //{{{ test: initial_octant_test
+    #[test]
+    fn initial_octant_test()
+    {
+        // the pitch and yaw of the default camera describe its position, so orbiting by nothing
+        // leaves it in place
+        let mut camera = Camera::default();
+        let position = camera.position();
+        assert!((direction(camera.pitch(), camera.yaw()) - position.normalize()).norm() < 1e-6);
+        camera.orbit(0.0, 0.0);
+        assert!((camera.position() - position).norm() < 1e-6);
+
+        let mut view = View::default();
+        view.set_initial_octant(6).unwrap();
+        assert_eq!(octant(&view.camera.position()), 6);
+        view.camera.orbit(rad(10.0), rad(20.0));
+        view.reset();
+        assert_eq!(octant(&view.camera.position()), 6);
+        assert!((view.camera.position() - DIAGONAL_POSITIONS[6]).norm() < 1e-6);
+
+        // octants outside 0 to 7 leave the view alone
+        assert_eq!(view.set_initial_octant(8), Err("octant"));
+        assert_eq!(view.set_initial_octant(-1), Err("octant"));
+        assert_eq!(view.options.initial_octant(), 6);
+        view.reset();
+        assert_eq!(octant(&view.camera.position()), 6);
+    }
+    //}}}
+    //{{{ test: units_per_meter_test
+    #[test]
+    fn units_per_meter_test()
+    {
+        let close = |a: f32, b: f32| (a - b).abs() <= 1e-5 * b.abs();
+        let default_view = View::default();
+        let mut view = View::default();
+        view.set_units_per_meter(1000.0);
+        assert!(close(view.projection.near, 1000.0 * default_view.projection.near));
+        assert!(close(view.projection.far, 1000.0 * default_view.projection.far));
+        assert!(close(view.camera.dist(), 1000.0 * default_view.camera.dist()));
+        assert!(close(view.options.key_pan_delta, 1000.0 * default_view.options.key_pan_delta));
+        assert_eq!(view.options.zoom_speed, default_view.options.zoom_speed);
+        // the camera still looks from the same direction
+        assert!((view.camera.direction() - default_view.camera.direction()).norm() < 1e-6);
+
+        // resetting keeps the scale
+        view.camera.zoom(500.0);
+        view.reset();
+        assert!(close(view.camera.dist(), 1000.0 * default_view.camera.dist()));
+        assert!(close(view.projection.near, 1000.0 * default_view.projection.near));
+
+        // changing the units again scales from the current ones
+        view.set_units_per_meter(1.0);
+        assert!(close(view.projection.far, default_view.projection.far));
+        assert!(close(view.options.key_pan_delta, default_view.options.key_pan_delta));
+        assert!(close(view.camera.dist(), default_view.camera.dist()));
+    }
+    //}}}
+    //{{{ test: clip_plane_test
+    #[test]
+    fn clip_plane_test()
+    {
+        let mut view = View::default();
+        let planes = [
+            (Vec3::new(0.0, 0.0, 2.0), Vec3::new(0.0, 0.0, 3.0)),
+            (Vec3::zeros(), Vec3::zeros()),
+            (Vec3::new(1.0, 0.0, 0.0), -Vec3::x()),
+        ];
+        view.set_clip_planes(&planes, ClipMode::InsideAny);
+        view.update_uniform();
+        // the plane with a zero normal is skipped
+        assert_eq!(view.uniform.clip_plane_count, 2);
+        assert_eq!(view.uniform.clip_planes[0], [0.0, 0.0, 1.0, -2.0]);
+        assert_eq!(view.uniform.clip_planes[1], [-1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(view.uniform.clip_mode, 1);
+        assert_eq!(view.gizmo_uniform().clip_plane_count, 0);
+
+        view.set_clip_planes(&[], ClipMode::InsideAll);
+        assert_eq!(view.uniform.clip_plane_count, 0);
+        assert_eq!(view.uniform.clip_planes, [[0.0; 4]; MAX_CLIP_PLANES]);
+    }
+    //}}}
+    //{{{ test: resize_viewport_test
+    #[test]
+    fn resize_viewport_test()
+    {
+        let mut view = build_view();
+
+        let mut view_controller = EventController::default();
+
+        view_controller.resize_update(winit::dpi::PhysicalSize::new(800, 400));
+        view_controller.update_view_3d(&mut view);
+
+        assert_eq!(view.viewport(), [800.0, 400.0]);
+        assert_eq!(view.projection.aspect, 2.0);
+        assert_eq!(std::mem::size_of::<ViewUniform>() % 16, 0);
+    }
+    //}}}
+    //{{{ test: set_viewport_size_test
+    #[test]
+    fn set_viewport_size_test()
+    {
+        // the size applies straight away, without waiting for the event controller
+        let mut view = View::default();
+        let square = view.uniform.view_proj;
+        view.set_viewport_size(1000.0, 500.0);
+        assert_eq!(view.viewport(), [1000.0, 500.0]);
+        assert_eq!(view.projection.aspect, 2.0);
+        view.set_viewport_size(600.0, 600.0);
+        assert_eq!(view.projection.aspect, 1.0);
+        assert_ne!(view.uniform.view_proj, square);
+
+        // a minimised window keeps the last aspect ratio
+        view.set_viewport_size(0.0, 0.0);
+        assert_eq!(view.projection.aspect, 1.0);
+        assert!(view.uniform.view_proj.iter().flatten().all(|x| x.is_finite()));
+    }
+    //}}}
+    //{{{ test: frame_box_test
+    #[test]
+    fn frame_box_test()
+    {
+        let (min, max) = (Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 3.0, 1.0));
+        let corners = (0..8).map(|i| {
+            Vec3::new(
+                if i & 1 == 0 { min[0] } else { max[0] },
+                if i & 2 == 0 { min[1] } else { max[1] },
+                if i & 4 == 0 { min[2] } else { max[2] },
+            )
+        });
+        let mut view = View::default();
+        let direction = view.camera.direction();
+        view.frame_box(min, max, 0.0);
+
+        // the camera looks at the centre of the box from the same direction as before
+        assert!((view.camera.focus() - Vec3::new(0.0, 1.0, 0.0)).norm() < 1e-6);
+        assert!((view.camera.direction() - direction).norm() < 1e-5);
+
+        // and every corner lands on screen
+        let view_proj = Mat4::from(view.uniform.view_proj);
+        for corner in corners.clone()
+        {
+            let clip = view_proj * Vec4::new(corner[0], corner[1], corner[2], 1.0);
+            let ndc = clip.xy() / clip[3];
+            assert!(ndc.amax() <= 1.0, "{} is off screen at {}", corner, ndc);
+        }
+
+        // an orthographic view fits its extent to the box
+        view.set_projection_kind(ProjectionKind::Orthographic);
+        view.frame_box(min, max, 0.0);
+        let view_proj = Mat4::from(view.uniform.view_proj);
+        for corner in corners
+        {
+            let ndc = (view_proj * Vec4::new(corner[0], corner[1], corner[2], 1.0)).xy();
+            assert!(ndc.amax() <= 1.0, "{} is off screen at {}", corner, ndc);
+        }
+    }
+    //}}}
+    //{{{ test: animate_to_test
+    #[test]
+    fn animate_to_test()
+    {
+        let assert_close = |a: Vec3, b: Vec3| assert!((a - b).norm() < 1e-5, "{} != {}", a, b);
+        let secs = std::time::Duration::from_secs_f32;
+
+        let mut view = build_view();
+        let start_position = view.camera.position;
+        let target_position = Vec3::new(2.0, -4.0, 1.0);
+        let target_focus = Vec3::new(1.0, 0.0, 0.0);
+        view.animate_to(target_position, target_focus, 2.0);
+        assert!(view.is_animating());
+        assert_eq!(view.camera.octant, 3);
+        let start = view.animation.unwrap().start;
+
+        assert!(view.advance_animation(start));
+        assert_close(view.camera.position, start_position);
+
+        // halfway through smoothstep is also at one half
+        assert!(view.advance_animation(start + secs(1.0)));
+        assert_close(view.camera.focus, Vec3::new(0.5, 0.0, 0.0));
+        let dist = 0.5 * (start_position.norm() + (target_position - target_focus).norm());
+        assert!((view.camera.dist() - dist).abs() < 1e-5);
+
+        assert!(!view.advance_animation(start + secs(2.5)));
+        assert!(!view.is_animating());
+        assert_close(view.camera.position, target_position);
+        assert_close(view.camera.focus, target_focus);
+        let offset = view.camera.dist() * direction(view.camera.pitch, view.camera.yaw);
+        assert_close(view.camera.focus + offset, target_position);
+
+        // opposite directions still pass through a well defined viewpoint
+        view.animate_to(target_focus - (target_position - target_focus), target_focus, 2.0);
+        assert!(view.advance_animation(start + secs(1.0)));
+        assert!(view.camera.position.iter().all(|x| x.is_finite()));
+        assert!((view.camera.dist() - (target_position - target_focus).norm()).abs() < 1e-5);
+
+        // a zero duration jumps straight to the target
+        view.animate_to(start_position, Vec3::zeros(), 0.0);
+        assert!(!view.is_animating());
+        assert_close(view.camera.position, start_position);
+        assert_eq!(view.camera.octant, 0);
+    }
+    //}}}
+    //{{{ test: smoothstep_test
+    #[test]
+    fn smoothstep_test()
+    {
+        assert_eq!(smoothstep(0.0), 0.0);
+        assert_eq!(smoothstep(0.5), 0.5);
+        assert_eq!(smoothstep(1.0), 1.0);
+        assert_eq!(smoothstep(2.0), 1.0);
+        assert!(smoothstep(0.1) < 0.1);
+        assert!(smoothstep(0.9) > 0.9);
+    }
+    //}}}
+    //{{{ test: reversed_z_test
+    #[test]
+    fn reversed_z_test()
+    {
+        let mut view = build_view();
+        view.uniform.viewport = [800.0, 400.0];
+        let (origin, direction) = view.screen_to_ray(Vec2::new(300.0, 200.0));
+
+        view.set_reversed_z(true);
+        let (near, far) = (view.projection().near, view.projection().far);
+        let proj = view.projection().calc_matrix();
+        let depth_at = |d: f32| {
+            let clip = proj * Vec4::new(0.0, 0.0, -d, 1.0);
+            clip[2] / clip[3]
+        };
+        // the near plane is the furthest depth and the far plane the nearest
+        assert!((depth_at(near) - 1.0).abs() < 1e-5);
+        assert!(depth_at(far).abs() < 1e-5);
+        assert!(depth_at(1.0) > depth_at(2.0));
+
+        // picking rays do not depend on the depth mode
+        let (reversed_origin, reversed_direction) = view.screen_to_ray(Vec2::new(300.0, 200.0));
+        assert!((origin - reversed_origin).norm() < 1e-4);
+        assert!((direction - reversed_direction).norm() < 1e-4);
+
+        // and the depth mode survives a reset of the view
+        view.reset();
+        assert!(view.projection().reversed_z());
+    }
+    //}}}
+    //{{{ test: gizmo_matrix_test
+    #[test]
+    fn gizmo_matrix_test()
+    {
+        let mut view = build_view();
+        let gizmo = view.gizmo_matrix();
+        let rotation = view.camera.calc_matrix();
+        let project = |m: &Mat4, p: Vec3| {
+            let clip = m * Vec4::new(p[0], p[1], p[2], 1.0);
+            clip.xyz() / clip[3]
+        };
+
+        // the origin sits in the middle of the corner and the axes turn with the camera
+        let origin = project(&gizmo, Vec3::zeros());
+        assert!(origin.xy().norm() < 1e-5);
+        for axis in [Vec3::x(), Vec3::y(), Vec3::z()]
+        {
+            let tip = project(&gizmo, axis);
+            let turned = rotation * Vec4::new(axis[0], axis[1], axis[2], 0.0);
+            assert!(((tip - origin).xy() - turned.xy() / GIZMO_EXTENT).norm() < 1e-5);
+            assert!(tip[2] > 0.0 && tip[2] < 1.0);
+        }
+
+        // moving the camera without turning it leaves the gizmo as it is
+        let offset = Vec3::new(1.0, -2.0, 0.5);
+        view.camera.position += offset;
+        view.camera.focus += offset;
+        view.camera.zoom(0.5);
+        view.update_uniform();
+        assert!((view.gizmo_matrix() - gizmo).norm() < 1e-5);
+        assert_eq!(Mat4::from(view.gizmo_uniform().view_proj), view.gizmo_matrix());
+    }
+    //}}}
+    //{{{ test: linearize_depths_test
+    #[test]
+    fn linearize_depths_test()
+    {
+        let distances = [1.0, 10.0, 75.0];
+        let kinds = [ProjectionKind::Perspective, ProjectionKind::Orthographic];
+        for (kind, reversed_z) in kinds.into_iter().flat_map(|k| [(k, false), (k, true)])
+        {
+            let projection = Projection {
+                kind: kind,
+                reversed_z: reversed_z,
+                ..Projection::default()
+            };
+            let proj = projection.calc_matrix();
+            let mut depths: Vec<f32> = distances
+                .iter()
+                .map(|d| {
+                    let clip = proj * Vec4::new(0.0, 0.0, -d, 1.0);
+                    clip[2] / clip[3]
+                })
+                .collect();
+            projection.linearize_depths(&mut depths);
+            for (depth, distance) in depths.iter().zip(distances)
+            {
+                assert!((depth - distance).abs() < 1e-3 * distance);
+            }
+        }
+    }
+    //}}}
 }
 //}}}
\ No newline at end of file
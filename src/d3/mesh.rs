@@ -6,14 +6,14 @@
 
 
 //{{{ crate imports
-use crate::common::{self, CellType, Color, Vec3};
+use crate::common::{self, CellType, Color, MeshOptions, TessellationQuality, Vec2, Vec3};
 use crate::core::MeshCore;
 use crate::d3::vertex::{Vertex, VertexDescriptor};
 //}}}
 //{{{ std imports
-use core::panic;
 //}}}
 //{{{ dep imports
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 //}}}
 //--------------------------------------------------------------------------------------------------
@@ -27,10 +27,25 @@ pub enum Error
 {
     #[error("Indices out of bounds")]
     IndexOutOfBounds,
+    #[error("Vertex attribute or index lengths do not agree")]
+    LengthMismatch,
+    #[error("Invalid cell type")]
+    InvalidCellType,
+    #[error("Non-finite value in {0}")]
+    NonFinite(&'static str),
+    #[error("Invalid polygon face: {0}")]
+    InvalidPolygonFace(&'static str),
+    #[error("Invalid cuboid axes: {0}")]
+    InvalidCuboidAxes(&'static str),
+    #[error("Invalid JSON descriptor: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid descriptor: {0}")]
+    InvalidDescriptor(String),
 }
 //}}}
 //{{{ struct: LineDescriptor
 /// This struct encapuslates the geometric information needed to fully specify a line.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct LineDescriptor {
     /// First endpoint of line
     pub v1: Vec3,
@@ -38,9 +53,13 @@ pub struct LineDescriptor {
     pub v2: Vec3,
     /// Color of line
     pub color: Color,
+    /// If set the line is dashed, with dashes and gaps of these lengths in world units
+    #[serde(default)]
+    pub dash_pattern: Option<(f32, f32)>,
 }
 //}}}
 //{{{ struct: TriangleDescriptor
+#[derive(Clone, Deserialize, Serialize)]
 pub struct TriangleDescriptor {
     /// First vertex of triangle
     pub v1: Vec3,
@@ -57,6 +76,7 @@ pub struct TriangleDescriptor {
 }
 //}}}
 //{{{ struct: PlaneDescriptor
+#[derive(Clone, Deserialize, Serialize)]
 pub struct PlaneDescriptor {
     /// Origin of local coordinate system
     pub origin: Vec3,
@@ -78,10 +98,37 @@ pub struct PlaneDescriptor {
     pub tri_color: Color,
     /// Type of cell
     pub cell_type: CellType,
+    /// What the triangles are colored by, for debugging the tessellation
+    #[serde(default)]
+    pub color_by: ColorBy,
+}
+//}}}
+//{{{ struct: PolygonFaceDescriptor
+/// A flat face bounded by a closed polygon of any number of sides, such as the face of a CAD
+/// model. The points must lie in a plane, to within [`PolygonFaceDescriptor::PLANARITY_TOLERANCE`],
+/// and the polygon must not cross itself. The last point joins back to the first.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PolygonFaceDescriptor {
+    /// Corners of the polygon, in order around it. The face is lit on the side from which they
+    /// run counterclockwise.
+    pub points: Vec<Vec3>,
+    /// Color of the boundary in a line render
+    pub line_color: Color,
+    /// Color of the face in a triangle render
+    pub tri_color: Color,
+    /// Type of cell, lines draw the closed boundary and triangles fill the face
+    pub cell_type: CellType,
+}
+//}}}
+//{{{ impl: PolygonFaceDescriptor
+impl PolygonFaceDescriptor {
+    /// Farthest the points may lie from the plane of the face, relative to the size of the face.
+    pub const PLANARITY_TOLERANCE: f32 = 1e-4;
 }
 //}}}
 //{{{ struct: CuboidDescriptor
 /// This struct encapsulates the geometric information needed to fully specify a cuboid.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct CuboidDescriptor {
     /// The bottom left corner of the cuboid
     pub origin: Vec3,
@@ -103,10 +150,125 @@ pub struct CuboidDescriptor {
     pub tri_color: Color,
     /// Type of cell
     pub cell_type: CellType,
+    /// How the axes are checked or corrected before the cuboid is built
+    #[serde(default)]
+    pub axes_policy: AxesPolicy,
+}
+//}}}
+//{{{ impl: CuboidDescriptor
+impl CuboidDescriptor {
+    /// Returns the x, y and z axes the cuboid is built along once the axes policy is applied, or
+    /// the reason the policy rejects the supplied axes.
+    pub fn axes(&self) -> Result<[Vec3; 3], &'static str> {
+        self.axes_policy.apply(&self.x_axis, &self.y_axis, &self.z_axis)
+    }
+}
+//}}}
+//{{{ enum: AxesPolicy
+/// How the axes of a [`CuboidDescriptor`] are treated before the cuboid is built.
+///
+/// A cuboid is built along its axes as they are, so a skewed frame gives a sheared box and a
+/// left-handed one turns the faces inside out. The default keeps that behaviour, the other
+/// policies either correct the axes or reject them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxesPolicy {
+    /// The axes are used as given, however skewed or left-handed they are.
+    #[default]
+    AsGiven,
+    /// The axes are made orthonormal with Gram-Schmidt, keeping the direction of the x axis and
+    /// the plane of the x and y axes. They must still form a right-handed frame.
+    Orthonormalize,
+    /// The axes must form a right-handed frame and be orthogonal to within
+    /// [`AxesPolicy::ORTHOGONALITY_TOLERANCE`], and are used as given.
+    RequireRightHanded,
+}
+//}}}
+//{{{ impl: AxesPolicy
+impl AxesPolicy {
+    /// Tolerance on the cosine of the angle between two axes for them to count as orthogonal.
+    pub const ORTHOGONALITY_TOLERANCE: f32 = 1e-4;
+
+    /// Applies the policy to the given axes, returning the axes to build along or the reason the
+    /// policy rejects them.
+    pub fn apply(&self, x: &Vec3, y: &Vec3, z: &Vec3) -> Result<[Vec3; 3], &'static str> {
+        match self {
+            AxesPolicy::AsGiven => Ok([*x, *y, *z]),
+            AxesPolicy::Orthonormalize => {
+                // Gram-Schmidt in the order x, y, z, which preserves the handedness of the frame
+                let x = x.try_normalize(f32::EPSILON).ok_or("cuboid x_axis has zero length")?;
+                let y = (y - x * y.dot(&x))
+                    .try_normalize(f32::EPSILON)
+                    .ok_or("cuboid x_axis and y_axis are parallel")?;
+                let z = (z - x * z.dot(&x) - y * z.dot(&y))
+                    .try_normalize(f32::EPSILON)
+                    .ok_or("cuboid axes are coplanar")?;
+                if x.cross(&y).dot(&z) <= 0.0 {
+                    return Err("cuboid axes are left-handed");
+                }
+                Ok([x, y, z])
+            }
+            AxesPolicy::RequireRightHanded => {
+                let cos = |a: &Vec3, b: &Vec3| a.dot(b) / (a.norm() * b.norm());
+                let tol = Self::ORTHOGONALITY_TOLERANCE;
+                if cos(x, y).abs() > tol || cos(y, z).abs() > tol || cos(z, x).abs() > tol {
+                    return Err("cuboid axes are not orthogonal");
+                }
+                if x.cross(y).dot(z) <= 0.0 {
+                    return Err("cuboid axes are left-handed");
+                }
+                Ok([*x, *y, *z])
+            }
+        }
+    }
+}
+//}}}
+//{{{ enum: ColorBy
+/// What the triangles of a generated primitive are colored by.
+///
+/// Anything but `None` replaces the triangle color of the descriptor with the viridis colormap
+/// evaluated per vertex on one of the parameters of the surface, or on the order the vertices
+/// are generated in. Seams, poles and badly shaped triangles then stand out, which helps when
+/// debugging the tessellation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorBy {
+    /// The triangles keep the solid color of the descriptor.
+    #[default]
+    None,
+    /// The first parameter, along the x axis of a plane or around the axis of other primitives.
+    ByU,
+    /// The second parameter, along the y axis of a plane, the axis of a cylinder, out from the
+    /// center of a disc or from pole to pole of a sphere or ellipsoid.
+    ByV,
+    /// The index of the vertex, from the first generated to the last.
+    ByIndex,
+}
+//}}}
+//{{{ impl: ColorBy
+impl ColorBy {
+    /// The colormap the parameters are evaluated on.
+    pub const COLORMAP: &'static str = "viridis";
+
+    /// Colors the triangles of `mesh` from the `(u, v)` parameters of each of its vertices, both
+    /// running over `[0, 1]`.
+    fn apply(&self, mesh: &mut Mesh, params: &[(f32, f32)]) {
+        let fvals: Vec<f32> = match self {
+            ColorBy::None => return,
+            ColorBy::ByU => params.iter().map(|(u, _)| *u).collect(),
+            ColorBy::ByV => params.iter().map(|(_, v)| *v).collect(),
+            ColorBy::ByIndex => {
+                let last = params.len().saturating_sub(1).max(1) as f32;
+                (0..params.len()).map(|i| i as f32 / last).collect()
+            }
+        };
+        mesh.set_triangle_colors_from_colormap(&fvals, Self::COLORMAP);
+    }
 }
 //}}}
 //{{{ struct: CylinderDescriptor
 /// This struct encapsulates the geometric information needed to fully specify a cylinder.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct CylinderDescriptor {
     /// This is the center of the circle which is the bottom face of the cylinder
     pub origin: Vec3,
@@ -116,8 +278,15 @@ pub struct CylinderDescriptor {
     pub radius: f32,
     /// Length from the bottom face to the top face of the cylinder
     pub height: f32,
-    /// Number of sides (triangles) to use when approximating the cylinder
+    /// Number of sides (triangles) to use when approximating the cylinder, zero to leave it to
+    /// the tessellation quality
     pub num_sides: usize,
+    /// Number of sides of the discs capping a closed cylinder, `None` to use `num_sides`, zero to
+    /// leave it to the tessellation quality. The caps are separate discs which never share
+    /// vertices with the lateral surface, so with a different number of sides their rims no
+    /// longer follow its edge, leaving a small seam which narrows as either number grows.
+    #[serde(default)]
+    pub cap_num_sides: Option<usize>,
     /// Color of lines in render
     pub line_color: Color,
     /// Color of triangles in render
@@ -126,12 +295,25 @@ pub struct CylinderDescriptor {
     pub open: bool,
     /// Type of cell
     pub cell_type: CellType,
+    /// What the triangles are colored by, for debugging the tessellation
+    #[serde(default)]
+    pub color_by: ColorBy,
+}
+//}}}
+//{{{ impl: CylinderDescriptor
+impl CylinderDescriptor {
+    /// Replaces the subdivision counts left at zero by those of `quality`.
+    pub fn apply_quality(&mut self, quality: TessellationQuality) {
+        self.num_sides = quality.sides(self.num_sides, self.radius);
+        self.cap_num_sides = self.cap_num_sides.map(|m| quality.sides(m, self.radius));
+    }
 }
 //}}}
 //{{{ struct: DiscDescriptor
 /// This struct encapsulates the geometric information needed to fully specify a disc.
 /// The disc is defined by a center point, a normal vector, and a radius. The disc can
 /// be used to represent a flat circular surface.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct DiscDescriptor {
     /// This is the center of the circle which is the bottom face of the cylinder
     pub origin: Vec3,
@@ -139,7 +321,8 @@ pub struct DiscDescriptor {
     pub axis: Vec3,
     /// Radius of the cylinder, as measured from ``axis``.
     pub radius: f32,
-    /// Number of sides (triangles) to use when approximating the disc
+    /// Number of sides (triangles) to use when approximating the disc, zero to leave it to the
+    /// tessellation quality
     pub num_sides: usize,
     /// Color of lines in render
     pub line_color: Color,
@@ -147,10 +330,22 @@ pub struct DiscDescriptor {
     pub tri_color: Color,
     /// Type of cell
     pub cell_type: CellType,
+    /// What the triangles are colored by, for debugging the tessellation
+    #[serde(default)]
+    pub color_by: ColorBy,
+}
+//}}}
+//{{{ impl: DiscDescriptor
+impl DiscDescriptor {
+    /// Replaces the subdivision counts left at zero by those of `quality`.
+    pub fn apply_quality(&mut self, quality: TessellationQuality) {
+        self.num_sides = quality.sides(self.num_sides, self.radius);
+    }
 }
 //}}}
 //{{{ struct: SphereDescriptor
 /// This struct encapuslates the geometric information needed to fully specify a sphere
+#[derive(Clone, Deserialize, Serialize)]
 pub struct SphereDescriptor {
     /// Center of the sphere
     pub origin: Vec3,
@@ -158,9 +353,52 @@ pub struct SphereDescriptor {
     pub axis: Vec3,
     /// Radius of the sphere, as measured from ``origin``.
     pub radius: f32,
-    /// Number of latitudinal subdivisions of the sphere (number of divisions from pole to pole)
+    /// Number of latitudinal subdivisions of the sphere (number of divisions from pole to pole),
+    /// zero to leave it to the tessellation quality
+    pub n_lat: usize,
+    /// Number of longitudinal subdivisions of the sphere (number of divisions around the
+    /// equator), zero to leave it to the tessellation quality
+    pub n_long: usize,
+    /// Color of lines in render
+    pub line_color: Color,
+    /// Color of triangles in render
+    pub tri_color: Color,
+    /// Type of cell
+    pub cell_type: CellType,
+    /// What the triangles are colored by, for debugging the tessellation
+    #[serde(default)]
+    pub color_by: ColorBy,
+}
+//}}}
+//{{{ impl: SphereDescriptor
+impl SphereDescriptor {
+    /// Replaces the subdivision counts left at zero by those of `quality`.
+    pub fn apply_quality(&mut self, quality: TessellationQuality) {
+        self.n_lat = quality.latitudes(self.n_lat, self.radius);
+        self.n_long = quality.longitudes(self.n_long, self.radius);
+    }
+}
+//}}}
+//{{{ struct: EllipsoidDescriptor
+/// This struct encapuslates the geometric information needed to fully specify an ellipsoid, a
+/// sphere stretched by a different radius along each of its axes
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EllipsoidDescriptor {
+    /// Center of the ellipsoid
+    pub origin: Vec3,
+    /// The polar axis of the ellipsoid, which need not be of unit length
+    pub axis: Vec3,
+    /// Radius along the first equatorial axis, which is `orthogonal_vector` of the polar axis
+    pub radius_x: f32,
+    /// Radius along the second equatorial axis, the cross product of the polar and first axes
+    pub radius_y: f32,
+    /// Radius along the polar axis
+    pub radius_z: f32,
+    /// Number of latitudinal subdivisions (number of divisions from pole to pole), zero to leave
+    /// it to the tessellation quality
     pub n_lat: usize,
-    /// Number of longitudinal subdivisions of the sphere (number of divisions around the equator)
+    /// Number of longitudinal subdivisions (number of divisions around the equator), zero to
+    /// leave it to the tessellation quality
     pub n_long: usize,
     /// Color of lines in render
     pub line_color: Color,
@@ -168,9 +406,24 @@ pub struct SphereDescriptor {
     pub tri_color: Color,
     /// Type of cell
     pub cell_type: CellType,
+    /// What the triangles are colored by, for debugging the tessellation
+    #[serde(default)]
+    pub color_by: ColorBy,
+}
+//}}}
+//{{{ impl: EllipsoidDescriptor
+impl EllipsoidDescriptor {
+    /// Replaces the subdivision counts left at zero by those of `quality`.
+    pub fn apply_quality(&mut self, quality: TessellationQuality) {
+        // sized for the largest radius, which keeps the error bounded everywhere
+        let radius = self.radius_x.max(self.radius_y).max(self.radius_z);
+        self.n_lat = quality.latitudes(self.n_lat, radius);
+        self.n_long = quality.longitudes(self.n_long, radius);
+    }
 }
 //}}}
 //{{{ struct: AxesDescriptor
+#[derive(Clone, Deserialize, Serialize)]
 pub struct AxesDescriptor {
     pub origin: Vec3,
     pub x_axis: Vec3,
@@ -180,16 +433,55 @@ pub struct AxesDescriptor {
     pub pos_len: f32,
 }
 //}}}
+//{{{ enum: Descriptor
+/// Any of the shape descriptors, tagged by the kind of shape so that a scene can be read from a
+/// file or script, e.g. `{"type": "sphere", "origin": [0, 0, 0], ...}`.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Descriptor {
+    Line(LineDescriptor),
+    Triangle(TriangleDescriptor),
+    Plane(PlaneDescriptor),
+    Cuboid(CuboidDescriptor),
+    Cylinder(CylinderDescriptor),
+    Disc(DiscDescriptor),
+    Sphere(SphereDescriptor),
+    Ellipsoid(EllipsoidDescriptor),
+    Axes(AxesDescriptor),
+}
+//}}}
 //{{{ trait: Mesh3D
 pub trait Mesh3D<'a> {
     fn create_line(line_disc: &LineDescriptor) -> Self;
     fn create_triangle(triangle_disc: &TriangleDescriptor) -> Self;
     fn create_plane(plane_disc: &PlaneDescriptor) -> Self;
-    fn create_cuboid(cuboid: &CuboidDescriptor) -> Self;
+    fn create_polygon_face(face: &PolygonFaceDescriptor) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn create_cuboid(cuboid: &CuboidDescriptor) -> Result<Self, Error>
+    where
+        Self: Sized;
     fn create_cylinder(cylinder: &CylinderDescriptor) -> Self;
     fn create_disc(disc: &DiscDescriptor) -> Self;
     fn create_sphere(sphere: &SphereDescriptor) -> Self;
+    fn create_ellipsoid(ellipsoid: &EllipsoidDescriptor) -> Self;
     fn create_axes(axes: &AxesDescriptor) -> Self;
+    fn from_vertices_indices(
+        positions: &[Vec3],
+        normals: Option<&[Vec3]>,
+        colors: &[Color],
+        indices: &[u32],
+        cell_type: CellType,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn from_descriptor(descriptor: &Descriptor) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn from_json_descriptor(json: &str) -> Result<Self, Error>
+    where
+        Self: Sized;
+    fn append_primitive(&mut self, descriptor: &Descriptor) -> Result<(), Error>;
     fn add_vertex(&mut self, v: &Vec3, normal: &Vec3, line_color: &Color, tri_color: &Color);
     fn add_line_indices(&mut self, i1: u32, i2: u32) -> Result<(), Error>;
     fn add_triangle_indices(&mut self, i1: u32, i2: u32, i3: u32) -> Result<(), Error> ;
@@ -202,6 +494,14 @@ pub trait Mesh3D<'a> {
         line_color: &Color,
         tri_color: &Color,
     );
+    fn add_triangle_fan(
+        &mut self,
+        center: &Vec3,
+        rim: &[Vec3],
+        line_color: &Color,
+        tri_color: &Color,
+    );
+    fn add_triangle_strip(&mut self, points: &[Vec3], line_color: &Color, tri_color: &Color);
 }
 //}}}
 //{{{ impl: Mesh3D for Mesh
@@ -224,6 +524,10 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         out.append_vertex(&v1);
         out.append_vertex(&v2);
         out.append_indices(&[0, 1]);
+        out.set_options(MeshOptions {
+            dash_pattern: line_disc.dash_pattern,
+            ..MeshOptions::default()
+        });
         out
     }
     //}}}
@@ -263,9 +567,7 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 );
                 out
             }
-            _ => {
-                panic!("Invalid cell type");
-            }
+            CellType::None => Self::from_num_triangles(0),
         }
     }
     //}}}
@@ -283,14 +585,17 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         let v2 = plane_disc.origin + (xmax * xax) + (ymax * yax);
         let v3 = plane_disc.origin + (xmin * xax) + (ymax * yax);
 
+        // the parameters of the corners v0 to v3
+        let uv = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+
         let line_color = plane_disc.line_color;
         let tri_color = plane_disc.tri_color;
-        match plane_disc.cell_type {
+        let (mut out, corners) = match plane_disc.cell_type {
             CellType::Triangle => {
                 let mut out = Self::from_num_triangles(2);
                 out.add_triangle(&v0, &v1, &v2, &line_color, &tri_color);
                 out.add_triangle(&v0, &v2, &v3, &line_color, &tri_color);
-                out
+                (out, vec![0, 1, 2, 0, 2, 3])
             }
             CellType::Line => {
                 let mut out = Self::from_num_lines(4);
@@ -298,25 +603,61 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 out.add_line(&v1, &v2, &line_color, &tri_color);
                 out.add_line(&v2, &v3, &line_color, &tri_color);
                 out.add_line(&v3, &v0, &line_color, &tri_color);
+                (out, vec![0, 1, 1, 2, 2, 3, 3, 0])
+            }
+            CellType::None => return Self::from_num_triangles(0),
+        };
+        let params: Vec<(f32, f32)> = corners.iter().map(|&k| uv[k]).collect();
+        plane_disc.color_by.apply(&mut out, &params);
+        out
+    }
+    //}}}
+    //{{{ fun: create_polygon_face
+    /// The face is filled by ear clipping the polygon in its own plane, every vertex taking the
+    /// normal of the face, or outlined by lines around its boundary. Fails, naming the problem,
+    /// if the points do not make a flat, simple polygon.
+    fn create_polygon_face(face: &PolygonFaceDescriptor) -> Result<Self, Error> {
+        let (points, normal, triangles) =
+            triangulate_polygon_face(&face.points).map_err(Error::InvalidPolygonFace)?;
+        let n = points.len();
+        let out = match face.cell_type {
+            CellType::Triangle => {
+                let mut out = Mesh::from_num_triangles(triangles.len());
+                for p in points.iter() {
+                    out.add_vertex(p, &normal, &face.line_color, &face.tri_color);
+                }
+                for [a, b, c] in triangles {
+                    out.append_indices(&[a as u32, b as u32, c as u32]);
+                }
                 out
             }
-            _ => {
-                panic!("Invalid cell type");
+            CellType::Line => {
+                let mut out = Mesh::from_num_lines(n);
+                for p in points.iter() {
+                    out.add_vertex(p, &normal, &face.line_color, &face.tri_color);
+                }
+                for i in 0..n {
+                    out.append_indices(&[i as u32, ((i + 1) % n) as u32]);
+                }
+                out
             }
-        }
+            CellType::None => Self::from_num_triangles(0),
+        };
+        Ok(out)
     }
     //}}}
     //{{{ fun: create_cuboid
-    fn create_cuboid(cuboid_disc: &CuboidDescriptor) -> Self {
+    fn create_cuboid(cuboid_disc: &CuboidDescriptor) -> Result<Self, Error> {
         //{{{ locals
         let lc = cuboid_disc.line_color;
         let tc = cuboid_disc.tri_color;
         //}}}
         //{{{ com: compute the vertices of the cuboid
+        let [x_axis, y_axis, z_axis] = cuboid_disc.axes().map_err(Error::InvalidCuboidAxes)?;
         let o = cuboid_disc.origin;
-        let dx = cuboid_disc.x_axis * cuboid_disc.lenx;
-        let dy = cuboid_disc.y_axis * cuboid_disc.leny;
-        let dz = cuboid_disc.z_axis * cuboid_disc.lenz;
+        let dx = x_axis * cuboid_disc.lenx;
+        let dy = y_axis * cuboid_disc.leny;
+        let dz = z_axis * cuboid_disc.lenz;
         let v0 = o;
         let v1 = o + dx;
         let v2 = o + dx + dy;
@@ -327,7 +668,7 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         let v7 = o + dy + dz;
         //}}}
         //{{{ com: append the cells
-        match cuboid_disc.cell_type {
+        let out = match cuboid_disc.cell_type {
             //{{{ case: triangle
             CellType::Triangle => {
                 let mut out = Self::from_num_triangles(12);
@@ -375,18 +716,17 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 out
             }
             //}}}
-            //{{{ default
-            _ => {
-                panic!("Invalid cell type");
-            } //}}}
-        }
+            CellType::None => Self::from_num_triangles(0),
+        };
         //}}}
+
+        Ok(out)
     }
     //}}}
     //{{{ fun: create_cylinder
     fn create_cylinder(cyl_disc: &CylinderDescriptor) -> Self {
         //{{{ locals
-        let n = cyl_disc.num_sides;
+        let n = TessellationQuality::default().sides(cyl_disc.num_sides, cyl_disc.radius);
         let origin = cyl_disc.origin;
         let axis = cyl_disc.axis;
         let radius = cyl_disc.radius;
@@ -396,10 +736,15 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         let open = cyl_disc.open;
         let cell_type = cyl_disc.cell_type;
         let x_axis = common::orthogonal_vector(&axis);
-        let y_axis = x_axis.cross(&axis);
-        let d_theta = 2.0 * std::f32::consts::PI / (n - 1) as f32;
+        // (x_axis, y_axis, axis) is right-handed, so the circles run counter-clockwise about the
+        // axis and the sides wind outwards
+        let y_axis = axis.cross(&x_axis);
+        let d_theta = 2.0 * std::f32::consts::PI / n as f32;
         let bottom = origin;
         let top = origin + height * axis;
+        // the parameters of the vertices, around and up the axis, in the order they are added
+        let mut params: Vec<(f32, f32)> = (0..n).map(|i| (i as f32 / n as f32, 0.0)).collect();
+        params.extend((0..n).map(|i| (i as f32 / n as f32, 1.0)));
         //}}}
         //{{{ com: initialize mesh
         let mut out = match cell_type {
@@ -411,9 +756,7 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 let num_lines = if open { n * 3 + 1 } else { n * 5 + 1 };
                 Self::from_num_lines(num_lines)
             }
-            _ => {
-                panic!("Invalid cell type");
-            }
+            CellType::None => return Self::from_num_triangles(0),
         };
         //}}}
         //{{{ com: add vertices of bottom circle
@@ -447,47 +790,34 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         }
         //}}}
         //{{{ com: add the cells
-        match cell_type {
-            //{{{ case: triangle
-            CellType::Triangle => {
-                // first to penultimate iteration
-                for i in 0..n - 1 {
-                    let t1 = [(i + n + 1) as u32, (i + n) as u32, i as u32];
-                    let t2 = [(i + 1) as u32, (i + n + 1) as u32, i as u32];
-                    out.append_indices(&t1);
-                    out.append_indices(&t2);
-                }
-                // last iterations
-                {
-                    let i = n - 1;
-                    let t1 = [(n + 1) as u32, n as u32, i as u32];
-                    let t2 = [(i + 1) as u32, (n + 1) as u32, i as u32];
-                    out.append_indices(&t1);
-                    out.append_indices(&t2);
-                }
+        if out.is_triangle() {
+            // the last quad wraps around to the first vertex of each circle
+            for i in 0..n {
+                let j = (i + 1) % n;
+                let t1 = [(j + n) as u32, (i + n) as u32, i as u32];
+                let t2 = [j as u32, (j + n) as u32, i as u32];
+                out.append_indices(&t1);
+                out.append_indices(&t2);
             }
-            //}}}
-            //{{{ case: line
-            CellType::Line => {
-                for i in 0..n-1 {
-                    let bottom_line = [i as u32, (i+1) as u32];
-                    let top_line = [(i+n) as u32, (i+n+1) as u32];
-                    let middle_line = [i as u32, (i+n) as u32];
-                    out.append_indices(&bottom_line);
-                    out.append_indices(&top_line);
-                    out.append_indices(&middle_line);
-                }
+        } else {
+            for i in 0..n {
+                let j = (i + 1) % n;
+                let bottom_line = [i as u32, j as u32];
+                let top_line = [(i+n) as u32, (j+n) as u32];
+                let middle_line = [i as u32, (i+n) as u32];
+                out.append_indices(&bottom_line);
+                out.append_indices(&top_line);
+                out.append_indices(&middle_line);
             }
-            //}}}
-            //{{{ default 
-            _ => {
-                panic!("Invalid cell type");
-            }
-            //}}}
         }
         //}}}
         //{{{ com: add caps
         if !open {
+            // resolved here rather than by the discs, so the parameters match their vertices
+            let n = match cyl_disc.cap_num_sides {
+                Some(m) => TessellationQuality::default().sides(m, radius),
+                None => n,
+            };
             let bottom_disc_desc = DiscDescriptor {
                 origin: origin,
                 axis: -axis,
@@ -496,8 +826,10 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 line_color: line_color,
                 tri_color: tri_color,   
                 cell_type: cell_type,
+                color_by: ColorBy::None,
             };
             let bottom_disc = Mesh::create_disc(&bottom_disc_desc);
+            params.extend(disc_params(n).into_iter().map(|(u, _)| (u, 0.0)));
             out.merge(bottom_disc);
 
             let top_disc_desc = DiscDescriptor {
@@ -508,24 +840,29 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
                 line_color: line_color,
                 tri_color: tri_color,   
                 cell_type: cell_type,
+                color_by: ColorBy::None,
             };
             let top_disc = Mesh::create_disc(&top_disc_desc);
+            params.extend(disc_params(n).into_iter().map(|(u, _)| (u, 1.0)));
             out.merge(top_disc);
         }
         //}}}
+        cyl_disc.color_by.apply(&mut out, &params);
         out
     }
     //}}}
     //{{{ fun: create_disc
     fn create_disc(disc: &DiscDescriptor) -> Self {
         //{{{ locals
-        let n = disc.num_sides;
+        let n = TessellationQuality::default().sides(disc.num_sides, disc.radius);
         let origin = disc.origin;
         let axis = disc.axis;
         let x_axis = common::orthogonal_vector(&axis);
-        let y_axis = x_axis.cross(&axis);
+        // (x_axis, y_axis, axis) is right-handed, so the fan winds counter-clockwise about the
+        // axis and the disc faces along it
+        let y_axis = axis.cross(&x_axis);
         let radius = disc.radius;
-        let d_theta = 2.0 * std::f32::consts::PI / (n - 1) as f32;
+        let d_theta = 2.0 * std::f32::consts::PI / n as f32;
         let lc = disc.line_color;
         let tc = disc.tri_color;
         let cell_type = disc.cell_type;
@@ -534,9 +871,7 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         let mut out = match cell_type {
             CellType::Triangle => Self::from_num_triangles(n),
             CellType::Line => Self::from_num_lines(2 * n),
-            _ => {
-                panic!("Invalid cell type");
-            }
+            CellType::None => return Self::from_num_triangles(0),
         };
         //}}}
         //{{{ com: append central vertex
@@ -565,127 +900,54 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         }
         //}}}
         //{{{ com: append the cells
-        match cell_type {
-            //{{{ case: triangle
-            CellType::Triangle => {
-                for i in 0..n {
-                    let tri = [0 as u32, i as u32, (i + 1) as u32];
-                    out.append_indices(&tri);
-                }
+        if out.is_triangle() {
+            // the rim vertices are 1..=n, the last triangle closes back to vertex 1
+            for i in 0..n {
+                let tri = [0 as u32, (i + 1) as u32, ((i + 1) % n + 1) as u32];
+                out.append_indices(&tri);
             }
-            //}}}
-            //{{{ case: line
-            CellType::Line => {
-                for i in 0..n {
-                    let line = [0 as u32, i as u32];
-                    out.append_indices(&line);
-                    let line = [i as u32, (i + 1) as u32];
-                    out.append_indices(&line);
-                }
+        } else {
+            for i in 0..n {
+                let line = [0 as u32, (i + 1) as u32];
+                out.append_indices(&line);
+                let line = [(i + 1) as u32, ((i + 1) % n + 1) as u32];
+                out.append_indices(&line);
             }
-            //}}}
-            //{{{ default
-            _ => {
-                panic!("Invalid cell type");
-            } //}}}
         }
         //}}}
+        disc.color_by.apply(&mut out, &disc_params(n));
         out
     }
     //..............................................................................
     //}}}
     //{{{ fun: create_sphere
     fn create_sphere(sphere_disc: &SphereDescriptor) -> Self {
-        //{{{ locals
-        let origin = sphere_disc.origin;
         let axis = sphere_disc.axis;
-        let radius = sphere_disc.radius;
-        let n_lat = sphere_disc.n_lat;
-        let n_long = sphere_disc.n_long;
-        let lc = sphere_disc.line_color;
-        let tc = sphere_disc.tri_color;
-        let cell_type = sphere_disc.cell_type;
         let x_axis = common::orthogonal_vector(&axis);
-        let y_axis = x_axis.cross(&axis);
-        //}}}
-        //{{{ com:  initialize mesh 
-        let mut out =  match cell_type {
-            CellType::Triangle => Self::from_num_triangles(2 * n_lat * n_long),
-            CellType::Line => Self::from_num_lines(2 * n_lat * n_long),
-            _ => {
-                panic!("Invalid cell type");
-            }
+        // (x_axis, y_axis, axis) is right-handed, so the triangles wind outwards
+        let y_axis = axis.cross(&x_axis);
+        let ellipsoid = EllipsoidDescriptor {
+            origin: sphere_disc.origin,
+            axis: axis,
+            radius_x: sphere_disc.radius,
+            radius_y: sphere_disc.radius,
+            radius_z: sphere_disc.radius,
+            n_lat: sphere_disc.n_lat,
+            n_long: sphere_disc.n_long,
+            line_color: sphere_disc.line_color,
+            tri_color: sphere_disc.tri_color,
+            cell_type: sphere_disc.cell_type,
+            color_by: sphere_disc.color_by,
         };
-        //}}}
-        //{{{ com: append vertices 
-        let pi = std::f32::consts::PI;
-        let d_lat = pi / ((n_lat - 1) as f32);
-        let d_long = (2.0 * pi) / ((n_long - 1) as f32);
-
-        for i in 0..n_lat {
-            let phi = i as f32 * d_lat;
-            let (sin_phi, cos_phi) = phi.sin_cos();
-            for j in 0..n_long {
-                let theta = j as f32 * d_long;
-                let (sin_theta, cos_theta) = theta.sin_cos();
-                let pnt = origin + (radius * sin_phi * cos_theta * x_axis) + 
-                                   (radius * sin_phi * sin_theta * y_axis) + 
-                                   (radius * cos_phi * axis);
-                let normal = (pnt - origin).normalize();
-                let v = Vertex::new(&VertexDescriptor {
-                    position: pnt,
-                    normal: normal,
-                    line_color: lc,
-                    triangle_color: tc,
-                });
-                out.append_vertex(&v);
-            }
-        }
-        //}}}
-
-        let cart_map = |i, j| i * n_long + j;
-
-        match cell_type {
-            CellType::Triangle => {
-
-                for i in 0..n_lat-1 {
-                    for j in 0..n_long-1 {
-                        let i1 = cart_map(i, j) as u32;
-                        let i2 = cart_map(i+1, j) as u32;  
-                        let i3 = cart_map(i+1, j+1) as u32;
-                        let i4 = cart_map(i, j+1) as u32;
-                        let tri1 = [i1, i2, i3];
-                        let tri2 = [i1, i3, i4];
-                        out.append_indices(&tri1);
-                        out.append_indices(&tri2);
-                    }
-                }
-            }, 
-            CellType::Line => {
-
-                for i in 1..n_lat-1 {
-                    for j in 0..n_long-1 {
-                        let i1 = cart_map(i, j) as u32;
-                        let i2 = cart_map(i, j+1) as u32;  
-                        let li = [i1, i2];
-                        out.append_indices(&li);
-                    }
-                }
-
-                for j in 0..n_long {
-                    for i in 0..n_lat-1 {
-                        let i1 = cart_map(i, j) as u32;
-                        let i2 = cart_map(i+1, j) as u32;
-                        let li = [i1, i2];
-                        out.append_indices(&li);
-                    }
-                }
-            },
-            _ => {
-                panic!("Invalid cell type");
-            },
-        }
-        out
+        uv_ellipsoid(&ellipsoid, &[x_axis, y_axis, axis])
+    }
+    //}}}
+    //{{{ fun: create_ellipsoid
+    fn create_ellipsoid(ellipsoid: &EllipsoidDescriptor) -> Self {
+        let axis = ellipsoid.axis.normalize();
+        let x_axis = common::orthogonal_vector(&axis);
+        let y_axis = axis.cross(&x_axis);
+        uv_ellipsoid(ellipsoid, &[x_axis, y_axis, axis])
     }
     //}}}
     //{{{ fun: create_axes
@@ -720,6 +982,115 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         out
     }
     //}}}
+    //{{{ fun: from_descriptor
+    /// Creates the mesh of whichever shape the descriptor holds. Fails for a cuboid whose axes
+    /// its axes policy rejects.
+    fn from_descriptor(descriptor: &Descriptor) -> Result<Self, Error> {
+        let out = match descriptor {
+            Descriptor::Line(line) => Self::create_line(line),
+            Descriptor::Triangle(triangle) => Self::create_triangle(triangle),
+            Descriptor::Plane(plane) => Self::create_plane(plane),
+            Descriptor::Cuboid(cuboid) => Self::create_cuboid(cuboid)?,
+            Descriptor::Cylinder(cylinder) => Self::create_cylinder(cylinder),
+            Descriptor::Disc(disc) => Self::create_disc(disc),
+            Descriptor::Sphere(sphere) => Self::create_sphere(sphere),
+            Descriptor::Ellipsoid(ellipsoid) => Self::create_ellipsoid(ellipsoid),
+            Descriptor::Axes(axes) => Self::create_axes(axes),
+        };
+        Ok(out)
+    }
+    //}}}
+    //{{{ fun: from_json_descriptor
+    /// Parses a JSON [`Descriptor`], tagged with the kind of shape in its `type` field, and
+    /// creates the mesh of that shape. The descriptor is held to the checks the RPC server
+    /// applies to the matching add request, see `validate_descriptor`.
+    fn from_json_descriptor(json: &str) -> Result<Self, Error> {
+        let descriptor: Descriptor = serde_json::from_str(json)?;
+        crate::d3::validate_descriptor(&descriptor).map_err(Error::InvalidDescriptor)?;
+        Self::from_descriptor(&descriptor)
+    }
+    //}}}
+    //{{{ fun: append_primitive
+    /// Appends the mesh of whichever shape the descriptor holds, see [`Self::from_descriptor`],
+    /// to this one, so that a composite object such as an arrow is one mesh with a single uid.
+    /// Fails, leaving the mesh untouched, unless the shape has the same cell type as the mesh.
+    /// Shapes without any vertex are skipped.
+    fn append_primitive(&mut self, descriptor: &Descriptor) -> Result<(), Error> {
+        let primitive = Self::from_descriptor(descriptor)?;
+        if primitive.num_vertices() == 0 {
+            return Ok(());
+        }
+        if primitive.cell_type != self.cell_type {
+            return Err(Error::InvalidCellType);
+        }
+        self.merge(primitive);
+        Ok(())
+    }
+    //}}}
+    //{{{ fun: from_vertices_indices
+    /// Each vertex uses its color for both lines and triangles. The number of colors, and of
+    /// normals if given, must match the number of positions, the number of indices must be a
+    /// multiple of the cell size, every index must refer to one of the given positions and all
+    /// values must be finite.
+    ///
+    /// If no normals are given, triangle meshes get area-weighted vertex normals averaged from
+    /// the faces and line meshes get zero normals.
+    fn from_vertices_indices(
+        positions: &[Vec3],
+        normals: Option<&[Vec3]>,
+        colors: &[Color],
+        indices: &[u32],
+        cell_type: CellType,
+    ) -> Result<Self, Error> {
+        //{{{ com: validate the input
+        if colors.len() != positions.len()
+            || normals.is_some_and(|normals| normals.len() != positions.len())
+        {
+            return Err(Error::LengthMismatch);
+        }
+        let mut mesh = match cell_type {
+            CellType::Line if indices.len() % 2 == 0 => Mesh::from_num_lines(indices.len() / 2),
+            CellType::Triangle if indices.len() % 3 == 0 => {
+                Mesh::from_num_triangles(indices.len() / 3)
+            }
+            CellType::Line | CellType::Triangle => return Err(Error::LengthMismatch),
+            _ => return Err(Error::InvalidCellType),
+        };
+        if indices.iter().any(|&i| i as usize >= positions.len()) {
+            return Err(Error::IndexOutOfBounds);
+        }
+        if !positions.iter().all(|v| v.iter().all(|x| x.is_finite())) {
+            return Err(Error::NonFinite("positions"));
+        }
+        if normals.is_some_and(|normals| !normals.iter().all(|n| n.iter().all(|x| x.is_finite()))) {
+            return Err(Error::NonFinite("normals"));
+        }
+        if !colors.iter().all(|c| c.to_rgba().iter().all(|x| x.is_finite())) {
+            return Err(Error::NonFinite("colors"));
+        }
+        //}}}
+        //{{{ com: compute the normals if they are not given
+        let computed_normals: Vec<Vec3>;
+        let normals = match normals {
+            Some(normals) => normals,
+            None => {
+                computed_normals = match cell_type {
+                    CellType::Triangle => vertex_normals(positions, indices),
+                    _ => vec![Vec3::zeros(); positions.len()],
+                };
+                &computed_normals
+            }
+        };
+        //}}}
+        //{{{ com: pack the vertices and indices
+        for ((v, n), c) in positions.iter().zip(normals).zip(colors) {
+            mesh.add_vertex(v, n, c, c);
+        }
+        mesh.append_indices(indices);
+        //}}}
+        Ok(mesh)
+    }
+    //}}}
     //{{{ fun: add_vertex
     fn add_vertex(&mut self, v: &Vec3, normal: &Vec3, line_color: &Color, tri_color: &Color) 
     {
@@ -813,20 +1184,263 @@ impl<'a> Mesh3D<'a> for Mesh<'a> {
         }));
     }
     //}}}
+    //{{{ fun: add_triangle_fan
+    /// Appends an open fan of triangles from `center` to each consecutive pair of `rim` points,
+    /// so `n` rim points give `n - 1` triangles. Repeat the first rim point at the end to close
+    /// the fan. The vertices are shared between triangles and get area-weighted normals.
+    fn add_triangle_fan(
+        &mut self,
+        center: &Vec3,
+        rim: &[Vec3],
+        line_color: &Color,
+        tri_color: &Color,
+    ) {
+        assert!(self.is_triangle());
+        if rim.len() < 2 {
+            return;
+        }
+        let (nv, ni) = (self.num_vertices() as u32, self.num_indices());
+        self.append_fan_indices(nv, nv + 1, rim.len());
+        let indices: Vec<u32> = self.index_slice()[ni..].iter().map(|i| i - nv).collect();
+        let positions: Vec<Vec3> = std::iter::once(*center).chain(rim.iter().copied()).collect();
+        for (v, n) in positions.iter().zip(&vertex_normals(&positions, &indices)) {
+            self.add_vertex(v, n, line_color, tri_color);
+        }
+    }
+    //}}}
+    //{{{ fun: add_triangle_strip
+    /// Appends a strip of triangles over consecutive triples of `points`, so `n` points give
+    /// `n - 2` triangles all wound the same way as the first. The vertices are shared between
+    /// triangles and get area-weighted normals.
+    fn add_triangle_strip(&mut self, points: &[Vec3], line_color: &Color, tri_color: &Color) {
+        assert!(self.is_triangle());
+        if points.len() < 3 {
+            return;
+        }
+        let (nv, ni) = (self.num_vertices() as u32, self.num_indices());
+        self.append_strip_indices(nv, points.len());
+        let indices: Vec<u32> = self.index_slice()[ni..].iter().map(|i| i - nv).collect();
+        for (v, n) in points.iter().zip(&vertex_normals(points, &indices)) {
+            self.add_vertex(v, n, line_color, tri_color);
+        }
+    }
+    //}}}
+}
+//..................................................................................................
+//}}}
+//{{{ fun: vertex_normals
+/// Averages the normals of the triangles meeting at each position, weighted by their area.
+/// Positions that belong to no triangle, or only to degenerate ones, get a zero normal.
+fn vertex_normals(positions: &[Vec3], indices: &[u32]) -> Vec<Vec3> {
+    let mut acc = vec![Vec3::zeros(); positions.len()];
+    for tri in indices.chunks_exact(3) {
+        let (i1, i2, i3) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+        let n = (positions[i2] - positions[i1]).cross(&(positions[i3] - positions[i1]));
+        acc[i1] += n;
+        acc[i2] += n;
+        acc[i3] += n;
+    }
+    for n in acc.iter_mut() {
+        *n = n.try_normalize(f32::EPSILON).unwrap_or_else(Vec3::zeros);
+    }
+    acc
 }
 //..................................................................................................
 //}}}
+//{{{ fun: triangulate_polygon_face
+/// Checks that the points make a flat, simple polygon and triangulates it. Returns the distinct
+/// points, the unit normal of the face and its triangles, which index into the points and wind
+/// counterclockwise about the normal, or the reason the points do not make a face.
+pub(crate) fn triangulate_polygon_face(
+    points: &[Vec3],
+) -> Result<(Vec<Vec3>, Vec3, Vec<[usize; 3]>), &'static str> {
+    //{{{ com: drop repeated points
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 3 {
+        return Err("polygon face has fewer than three distinct points");
+    }
+    //}}}
+    //{{{ com: find the plane of the face
+    let (min, max) = points.iter().fold((points[0], points[0]), |(min, max), p| {
+        (min.inf(p), max.sup(p))
+    });
+    let size = (max - min).norm();
+    // Newell's normal, whose length is twice the area enclosed by the polygon
+    let newell: Vec3 = (0..n).map(|i| points[i].cross(&points[(i + 1) % n])).sum();
+    if newell.norm() <= f32::EPSILON * size * size {
+        // points spread out of a line yet enclosing no area double back over themselves
+        let spread = points
+            .iter()
+            .map(|p| (points[1] - points[0]).cross(&(p - points[0])).norm())
+            .fold(0.0, f32::max);
+        return Err(if spread <= f32::EPSILON * size * size {
+            "polygon face points are collinear"
+        } else {
+            "polygon face is self-intersecting"
+        });
+    }
+    let normal = newell.normalize();
+    let center = points.iter().sum::<Vec3>() / n as f32;
+    let tolerance = PolygonFaceDescriptor::PLANARITY_TOLERANCE * size;
+    if points.iter().any(|p| (p - center).dot(&normal).abs() > tolerance) {
+        return Err("polygon face points are not coplanar");
+    }
+    //}}}
+    //{{{ com: triangulate the polygon in the plane
+    // u, v and the normal form a right-handed frame, so the projected polygon is counterclockwise
+    let u = common::orthogonal_vector(&normal);
+    let v = normal.cross(&u);
+    let projected: Vec<Vec2> = points.iter().map(|p| Vec2::new(p.dot(&u), p.dot(&v))).collect();
+    if !common::is_simple_polygon(&projected) {
+        return Err("polygon face is self-intersecting");
+    }
+    let triangles = common::ear_clip(&projected);
+    //}}}
+    Ok((points, normal, triangles))
+}
+//}}}
+//{{{ fun: uv_ellipsoid
+/// Samples an ellipsoid on a latitude-longitude grid, `frame` holds its x, y and polar axes,
+/// which must be orthogonal and right-handed, and each is scaled by the matching radius.
+///
+/// Every latitude, including the poles, has `n_long` vertices and the longitudes wrap around so
+/// there is no seam. The normals are the normalized gradient of the implicit surface, which is
+/// the position in the unit sphere scaled by the inverse radii, rather than the direction from
+/// the origin, which is only normal to the surface when all radii are equal.
+fn uv_ellipsoid<'a>(
+    ellipsoid: &EllipsoidDescriptor,
+    frame: &[Vec3; 3],
+) -> Mesh<'a> {
+    //{{{ locals
+    let origin = ellipsoid.origin;
+    let radii = [ellipsoid.radius_x, ellipsoid.radius_y, ellipsoid.radius_z];
+    // the fixed default level does not depend on the radius
+    let n_lat = TessellationQuality::default().latitudes(ellipsoid.n_lat, 1.0);
+    let n_long = TessellationQuality::default().longitudes(ellipsoid.n_long, 1.0);
+    let lc = ellipsoid.line_color;
+    let tc = ellipsoid.tri_color;
+    let cell_type = ellipsoid.cell_type;
+    //}}}
+    //{{{ com:  initialize mesh 
+    let mut out =  match cell_type {
+        CellType::Triangle => Mesh::from_num_triangles(2 * n_lat * n_long),
+        CellType::Line => Mesh::from_num_lines(2 * n_lat * n_long),
+        CellType::None => return Mesh::from_num_triangles(0),
+    };
+    //}}}
+    //{{{ com: append vertices 
+    let pi = std::f32::consts::PI;
+    let d_lat = pi / ((n_lat - 1) as f32);
+    // the longitudes wrap around, so there is no duplicate seam column
+    let d_long = (2.0 * pi) / (n_long as f32);
+
+    for i in 0..n_lat {
+        let phi = i as f32 * d_lat;
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        for j in 0..n_long {
+            let theta = j as f32 * d_long;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let unit = [sin_phi * cos_theta, sin_phi * sin_theta, cos_phi];
+            let mut pnt = origin;
+            let mut gradient = Vec3::zeros();
+            for k in 0..3 {
+                pnt += radii[k] * unit[k] * frame[k];
+                gradient += unit[k] / (radii[k] * frame[k].norm_squared()) * frame[k];
+            }
+            let v = Vertex::new(&VertexDescriptor {
+                position: pnt,
+                normal: gradient.normalize(),
+                line_color: lc,
+                triangle_color: tc,
+            });
+            out.append_vertex(&v);
+        }
+    }
+    //}}}
+
+    let cart_map = |i, j| i * n_long + j;
+
+    if out.is_triangle() {
+        for i in 0..n_lat-1 {
+            for j in 0..n_long {
+                let k = (j + 1) % n_long;
+                let i1 = cart_map(i, j) as u32;
+                let i2 = cart_map(i+1, j) as u32;
+                let i3 = cart_map(i+1, k) as u32;
+                let i4 = cart_map(i, k) as u32;
+                let tri1 = [i1, i2, i3];
+                let tri2 = [i1, i3, i4];
+                out.append_indices(&tri1);
+                out.append_indices(&tri2);
+            }
+        }
+    } else {
+        for i in 1..n_lat-1 {
+            for j in 0..n_long {
+                let i1 = cart_map(i, j) as u32;
+                let i2 = cart_map(i, (j + 1) % n_long) as u32;
+                let li = [i1, i2];
+                out.append_indices(&li);
+            }
+        }
+
+        for j in 0..n_long {
+            for i in 0..n_lat-1 {
+                let i1 = cart_map(i, j) as u32;
+                let i2 = cart_map(i+1, j) as u32;
+                let li = [i1, i2];
+                out.append_indices(&li);
+            }
+        }
+    }
+
+    let params: Vec<(f32, f32)> = (0..n_lat * n_long)
+        .map(|k| ((k % n_long) as f32 / n_long as f32, (k / n_long) as f32 / (n_lat - 1) as f32))
+        .collect();
+    ellipsoid.color_by.apply(&mut out, &params);
+    out
+}
+//}}}
+//{{{ fun: disc_params
+/// Returns the parameters of the vertices of a disc with `n` sides, in the order
+/// `create_disc` adds them: the center, then the rim from the first rim vertex around.
+fn disc_params(n: usize) -> Vec<(f32, f32)> {
+    std::iter::once((0.0, 0.0))
+        .chain((0..n).map(|i| (i as f32 / n as f32, 1.0)))
+        .collect()
+}
+//}}}
 
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 
     use super::*;
+    use crate::core::VertexCore;
+
+    /// Checks the vertex and index counts of a mesh, that its cells are whole and that every index
+    /// refers to a vertex of the mesh.
+    fn assert_topology(mesh: &Mesh, num_vertices: usize, num_indices: usize) {
+        assert_eq!(mesh.num_vertices(), num_vertices);
+        assert_eq!(mesh.num_indices(), num_indices);
+        assert_eq!(mesh.triangle_cell_indices().len() % 3, 0);
+        assert_eq!(mesh.line_cell_indices().len() % 2, 0);
+        assert!(mesh
+            .index_slice()
+            .iter()
+            .chain(mesh.line_cell_indices())
+            .all(|&i| (i as usize) < num_vertices));
+    }
 
     #[test]
     fn create_cuboid_test() {
-        let cube = Mesh::create_cuboid(&CuboidDescriptor {
+        let cuboid_disc = CuboidDescriptor {
             origin: Vec3::new(0.1, 0.1, 0.0),
             x_axis: Vec3::x(),
             y_axis: Vec3::y(),
@@ -837,7 +1451,693 @@ mod tests {
             line_color: Color::White,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            axes_policy: AxesPolicy::AsGiven,
+        };
+        let cube = Mesh::create_cuboid(&cuboid_disc).unwrap();
+        assert!(cube.is_triangle());
+        assert_topology(&cube, 36, 36);
+
+        let cube = Mesh::create_cuboid(&CuboidDescriptor {
+            cell_type: CellType::Line,
+            ..cuboid_disc.clone()
+        }).unwrap();
+        assert!(cube.is_line());
+        assert_topology(&cube, 24, 24);
+
+        let left_handed = CuboidDescriptor {
+            z_axis: -Vec3::z(),
+            axes_policy: AxesPolicy::RequireRightHanded,
+            ..cuboid_disc
+        };
+        let res = Mesh::create_cuboid(&left_handed);
+        assert!(matches!(res, Err(Error::InvalidCuboidAxes(_))));
+        let res = Mesh::from_descriptor(&Descriptor::Cuboid(left_handed));
+        assert!(matches!(res, Err(Error::InvalidCuboidAxes(_))));
+    }
+
+    #[test]
+    fn decode_vertices_test() {
+        let cube = Mesh::create_cuboid(&CuboidDescriptor {
+            origin: Vec3::new(0.1, 0.1, 0.0),
+            x_axis: Vec3::x(),
+            y_axis: Vec3::y(),
+            z_axis: Vec3::z(),
+            lenx: 1.0,
+            leny: 2.0,
+            lenz: 3.0,
+            line_color: Color::White,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+            axes_policy: AxesPolicy::AsGiven,
+        }).unwrap();
+        let positions = cube.positions();
+        assert_eq!(positions.len(), cube.num_vertices());
+        for (i, position) in positions.iter().enumerate() {
+            assert_eq!(*position, cube.vertex_position(i));
+        }
+        // every corner of the cuboid is among the positions, and nothing else
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { 0.1 } else { 1.1 },
+                if i & 2 == 0 { 0.1 } else { 2.1 },
+                if i & 4 == 0 { 0.0 } else { 3.0 },
+            );
+            assert!(positions.iter().any(|p| (p - corner).norm() < 1e-6));
+        }
+        assert!(positions.iter().all(|p| {
+            [0.1, 1.1].iter().any(|x| (p[0] - x).abs() < 1e-6)
+                && [0.1, 2.1].iter().any(|y| (p[1] - y).abs() < 1e-6)
+                && [0.0, 3.0].iter().any(|z| (p[2] - z).abs() < 1e-6)
+        }));
+
+        let normals = cube.normals().unwrap();
+        assert_eq!(normals.len(), cube.num_vertices());
+        assert!(normals.iter().all(|n| (n.norm() - 1.0).abs() < 1e-5));
+        assert!(cube.colors().iter().all(|c| *c == Color::Green.to_rgba()));
+    }
+
+    #[test]
+    fn primitive_topology_test() {
+        let (lc, tc) = (Color::Black, Color::Red);
+        for cell_type in [CellType::Triangle, CellType::Line] {
+            let is_tri = cell_type == CellType::Triangle;
+            let triangle = Mesh::create_triangle(&TriangleDescriptor {
+                v1: Vec3::zeros(),
+                v2: Vec3::x(),
+                v3: Vec3::y(),
+                line_color: lc,
+                tri_color: tc,
+                cell_type: cell_type,
+            });
+            assert_eq!(triangle.is_triangle(), is_tri);
+            let nv = if is_tri { 3 } else { 6 };
+            assert_topology(&triangle, nv, nv);
+
+            let plane = Mesh::create_plane(&PlaneDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::y(),
+                x_min: -1.0,
+                x_max: 1.0,
+                y_min: -0.5,
+                y_max: 0.5,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: cell_type,
+                color_by: ColorBy::None,
+            });
+            assert_eq!(plane.is_triangle(), is_tri);
+            let nv = if is_tri { 6 } else { 8 };
+            assert_topology(&plane, nv, nv);
+
+            let n = 8;
+            let disc = Mesh::create_disc(&DiscDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                num_sides: n,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: cell_type,
+                color_by: ColorBy::None,
+            });
+            assert_eq!(disc.is_triangle(), is_tri);
+            assert_topology(&disc, n + 1, if is_tri { 3 * n } else { 4 * n });
+
+            // caps with fewer sides than the lateral surface
+            for (open, cap_num_sides) in [(true, None), (false, None), (false, Some(5))] {
+                let cylinder = Mesh::create_cylinder(&CylinderDescriptor {
+                    origin: Vec3::zeros(),
+                    axis: Vec3::z(),
+                    radius: 1.0,
+                    height: 2.0,
+                    num_sides: n,
+                    cap_num_sides: cap_num_sides,
+                    line_color: lc,
+                    tri_color: tc,
+                    open: open,
+                    cell_type: cell_type,
+                    color_by: ColorBy::None,
+                });
+                assert_eq!(cylinder.is_triangle(), is_tri);
+                let side_indices = 6 * n;
+                if open {
+                    assert_topology(&cylinder, 2 * n, side_indices);
+                } else {
+                    // each cap is a disc
+                    let m = cap_num_sides.unwrap_or(n);
+                    let cap_indices = if is_tri { 3 * m } else { 4 * m };
+                    assert_topology(&cylinder, 2 * n + 2 * (m + 1), side_indices + 2 * cap_indices);
+                }
+            }
+
+            let (n_lat, n_long) = (4, 6);
+            let sphere = Mesh::create_sphere(&SphereDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                n_lat: n_lat,
+                n_long: n_long,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: cell_type,
+                color_by: ColorBy::None,
+            });
+            assert_eq!(sphere.is_triangle(), is_tri);
+            let num_indices = if is_tri {
+                6 * (n_lat - 1) * n_long
+            } else {
+                // parallels away from the poles, then meridians
+                2 * ((n_lat - 2) * n_long + n_long * (n_lat - 1))
+            };
+            assert_topology(&sphere, n_lat * n_long, num_indices);
+        }
+
+        let line = Mesh::create_line(&LineDescriptor {
+            v1: Vec3::zeros(),
+            v2: Vec3::x(),
+            color: lc,
+            dash_pattern: None,
+        });
+        assert_topology(&line, 2, 2);
+    }
+
+    /// Returns the triangles of a closed mesh whose geometric normal, from the winding of their
+    /// corners, points towards the centroid of the mesh rather than away from it. Degenerate
+    /// triangles, which have no normal, are skipped.
+    fn inward_triangles(mesh: &Mesh) -> Vec<[Vec3; 3]> {
+        let centroid = (0..mesh.num_vertices())
+            .map(|i| mesh.vertex_position(i))
+            .sum::<Vec3>()
+            / mesh.num_vertices() as f32;
+        mesh.triangles()
+            .filter(|[v1, v2, v3]| {
+                let normal = (v2 - v1).cross(&(v3 - v1));
+                let outward = (v1 + v2 + v3) / 3.0 - centroid;
+                normal.norm() > 1e-6 && normal.dot(&outward) <= 0.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn outward_winding_test() {
+        let (lc, tc) = (Color::Black, Color::Red);
+        let cuboid = Mesh::create_cuboid(&CuboidDescriptor {
+            origin: Vec3::new(1.0, -2.0, 0.5),
+            x_axis: Vec3::x(),
+            y_axis: Vec3::y(),
+            z_axis: Vec3::z(),
+            lenx: 1.0,
+            leny: 2.0,
+            lenz: 3.0,
+            line_color: lc,
+            tri_color: tc,
+            cell_type: CellType::Triangle,
+            axes_policy: AxesPolicy::AsGiven,
+        }).unwrap();
+        assert!(inward_triangles(&cuboid).is_empty());
+
+        let cylinder = Mesh::create_cylinder(&CylinderDescriptor {
+            origin: Vec3::new(1.0, -2.0, 0.5),
+            axis: Vec3::new(1.0, 1.0, 0.0).normalize(),
+            radius: 1.0,
+            height: 2.0,
+            num_sides: 12,
+            cap_num_sides: None,
+            line_color: lc,
+            tri_color: tc,
+            open: false,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
         });
+        assert!(inward_triangles(&cylinder).is_empty());
+
+        let sphere = Mesh::create_sphere(&SphereDescriptor {
+            origin: Vec3::new(1.0, -2.0, 0.5),
+            axis: Vec3::z(),
+            radius: 1.0,
+            n_lat: 8,
+            n_long: 12,
+            line_color: lc,
+            tri_color: tc,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        assert!(inward_triangles(&sphere).is_empty());
+
+        let ellipsoid = Mesh::create_ellipsoid(&EllipsoidDescriptor {
+            origin: Vec3::new(1.0, -2.0, 0.5),
+            axis: Vec3::z(),
+            radius_x: 1.0,
+            radius_y: 2.0,
+            radius_z: 0.5,
+            n_lat: 8,
+            n_long: 12,
+            line_color: lc,
+            tri_color: tc,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        assert!(inward_triangles(&ellipsoid).is_empty());
+
+        // an open disc has no inside, but faces along its axis
+        let axis = Vec3::new(0.0, 1.0, 1.0).normalize();
+        let disc = Mesh::create_disc(&DiscDescriptor {
+            origin: Vec3::zeros(),
+            axis: axis,
+            radius: 1.0,
+            num_sides: 12,
+            line_color: lc,
+            tri_color: tc,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        assert!(disc.triangles().all(|[v1, v2, v3]| (v2 - v1).cross(&(v3 - v1)).dot(&axis) >= 0.0));
+    }
+
+    #[test]
+    fn create_disc_test() {
+        let n = 6;
+        for cell_type in [CellType::Triangle, CellType::Line] {
+            let disc = Mesh::create_disc(&DiscDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 2.0,
+                num_sides: n,
+                line_color: Color::Black,
+                tri_color: Color::Red,
+                cell_type: cell_type,
+                color_by: ColorBy::None,
+            });
+            assert!(disc.index_slice().iter().all(|&i| (i as usize) < disc.num_vertices()));
+            // no cell repeats a vertex
+            let cell_len = if disc.is_triangle() { 3 } else { 2 };
+            for cell in disc.index_slice().chunks_exact(cell_len) {
+                assert!((1..cell_len).all(|k| !cell[k..].contains(&cell[k - 1])));
+            }
+            // the rim vertices are distinct
+            for i in 1..=n {
+                for j in i + 1..=n {
+                    assert!((disc.vertex_position(i) - disc.vertex_position(j)).norm() > 1e-3);
+                }
+            }
+        }
+
+        let disc = Mesh::create_disc(&DiscDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 2.0,
+            num_sides: n,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        // the fan closes back to the first rim vertex
+        assert_eq!(&disc.index_slice()[3 * (n - 1)..], &[0, n as u32, 1]);
+        let area: f32 = disc
+            .triangles()
+            .map(|[v1, v2, v3]| 0.5 * (v2 - v1).cross(&(v3 - v1)).norm())
+            .sum();
+        let hexagon_area = 1.5 * 3f32.sqrt() * 4.0;
+        assert!((area - hexagon_area).abs() < 1e-4);
+    }
+
+    #[test]
+    fn append_primitive_test() {
+        let cylinder_desc = CylinderDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 0.2,
+            height: 2.0,
+            num_sides: 8,
+            cap_num_sides: None,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            open: false,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        };
+        let mut disc_desc = DiscDescriptor {
+            origin: Vec3::new(0.0, 0.0, 2.0),
+            axis: Vec3::z(),
+            radius: 0.5,
+            num_sides: 12,
+            line_color: Color::Black,
+            tri_color: Color::Blue,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        };
+        let cylinder = Mesh::create_cylinder(&cylinder_desc);
+        let disc = Mesh::create_disc(&disc_desc);
+
+        let mut composite = Mesh::create_cylinder(&cylinder_desc);
+        composite.append_primitive(&Descriptor::Disc(disc_desc.clone())).unwrap();
+        assert_eq!(composite.num_vertices(), cylinder.num_vertices() + disc.num_vertices());
+        assert_eq!(composite.num_indices(), cylinder.num_indices() + disc.num_indices());
+        // the disc's cells follow the cylinder's, pointing at its own vertices
+        let offset = cylinder.num_vertices() as u32;
+        let appended = &composite.index_slice()[cylinder.num_indices()..];
+        assert!(appended.iter().zip(disc.index_slice()).all(|(&i, &j)| i == j + offset));
+        assert!(composite.index_slice().iter().all(|&i| (i as usize) < composite.num_vertices()));
+
+        // a disc of lines does not go into a mesh of triangles
+        disc_desc.cell_type = CellType::Line;
+        let before = composite.num_vertices();
+        assert!(matches!(
+            composite.append_primitive(&Descriptor::Disc(disc_desc)),
+            Err(Error::InvalidCellType)
+        ));
+        assert_eq!(composite.num_vertices(), before);
+    }
+
+    #[test]
+    fn create_polygon_face_test() {
+        let n = Vertex::len();
+        let normal_at = |mesh: &Mesh, i: usize| {
+            Vec3::from_column_slice(&mesh.vertex_slice()[i * n + 3..i * n + 6])
+        };
+        // an L shaped face, tilted out of the coordinate planes, with a repeated closing point
+        let u = Vec3::new(1.0, 0.0, 1.0).normalize();
+        let v = Vec3::y();
+        let face_normal = u.cross(&v);
+        let corners = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)];
+        let mut points: Vec<Vec3> = corners.iter().map(|&(x, y)| x * u + y * v).collect();
+        points.push(points[0]);
+        let face = |points: Vec<Vec3>, cell_type: CellType| PolygonFaceDescriptor {
+            points,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type,
+        };
+
+        let mesh = Mesh::create_polygon_face(&face(points.clone(), CellType::Triangle)).unwrap();
+        assert_eq!(mesh.num_vertices(), 6);
+        assert_eq!(mesh.num_triangles(), 4);
+        assert!((0..6).all(|i| (normal_at(&mesh, i) - face_normal).norm() < 1e-5));
+        let mut area = 0.0;
+        for [v1, v2, v3] in mesh.triangles() {
+            let normal = (v2 - v1).cross(&(v3 - v1));
+            // every triangle winds counterclockwise about the face normal
+            assert!(normal.dot(&face_normal) > 0.0);
+            area += 0.5 * normal.norm();
+        }
+        assert!((area - 3.0).abs() < 1e-5);
+
+        // the boundary closes back to the first point
+        let mesh = Mesh::create_polygon_face(&face(points, CellType::Line)).unwrap();
+        assert_eq!(mesh.index_slice().len(), 12);
+        assert_eq!(&mesh.index_slice()[10..], &[5, 0]);
+
+        let reject = |points: Vec<Vec3>| {
+            match Mesh::create_polygon_face(&face(points, CellType::Triangle)) {
+                Err(Error::InvalidPolygonFace(reason)) => Some(reason),
+                _ => None,
+            }
+        };
+        let warped = vec![Vec3::zeros(), Vec3::x(), Vec3::new(1.0, 1.0, 0.1), Vec3::y()];
+        assert_eq!(reject(warped), Some("polygon face points are not coplanar"));
+        let bow_tie = vec![Vec3::zeros(), Vec3::new(2.0, 1.0, 0.0), Vec3::x(), Vec3::y()];
+        assert_eq!(reject(bow_tie), Some("polygon face is self-intersecting"));
+        let collinear = vec![Vec3::zeros(), Vec3::x(), 2.0 * Vec3::x()];
+        assert_eq!(reject(collinear), Some("polygon face points are collinear"));
+        let two_points = vec![Vec3::zeros(), Vec3::x(), Vec3::zeros()];
+        assert_eq!(
+            reject(two_points),
+            Some("polygon face has fewer than three distinct points")
+        );
+    }
+
+    #[test]
+    fn sphere_watertight_test() {
+        let (n_lat, n_long) = (6, 8);
+        let sphere = Mesh::create_sphere(&SphereDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            n_lat: n_lat,
+            n_long: n_long,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        // the vertices of the first and last rings all sit on the poles
+        let is_pole = |i: u32| (i as usize) < n_long || (i as usize) >= (n_lat - 1) * n_long;
+        let mut edge_count = std::collections::HashMap::new();
+        for tri in sphere.index_slice().chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                *edge_count.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+        let away_from_poles: Vec<_> = edge_count
+            .iter()
+            .filter(|((a, b), _)| !is_pole(*a) && !is_pole(*b))
+            .collect();
+        // the parallels of the inner rings, and the meridians and diagonals between them
+        let (inner_rings, inner_bands) = (n_lat - 2, n_lat - 3);
+        assert_eq!(away_from_poles.len(), (inner_rings + 2 * inner_bands) * n_long);
+        assert!(away_from_poles.iter().all(|(_, count)| **count == 2));
+    }
+
+    #[test]
+    fn create_ellipsoid_test() {
+        let (a, b, c) = (1.0, 2.0, 0.5);
+        let ellipsoid = Mesh::create_ellipsoid(&EllipsoidDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::new(0.0, 0.0, 3.0),
+            radius_x: a,
+            radius_y: b,
+            radius_z: c,
+            n_lat: 6,
+            n_long: 8,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        });
+        let n = Vertex::len();
+        let x_axis = common::orthogonal_vector(&Vec3::z());
+        let frame = [x_axis, Vec3::z().cross(&x_axis), Vec3::z()];
+        let mut off_radial = 0;
+        for i in 0..ellipsoid.num_vertices() {
+            let vert = &ellipsoid.vertex_slice()[i * n..(i + 1) * n];
+            let position = Vec3::from_column_slice(&vert[0..3]);
+            let normal = Vec3::from_column_slice(&vert[3..6]);
+            let local = Vec3::new(
+                position.dot(&frame[0]),
+                position.dot(&frame[1]),
+                position.dot(&frame[2]),
+            );
+            // every vertex lies on the surface, despite the unnormalized axis
+            let level = (local.x / a).powi(2) + (local.y / b).powi(2) + (local.z / c).powi(2);
+            assert!((level - 1.0).abs() < 1e-5);
+            // and its normal is the gradient of the implicit surface there
+            let gradient = frame[0] * (local.x / (a * a))
+                + frame[1] * (local.y / (b * b))
+                + frame[2] * (local.z / (c * c));
+            assert!((normal - gradient.normalize()).norm() < 1e-5);
+            if (normal - position.normalize()).norm() > 1e-2 {
+                off_radial += 1;
+            }
+        }
+        // away from the poles and the principal planes the normal is not radial
+        assert!(off_radial > 0);
+    }
+
+    #[test]
+    fn zero_subdivisions_test() {
+        let mut sphere_desc = SphereDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            n_lat: 0,
+            n_long: 0,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::None,
+        };
+        let default_sphere = Mesh::create_sphere(&sphere_desc);
+        sphere_desc.apply_quality(TessellationQuality::default());
+        assert_eq!((sphere_desc.n_lat, sphere_desc.n_long), (16, 32));
+        let medium_sphere = Mesh::create_sphere(&sphere_desc);
+        assert_eq!(default_sphere.num_vertices(), medium_sphere.num_vertices());
+        assert_eq!(default_sphere.num_indices(), medium_sphere.num_indices());
+
+        // counts already set are kept whatever the quality
+        sphere_desc.apply_quality(TessellationQuality::High);
+        assert_eq!((sphere_desc.n_lat, sphere_desc.n_long), (16, 32));
+
+        let mut disc_desc = DiscDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            num_sides: 0,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Line,
+            color_by: ColorBy::None,
+        };
+        // a line disc draws its rim and its spokes
+        assert_eq!(Mesh::create_disc(&disc_desc).num_indices(), 4 * 32);
+        disc_desc.apply_quality(TessellationQuality::Low);
+        assert_eq!(Mesh::create_disc(&disc_desc).num_indices(), 4 * 12);
+    }
+
+    #[test]
+    fn color_by_test() {
+        let (n_lat, n_long) = (5, 6);
+        let sphere = |color_by: ColorBy| {
+            Mesh::create_sphere(&SphereDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                n_lat: n_lat,
+                n_long: n_long,
+                line_color: Color::Black,
+                tri_color: Color::Red,
+                cell_type: CellType::Triangle,
+                color_by: color_by,
+            })
+        };
+        let tri_colors = |mesh: &Mesh| -> Vec<[f32; 3]> {
+            let (n, offset) = (Vertex::len(), Vertex::triangle_color_offset());
+            (0..mesh.num_vertices())
+                .map(|i| {
+                    let start = i * n + offset;
+                    mesh.vertex_slice()[start..start + 3].try_into().unwrap()
+                })
+                .collect()
+        };
+        let cmap = crate::Colormap::new(ColorBy::COLORMAP.to_string()).unwrap();
+
+        // by default the triangles keep their solid color
+        assert!(tri_colors(&sphere(ColorBy::None)).iter().all(|c| *c == Color::Red.to_rgb()));
+
+        // each latitude has a single color, running from one pole to the other
+        let by_v = tri_colors(&sphere(ColorBy::ByV));
+        for i in 0..n_lat {
+            let expected = cmap.get_color(i as f32 / (n_lat - 1) as f32);
+            assert!(by_v[i * n_long..(i + 1) * n_long].iter().all(|c| *c == expected));
+        }
+
+        // each longitude has a single color, which jumps back at the seam
+        let by_u = tri_colors(&sphere(ColorBy::ByU));
+        for (k, color) in by_u.iter().enumerate() {
+            assert_eq!(*color, cmap.get_color((k % n_long) as f32 / n_long as f32));
+        }
+
+        // colors by index run over the whole colormap, including the caps of a cylinder
+        let cylinder = Mesh::create_cylinder(&CylinderDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            height: 2.0,
+            num_sides: 8,
+            cap_num_sides: None,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            open: false,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::ByIndex,
+        });
+        let by_index = tri_colors(&cylinder);
+        assert_eq!(by_index[0], cmap.get_color(0.0));
+        assert_eq!(by_index[by_index.len() - 1], cmap.get_color(1.0));
+
+        // caps whose sides are left to the tessellation quality are colored like any others
+        for color_by in [ColorBy::ByU, ColorBy::ByV, ColorBy::ByIndex] {
+            let cylinder = Mesh::create_cylinder(&CylinderDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                height: 2.0,
+                num_sides: 8,
+                cap_num_sides: Some(0),
+                line_color: Color::Black,
+                tri_color: Color::Red,
+                open: false,
+                cell_type: CellType::Triangle,
+                color_by: color_by,
+            });
+            let m = TessellationQuality::default().sides(0, 1.0);
+            assert_eq!(cylinder.num_vertices(), 2 * 8 + 2 * (m + 1));
+        }
+
+        // the parameters of a plane follow its axes
+        let plane = Mesh::create_plane(&PlaneDescriptor {
+            origin: Vec3::zeros(),
+            x_axis: Vec3::x(),
+            y_axis: Vec3::y(),
+            x_min: -1.0,
+            x_max: 1.0,
+            y_min: -0.5,
+            y_max: 0.5,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: ColorBy::ByU,
+        });
+        for (i, color) in tri_colors(&plane).iter().enumerate() {
+            let u = 0.5 * (plane.vertex_position(i).x + 1.0);
+            assert_eq!(*color, cmap.get_color(u));
+        }
+    }
+
+    #[test]
+    fn triangle_fan_strip_test() {
+        let n = Vertex::len();
+        let normal_at = |mesh: &Mesh, i: usize| {
+            Vec3::from_column_slice(&mesh.vertex_slice()[i * n + 3..i * n + 6])
+        };
+
+        // a tilted fan of five rim points gives four triangles facing along its axis
+        let axis = Vec3::new(1.0, 0.0, 1.0).normalize();
+        let x_axis = common::orthogonal_vector(&axis);
+        let y_axis = axis.cross(&x_axis);
+        let rim: Vec<Vec3> = (0..5)
+            .map(|i| {
+                let theta = i as f32 * std::f32::consts::FRAC_PI_4;
+                theta.cos() * x_axis + theta.sin() * y_axis
+            })
+            .collect();
+        let mut fan = Mesh::from_num_triangles(4);
+        fan.add_triangle_fan(&Vec3::zeros(), &rim, &Color::Black, &Color::Red);
+        assert_topology(&fan, 6, 12);
+        assert!(fan.triangles().all(|[v1, v2, v3]| (v2 - v1).cross(&(v3 - v1)).dot(&axis) > 0.0));
+        assert!((0..6).all(|i| (normal_at(&fan, i) - axis).norm() < 1e-5));
+
+        // a zig-zag strip in the xy-plane keeps one winding, so all its normals agree
+        let points: Vec<Vec3> = (0..6).map(|i| Vec3::new(i as f32, (i % 2) as f32, 0.0)).collect();
+        let mut strip = Mesh::from_num_triangles(4);
+        strip.add_triangle_strip(&points, &Color::Black, &Color::Red);
+        assert_topology(&strip, 6, 12);
+        assert!(strip.triangles().all(|[v1, v2, v3]| (v2 - v1).cross(&(v3 - v1)).z < 0.0));
+        assert!((0..6).all(|i| (normal_at(&strip, i) + Vec3::z()).norm() < 1e-5));
+    }
+
+    #[test]
+    fn axes_policy_test() {
+        let x = Vec3::new(2.0, 0.0, 0.0);
+        let y = Vec3::new(1.0, 1.0, 0.0);
+        let z = Vec3::new(0.0, 1.0, 3.0);
+        assert_eq!(AxesPolicy::AsGiven.apply(&x, &y, &z), Ok([x, y, z]));
+
+        let [ox, oy, oz] = AxesPolicy::Orthonormalize.apply(&x, &y, &z).unwrap();
+        assert!((ox - Vec3::x()).norm() < 1e-6);
+        assert!((oy - Vec3::y()).norm() < 1e-6);
+        assert!((oz - Vec3::z()).norm() < 1e-6);
+
+        assert_eq!(
+            AxesPolicy::RequireRightHanded.apply(&x, &y, &z),
+            Err("cuboid axes are not orthogonal")
+        );
+        assert_eq!(
+            AxesPolicy::RequireRightHanded.apply(&x, &Vec3::y(), &-Vec3::z()),
+            Err("cuboid axes are left-handed")
+        );
+        assert!(AxesPolicy::RequireRightHanded.apply(&x, &Vec3::y(), &Vec3::z()).is_ok());
     }
 
     #[test]
@@ -852,6 +2152,8 @@ mod tests {
         });
 
         axes_mesh.uid = 1;
+        assert!(axes_mesh.is_line());
+        assert_topology(&axes_mesh, 6, 6);
     }
 
     #[test]
@@ -867,9 +2169,200 @@ mod tests {
             line_color: Color::White,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
-        });
+            axes_policy: AxesPolicy::AsGiven,
+        }).unwrap();
 
         // let mut vertex_view = cube.vertex_view_mut(0);
     }
+
+    #[test]
+    fn from_vertices_indices_test() {
+        let positions = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ];
+        let colors = [Color::Red, Color::Green, Color::Blue];
+        let mesh =
+            Mesh::from_vertices_indices(&positions, None, &colors, &[0, 1, 2], CellType::Triangle)
+                .unwrap();
+        assert_eq!(mesh.num_vertices(), 3);
+        assert_eq!(mesh.index_slice(), &[0, 1, 2]);
+        let n = Vertex::len();
+        for i in 0..3 {
+            // the computed normal of a counter-clockwise triangle in the xy-plane is +z
+            let vert = &mesh.vertex_slice()[i * n..(i + 1) * n];
+            assert_eq!(&vert[0..3], positions[i].as_slice());
+            assert_eq!(&vert[3..6], &[0.0, 0.0, 1.0]);
+        }
+
+        let normals = [Vec3::x(); 2];
+        let res = Mesh::from_vertices_indices(
+            &positions,
+            Some(&normals),
+            &colors,
+            &[0, 1, 2],
+            CellType::Triangle,
+        );
+        assert!(matches!(res, Err(Error::LengthMismatch)));
+        let res = Mesh::from_vertices_indices(&positions, None, &colors, &[1, 5], CellType::Line);
+        assert!(matches!(res, Err(Error::IndexOutOfBounds)));
+        let res = Mesh::from_vertices_indices(&positions, None, &colors, &[0, 1], CellType::None);
+        assert!(matches!(res, Err(Error::InvalidCellType)));
+        let inf_colors = [Color::Red, Color::Other((f32::INFINITY, 0.0, 0.0)), Color::Blue];
+        let res = Mesh::from_vertices_indices(&positions, None, &inf_colors, &[0, 1], CellType::Line);
+        assert!(matches!(res, Err(Error::NonFinite("colors"))));
+    }
+
+    /// One descriptor of each kind of shape.
+    pub(crate) fn sample_descriptors() -> Vec<Descriptor> {
+        let (lc, tc) = (Color::Black, Color::Other((0.2, 0.4, 0.6)));
+        vec![
+            Descriptor::Line(LineDescriptor {
+                v1: Vec3::zeros(),
+                v2: Vec3::x(),
+                color: lc,
+                dash_pattern: None,
+            }),
+            Descriptor::Triangle(TriangleDescriptor {
+                v1: Vec3::zeros(),
+                v2: Vec3::x(),
+                v3: Vec3::y(),
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Triangle,
+            }),
+            Descriptor::Plane(PlaneDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::y(),
+                x_min: -1.0,
+                x_max: 1.0,
+                y_min: -0.5,
+                y_max: 0.5,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Line,
+                color_by: ColorBy::None,
+            }),
+            Descriptor::Cuboid(CuboidDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::y(),
+                z_axis: Vec3::z(),
+                lenx: 1.0,
+                leny: 2.0,
+                lenz: 3.0,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Triangle,
+                axes_policy: AxesPolicy::AsGiven,
+            }),
+            Descriptor::Cylinder(CylinderDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                height: 2.0,
+                num_sides: 12,
+                cap_num_sides: None,
+                line_color: lc,
+                tri_color: tc,
+                open: false,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }),
+            Descriptor::Disc(DiscDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                num_sides: 12,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }),
+            Descriptor::Sphere(SphereDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius: 1.0,
+                n_lat: 6,
+                n_long: 8,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }),
+            Descriptor::Ellipsoid(EllipsoidDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::z(),
+                radius_x: 1.0,
+                radius_y: 2.0,
+                radius_z: 3.0,
+                n_lat: 6,
+                n_long: 8,
+                line_color: lc,
+                tri_color: tc,
+                cell_type: CellType::Line,
+                color_by: ColorBy::None,
+            }),
+            Descriptor::Axes(AxesDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::y(),
+                z_axis: Vec3::z(),
+                neg_len: 1.0,
+                pos_len: 2.0,
+            }),
+        ]
+    }
+
+    #[test]
+    fn default_cell_type_test() {
+        // shapes whose cell type is left at its default have no cells, instead of panicking
+        assert_eq!(CellType::default(), CellType::None);
+        for mut descriptor in sample_descriptors() {
+            let cell_type = match &mut descriptor {
+                Descriptor::Triangle(d) => &mut d.cell_type,
+                Descriptor::Plane(d) => &mut d.cell_type,
+                Descriptor::Cuboid(d) => &mut d.cell_type,
+                Descriptor::Cylinder(d) => &mut d.cell_type,
+                Descriptor::Disc(d) => &mut d.cell_type,
+                Descriptor::Sphere(d) => &mut d.cell_type,
+                Descriptor::Ellipsoid(d) => &mut d.cell_type,
+                Descriptor::Line(_) | Descriptor::Axes(_) => continue,
+            };
+            *cell_type = CellType::default();
+            let mesh = Mesh::from_descriptor(&descriptor).unwrap();
+            assert!(mesh.is_empty());
+            assert!(!mesh.has_cells());
+        }
+    }
+
+    #[test]
+    fn json_descriptor_round_trip_test() {
+        for descriptor in &sample_descriptors() {
+            let json = serde_json::to_string(descriptor).unwrap();
+            let parsed: Descriptor = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+            let expected = Mesh::from_descriptor(descriptor).unwrap();
+            let mesh = Mesh::from_json_descriptor(&json).unwrap();
+            assert_eq!(mesh.vertex_slice(), expected.vertex_slice());
+            assert_eq!(mesh.index_slice(), expected.index_slice());
+        }
+
+        let res = Mesh::from_json_descriptor(r#"{"type": "torus", "radius": 1.0}"#);
+        assert!(matches!(res, Err(Error::Json(_))));
+
+        // well-formed JSON of an invalid shape is rejected as the RPC server would
+        let sphere = sample_descriptors()
+            .into_iter()
+            .find(|descriptor| matches!(descriptor, Descriptor::Sphere(_)))
+            .unwrap();
+        let mut json = serde_json::to_value(&sphere).unwrap();
+        json["radius"] = serde_json::json!(0.0);
+        let res = Mesh::from_json_descriptor(&json.to_string());
+        assert!(matches!(res, Err(Error::InvalidDescriptor(_))));
+    }
 }
 //}}}
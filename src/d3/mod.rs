@@ -17,19 +17,27 @@ mod mesh;
 mod state;
 mod rpc;
 
-pub(crate) use state::State;
-pub(crate) use rpc::run_server;
+pub use state::{AddShapeError, State, State3D};
+pub(crate) use rpc::{run_server, validate_descriptor};
+pub(crate) use vertex::Vertex;
 
 pub use mesh::{
     AxesDescriptor, 
+    AxesPolicy,
+    ColorBy,
     CuboidDescriptor, 
     CylinderDescriptor, 
+    Descriptor,
     DiscDescriptor,
     LineDescriptor, 
     Mesh, 
     PlaneDescriptor,
+    PolygonFaceDescriptor,
     SphereDescriptor, 
+    EllipsoidDescriptor,
     TriangleDescriptor,
-    Mesh3D
+    Mesh3D,
+    Error as MeshError
 };
-pub use rpc::Client3D;
+pub use camera::CameraDescriptor;
+pub use rpc::{Client3D, Error as ClientError, RPC_SCHEMA};
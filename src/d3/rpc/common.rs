@@ -5,8 +5,13 @@
 
 //{{{ crate imports
 use super::d3rpc;
-use crate::common::{CellType, Color, Validated, Vec3};
+use crate::common::{
+    first_degenerate, first_non_finite, model_from_values, model_to_values, ClipMode,
+    Color, DepthBuffer, FrontFace, Mat4, MeshColorMode, MeshDiagnostics, MeshOptions, SceneBudget,
+    SceneStats, Validated, Vec3, ViewSensitivity,
+};
 use crate::d3::mesh::*;
+use crate::d3::camera::CameraDescriptor;
 use std::cell;
 //}}}
 //{{{ std imports
@@ -39,19 +44,146 @@ impl From<Vec3> for d3rpc::Vec3 {
 //{{{ impl From<d3rpc::Color> for Color
 impl From<d3rpc::Color> for Color {
     fn from(c: d3rpc::Color) -> Self {
-        Color::Other((c.r, c.g, c.b))
+        match c.a {
+            Some(a) => Color::Rgba((c.r, c.g, c.b, a)),
+            None => Color::Other((c.r, c.g, c.b)),
+        }
     }
 }
 //}}}
 //{{{ impl From<Color> for d3rpc::Color
 impl From<Color> for d3rpc::Color {
     fn from(c: Color) -> Self {
-        let rgp = c.to_rgb();
+        let rgba = c.to_rgba();
         Self {
-            r: rgp[0],
-            g: rgp[1],
-            b: rgp[2],
+            r: rgba[0],
+            g: rgba[1],
+            b: rgba[2],
+            a: matches!(c, Color::Rgba(_)).then_some(rgba[3]),
+        }
+    }
+}
+//}}}
+
+//{{{ fun: vec3_is_finite
+/// Returns `true` if the vector is absent or all of its components are finite.
+fn vec3_is_finite(v: &Option<d3rpc::Vec3>) -> bool {
+    v.as_ref()
+        .map_or(true, |v| v.x.is_finite() && v.y.is_finite() && v.z.is_finite())
+}
+//}}}
+//{{{ fun: color_is_finite
+/// Returns `true` if the color is absent or all of its channels are finite.
+fn color_is_finite(c: &Option<d3rpc::Color>) -> bool {
+    c.as_ref().map_or(true, |c| {
+        c.r.is_finite() && c.g.is_finite() && c.b.is_finite() && c.a.map_or(true, f32::is_finite)
+    })
+}
+//}}}
+
+//{{{ fun: color_mode_from_rpc
+/// Returns the color mode of a mesh sent with the given uniform color, drawn with the colors of
+/// its vertices when there is none.
+pub fn color_mode_from_rpc(uniform_color: Option<d3rpc::Color>) -> MeshColorMode {
+    uniform_color.map_or(MeshColorMode::PerVertex, |c| MeshColorMode::Uniform(c.into()))
+}
+//}}}
+//{{{ fun: color_mode_to_rpc
+/// Returns the uniform color a mesh with the given color mode is sent with, if any.
+pub fn color_mode_to_rpc(mode: MeshColorMode) -> Option<d3rpc::Color> {
+    match mode {
+        MeshColorMode::PerVertex => None,
+        MeshColorMode::Uniform(color) => Some(color.into()),
+    }
+}
+//}}}
+//{{{ fun: options_are_finite
+/// Returns `true` if the mesh options are absent or all of their values are finite.
+fn options_are_finite(o: &Option<d3rpc::MeshOptions>) -> bool {
+    o.as_ref().map_or(true, |o| {
+        color_is_finite(&o.edge_color) && o.dash_length.is_finite() && o.gap_length.is_finite()
+    })
+}
+//}}}
+
+//{{{ fun: to_vec3
+fn to_vec3(v: &Option<d3rpc::Vec3>) -> Option<Vec3> {
+    v.clone().map(Vec3::from)
+}
+//}}}
+//{{{ fun: is_zero
+/// Returns `true` if the vector is too short to define a direction.
+fn is_zero(v: &Vec3) -> bool {
+    v.norm() <= f32::EPSILON
+}
+//}}}
+//{{{ fun: are_parallel
+/// Returns `true` if the two vectors are parallel, or either is zero.
+fn are_parallel(a: &Vec3, b: &Vec3) -> bool {
+    a.cross(b).norm() <= f32::EPSILON * a.norm() * b.norm()
+}
+//}}}
+
+//{{{ fun: validate_descriptor
+/// Validates a shape descriptor with the same checks the RPC server applies to the matching add
+/// request, so that shapes from other sources, such as scene files, are held to the same rules.
+pub(crate) fn validate_descriptor(descriptor: &Descriptor) -> Result<(), String> {
+    let client_name = String::new();
+    match descriptor.clone() {
+        Descriptor::Line(line) => d3rpc::AddLineRequest {
+            client_name,
+            name: String::new(),
+            line_descriptor: Some(line.into()),
+        }
+        .validate(),
+        Descriptor::Triangle(triangle) => d3rpc::AddTriangleRequest {
+            client_name,
+            name: String::new(),
+            triangle_descriptor: Some(triangle.into()),
+        }
+        .validate(),
+        Descriptor::Plane(plane) => d3rpc::AddPlaneRequest {
+            client_name,
+            name: String::new(),
+            plane_descriptor: Some(plane.into()),
+        }
+        .validate(),
+        Descriptor::Cuboid(cuboid) => d3rpc::AddCuboidRequest {
+            client_name,
+            name: String::new(),
+            cuboid_descriptor: Some(cuboid.into()),
         }
+        .validate(),
+        Descriptor::Cylinder(cylinder) => d3rpc::AddCylinderRequest {
+            client_name,
+            name: String::new(),
+            cylinder_descriptor: Some(cylinder.into()),
+        }
+        .validate(),
+        Descriptor::Disc(disc) => d3rpc::AddDiscRequest {
+            client_name,
+            name: String::new(),
+            disc_descriptor: Some(disc.into()),
+        }
+        .validate(),
+        Descriptor::Sphere(sphere) => d3rpc::AddSphereRequest {
+            client_name,
+            name: String::new(),
+            sphere_descriptor: Some(sphere.into()),
+        }
+        .validate(),
+        Descriptor::Ellipsoid(ellipsoid) => d3rpc::AddEllipsoidRequest {
+            client_name,
+            name: String::new(),
+            ellipsoid_descriptor: Some(ellipsoid.into()),
+        }
+        .validate(),
+        Descriptor::Axes(axes) => d3rpc::AddAxesRequest {
+            client_name,
+            name: String::new(),
+            axes_descriptor: Some(axes.into()),
+        }
+        .validate(),
     }
 }
 //}}}
@@ -65,6 +197,32 @@ impl Validated for d3rpc::AddLineRequest {
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let ld = self.line_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("v1", vec3_is_finite(&ld.v1)),
+            ("v2", vec3_is_finite(&ld.v2)),
+            ("color", color_is_finite(&ld.color)),
+            ("dash_length", ld.dash_length.is_finite()),
+            ("gap_length", ld.gap_length.is_finite()),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let ld = self.line_descriptor.as_ref()?;
+        let (v1, v2) = (
+            to_vec3(&ld.v1)?,
+            to_vec3(&ld.v2)?,
+        );
+        first_degenerate(&[
+            (is_zero(&(v2 - v1)), "line endpoints v1 and v2 coincide"),
+            (
+                ld.dash_length < 0.0 || ld.gap_length < 0.0,
+                "line dash_length and gap_length must not be negative",
+            ),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::LineDescriptor> for LineDescriptor
@@ -74,6 +232,7 @@ impl From<d3rpc::LineDescriptor> for LineDescriptor {
             v1: ld.v1.unwrap().into(),
             v2: ld.v2.unwrap().into(),
             color: ld.color.unwrap().into(),
+            dash_pattern: (ld.dash_length > 0.0).then_some((ld.dash_length, ld.gap_length)),
         }
     }
 }
@@ -81,10 +240,13 @@ impl From<d3rpc::LineDescriptor> for LineDescriptor {
 //{{{ impl From<LineDescriptor> for d3rpc::LineDescriptor
 impl From<LineDescriptor> for d3rpc::LineDescriptor {
     fn from(ld: LineDescriptor) -> Self {
+        let (dash_length, gap_length) = ld.dash_pattern.unwrap_or((0.0, 0.0));
         d3rpc::LineDescriptor {
             v1: Some(ld.v1.into()),
             v2: Some(ld.v2.into()),
             color: Some(ld.color.into()),
+            dash_length: dash_length,
+            gap_length: gap_length,
         }
     }
 }
@@ -106,6 +268,33 @@ impl Validated for d3rpc::AddTriangleRequest {
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let td = self.triangle_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("v1", vec3_is_finite(&td.v1)),
+            ("v2", vec3_is_finite(&td.v2)),
+            ("v3", vec3_is_finite(&td.v3)),
+            ("line_color", color_is_finite(&td.line_color)),
+            ("tri_color", color_is_finite(&td.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let td = self.triangle_descriptor.as_ref()?;
+        let (v1, v2, v3) = (
+            to_vec3(&td.v1)?,
+            to_vec3(&td.v2)?,
+            to_vec3(&td.v3)?,
+        );
+        first_degenerate(&[
+            (
+                is_zero(&(v2 - v1)) || is_zero(&(v3 - v2)) || is_zero(&(v1 - v3)),
+                "triangle has coincident vertices",
+            ),
+            (are_parallel(&(v2 - v1), &(v3 - v1)), "triangle vertices are collinear"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::TriangleDescriptor> for TriangleDescriptor 
@@ -156,6 +345,34 @@ impl Validated for d3rpc::AddPlaneRequest {
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let pd = self.plane_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&pd.origin)),
+            ("x_axis", vec3_is_finite(&pd.x_axis)),
+            ("y_axis", vec3_is_finite(&pd.y_axis)),
+            ("x_min", pd.x_min.is_finite()),
+            ("x_max", pd.x_max.is_finite()),
+            ("y_min", pd.y_min.is_finite()),
+            ("y_max", pd.y_max.is_finite()),
+            ("line_color", color_is_finite(&pd.line_color)),
+            ("tri_color", color_is_finite(&pd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let pd = self.plane_descriptor.as_ref()?;
+        let (x_axis, y_axis) = (
+            to_vec3(&pd.x_axis)?,
+            to_vec3(&pd.y_axis)?,
+        );
+        first_degenerate(&[
+            (is_zero(&x_axis), "plane x_axis has zero length"),
+            (is_zero(&y_axis), "plane y_axis has zero length"),
+            (are_parallel(&x_axis, &y_axis), "plane x_axis and y_axis are parallel"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::PlaneDescriptor> for PlaneDescriptor
@@ -172,6 +389,7 @@ impl From<d3rpc::PlaneDescriptor> for PlaneDescriptor {
             line_color: pd.line_color.unwrap().into(),
             tri_color: pd.tri_color.unwrap().into(),
             cell_type: pd.cell_type.into(),
+            color_by: pd.color_by.into(),
         }
     }
 }
@@ -190,6 +408,65 @@ impl From<PlaneDescriptor> for d3rpc::PlaneDescriptor {
             line_color: Some(pd.line_color.into()),
             tri_color: Some(pd.tri_color.into()),
             cell_type: pd.cell_type.into(),
+            color_by: pd.color_by.into(),
+        }
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::AddPolygonFaceRequest
+impl Validated for d3rpc::AddPolygonFaceRequest {
+    fn is_valid(&self) -> bool {
+        match self.polygon_face_descriptor {
+            None => false,
+            Some(ref pfd) => {
+                pfd.points.len() >= 3 &&
+                pfd.line_color.is_some() &&
+                pfd.tri_color.is_some() &&
+                pfd.cell_type > 0
+            }
+        }
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let pfd = self.polygon_face_descriptor.as_ref()?;
+        let points_finite = pfd
+            .points
+            .iter()
+            .all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite());
+        first_non_finite(&[
+            ("points", points_finite),
+            ("line_color", color_is_finite(&pfd.line_color)),
+            ("tri_color", color_is_finite(&pfd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let pfd = self.polygon_face_descriptor.as_ref()?;
+        let points: Vec<Vec3> = pfd.points.iter().cloned().map(Vec3::from).collect();
+        triangulate_polygon_face(&points).err()
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::PolygonFaceDescriptor> for PolygonFaceDescriptor
+impl From<d3rpc::PolygonFaceDescriptor> for PolygonFaceDescriptor {
+    fn from(pfd: d3rpc::PolygonFaceDescriptor) -> Self {
+        PolygonFaceDescriptor {
+            points: pfd.points.into_iter().map(Vec3::from).collect(),
+            line_color: pfd.line_color.unwrap().into(),
+            tri_color: pfd.tri_color.unwrap().into(),
+            cell_type: pfd.cell_type.into(),
+        }
+    }
+}
+//}}}
+//{{{ impl From<PolygonFaceDescriptor> for d3rpc::PolygonFaceDescriptor
+impl From<PolygonFaceDescriptor> for d3rpc::PolygonFaceDescriptor {
+    fn from(pfd: PolygonFaceDescriptor) -> Self {
+        d3rpc::PolygonFaceDescriptor {
+            points: pfd.points.into_iter().map(d3rpc::Vec3::from).collect(),
+            line_color: Some(pfd.line_color.into()),
+            tri_color: Some(pfd.tri_color.into()),
+            cell_type: pfd.cell_type.into(),
         }
     }
 }
@@ -216,6 +493,48 @@ impl Validated for d3rpc::AddCuboidRequest
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let cd = self.cuboid_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&cd.origin)),
+            ("x_axis", vec3_is_finite(&cd.x_axis)),
+            ("y_axis", vec3_is_finite(&cd.y_axis)),
+            ("z_axis", vec3_is_finite(&cd.z_axis)),
+            ("lenx", cd.lenx.is_finite()),
+            ("leny", cd.leny.is_finite()),
+            ("lenz", cd.lenz.is_finite()),
+            ("line_color", color_is_finite(&cd.line_color)),
+            ("tri_color", color_is_finite(&cd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let cd = self.cuboid_descriptor.as_ref()?;
+        let (x_axis, y_axis, z_axis) = (
+            to_vec3(&cd.x_axis)?,
+            to_vec3(&cd.y_axis)?,
+            to_vec3(&cd.z_axis)?,
+        );
+        first_degenerate(&[
+            (is_zero(&x_axis), "cuboid x_axis has zero length"),
+            (is_zero(&y_axis), "cuboid y_axis has zero length"),
+            (is_zero(&z_axis), "cuboid z_axis has zero length"),
+            (are_parallel(&x_axis, &y_axis), "cuboid x_axis and y_axis are parallel"),
+            (are_parallel(&y_axis, &z_axis), "cuboid y_axis and z_axis are parallel"),
+            (are_parallel(&z_axis, &x_axis), "cuboid z_axis and x_axis are parallel"),
+            (
+                x_axis.cross(&y_axis).dot(&z_axis).abs()
+                <= f32::EPSILON * x_axis.norm() * y_axis.norm() * z_axis.norm(),
+                "cuboid axes are coplanar",
+            ),
+        ])
+        .or_else(|| {
+            AxesPolicy::from(cd.axes_policy)
+                .apply(&x_axis, &y_axis, &z_axis)
+                .err()
+        })
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::CuboidDescriptor> for CuboidDescriptor
@@ -233,6 +552,7 @@ impl From<d3rpc::CuboidDescriptor> for CuboidDescriptor
             line_color: cd.line_color.unwrap().into(),
             tri_color: cd.tri_color.unwrap().into(),
             cell_type: cd.cell_type.into(),
+            axes_policy: cd.axes_policy.into(),
         }
     }
 }
@@ -252,10 +572,80 @@ impl From<CuboidDescriptor> for d3rpc::CuboidDescriptor
             line_color: Some(cd.line_color.into()),
             tri_color: Some(cd.tri_color.into()),
             cell_type: cd.cell_type.into(),
+            axes_policy: cd.axes_policy.into(),
         }
     }
 }
 //}}}
+//{{{ impl From<i32> for ColorBy
+impl From<i32> for ColorBy {
+    fn from(value: i32) -> Self {
+        match d3rpc::ColorBy::try_from(value) {
+            Ok(d3rpc::ColorBy::U) => ColorBy::ByU,
+            Ok(d3rpc::ColorBy::V) => ColorBy::ByV,
+            Ok(d3rpc::ColorBy::Index) => ColorBy::ByIndex,
+            _ => ColorBy::None,
+        }
+    }
+}
+//}}}
+//{{{ impl From<ColorBy> for i32
+impl From<ColorBy> for i32 {
+    fn from(color_by: ColorBy) -> Self {
+        let color_by = match color_by {
+            ColorBy::None => d3rpc::ColorBy::None,
+            ColorBy::ByU => d3rpc::ColorBy::U,
+            ColorBy::ByV => d3rpc::ColorBy::V,
+            ColorBy::ByIndex => d3rpc::ColorBy::Index,
+        };
+        color_by as i32
+    }
+}
+//}}}
+//{{{ impl From<i32> for ClipMode
+impl From<i32> for ClipMode {
+    fn from(value: i32) -> Self {
+        match d3rpc::ClipMode::try_from(value) {
+            Ok(d3rpc::ClipMode::InsideAny) => ClipMode::InsideAny,
+            _ => ClipMode::InsideAll,
+        }
+    }
+}
+//}}}
+//{{{ impl From<ClipMode> for i32
+impl From<ClipMode> for i32 {
+    fn from(mode: ClipMode) -> Self {
+        let mode = match mode {
+            ClipMode::InsideAll => d3rpc::ClipMode::InsideAll,
+            ClipMode::InsideAny => d3rpc::ClipMode::InsideAny,
+        };
+        mode as i32
+    }
+}
+//}}}
+//{{{ impl From<i32> for AxesPolicy
+impl From<i32> for AxesPolicy {
+    fn from(value: i32) -> Self {
+        match d3rpc::AxesPolicy::try_from(value) {
+            Ok(d3rpc::AxesPolicy::Orthonormalize) => AxesPolicy::Orthonormalize,
+            Ok(d3rpc::AxesPolicy::RequireRightHanded) => AxesPolicy::RequireRightHanded,
+            _ => AxesPolicy::AsGiven,
+        }
+    }
+}
+//}}}
+//{{{ impl From<AxesPolicy> for i32
+impl From<AxesPolicy> for i32 {
+    fn from(policy: AxesPolicy) -> Self {
+        let policy = match policy {
+            AxesPolicy::AsGiven => d3rpc::AxesPolicy::AsGiven,
+            AxesPolicy::Orthonormalize => d3rpc::AxesPolicy::Orthonormalize,
+            AxesPolicy::RequireRightHanded => d3rpc::AxesPolicy::RequireRightHanded,
+        };
+        policy as i32
+    }
+}
+//}}}
 
 //{{{ impl Validated for d3rpc::AddCylinderRequest
 impl Validated for d3rpc::AddCylinderRequest
@@ -268,7 +658,6 @@ impl Validated for d3rpc::AddCylinderRequest
                 cd.axis.is_some() &&
                 cd.radius > 0.0 &&
                 cd.height > 0.0 &&
-                cd.num_sides > 0 &&
                 cd.line_color.is_some() &&
                 cd.tri_color.is_some() &&
                 cd.cell_type > 0
@@ -276,6 +665,26 @@ impl Validated for d3rpc::AddCylinderRequest
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let cd = self.cylinder_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&cd.origin)),
+            ("axis", vec3_is_finite(&cd.axis)),
+            ("radius", cd.radius.is_finite()),
+            ("height", cd.height.is_finite()),
+            ("line_color", color_is_finite(&cd.line_color)),
+            ("tri_color", color_is_finite(&cd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let cd = self.cylinder_descriptor.as_ref()?;
+        let axis = to_vec3(&cd.axis)?;
+        first_degenerate(&[
+            (is_zero(&axis), "cylinder axis has zero length"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::CylinderDescriptor> for CylinderDescriptor
@@ -288,10 +697,12 @@ impl From<d3rpc::CylinderDescriptor> for CylinderDescriptor
             radius: cd.radius,
             height: cd.height,
             num_sides: cd.num_sides as usize,
+            cap_num_sides: (cd.cap_num_sides > 0).then_some(cd.cap_num_sides as usize),
             line_color: cd.line_color.unwrap().into(),
             tri_color: cd.tri_color.unwrap().into(),
             open: cd.open,
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
         }
     }
 }
@@ -310,6 +721,8 @@ impl From<CylinderDescriptor> for d3rpc::CylinderDescriptor
             tri_color: Some(cd.tri_color.into()),
             open: cd.open,
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
+            cap_num_sides: cd.cap_num_sides.unwrap_or(0) as u32,
         }
     }
 }
@@ -325,7 +738,6 @@ impl Validated for d3rpc::AddDiscRequest
                 cd.origin.is_some() &&
                 cd.axis.is_some() &&
                 cd.radius > 0.0 &&
-                cd.num_sides> 0 &&
                 cd.line_color.is_some() &&
                 cd.tri_color.is_some() && 
                 cd.cell_type > 0
@@ -333,6 +745,25 @@ impl Validated for d3rpc::AddDiscRequest
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let cd = self.disc_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&cd.origin)),
+            ("axis", vec3_is_finite(&cd.axis)),
+            ("radius", cd.radius.is_finite()),
+            ("line_color", color_is_finite(&cd.line_color)),
+            ("tri_color", color_is_finite(&cd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let dd = self.disc_descriptor.as_ref()?;
+        let axis = to_vec3(&dd.axis)?;
+        first_degenerate(&[
+            (is_zero(&axis), "disc axis has zero length"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::DiscDescriptor> for DiscDescriptor
@@ -347,6 +778,7 @@ impl From<d3rpc::DiscDescriptor> for DiscDescriptor
             line_color: cd.line_color.unwrap().into(),
             tri_color: cd.tri_color.unwrap().into(),
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
             }
     }
 }
@@ -363,6 +795,7 @@ impl From<DiscDescriptor> for d3rpc::DiscDescriptor
             line_color: Some(cd.line_color.into()),
             tri_color: Some(cd.tri_color.into()),
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
             }
     }
 }
@@ -378,8 +811,6 @@ impl Validated for d3rpc::AddSphereRequest
                 cd.origin.is_some() &&
                 cd.axis.is_some() &&    
                 cd.radius > 0.0 &&
-                cd.n_lat > 0 &&
-                cd.n_long > 0 &&
                 cd.line_color.is_some() &&
                 cd.tri_color.is_some() && 
                 cd.cell_type > 0
@@ -387,6 +818,25 @@ impl Validated for d3rpc::AddSphereRequest
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let cd = self.sphere_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&cd.origin)),
+            ("axis", vec3_is_finite(&cd.axis)),
+            ("radius", cd.radius.is_finite()),
+            ("line_color", color_is_finite(&cd.line_color)),
+            ("tri_color", color_is_finite(&cd.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let sd = self.sphere_descriptor.as_ref()?;
+        let axis = to_vec3(&sd.axis)?;
+        first_degenerate(&[
+            (is_zero(&axis), "sphere axis has zero length"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::SphereDescriptor> for SphereDescriptor
@@ -402,6 +852,7 @@ impl From<d3rpc::SphereDescriptor> for SphereDescriptor
             line_color: cd.line_color.unwrap().into(),
             tri_color: cd.tri_color.unwrap().into(),
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
         }
     }
 }
@@ -419,6 +870,93 @@ impl From<SphereDescriptor> for d3rpc::SphereDescriptor
             line_color: Some(cd.line_color.into()),
             tri_color: Some(cd.tri_color.into()),
             cell_type: cd.cell_type.into(),
+            color_by: cd.color_by.into(),
+        }
+    }
+}
+//}}}
+
+//{{{ impl Validated for d3rpc::AddEllipsoidRequest
+impl Validated for d3rpc::AddEllipsoidRequest
+{
+    fn is_valid(&self) -> bool {
+        let is_val = match self.ellipsoid_descriptor {
+            None => false,
+            Some(ref ed) => {
+                ed.origin.is_some() &&
+                ed.axis.is_some() &&
+                ed.radius_x > 0.0 &&
+                ed.radius_y > 0.0 &&
+                ed.radius_z > 0.0 &&
+                // zero counts are left to the tessellation quality
+                (ed.n_lat == 0 || ed.n_lat > 1) &&
+                (ed.n_long == 0 || ed.n_long > 2) &&
+                ed.line_color.is_some() &&
+                ed.tri_color.is_some() &&
+                ed.cell_type > 0
+            }
+        };
+        is_val
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let ed = self.ellipsoid_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&ed.origin)),
+            ("axis", vec3_is_finite(&ed.axis)),
+            ("radius_x", ed.radius_x.is_finite()),
+            ("radius_y", ed.radius_y.is_finite()),
+            ("radius_z", ed.radius_z.is_finite()),
+            ("line_color", color_is_finite(&ed.line_color)),
+            ("tri_color", color_is_finite(&ed.tri_color)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let ed = self.ellipsoid_descriptor.as_ref()?;
+        let axis = to_vec3(&ed.axis)?;
+        first_degenerate(&[
+            (is_zero(&axis), "ellipsoid axis has zero length"),
+        ])
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::EllipsoidDescriptor> for EllipsoidDescriptor
+impl From<d3rpc::EllipsoidDescriptor> for EllipsoidDescriptor
+{
+    fn from(ed: d3rpc::EllipsoidDescriptor) -> Self {
+        EllipsoidDescriptor {
+            origin: ed.origin.unwrap().into(),
+            axis: ed.axis.unwrap().into(),
+            radius_x: ed.radius_x,
+            radius_y: ed.radius_y,
+            radius_z: ed.radius_z,
+            n_lat: ed.n_lat as usize,
+            n_long: ed.n_long as usize,
+            line_color: ed.line_color.unwrap().into(),
+            tri_color: ed.tri_color.unwrap().into(),
+            cell_type: ed.cell_type.into(),
+            color_by: ed.color_by.into(),
+        }
+    }
+}
+//}}}
+//{{{ impl From<EllipsoidDescriptor> for d3rpc::EllipsoidDescriptor
+impl From<EllipsoidDescriptor> for d3rpc::EllipsoidDescriptor
+{
+    fn from(ed: EllipsoidDescriptor) -> Self {
+        d3rpc::EllipsoidDescriptor {
+            origin: Some(ed.origin.into()),
+            axis: Some(ed.axis.into()),
+            radius_x: ed.radius_x,
+            radius_y: ed.radius_y,
+            radius_z: ed.radius_z,
+            n_lat: ed.n_lat as u32,
+            n_long: ed.n_long as u32,
+            line_color: Some(ed.line_color.into()),
+            tri_color: Some(ed.tri_color.into()),
+            cell_type: ed.cell_type.into(),
+            color_by: ed.color_by.into(),
         }
     }
 }
@@ -441,6 +979,32 @@ impl Validated for d3rpc::AddAxesRequest
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let ad = self.axes_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("origin", vec3_is_finite(&ad.origin)),
+            ("x_axis", vec3_is_finite(&ad.x_axis)),
+            ("y_axis", vec3_is_finite(&ad.y_axis)),
+            ("z_axis", vec3_is_finite(&ad.z_axis)),
+            ("neg_len", ad.neg_len.is_finite()),
+            ("pos_len", ad.pos_len.is_finite()),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let ad = self.axes_descriptor.as_ref()?;
+        let (x_axis, y_axis, z_axis) = (
+            to_vec3(&ad.x_axis)?,
+            to_vec3(&ad.y_axis)?,
+            to_vec3(&ad.z_axis)?,
+        );
+        first_degenerate(&[
+            (is_zero(&x_axis), "axes x_axis has zero length"),
+            (is_zero(&y_axis), "axes y_axis has zero length"),
+            (is_zero(&z_axis), "axes z_axis has zero length"),
+        ])
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::AxesDescriptor> for AxesDescriptor
@@ -483,11 +1047,175 @@ impl Validated for d3rpc::AddMeshRequest
             Some(ref md) => {
                 md.vertices.len() > 0 &&
                 md.indices.len() > 0 &&
-                md.cell_type > 0
+                md.cell_type > 0 &&
+                model_from_values(&md.model).is_some()
             }
         };
         is_val
     }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::AppendToMeshRequest
+impl Validated for d3rpc::AppendToMeshRequest
+{
+    fn is_valid(&self) -> bool {
+        let is_val = match self.mesh_descriptor {
+            None => false,
+            Some(ref md) => {
+                md.vertices.len() > 0 &&
+                md.indices.len() > 0 &&
+                md.cell_type > 0 &&
+                model_from_values(&md.model).is_some()
+            }
+        };
+        is_val
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::ReplaceMeshRequest
+impl Validated for d3rpc::ReplaceMeshRequest
+{
+    fn is_valid(&self) -> bool {
+        let is_val = match self.mesh_descriptor {
+            None => false,
+            Some(ref md) => {
+                md.vertices.len() > 0 &&
+                md.indices.len() > 0 &&
+                md.cell_type > 0 &&
+                model_from_values(&md.model).is_some()
+            }
+        };
+        is_val
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        let md = self.mesh_descriptor.as_ref()?;
+        first_non_finite(&[
+            ("vertices", md.vertices.iter().all(|x| x.is_finite())),
+            ("model", md.model.iter().all(|x| x.is_finite())),
+            ("uniform_color", color_is_finite(&md.uniform_color)),
+            ("options", options_are_finite(&md.options)),
+        ])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::SetMeshModelRequest
+impl Validated for d3rpc::SetMeshModelRequest
+{
+    fn is_valid(&self) -> bool {
+        model_from_values(&self.model).is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        first_non_finite(&[("model", self.model.iter().all(|x| x.is_finite()))])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::SetMeshTagRequest
+impl Validated for d3rpc::SetMeshTagRequest
+{
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str>
+    {
+        None
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::TransformGroupRequest
+impl Validated for d3rpc::TransformGroupRequest
+{
+    fn is_valid(&self) -> bool {
+        !self.tag.is_empty() && model_from_values(&self.transform).is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        first_non_finite(&[("transform", self.transform.iter().all(|x| x.is_finite()))])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::SetMeshColorModeRequest
+impl Validated for d3rpc::SetMeshColorModeRequest
+{
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        first_non_finite(&[("uniform_color", color_is_finite(&self.uniform_color))])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::SetMeshOptionsRequest
+impl Validated for d3rpc::SetMeshOptionsRequest
+{
+    fn is_valid(&self) -> bool {
+        self.options.is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        first_non_finite(&[("options", options_are_finite(&self.options))])
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::ValidateMeshRequest
+impl Validated for d3rpc::ValidateMeshRequest
+{
+    fn is_valid(&self) -> bool {
+        self.mesh_descriptor.is_some()
+    }
+
+    /// Non-finite vertices are what the request reports on, so they are let through.
+    fn non_finite_field(&self) -> Option<&'static str> {
+        None
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::MeshOptions> for MeshOptions
+impl From<d3rpc::MeshOptions> for MeshOptions
+{
+    fn from(options: d3rpc::MeshOptions) -> Self {
+        MeshOptions {
+            edge_color: options.edge_color.map(Color::from),
+            dash_pattern: (options.dash_length > 0.0)
+                .then_some((options.dash_length, options.gap_length)),
+        }
+    }
+}
+//}}}
+//{{{ impl From<MeshOptions> for d3rpc::MeshOptions
+impl From<MeshOptions> for d3rpc::MeshOptions
+{
+    fn from(options: MeshOptions) -> Self {
+        let (dash_length, gap_length) = options.dash_pattern.unwrap_or((0.0, 0.0));
+        d3rpc::MeshOptions {
+            edge_color: options.edge_color.map(d3rpc::Color::from),
+            dash_length: dash_length,
+            gap_length: gap_length,
+        }
+    }
 }
 //}}}
 //{{{ impl From<d3rpc::MeshDescriptor> for Mesh<'a>
@@ -498,6 +1226,16 @@ impl<'a> From<d3rpc::MeshDescriptor> for Mesh<'a>
             vertices: md.vertices, 
             indices: md.indices,
             cell_type: (md.cell_type as i32).into(),
+            line_indices: md.line_indices,
+            overlay: md.overlay,
+            line_depth_bias: md.line_depth_bias,
+            name: None,
+            tag: None,
+            color_mode: color_mode_from_rpc(md.uniform_color),
+            options: md.options.map(MeshOptions::from).unwrap_or_default(),
+            model: model_from_values(&md.model).unwrap_or_else(Mat4::identity),
+            layer: 0,
+            front_face: if md.clockwise { FrontFace::Cw } else { FrontFace::Ccw },
             uid: 0,
             phant: PhantomData,
         }
@@ -512,6 +1250,150 @@ impl<'a> From<Mesh<'a>> for d3rpc::MeshDescriptor
             vertices: md.vertices,
             indices: md.indices,
             cell_type: (md.cell_type as i32).into(),
+            line_indices: md.line_indices,
+            overlay: md.overlay,
+            line_depth_bias: md.line_depth_bias,
+            model: model_to_values(&md.model),
+            uniform_color: color_mode_to_rpc(md.color_mode),
+            options: Some(md.options.into()),
+            clockwise: md.front_face == FrontFace::Cw,
+        }
+    }
+}
+//}}}
+
+//{{{ impl From<CameraDescriptor> for d3rpc::GetCameraResponse
+impl From<CameraDescriptor> for d3rpc::GetCameraResponse {
+    fn from(camera: CameraDescriptor) -> Self {
+        d3rpc::GetCameraResponse {
+            position: Some(camera.position.into()),
+            focus: Some(camera.focus.into()),
+            pitch: camera.pitch,
+            yaw: camera.yaw,
+            fov: camera.fov,
+        }
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::GetCameraResponse> for CameraDescriptor
+impl From<d3rpc::GetCameraResponse> for CameraDescriptor {
+    fn from(camera: d3rpc::GetCameraResponse) -> Self {
+        CameraDescriptor {
+            position: camera.position.unwrap_or_default().into(),
+            focus: camera.focus.unwrap_or_default().into(),
+            pitch: camera.pitch,
+            yaw: camera.yaw,
+            fov: camera.fov,
+        }
+    }
+}
+//}}}
+//{{{ impl From<SceneStats> for d3rpc::GetSceneStatsResponse
+/// An unlimited quantity is sent as a maximum of zero.
+impl From<SceneStats> for d3rpc::GetSceneStatsResponse {
+    fn from(stats: SceneStats) -> Self {
+        d3rpc::GetSceneStatsResponse {
+            num_meshes: stats.num_meshes as u64,
+            num_vertices: stats.num_vertices as u64,
+            max_meshes: stats.budget.max_meshes.unwrap_or(0) as u64,
+            max_vertices: stats.budget.max_vertices.unwrap_or(0) as u64,
+        }
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::GetSceneStatsResponse> for SceneStats
+impl From<d3rpc::GetSceneStatsResponse> for SceneStats {
+    fn from(stats: d3rpc::GetSceneStatsResponse) -> Self {
+        SceneStats {
+            num_meshes: stats.num_meshes as usize,
+            num_vertices: stats.num_vertices as usize,
+            budget: SceneBudget {
+                max_meshes: (stats.max_meshes > 0).then_some(stats.max_meshes as usize),
+                max_vertices: (stats.max_vertices > 0).then_some(stats.max_vertices as usize),
+            },
+        }
+    }
+}
+//}}}
+//{{{ impl From<MeshDiagnostics> for d3rpc::MeshStatsResponse
+impl From<MeshDiagnostics> for d3rpc::MeshStatsResponse {
+    fn from(diagnostics: MeshDiagnostics) -> Self {
+        d3rpc::MeshStatsResponse {
+            num_vertices: diagnostics.num_vertices as u64,
+            num_triangles: diagnostics.num_triangles as u64,
+            min: diagnostics.bounds.map(|(min, _)| min.into()),
+            max: diagnostics.bounds.map(|(_, max)| max.into()),
+            is_manifold: diagnostics.is_manifold,
+            has_degenerate: diagnostics.has_degenerate,
+            non_finite_count: diagnostics.non_finite_count as u64,
+        }
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::MeshStatsResponse> for MeshDiagnostics
+impl From<d3rpc::MeshStatsResponse> for MeshDiagnostics {
+    fn from(stats: d3rpc::MeshStatsResponse) -> Self {
+        MeshDiagnostics {
+            num_vertices: stats.num_vertices as usize,
+            num_triangles: stats.num_triangles as usize,
+            bounds: stats.min.zip(stats.max).map(|(min, max)| (min.into(), max.into())),
+            is_manifold: stats.is_manifold,
+            has_degenerate: stats.has_degenerate,
+            non_finite_count: stats.non_finite_count as usize,
+        }
+    }
+}
+//}}}
+//{{{ impl From<DepthBuffer> for d3rpc::ReadDepthBufferResponse
+impl From<DepthBuffer> for d3rpc::ReadDepthBufferResponse {
+    fn from(depth_buffer: DepthBuffer) -> Self {
+        d3rpc::ReadDepthBufferResponse {
+            width: depth_buffer.width,
+            height: depth_buffer.height,
+            depths: depth_buffer.depths,
+        }
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::ReadDepthBufferResponse> for DepthBuffer
+impl From<d3rpc::ReadDepthBufferResponse> for DepthBuffer {
+    fn from(msg: d3rpc::ReadDepthBufferResponse) -> Self {
+        DepthBuffer {
+            width: msg.width,
+            height: msg.height,
+            depths: msg.depths,
+        }
+    }
+}
+//}}}
+//{{{ impl Validated for d3rpc::SetCameraRequest
+impl Validated for d3rpc::SetCameraRequest {
+    fn is_valid(&self) -> bool {
+        self.position.is_some() && self.focus.is_some()
+    }
+
+    fn non_finite_field(&self) -> Option<&'static str> {
+        first_non_finite(&[
+            ("position", vec3_is_finite(&self.position)),
+            ("focus", vec3_is_finite(&self.focus)),
+        ])
+    }
+
+    fn degenerate_reason(&self) -> Option<&'static str> {
+        let (position, focus) = (to_vec3(&self.position)?, to_vec3(&self.focus)?);
+        first_degenerate(&[
+            (is_zero(&(focus - position)), "camera position and focus coincide"),
+        ])
+    }
+}
+//}}}
+//{{{ impl From<d3rpc::SetViewSensitivityRequest> for ViewSensitivity
+impl From<d3rpc::SetViewSensitivityRequest> for ViewSensitivity {
+    fn from(msg: d3rpc::SetViewSensitivityRequest) -> Self {
+        ViewSensitivity {
+            key_pan_delta: msg.key_pan_delta,
+            key_rotate_delta: msg.key_rotate_delta,
+            zoom_speed: msg.zoom_speed,
         }
     }
 }
@@ -521,7 +1403,362 @@ impl<'a> From<Mesh<'a>> for d3rpc::MeshDescriptor
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
-mod tests {}
+mod tests {
+
+    use super::*;
+    use crate::common::CellType;
+    use crate::d3::mesh::tests::sample_descriptors;
+
+    #[test]
+    fn mesh_descriptor_round_trip_test() {
+        // the name of a mesh stays behind
+        let check = |mesh: Mesh, expected: Mesh| {
+            let mesh_descriptor: d3rpc::MeshDescriptor = mesh.into();
+            let mesh: Mesh = mesh_descriptor.into();
+            assert_eq!(mesh.vertex_slice(), expected.vertex_slice());
+            assert_eq!(mesh.index_slice(), expected.index_slice());
+            assert_eq!(mesh.line_cell_indices(), expected.line_cell_indices());
+            assert_eq!(mesh.cell_type, expected.cell_type);
+            assert_eq!(mesh.is_overlay(), expected.is_overlay());
+            assert_eq!(mesh.line_depth_bias(), expected.line_depth_bias());
+            assert_eq!(mesh.model(), expected.model());
+            assert_eq!(mesh.color_mode(), expected.color_mode());
+            assert_eq!(mesh.options(), expected.options());
+            assert_eq!(mesh.front_face(), expected.front_face());
+        };
+        for descriptor in sample_descriptors() {
+            check(
+                Mesh::from_descriptor(&descriptor).unwrap(),
+                Mesh::from_descriptor(&descriptor).unwrap(),
+            );
+        }
+        let overlay = || {
+            let mut mesh = Mesh::from_descriptor(&sample_descriptors()[1]).unwrap();
+            mesh.append_line_indices(&[0, 1]);
+            mesh.set_overlay(true);
+            mesh.set_line_depth_bias(-2);
+            mesh.set_model(Mat4::new_translation(&Vec3::new(1.0, 2.0, 3.0)));
+            mesh.set_color_mode(MeshColorMode::Uniform(Color::Other((0.25, 0.5, 0.75))));
+            mesh.set_options(MeshOptions {
+                edge_color: Some(Color::Other((0.0, 0.0, 0.0))),
+                dash_pattern: Some((0.1, 0.05)),
+            });
+            mesh.set_front_face(FrontFace::Cw);
+            mesh
+        };
+        check(overlay(), overlay());
+    }
+
+    #[test]
+    fn color_alpha_round_trip_test() {
+        for color in [Color::Red, Color::Other((0.1, 0.2, 0.3)), Color::Rgba((0.1, 0.2, 0.3, 0.5))] {
+            let msg: d3rpc::Color = color.into();
+            assert_eq!(msg.a.is_some(), matches!(color, Color::Rgba(_)));
+            assert_eq!(Color::from(msg).to_rgba(), color.to_rgba());
+        }
+    }
+
+    #[test]
+    fn non_finite_validation_test() {
+        let valid = d3rpc::AddLineRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            line_descriptor: Some(LineDescriptor {
+                v1: Vec3::new(0.0, 0.0, 0.0),
+                v2: Vec3::new(1.0, 0.0, 0.0),
+                color: Color::Red,
+                dash_pattern: None,
+            }.into()),
+        };
+        assert!(valid.validate().is_ok());
+
+        let mut no_color = valid.clone();
+        no_color.line_descriptor.as_mut().unwrap().color = None;
+        assert!(no_color.validate().is_err());
+
+        let mut nan_line = valid.clone();
+        nan_line.line_descriptor.as_mut().unwrap().v2 = Some(Vec3::new(1.0, f32::NAN, 0.0).into());
+        assert_eq!(nan_line.non_finite_field(), Some("v2"));
+        assert!(nan_line.validate().unwrap_err().contains("v2"));
+
+        let inf_sphere = d3rpc::AddSphereRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            sphere_descriptor: Some(d3rpc::SphereDescriptor {
+                origin: Some(Vec3::zeros().into()),
+                axis: Some(Vec3::z().into()),
+                radius: f32::INFINITY,
+                n_lat: 4,
+                n_long: 4,
+                line_color: Some(Color::Red.into()),
+                tri_color: Some(Color::Green.into()),
+                cell_type: 2,
+                color_by: 0,
+            }),
+        };
+        assert_eq!(inf_sphere.non_finite_field(), Some("radius"));
+
+        let nan_mesh = d3rpc::AddMeshRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            mesh_descriptor: Some(d3rpc::MeshDescriptor {
+                vertices: vec![0.0, f32::NAN, 0.0],
+                indices: vec![0],
+                cell_type: 1,
+                line_indices: vec![],
+                overlay: false,
+                line_depth_bias: 0,
+                model: vec![],
+                uniform_color: None,
+                options: None,
+                clockwise: false,
+            }),
+        };
+        assert!(nan_mesh.validate().unwrap_err().contains("vertices"));
+
+        // the model matrix has 16 finite entries, or none for the identity
+        let mut bad_model = nan_mesh.clone();
+        let md = bad_model.mesh_descriptor.as_mut().unwrap();
+        md.vertices = vec![0.0; 3];
+        md.model = vec![1.0; 15];
+        assert!(bad_model.validate().is_err());
+        bad_model.mesh_descriptor.as_mut().unwrap().model = vec![f32::NAN; 16];
+        assert!(bad_model.validate().unwrap_err().contains("model"));
+        bad_model.mesh_descriptor.as_mut().unwrap().model = vec![1.0; 16];
+        assert!(bad_model.validate().is_ok());
+        let nan_color = Color::Other((f32::NAN, 0.0, 0.0));
+        bad_model.mesh_descriptor.as_mut().unwrap().uniform_color = Some(nan_color.into());
+        assert!(bad_model.validate().unwrap_err().contains("uniform_color"));
+        let md = bad_model.mesh_descriptor.as_mut().unwrap();
+        md.uniform_color = None;
+        md.options = Some(d3rpc::MeshOptions { dash_length: f32::NAN, ..Default::default() });
+        assert!(bad_model.validate().unwrap_err().contains("options"));
+    }
+
+    fn cuboid_request(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> d3rpc::AddCuboidRequest {
+        d3rpc::AddCuboidRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            cuboid_descriptor: Some(CuboidDescriptor {
+                origin: Vec3::zeros(),
+                x_axis,
+                y_axis,
+                z_axis,
+                lenx: 1.0,
+                leny: 1.0,
+                lenz: 1.0,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                axes_policy: AxesPolicy::AsGiven,
+            }.into()),
+        }
+    }
+
+    #[test]
+    fn degenerate_validation_test() {
+        //{{{ com: lines and triangles
+        let line = d3rpc::AddLineRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            line_descriptor: Some(LineDescriptor {
+                v1: Vec3::new(1.0, 2.0, 3.0),
+                v2: Vec3::new(1.0, 2.0, 3.0),
+                color: Color::Red,
+                dash_pattern: None,
+            }.into()),
+        };
+        assert_eq!(line.validate(), Err("line endpoints v1 and v2 coincide".to_string()));
+
+        let triangle = |v3: Vec3| d3rpc::AddTriangleRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            triangle_descriptor: Some(TriangleDescriptor {
+                v1: Vec3::zeros(),
+                v2: Vec3::x(),
+                v3,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+            }.into()),
+        };
+        assert!(triangle(Vec3::y()).validate().is_ok());
+        assert_eq!(triangle(Vec3::x()).degenerate_reason(), Some("triangle has coincident vertices"));
+        assert_eq!(
+            triangle(Vec3::new(2.0, 0.0, 0.0)).degenerate_reason(),
+            Some("triangle vertices are collinear")
+        );
+        //}}}
+        //{{{ com: plane
+        let plane = |x_axis: Vec3, y_axis: Vec3| d3rpc::AddPlaneRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            plane_descriptor: Some(PlaneDescriptor {
+                origin: Vec3::zeros(),
+                x_axis,
+                y_axis,
+                x_min: -1.0,
+                x_max: 1.0,
+                y_min: -1.0,
+                y_max: 1.0,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }.into()),
+        };
+        assert!(plane(Vec3::x(), Vec3::y()).validate().is_ok());
+        assert_eq!(plane(Vec3::zeros(), Vec3::y()).degenerate_reason(), Some("plane x_axis has zero length"));
+        assert_eq!(plane(Vec3::x(), Vec3::zeros()).degenerate_reason(), Some("plane y_axis has zero length"));
+        assert_eq!(
+            plane(Vec3::x(), -2.0 * Vec3::x()).degenerate_reason(),
+            Some("plane x_axis and y_axis are parallel")
+        );
+        //}}}
+        //{{{ com: polygon face
+        let polygon_face = |points: Vec<Vec3>| d3rpc::AddPolygonFaceRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            polygon_face_descriptor: Some(PolygonFaceDescriptor {
+                points,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+            }.into()),
+        };
+        let square = vec![Vec3::zeros(), Vec3::x(), Vec3::new(1.0, 1.0, 0.0), Vec3::y()];
+        assert!(polygon_face(square).validate().is_ok());
+        assert!(!polygon_face(vec![Vec3::zeros(), Vec3::x()]).is_valid());
+        assert_eq!(
+            polygon_face(vec![Vec3::zeros(), Vec3::x(), Vec3::new(1.0, 1.0, 0.0), Vec3::z()])
+                .degenerate_reason(),
+            Some("polygon face points are not coplanar")
+        );
+        assert_eq!(
+            polygon_face(vec![Vec3::zeros(), Vec3::new(1.0, 1.0, 0.0), Vec3::x(), Vec3::y()])
+                .degenerate_reason(),
+            Some("polygon face is self-intersecting")
+        );
+        //}}}
+        //{{{ com: cuboid
+        assert!(cuboid_request(Vec3::x(), Vec3::y(), Vec3::z()).validate().is_ok());
+        assert_eq!(
+            cuboid_request(Vec3::x(), Vec3::y(), Vec3::zeros()).degenerate_reason(),
+            Some("cuboid z_axis has zero length")
+        );
+        assert_eq!(
+            cuboid_request(Vec3::x(), Vec3::x(), Vec3::z()).degenerate_reason(),
+            Some("cuboid x_axis and y_axis are parallel")
+        );
+        assert_eq!(
+            cuboid_request(Vec3::x(), Vec3::y(), Vec3::new(1.0, 1.0, 0.0)).degenerate_reason(),
+            Some("cuboid axes are coplanar")
+        );
+        let with_policy = |z_axis: Vec3, policy: AxesPolicy| {
+            let mut request = cuboid_request(Vec3::x(), Vec3::y(), z_axis);
+            request.cuboid_descriptor.as_mut().unwrap().axes_policy = policy.into();
+            request
+        };
+        let left_handed = -Vec3::z();
+        let skewed = Vec3::new(0.5, 0.0, 1.0);
+        assert!(with_policy(left_handed, AxesPolicy::AsGiven).validate().is_ok());
+        assert!(with_policy(skewed, AxesPolicy::Orthonormalize).validate().is_ok());
+        assert_eq!(
+            with_policy(left_handed, AxesPolicy::Orthonormalize).degenerate_reason(),
+            Some("cuboid axes are left-handed")
+        );
+        assert_eq!(
+            with_policy(skewed, AxesPolicy::RequireRightHanded).degenerate_reason(),
+            Some("cuboid axes are not orthogonal")
+        );
+        assert_eq!(
+            with_policy(left_handed, AxesPolicy::RequireRightHanded).degenerate_reason(),
+            Some("cuboid axes are left-handed")
+        );
+        //}}}
+        //{{{ com: axis of revolution
+        let cylinder = d3rpc::AddCylinderRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            cylinder_descriptor: Some(CylinderDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::zeros(),
+                radius: 1.0,
+                height: 1.0,
+                num_sides: 8,
+                cap_num_sides: None,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                open: false,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }.into()),
+        };
+        assert_eq!(cylinder.degenerate_reason(), Some("cylinder axis has zero length"));
+
+        let disc = d3rpc::AddDiscRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            disc_descriptor: Some(DiscDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::zeros(),
+                radius: 1.0,
+                num_sides: 8,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }.into()),
+        };
+        assert_eq!(disc.degenerate_reason(), Some("disc axis has zero length"));
+
+        let sphere = d3rpc::AddSphereRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            sphere_descriptor: Some(SphereDescriptor {
+                origin: Vec3::zeros(),
+                axis: Vec3::zeros(),
+                radius: 1.0,
+                n_lat: 4,
+                n_long: 4,
+                line_color: Color::Red,
+                tri_color: Color::Green,
+                cell_type: CellType::Triangle,
+                color_by: ColorBy::None,
+            }.into()),
+        };
+        assert_eq!(sphere.degenerate_reason(), Some("sphere axis has zero length"));
+
+        let axes = d3rpc::AddAxesRequest {
+            client_name: "test".to_string(),
+            name: String::new(),
+            axes_descriptor: Some(AxesDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::zeros(),
+                z_axis: Vec3::z(),
+                neg_len: 1.0,
+                pos_len: 1.0,
+            }.into()),
+        };
+        assert_eq!(axes.degenerate_reason(), Some("axes y_axis has zero length"));
+        //}}}
+        //{{{ com: camera
+        let camera = |position: Vec3| d3rpc::SetCameraRequest {
+            client_name: "test".to_string(),
+            position: Some(position.into()),
+            focus: Some(Vec3::x().into()),
+            animate: true,
+        };
+        assert!(camera(Vec3::zeros()).validate().is_ok());
+        assert_eq!(camera(Vec3::x()).degenerate_reason(), Some("camera position and focus coincide"));
+        assert_eq!(
+            camera(Vec3::new(f32::NAN, 0.0, 0.0)).non_finite_field(),
+            Some("position")
+        );
+        //}}}
+    }
+}
 //}}}
 
 
@@ -8,6 +8,9 @@ pub struct Color {
     pub g: f32,
     #[prost(float, tag = "3")]
     pub b: f32,
+    /// the opacity, left out for an opaque color
+    #[prost(float, optional, tag = "4")]
+    pub a: ::core::option::Option<f32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -34,6 +37,11 @@ pub struct LineDescriptor {
     pub v2: ::core::option::Option<Vec3>,
     #[prost(message, optional, tag = "3")]
     pub color: ::core::option::Option<Color>,
+    /// lengths of the dashes and of the gaps between them, the line is solid when dash_length is 0
+    #[prost(float, tag = "4")]
+    pub dash_length: f32,
+    #[prost(float, tag = "5")]
+    pub gap_length: f32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -42,6 +50,8 @@ pub struct AddLineRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub line_descriptor: ::core::option::Option<LineDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -66,6 +76,8 @@ pub struct AddTriangleRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub triangle_descriptor: ::core::option::Option<TriangleDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -90,6 +102,8 @@ pub struct PlaneDescriptor {
     pub tri_color: ::core::option::Option<Color>,
     #[prost(enumeration = "CellType", tag = "10")]
     pub cell_type: i32,
+    #[prost(enumeration = "ColorBy", tag = "11")]
+    pub color_by: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -98,6 +112,30 @@ pub struct AddPlaneRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub plane_descriptor: ::core::option::Option<PlaneDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PolygonFaceDescriptor {
+    #[prost(message, repeated, tag = "1")]
+    pub points: ::prost::alloc::vec::Vec<Vec3>,
+    #[prost(message, optional, tag = "2")]
+    pub line_color: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "3")]
+    pub tri_color: ::core::option::Option<Color>,
+    #[prost(enumeration = "CellType", tag = "4")]
+    pub cell_type: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddPolygonFaceRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub polygon_face_descriptor: ::core::option::Option<PolygonFaceDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -122,6 +160,8 @@ pub struct CuboidDescriptor {
     pub tri_color: ::core::option::Option<Color>,
     #[prost(enumeration = "CellType", tag = "10")]
     pub cell_type: i32,
+    #[prost(enumeration = "AxesPolicy", tag = "11")]
+    pub axes_policy: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -130,6 +170,8 @@ pub struct AddCuboidRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub cuboid_descriptor: ::core::option::Option<CuboidDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -152,6 +194,11 @@ pub struct CylinderDescriptor {
     pub open: bool,
     #[prost(enumeration = "CellType", tag = "9")]
     pub cell_type: i32,
+    #[prost(enumeration = "ColorBy", tag = "10")]
+    pub color_by: i32,
+    /// number of sides of the end caps, 0 to use num_sides
+    #[prost(uint32, tag = "11")]
+    pub cap_num_sides: u32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -160,6 +207,8 @@ pub struct AddCylinderRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub cylinder_descriptor: ::core::option::Option<CylinderDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -178,6 +227,8 @@ pub struct DiscDescriptor {
     pub tri_color: ::core::option::Option<Color>,
     #[prost(enumeration = "CellType", tag = "7")]
     pub cell_type: i32,
+    #[prost(enumeration = "ColorBy", tag = "8")]
+    pub color_by: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -186,6 +237,8 @@ pub struct AddDiscRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub disc_descriptor: ::core::option::Option<DiscDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -206,6 +259,8 @@ pub struct SphereDescriptor {
     pub tri_color: ::core::option::Option<Color>,
     #[prost(enumeration = "CellType", tag = "8")]
     pub cell_type: i32,
+    #[prost(enumeration = "ColorBy", tag = "9")]
+    pub color_by: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -214,6 +269,44 @@ pub struct AddSphereRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub sphere_descriptor: ::core::option::Option<SphereDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EllipsoidDescriptor {
+    #[prost(message, optional, tag = "1")]
+    pub origin: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "2")]
+    pub axis: ::core::option::Option<Vec3>,
+    #[prost(float, tag = "3")]
+    pub radius_x: f32,
+    #[prost(float, tag = "4")]
+    pub radius_y: f32,
+    #[prost(float, tag = "5")]
+    pub radius_z: f32,
+    #[prost(uint32, tag = "6")]
+    pub n_lat: u32,
+    #[prost(uint32, tag = "7")]
+    pub n_long: u32,
+    #[prost(message, optional, tag = "8")]
+    pub line_color: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "9")]
+    pub tri_color: ::core::option::Option<Color>,
+    #[prost(enumeration = "CellType", tag = "10")]
+    pub cell_type: i32,
+    #[prost(enumeration = "ColorBy", tag = "11")]
+    pub color_by: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddEllipsoidRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub ellipsoid_descriptor: ::core::option::Option<EllipsoidDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -238,6 +331,23 @@ pub struct AddAxesRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub axes_descriptor: ::core::option::Option<AxesDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+}
+/// options controlling how a single mesh is drawn
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeshOptions {
+    /// the color of the edges drawn over the triangle cells, left out for the line color of the
+    /// vertices
+    #[prost(message, optional, tag = "1")]
+    pub edge_color: ::core::option::Option<Color>,
+    /// lengths of the dashes and of the gaps between them of the line cells, which are solid when
+    /// dash_length is 0
+    #[prost(float, tag = "2")]
+    pub dash_length: f32,
+    #[prost(float, tag = "3")]
+    pub gap_length: f32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -248,6 +358,26 @@ pub struct MeshDescriptor {
     pub indices: ::prost::alloc::vec::Vec<u32>,
     #[prost(enumeration = "CellType", tag = "3")]
     pub cell_type: i32,
+    #[prost(uint32, repeated, tag = "4")]
+    pub line_indices: ::prost::alloc::vec::Vec<u32>,
+    #[prost(bool, tag = "5")]
+    pub overlay: bool,
+    #[prost(int32, tag = "6")]
+    pub line_depth_bias: i32,
+    /// the model matrix placing the mesh in the world, its 16 entries column by column, left out
+    /// for the identity
+    #[prost(float, repeated, tag = "7")]
+    pub model: ::prost::alloc::vec::Vec<f32>,
+    /// the color every line and triangle of the mesh is drawn with, left out to draw them with the
+    /// colors of its vertices
+    #[prost(message, optional, tag = "8")]
+    pub uniform_color: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "9")]
+    pub options: ::core::option::Option<MeshOptions>,
+    /// whether the corners of the triangles run clockwise seen from their front, rather than
+    /// counterclockwise, which decides the side face culling leaves undrawn
+    #[prost(bool, tag = "10")]
+    pub clockwise: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -256,9 +386,179 @@ pub struct AddMeshRequest {
     pub client_name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub mesh_descriptor: ::core::option::Option<MeshDescriptor>,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AppendToMeshRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// the appended geometry, in the coordinates of the mesh, so its model is ignored
+    #[prost(message, optional, tag = "3")]
+    pub mesh_descriptor: ::core::option::Option<MeshDescriptor>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AppendToMeshResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplaceMeshRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    #[prost(message, optional, tag = "3")]
+    pub mesh_descriptor: ::core::option::Option<MeshDescriptor>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplaceMeshResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidateMeshRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub mesh_descriptor: ::core::option::Option<MeshDescriptor>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeshStatsResponse {
+    #[prost(uint64, tag = "1")]
+    pub num_vertices: u64,
+    #[prost(uint64, tag = "2")]
+    pub num_triangles: u64,
+    /// corners of the bounding box of the finite vertices, unset when there are none
+    #[prost(message, optional, tag = "3")]
+    pub min: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "4")]
+    pub max: ::core::option::Option<Vec3>,
+    #[prost(bool, tag = "5")]
+    pub is_manifold: bool,
+    #[prost(bool, tag = "6")]
+    pub has_degenerate: bool,
+    #[prost(uint64, tag = "7")]
+    pub non_finite_count: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddContoursRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// one value per vertex of the triangle mesh
+    #[prost(float, repeated, tag = "3")]
+    pub values: ::prost::alloc::vec::Vec<f32>,
+    #[prost(float, repeated, tag = "4")]
+    pub levels: ::prost::alloc::vec::Vec<f32>,
+    #[prost(message, optional, tag = "5")]
+    pub color: ::core::option::Option<Color>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddBoundingBoxRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    #[prost(message, optional, tag = "3")]
+    pub color: ::core::option::Option<Color>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddNormalsVisualizationRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// length of the segment drawn along each normal, in world units
+    #[prost(float, tag = "3")]
+    pub length: f32,
+    #[prost(message, optional, tag = "4")]
+    pub color: ::core::option::Option<Color>,
+}
+/// Places a mesh in the world without uploading its vertices again
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshModelRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// the model matrix, as in MeshDescriptor
+    #[prost(float, repeated, tag = "3")]
+    pub model: ::prost::alloc::vec::Vec<f32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshModelResponse {}
+/// Puts a mesh in the group with the given tag, shared by any number of meshes
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshTagRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// the tag of the group, left empty to take the mesh out of its group
+    #[prost(string, tag = "3")]
+    pub tag: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshTagResponse {}
+/// Moves every mesh in the group with the given tag at once
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransformGroupRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub tag: ::prost::alloc::string::String,
+    /// the transform applied after the model of each mesh, as the model in MeshDescriptor
+    #[prost(float, repeated, tag = "3")]
+    pub transform: ::prost::alloc::vec::Vec<f32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransformGroupResponse {
+    /// the number of meshes moved
+    #[prost(uint64, tag = "1")]
+    pub num_meshes: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshColorModeRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    /// the uniform color, as in MeshDescriptor
+    #[prost(message, optional, tag = "3")]
+    pub uniform_color: ::core::option::Option<Color>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshColorModeResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshOptionsRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    #[prost(message, optional, tag = "3")]
+    pub options: ::core::option::Option<MeshOptions>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMeshOptionsResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ClearRequest {
     #[prost(string, tag = "1")]
     pub client_name: ::prost::alloc::string::String,
@@ -268,176 +568,540 @@ pub struct ClearRequest {
 pub struct ClearResponse {}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct KillServerRequest {
+pub struct GetCameraRequest {
     #[prost(string, tag = "1")]
     pub client_name: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct KillServerResponse {}
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum CellType {
-    None = 0,
-    Line = 1,
-    Triangle = 2,
+pub struct GetCameraResponse {
+    #[prost(message, optional, tag = "1")]
+    pub position: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "2")]
+    pub focus: ::core::option::Option<Vec3>,
+    #[prost(float, tag = "3")]
+    pub pitch: f32,
+    #[prost(float, tag = "4")]
+    pub yaw: f32,
+    #[prost(float, tag = "5")]
+    pub fov: f32,
 }
-impl CellType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            CellType::None => "NONE",
-            CellType::Line => "LINE",
-            CellType::Triangle => "TRIANGLE",
-        }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "NONE" => Some(Self::None),
-            "LINE" => Some(Self::Line),
-            "TRIANGLE" => Some(Self::Triangle),
-            _ => None,
-        }
-    }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetCameraRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub position: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "3")]
+    pub focus: ::core::option::Option<Vec3>,
+    #[prost(bool, tag = "4")]
+    pub animate: bool,
 }
-/// Generated client implementations.
-pub mod state_service_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    #[derive(Debug, Clone)]
-    pub struct StateServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl StateServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> StateServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> StateServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-            >>::Error: Into<StdError> + Send + Sync,
-        {
-            StateServiceClient::new(InterceptedService::new(inner, interceptor))
-        }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
-        }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetCameraResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FocusMeshRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+    #[prost(bool, tag = "3")]
+    pub animate: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FocusMeshResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetRenderingEnabledRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub enabled: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetRenderingEnabledResponse {}
+/// Returns once a render pass has drawn every mesh added before the request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlushRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlushResponse {}
+/// opens another window onto the scene, with its own camera
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenWindowRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenWindowResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetViewSensitivityRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub key_pan_delta: f32,
+    #[prost(float, tag = "3")]
+    pub key_rotate_delta: f32,
+    #[prost(float, tag = "4")]
+    pub zoom_speed: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetViewSensitivityResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBackgroundGradientRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub top: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "3")]
+    pub bottom: ::core::option::Option<Color>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBackgroundGradientResponse {}
+/// Hides everything on the side of the plane the normal points to, leaving out the point or the
+/// normal removes the plane
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetClipPlaneRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub point: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "3")]
+    pub normal: ::core::option::Option<Vec3>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetClipPlaneResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClipPlane {
+    #[prost(message, optional, tag = "1")]
+    pub point: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "2")]
+    pub normal: ::core::option::Option<Vec3>,
+}
+/// Replaces the clipping planes, an empty list removes them
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetClipPlanesRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub planes: ::prost::alloc::vec::Vec<ClipPlane>,
+    #[prost(enumeration = "ClipMode", tag = "3")]
+    pub mode: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetClipPlanesResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTessellationQualityRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(enumeration = "TessellationQuality", tag = "2")]
+    pub quality: i32,
+    /// only used by the adaptive quality, where it must be positive
+    #[prost(float, tag = "3")]
+    pub chord_error: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTessellationQualityResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadDepthBufferRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadDepthBufferResponse {
+    #[prost(uint32, tag = "1")]
+    pub width: u32,
+    #[prost(uint32, tag = "2")]
+    pub height: u32,
+    #[prost(float, repeated, tag = "3")]
+    pub depths: ::prost::alloc::vec::Vec<f32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSceneStatsRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSceneStatsResponse {
+    #[prost(uint64, tag = "1")]
+    pub num_meshes: u64,
+    #[prost(uint64, tag = "2")]
+    pub num_vertices: u64,
+    #[prost(uint64, tag = "3")]
+    pub max_meshes: u64,
+    #[prost(uint64, tag = "4")]
+    pub max_vertices: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SceneBoundsRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SceneBoundsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub min: ::core::option::Option<Vec3>,
+    #[prost(message, optional, tag = "2")]
+    pub max: ::core::option::Option<Vec3>,
+    #[prost(bool, tag = "3")]
+    pub empty: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExistsRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub id: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExistsResponse {
+    #[prost(bool, tag = "1")]
+    pub exists: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisconnectRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisconnectResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PongResponse {
+    #[prost(uint64, tag = "1")]
+    pub server_time_millis: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillServerRequest {
+    #[prost(string, tag = "1")]
+    pub client_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillServerResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CellType {
+    None = 0,
+    Line = 1,
+    Triangle = 2,
+}
+impl CellType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CellType::None => "NONE",
+            CellType::Line => "LINE",
+            CellType::Triangle => "TRIANGLE",
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NONE" => Some(Self::None),
+            "LINE" => Some(Self::Line),
+            "TRIANGLE" => Some(Self::Triangle),
+            _ => None,
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ColorBy {
+    None = 0,
+    U = 1,
+    V = 2,
+    Index = 3,
+}
+impl ColorBy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ColorBy::None => "COLOR_BY_NONE",
+            ColorBy::U => "COLOR_BY_U",
+            ColorBy::V => "COLOR_BY_V",
+            ColorBy::Index => "COLOR_BY_INDEX",
         }
-        pub async fn add_line(
-            &mut self,
-            request: impl tonic::IntoRequest<super::AddLineRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::AddItemResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/d3rpc.StateService/AddLine",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("d3rpc.StateService", "AddLine"));
-            self.inner.unary(req, path, codec).await
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COLOR_BY_NONE" => Some(Self::None),
+            "COLOR_BY_U" => Some(Self::U),
+            "COLOR_BY_V" => Some(Self::V),
+            "COLOR_BY_INDEX" => Some(Self::Index),
+            _ => None,
         }
-        pub async fn add_triangle(
-            &mut self,
-            request: impl tonic::IntoRequest<super::AddTriangleRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::AddItemResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/d3rpc.StateService/AddTriangle",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("d3rpc.StateService", "AddTriangle"));
-            self.inner.unary(req, path, codec).await
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AxesPolicy {
+    AsGiven = 0,
+    Orthonormalize = 1,
+    RequireRightHanded = 2,
+}
+impl AxesPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AxesPolicy::AsGiven => "AS_GIVEN",
+            AxesPolicy::Orthonormalize => "ORTHONORMALIZE",
+            AxesPolicy::RequireRightHanded => "REQUIRE_RIGHT_HANDED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AS_GIVEN" => Some(Self::AsGiven),
+            "ORTHONORMALIZE" => Some(Self::Orthonormalize),
+            "REQUIRE_RIGHT_HANDED" => Some(Self::RequireRightHanded),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ClipMode {
+    InsideAll = 0,
+    InsideAny = 1,
+}
+impl ClipMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ClipMode::InsideAll => "CLIP_MODE_INSIDE_ALL",
+            ClipMode::InsideAny => "CLIP_MODE_INSIDE_ANY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CLIP_MODE_INSIDE_ALL" => Some(Self::InsideAll),
+            "CLIP_MODE_INSIDE_ANY" => Some(Self::InsideAny),
+            _ => None,
+        }
+    }
+}
+/// The tessellation quality supplying the subdivision counts left at zero in descriptors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TessellationQuality {
+    Medium = 0,
+    Low = 1,
+    High = 2,
+    /// sized from each shape's radius so that chords stay within chord_error of the curve
+    Adaptive = 3,
+}
+impl TessellationQuality {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TessellationQuality::Medium => "TESSELLATION_QUALITY_MEDIUM",
+            TessellationQuality::Low => "TESSELLATION_QUALITY_LOW",
+            TessellationQuality::High => "TESSELLATION_QUALITY_HIGH",
+            TessellationQuality::Adaptive => "TESSELLATION_QUALITY_ADAPTIVE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TESSELLATION_QUALITY_MEDIUM" => Some(Self::Medium),
+            "TESSELLATION_QUALITY_LOW" => Some(Self::Low),
+            "TESSELLATION_QUALITY_HIGH" => Some(Self::High),
+            "TESSELLATION_QUALITY_ADAPTIVE" => Some(Self::Adaptive),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod state_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct StateServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl StateServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> StateServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> StateServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            StateServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn add_line(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddLineRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddLine",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddLine"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn add_triangle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddTriangleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddTriangle",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddTriangle"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn add_plane(
             &mut self,
@@ -464,6 +1128,31 @@ pub mod state_service_client {
                 .insert(GrpcMethod::new("d3rpc.StateService", "AddPlane"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn add_polygon_face(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddPolygonFaceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddPolygonFace",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddPolygonFace"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn add_cuboid(
             &mut self,
             request: impl tonic::IntoRequest<super::AddCuboidRequest>,
@@ -564,6 +1253,31 @@ pub mod state_service_client {
                 .insert(GrpcMethod::new("d3rpc.StateService", "AddSphere"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn add_ellipsoid(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddEllipsoidRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddEllipsoid",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddEllipsoid"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn add_axes(
             &mut self,
             request: impl tonic::IntoRequest<super::AddAxesRequest>,
@@ -614,10 +1328,13 @@ pub mod state_service_client {
                 .insert(GrpcMethod::new("d3rpc.StateService", "AddMesh"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn clear(
+        pub async fn append_to_mesh(
             &mut self,
-            request: impl tonic::IntoRequest<super::ClearRequest>,
-        ) -> std::result::Result<tonic::Response<super::ClearResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::AppendToMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AppendToMeshResponse>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -628,16 +1345,19 @@ pub mod state_service_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/d3rpc.StateService/clear");
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AppendToMesh",
+            );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("d3rpc.StateService", "clear"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AppendToMesh"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn kill_server(
+        pub async fn replace_mesh(
             &mut self,
-            request: impl tonic::IntoRequest<super::KillServerRequest>,
+            request: impl tonic::IntoRequest<super::ReplaceMeshRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::KillServerResponse>,
+            tonic::Response<super::ReplaceMeshResponse>,
             tonic::Status,
         > {
             self.inner
@@ -651,168 +1371,2237 @@ pub mod state_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/d3rpc.StateService/KillServer",
+                "/d3rpc.StateService/ReplaceMesh",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("d3rpc.StateService", "KillServer"));
+                .insert(GrpcMethod::new("d3rpc.StateService", "ReplaceMesh"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod state_service_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with StateServiceServer.
-    #[async_trait]
-    pub trait StateService: Send + Sync + 'static {
-        async fn add_line(
-            &self,
-            request: tonic::Request<super::AddLineRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_triangle(
-            &self,
-            request: tonic::Request<super::AddTriangleRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_plane(
-            &self,
-            request: tonic::Request<super::AddPlaneRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_cuboid(
-            &self,
-            request: tonic::Request<super::AddCuboidRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_cylinder(
-            &self,
-            request: tonic::Request<super::AddCylinderRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_disc(
-            &self,
-            request: tonic::Request<super::AddDiscRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_sphere(
-            &self,
-            request: tonic::Request<super::AddSphereRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_axes(
-            &self,
-            request: tonic::Request<super::AddAxesRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn add_mesh(
-            &self,
-            request: tonic::Request<super::AddMeshRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
-        async fn clear(
-            &self,
-            request: tonic::Request<super::ClearRequest>,
-        ) -> std::result::Result<tonic::Response<super::ClearResponse>, tonic::Status>;
-        async fn kill_server(
-            &self,
-            request: tonic::Request<super::KillServerRequest>,
+        pub async fn validate_mesh(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ValidateMeshRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::KillServerResponse>,
+            tonic::Response<super::MeshStatsResponse>,
             tonic::Status,
-        >;
-    }
-    #[derive(Debug)]
-    pub struct StateServiceServer<T: StateService> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: StateService> StateServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/ValidateMesh",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "ValidateMesh"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        pub async fn add_contours(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddContoursRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddContours",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddContours"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        pub async fn add_bounding_box(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddBoundingBoxRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddBoundingBox",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "AddBoundingBox"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        pub async fn add_normals_visualization(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddNormalsVisualizationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddItemResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/AddNormalsVisualization",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("d3rpc.StateService", "AddNormalsVisualization"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn set_mesh_model(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMeshModelRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshModelResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetMeshModel",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetMeshModel"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        pub async fn set_mesh_tag(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMeshTagRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshTagResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetMeshTag",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetMeshTag"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
+        pub async fn transform_group(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TransformGroupRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::TransformGroupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/TransformGroup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "TransformGroup"));
+            self.inner.unary(req, path, codec).await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for StateServiceServer<T>
-    where
-        T: StateService,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        pub async fn set_mesh_color_mode(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::SetMeshColorModeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshColorModeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetMeshColorMode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetMeshColorMode"));
+            self.inner.unary(req, path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        pub async fn set_mesh_options(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMeshOptionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshOptionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetMeshOptions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetMeshOptions"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn clear(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ClearRequest>,
+        ) -> std::result::Result<tonic::Response<super::ClearResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/d3rpc.StateService/clear");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("d3rpc.StateService", "clear"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_camera(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCameraRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCameraResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/GetCamera",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "GetCamera"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_camera(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetCameraRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetCameraResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetCamera",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetCamera"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn focus_mesh(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FocusMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FocusMeshResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/FocusMesh",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "FocusMesh"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_rendering_enabled(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetRenderingEnabledRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetRenderingEnabledResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetRenderingEnabled",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetRenderingEnabled"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn flush(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FlushRequest>,
+        ) -> std::result::Result<tonic::Response<super::FlushResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/d3rpc.StateService/Flush");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("d3rpc.StateService", "Flush"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn open_window(
+            &mut self,
+            request: impl tonic::IntoRequest<super::OpenWindowRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::OpenWindowResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/OpenWindow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "OpenWindow"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_view_sensitivity(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetViewSensitivityRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetViewSensitivityResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetViewSensitivity",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetViewSensitivity"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_background_gradient(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetBackgroundGradientRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetBackgroundGradientResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetBackgroundGradient",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetBackgroundGradient"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_clip_plane(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetClipPlaneRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetClipPlaneResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetClipPlane",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetClipPlane"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_clip_planes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetClipPlanesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetClipPlanesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetClipPlanes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetClipPlanes"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn read_depth_buffer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReadDepthBufferRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReadDepthBufferResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/ReadDepthBuffer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "ReadDepthBuffer"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_tessellation_quality(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetTessellationQualityRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetTessellationQualityResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SetTessellationQuality",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SetTessellationQuality"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_scene_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSceneStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSceneStatsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/GetSceneStats",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "GetSceneStats"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn scene_bounds(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SceneBoundsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SceneBoundsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/SceneBounds",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "SceneBounds"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn exists(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExistsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ExistsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/Exists",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("d3rpc.StateService", "Exists"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn disconnect(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DisconnectRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DisconnectResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/Disconnect",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "Disconnect"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn ping(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PingRequest>,
+        ) -> std::result::Result<tonic::Response<super::PongResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/d3rpc.StateService/Ping");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("d3rpc.StateService", "Ping"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn kill_server(
+            &mut self,
+            request: impl tonic::IntoRequest<super::KillServerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::KillServerResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/d3rpc.StateService/KillServer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("d3rpc.StateService", "KillServer"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod state_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with StateServiceServer.
+    #[async_trait]
+    pub trait StateService: Send + Sync + 'static {
+        async fn add_line(
+            &self,
+            request: tonic::Request<super::AddLineRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_triangle(
+            &self,
+            request: tonic::Request<super::AddTriangleRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_plane(
+            &self,
+            request: tonic::Request<super::AddPlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_polygon_face(
+            &self,
+            request: tonic::Request<super::AddPolygonFaceRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_cuboid(
+            &self,
+            request: tonic::Request<super::AddCuboidRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_cylinder(
+            &self,
+            request: tonic::Request<super::AddCylinderRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_disc(
+            &self,
+            request: tonic::Request<super::AddDiscRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_sphere(
+            &self,
+            request: tonic::Request<super::AddSphereRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_ellipsoid(
+            &self,
+            request: tonic::Request<super::AddEllipsoidRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_axes(
+            &self,
+            request: tonic::Request<super::AddAxesRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_mesh(
+            &self,
+            request: tonic::Request<super::AddMeshRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn append_to_mesh(
+            &self,
+            request: tonic::Request<super::AppendToMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AppendToMeshResponse>,
+            tonic::Status,
+        >;
+        async fn replace_mesh(
+            &self,
+            request: tonic::Request<super::ReplaceMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReplaceMeshResponse>,
+            tonic::Status,
+        >;
+        async fn validate_mesh(
+            &self,
+            request: tonic::Request<super::ValidateMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::MeshStatsResponse>,
+            tonic::Status,
+        >;
+        async fn add_contours(
+            &self,
+            request: tonic::Request<super::AddContoursRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_bounding_box(
+            &self,
+            request: tonic::Request<super::AddBoundingBoxRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn add_normals_visualization(
+            &self,
+            request: tonic::Request<super::AddNormalsVisualizationRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddItemResponse>, tonic::Status>;
+        async fn set_mesh_model(
+            &self,
+            request: tonic::Request<super::SetMeshModelRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshModelResponse>,
+            tonic::Status,
+        >;
+        async fn set_mesh_tag(
+            &self,
+            request: tonic::Request<super::SetMeshTagRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshTagResponse>,
+            tonic::Status,
+        >;
+        async fn transform_group(
+            &self,
+            request: tonic::Request<super::TransformGroupRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::TransformGroupResponse>,
+            tonic::Status,
+        >;
+        async fn set_mesh_color_mode(
+            &self,
+            request: tonic::Request<super::SetMeshColorModeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshColorModeResponse>,
+            tonic::Status,
+        >;
+        async fn set_mesh_options(
+            &self,
+            request: tonic::Request<super::SetMeshOptionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetMeshOptionsResponse>,
+            tonic::Status,
+        >;
+        async fn clear(
+            &self,
+            request: tonic::Request<super::ClearRequest>,
+        ) -> std::result::Result<tonic::Response<super::ClearResponse>, tonic::Status>;
+        async fn get_camera(
+            &self,
+            request: tonic::Request<super::GetCameraRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCameraResponse>,
+            tonic::Status,
+        >;
+        async fn set_camera(
+            &self,
+            request: tonic::Request<super::SetCameraRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetCameraResponse>,
+            tonic::Status,
+        >;
+        async fn focus_mesh(
+            &self,
+            request: tonic::Request<super::FocusMeshRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FocusMeshResponse>,
+            tonic::Status,
+        >;
+        async fn set_rendering_enabled(
+            &self,
+            request: tonic::Request<super::SetRenderingEnabledRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetRenderingEnabledResponse>,
+            tonic::Status,
+        >;
+        async fn flush(
+            &self,
+            request: tonic::Request<super::FlushRequest>,
+        ) -> std::result::Result<tonic::Response<super::FlushResponse>, tonic::Status>;
+        async fn open_window(
+            &self,
+            request: tonic::Request<super::OpenWindowRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::OpenWindowResponse>,
+            tonic::Status,
+        >;
+        async fn set_view_sensitivity(
+            &self,
+            request: tonic::Request<super::SetViewSensitivityRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetViewSensitivityResponse>,
+            tonic::Status,
+        >;
+        async fn set_background_gradient(
+            &self,
+            request: tonic::Request<super::SetBackgroundGradientRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetBackgroundGradientResponse>,
+            tonic::Status,
+        >;
+        async fn set_clip_plane(
+            &self,
+            request: tonic::Request<super::SetClipPlaneRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetClipPlaneResponse>,
+            tonic::Status,
+        >;
+        async fn set_clip_planes(
+            &self,
+            request: tonic::Request<super::SetClipPlanesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetClipPlanesResponse>,
+            tonic::Status,
+        >;
+        async fn read_depth_buffer(
+            &self,
+            request: tonic::Request<super::ReadDepthBufferRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReadDepthBufferResponse>,
+            tonic::Status,
+        >;
+        async fn set_tessellation_quality(
+            &self,
+            request: tonic::Request<super::SetTessellationQualityRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetTessellationQualityResponse>,
+            tonic::Status,
+        >;
+        async fn get_scene_stats(
+            &self,
+            request: tonic::Request<super::GetSceneStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSceneStatsResponse>,
+            tonic::Status,
+        >;
+        async fn scene_bounds(
+            &self,
+            request: tonic::Request<super::SceneBoundsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SceneBoundsResponse>,
+            tonic::Status,
+        >;
+        async fn exists(
+            &self,
+            request: tonic::Request<super::ExistsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ExistsResponse>, tonic::Status>;
+        async fn disconnect(
+            &self,
+            request: tonic::Request<super::DisconnectRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DisconnectResponse>,
+            tonic::Status,
+        >;
+        async fn ping(
+            &self,
+            request: tonic::Request<super::PingRequest>,
+        ) -> std::result::Result<tonic::Response<super::PongResponse>, tonic::Status>;
+        async fn kill_server(
+            &self,
+            request: tonic::Request<super::KillServerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::KillServerResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct StateServiceServer<T: StateService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: StateService> StateServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for StateServiceServer<T>
+    where
+        T: StateService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
                 "/d3rpc.StateService/AddLine" => {
                     #[allow(non_camel_case_types)]
-                    struct AddLineSvc<T: StateService>(pub Arc<T>);
+                    struct AddLineSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddLineRequest>
+                    for AddLineSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddLineRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_line(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddLineSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddTriangle" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddTriangleSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddTriangleRequest>
+                    for AddTriangleSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddTriangleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_triangle(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddTriangleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddPlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddPlaneSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddPlaneRequest>
+                    for AddPlaneSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddPlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddPlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddPolygonFace" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddPolygonFaceSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddPolygonFaceRequest>
+                    for AddPolygonFaceSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddPolygonFaceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_polygon_face(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddPolygonFaceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddCuboid" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddCuboidSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddCuboidRequest>
+                    for AddCuboidSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddCuboidRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_cuboid(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddCuboidSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddCylinder" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddCylinderSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddCylinderRequest>
+                    for AddCylinderSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddCylinderRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_cylinder(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddCylinderSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddDisc" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddDiscSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddDiscRequest>
+                    for AddDiscSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddDiscRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_disc(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddDiscSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddSphere" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddSphereSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddSphereRequest>
+                    for AddSphereSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddSphereRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_sphere(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddSphereSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddEllipsoid" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddEllipsoidSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddEllipsoidRequest>
+                    for AddEllipsoidSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddEllipsoidRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_ellipsoid(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddEllipsoidSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddAxes" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddAxesSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddAxesRequest>
+                    for AddAxesSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddAxesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_axes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddAxesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddMesh" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddMeshSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddMeshRequest>
+                    for AddMeshSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddMeshRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_mesh(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddMeshSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AppendToMesh" => {
+                    #[allow(non_camel_case_types)]
+                    struct AppendToMeshSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AppendToMeshRequest>
+                    for AppendToMeshSvc<T> {
+                        type Response = super::AppendToMeshResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AppendToMeshRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::append_to_mesh(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AppendToMeshSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/ReplaceMesh" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReplaceMeshSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::ReplaceMeshRequest>
+                    for ReplaceMeshSvc<T> {
+                        type Response = super::ReplaceMeshResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReplaceMeshRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::replace_mesh(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReplaceMeshSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/ValidateMesh" => {
+                    #[allow(non_camel_case_types)]
+                    struct ValidateMeshSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::ValidateMeshRequest>
+                    for ValidateMeshSvc<T> {
+                        type Response = super::MeshStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ValidateMeshRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::validate_mesh(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ValidateMeshSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddContours" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddContoursSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddContoursRequest>
+                    for AddContoursSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddContoursRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_contours(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddContoursSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddBoundingBox" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddBoundingBoxSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddBoundingBoxRequest>
+                    for AddBoundingBoxSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddBoundingBoxRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_bounding_box(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddBoundingBoxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/AddNormalsVisualization" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddNormalsVisualizationSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::AddNormalsVisualizationRequest>
+                    for AddNormalsVisualizationSvc<T> {
+                        type Response = super::AddItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::AddNormalsVisualizationRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::add_normals_visualization(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddNormalsVisualizationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetMeshModel" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMeshModelSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SetMeshModelRequest>
+                    for SetMeshModelSvc<T> {
+                        type Response = super::SetMeshModelResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMeshModelRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::set_mesh_model(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetMeshModelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetMeshTag" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMeshTagSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SetMeshTagRequest>
+                    for SetMeshTagSvc<T> {
+                        type Response = super::SetMeshTagResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMeshTagRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::set_mesh_tag(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetMeshTagSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/TransformGroup" => {
+                    #[allow(non_camel_case_types)]
+                    struct TransformGroupSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::TransformGroupRequest>
+                    for TransformGroupSvc<T> {
+                        type Response = super::TransformGroupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TransformGroupRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::transform_group(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TransformGroupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetMeshColorMode" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMeshColorModeSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SetMeshColorModeRequest>
+                    for SetMeshColorModeSvc<T> {
+                        type Response = super::SetMeshColorModeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMeshColorModeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::set_mesh_color_mode(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetMeshColorModeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetMeshOptions" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMeshOptionsSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SetMeshOptionsRequest>
+                    for SetMeshOptionsSvc<T> {
+                        type Response = super::SetMeshOptionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMeshOptionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::set_mesh_options(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetMeshOptionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/clear" => {
+                    #[allow(non_camel_case_types)]
+                    struct clearSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::ClearRequest> for clearSvc<T> {
+                        type Response = super::ClearResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClearRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::clear(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = clearSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/GetCamera" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCameraSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::GetCameraRequest>
+                    for GetCameraSvc<T> {
+                        type Response = super::GetCameraResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCameraRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::get_camera(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetCameraSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetCamera" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetCameraSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SetCameraRequest>
+                    for SetCameraSvc<T> {
+                        type Response = super::SetCameraResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetCameraRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::set_camera(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetCameraSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/FocusMesh" => {
+                    #[allow(non_camel_case_types)]
+                    struct FocusMeshSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::FocusMeshRequest>
+                    for FocusMeshSvc<T> {
+                        type Response = super::FocusMeshResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FocusMeshRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::focus_mesh(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FocusMeshSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SetRenderingEnabled" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetRenderingEnabledSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddLineRequest>
-                    for AddLineSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetRenderingEnabledRequest>
+                    for SetRenderingEnabledSvc<T> {
+                        type Response = super::SetRenderingEnabledResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddLineRequest>,
+                            request: tonic::Request<super::SetRenderingEnabledRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_line(&inner, request).await
+                                <T as StateService>::set_rendering_enabled(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -824,7 +3613,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddLineSvc(inner);
+                        let method = SetRenderingEnabledSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -840,25 +3629,24 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddTriangle" => {
+                "/d3rpc.StateService/Flush" => {
                     #[allow(non_camel_case_types)]
-                    struct AddTriangleSvc<T: StateService>(pub Arc<T>);
+                    struct FlushSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddTriangleRequest>
-                    for AddTriangleSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::FlushRequest> for FlushSvc<T> {
+                        type Response = super::FlushResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddTriangleRequest>,
+                            request: tonic::Request<super::FlushRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_triangle(&inner, request).await
+                                <T as StateService>::flush(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -870,7 +3658,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddTriangleSvc(inner);
+                        let method = FlushSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -886,25 +3674,25 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddPlane" => {
+                "/d3rpc.StateService/OpenWindow" => {
                     #[allow(non_camel_case_types)]
-                    struct AddPlaneSvc<T: StateService>(pub Arc<T>);
+                    struct OpenWindowSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddPlaneRequest>
-                    for AddPlaneSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::OpenWindowRequest>
+                    for OpenWindowSvc<T> {
+                        type Response = super::OpenWindowResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddPlaneRequest>,
+                            request: tonic::Request<super::OpenWindowRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_plane(&inner, request).await
+                                <T as StateService>::open_window(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -916,7 +3704,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddPlaneSvc(inner);
+                        let method = OpenWindowSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -932,25 +3720,26 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddCuboid" => {
+                "/d3rpc.StateService/SetViewSensitivity" => {
                     #[allow(non_camel_case_types)]
-                    struct AddCuboidSvc<T: StateService>(pub Arc<T>);
+                    struct SetViewSensitivitySvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddCuboidRequest>
-                    for AddCuboidSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetViewSensitivityRequest>
+                    for SetViewSensitivitySvc<T> {
+                        type Response = super::SetViewSensitivityResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddCuboidRequest>,
+                            request: tonic::Request<super::SetViewSensitivityRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_cuboid(&inner, request).await
+                                <T as StateService>::set_view_sensitivity(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -962,7 +3751,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddCuboidSvc(inner);
+                        let method = SetViewSensitivitySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -978,25 +3767,29 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddCylinder" => {
+                "/d3rpc.StateService/SetBackgroundGradient" => {
                     #[allow(non_camel_case_types)]
-                    struct AddCylinderSvc<T: StateService>(pub Arc<T>);
+                    struct SetBackgroundGradientSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddCylinderRequest>
-                    for AddCylinderSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetBackgroundGradientRequest>
+                    for SetBackgroundGradientSvc<T> {
+                        type Response = super::SetBackgroundGradientResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddCylinderRequest>,
+                            request: tonic::Request<super::SetBackgroundGradientRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_cylinder(&inner, request).await
+                                <T as StateService>::set_background_gradient(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -1008,7 +3801,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddCylinderSvc(inner);
+                        let method = SetBackgroundGradientSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1024,25 +3817,25 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddDisc" => {
+                "/d3rpc.StateService/SetClipPlane" => {
                     #[allow(non_camel_case_types)]
-                    struct AddDiscSvc<T: StateService>(pub Arc<T>);
+                    struct SetClipPlaneSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddDiscRequest>
-                    for AddDiscSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetClipPlaneRequest>
+                    for SetClipPlaneSvc<T> {
+                        type Response = super::SetClipPlaneResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddDiscRequest>,
+                            request: tonic::Request<super::SetClipPlaneRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_disc(&inner, request).await
+                                <T as StateService>::set_clip_plane(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1054,7 +3847,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddDiscSvc(inner);
+                        let method = SetClipPlaneSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1070,25 +3863,25 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddSphere" => {
+                "/d3rpc.StateService/SetClipPlanes" => {
                     #[allow(non_camel_case_types)]
-                    struct AddSphereSvc<T: StateService>(pub Arc<T>);
+                    struct SetClipPlanesSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddSphereRequest>
-                    for AddSphereSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetClipPlanesRequest>
+                    for SetClipPlanesSvc<T> {
+                        type Response = super::SetClipPlanesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddSphereRequest>,
+                            request: tonic::Request<super::SetClipPlanesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_sphere(&inner, request).await
+                                <T as StateService>::set_clip_planes(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1100,7 +3893,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddSphereSvc(inner);
+                        let method = SetClipPlanesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1116,25 +3909,26 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddAxes" => {
+                "/d3rpc.StateService/ReadDepthBuffer" => {
                     #[allow(non_camel_case_types)]
-                    struct AddAxesSvc<T: StateService>(pub Arc<T>);
+                    struct ReadDepthBufferSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddAxesRequest>
-                    for AddAxesSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::ReadDepthBufferRequest>
+                    for ReadDepthBufferSvc<T> {
+                        type Response = super::ReadDepthBufferResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddAxesRequest>,
+                            request: tonic::Request<super::ReadDepthBufferRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_axes(&inner, request).await
+                                <T as StateService>::read_depth_buffer(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -1146,7 +3940,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddAxesSvc(inner);
+                        let method = ReadDepthBufferSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1162,25 +3956,29 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/AddMesh" => {
+                "/d3rpc.StateService/SetTessellationQuality" => {
                     #[allow(non_camel_case_types)]
-                    struct AddMeshSvc<T: StateService>(pub Arc<T>);
+                    struct SetTessellationQualitySvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::AddMeshRequest>
-                    for AddMeshSvc<T> {
-                        type Response = super::AddItemResponse;
+                    > tonic::server::UnaryService<super::SetTessellationQualityRequest>
+                    for SetTessellationQualitySvc<T> {
+                        type Response = super::SetTessellationQualityResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddMeshRequest>,
+                            request: tonic::Request<super::SetTessellationQualityRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::add_mesh(&inner, request).await
+                                <T as StateService>::set_tessellation_quality(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -1192,7 +3990,7 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddMeshSvc(inner);
+                        let method = SetTessellationQualitySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1208,24 +4006,25 @@ pub mod state_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/d3rpc.StateService/clear" => {
+                "/d3rpc.StateService/GetSceneStats" => {
                     #[allow(non_camel_case_types)]
-                    struct clearSvc<T: StateService>(pub Arc<T>);
+                    struct GetSceneStatsSvc<T: StateService>(pub Arc<T>);
                     impl<
                         T: StateService,
-                    > tonic::server::UnaryService<super::ClearRequest> for clearSvc<T> {
-                        type Response = super::ClearResponse;
+                    > tonic::server::UnaryService<super::GetSceneStatsRequest>
+                    for GetSceneStatsSvc<T> {
+                        type Response = super::GetSceneStatsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ClearRequest>,
+                            request: tonic::Request<super::GetSceneStatsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StateService>::clear(&inner, request).await
+                                <T as StateService>::get_scene_stats(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1237,7 +4036,189 @@ pub mod state_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = clearSvc(inner);
+                        let method = GetSceneStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/SceneBounds" => {
+                    #[allow(non_camel_case_types)]
+                    struct SceneBoundsSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::SceneBoundsRequest>
+                    for SceneBoundsSvc<T> {
+                        type Response = super::SceneBoundsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SceneBoundsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::scene_bounds(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SceneBoundsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/Exists" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExistsSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::ExistsRequest>
+                    for ExistsSvc<T> {
+                        type Response = super::ExistsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExistsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::exists(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExistsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/Disconnect" => {
+                    #[allow(non_camel_case_types)]
+                    struct DisconnectSvc<T: StateService>(pub Arc<T>);
+                    impl<
+                        T: StateService,
+                    > tonic::server::UnaryService<super::DisconnectRequest>
+                    for DisconnectSvc<T> {
+                        type Response = super::DisconnectResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DisconnectRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::disconnect(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DisconnectSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/d3rpc.StateService/Ping" => {
+                    #[allow(non_camel_case_types)]
+                    struct PingSvc<T: StateService>(pub Arc<T>);
+                    impl<T: StateService> tonic::server::UnaryService<super::PingRequest>
+                    for PingSvc<T> {
+                        type Response = super::PongResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PingRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StateService>::ping(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PingSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6,4 +6,9 @@ mod rpc_server;
 mod rpc_client;
 
 pub use rpc_server::run_server;
-pub use rpc_client::{Client3D, Error};
\ No newline at end of file
+pub(crate) use common::validate_descriptor;
+pub use rpc_client::{Client3D, Error};
+
+/// The protobuf schema of the 3D RPC service, the same file the client and server are generated
+/// from, for clients written in other languages.
+pub const RPC_SCHEMA: &str = include_str!("../../../protos/d3.proto");
\ No newline at end of file
@@ -6,22 +6,33 @@
 
 //{{{ crate imports
 use super::super::mesh::*;
+use super::super::camera::CameraDescriptor;
+use super::super::state::{simple_cuboid, simple_sphere, simple_triangle};
+use crate::common::{
+    clip_box_planes, model_to_values, ClipMode, Color, DepthBuffer, DrawContext, Mat4,
+    MeshColorMode, MeshDiagnostics, MeshOptions, SceneStats, TessellationQuality, Vec3,
+    ViewSensitivity,
+};
+use super::common::color_mode_to_rpc;
 use super::d3rpc;
+use crate::connection::{Connection, Resend};
 //}}}
 //{{{ std imports
+use std::future::Future;
+use std::time::{Duration, Instant};
 //}}}
 //{{{ dep imports
 use tonic::Request;
 use topohedral_tracing::*;
 use thiserror::Error;
-use tokio::runtime::Runtime;
-use tokio::runtime;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
 type RpcClient = d3rpc::state_service_client::StateServiceClient<tonic::transport::Channel>;
 
 //{{{ enum: Error
+/// The ways a request to the server can fail. Descriptors and meshes the server rejects as
+/// invalid come back as `Validation` errors, failures to reach it as the others.
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("IO error: {0}")]
@@ -30,15 +41,31 @@ pub enum Error {
     TonicError(#[from] tonic::transport::Error),
     #[error("Status error: {0}")]
     StatusError(#[from] tonic::Status),
+    #[error("Invalid {method} request: {message}")]
+    Validation { method: &'static str, message: String },
 }
 //..............................................................................
 //}}}
+//{{{ fun: status_error
+/// Returns the conversion of the status the server answered `method` with into an [`Error`],
+/// telling the requests it rejected as invalid apart from the other failures.
+fn status_error(method: &'static str) -> impl FnOnce(tonic::Status) -> Error {
+    move |status| match status.code() {
+        tonic::Code::InvalidArgument => Error::Validation {
+            method: method,
+            message: status.message().to_string(),
+        },
+        _ => Error::StatusError(status),
+    }
+}
+//}}}
 
 //{{{ struct: Client3D
 pub struct Client3D {
     client_name: String,
-    stub: RpcClient,
-    tokio_runtime: Runtime,
+    connection: Connection<RpcClient>,
+    /// The colors and cell type the `add_*_simple` methods draw shapes with
+    draw_context: DrawContext,
 }
 //..............................................................................
 //}}}
@@ -51,31 +78,81 @@ impl Client3D {
         info!("Starting 3D client");
         //}}}
         let client_name = "client3d";
-        let tokio_runtime = runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap();
-
-        let stub =
-            tokio_runtime.block_on(RpcClient::connect(format!("http://127.0.0.1:{}", port)))?;
+        let connection = Connection::new(port, RpcClient::new)?;
         Ok(Self {
             client_name: client_name.to_string(),
-            stub: stub,
-            tokio_runtime: tokio_runtime,
+            connection: connection,
+            draw_context: DrawContext::default(),
         })
     }
     //}}}
+    //{{{ fun: set_reconnect_attempts
+    /// Sets how many times a call whose connection to the server has broken, for instance
+    /// because the server was restarted, reconnects before failing. A call spends one attempt
+    /// per try at reconnecting, waiting a quarter of a second before each, and is sent again
+    /// once reconnected. Zero, the default, fails such calls straight away.
+    ///
+    /// Only calls which are safe to send twice are sent again: the calls adding meshes,
+    /// `append_to_mesh`, `open_window` and `kill_server` may have reached the server before the
+    /// connection broke, so they fail on a broken connection whatever the attempts.
+    pub fn set_reconnect_attempts(&mut self, attempts: u32)
+    {
+        self.connection.set_reconnect_attempts(attempts);
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: call
+    /// Sends `message`, which is safe to send twice, to the server through `send`, sending it
+    /// again if the connection breaks, see [`Self::set_reconnect_attempts`].
+    fn call<M, R, F, Fut>(
+        &mut self,
+        method: &'static str,
+        message: M,
+        send: F,
+    ) -> Result<tonic::Response<R>, Error>
+    where
+        M: Clone + Default,
+        F: Fn(RpcClient, Request<M>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        self.connection
+            .call(method, message, Resend::Idempotent, send)
+            .map_err(status_error(method))
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: call_once
+    /// Sends `message` to the server through `send`, never sending it twice, for the calls
+    /// which change the scene each time the server receives them.
+    fn call_once<M, R, F, Fut>(
+        &mut self,
+        method: &'static str,
+        message: M,
+        send: F,
+    ) -> Result<tonic::Response<R>, Error>
+    where
+        M: Clone + Default,
+        F: Fn(RpcClient, Request<M>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        self.connection
+            .call(method, message, Resend::Never, send)
+            .map_err(status_error(method))
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: add_line
     pub fn add_line(&mut self, line_desc: LineDescriptor)  -> Result<usize, Error> 
     {
         let line_desc_rpc: d3rpc::LineDescriptor = line_desc.into();
-        let request = Request::new(
-            d3rpc::AddLineRequest {
-                client_name: self.client_name.clone(),
-                line_descriptor: Some(line_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_line(request))?;
+        let request = d3rpc::AddLineRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            line_descriptor: Some(line_desc_rpc),
+        };
+        let response = self.call_once("add_line", request, |mut stub, request| async move {
+            stub.add_line(request).await
+        })?;
         Ok(response.into_inner().id as usize)    
     }
     //..............................................................................
@@ -84,13 +161,14 @@ impl Client3D {
     pub fn add_triangle(&mut self, triangle_desc: TriangleDescriptor)  -> Result<usize, Error>
     {
         let triangle_desc_rpc: d3rpc::TriangleDescriptor = triangle_desc.into();
-        let request = Request::new(
-            d3rpc::AddTriangleRequest {
-                client_name: self.client_name.clone(),
-                triangle_descriptor: Some(triangle_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_triangle(request))?;
+        let request = d3rpc::AddTriangleRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            triangle_descriptor: Some(triangle_desc_rpc),
+        };
+        let response = self.call_once("add_triangle", request, |mut stub, request| async move {
+            stub.add_triangle(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -99,13 +177,29 @@ impl Client3D {
     pub fn add_plane(&mut self, plane_desc: PlaneDescriptor) -> Result<usize, Error>
     {
         let plane_desc_rpc: d3rpc::PlaneDescriptor = plane_desc.into(); 
-        let request = Request::new(
-            d3rpc::AddPlaneRequest {
-                client_name: self.client_name.clone(),
-                plane_descriptor: Some(plane_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_plane(request))?;
+        let request = d3rpc::AddPlaneRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            plane_descriptor: Some(plane_desc_rpc),
+        };
+        let response = self.call_once("add_plane", request, |mut stub, request| async move {
+            stub.add_plane(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_polygon_face
+    pub fn add_polygon_face(&mut self, face_desc: PolygonFaceDescriptor) -> Result<usize, Error>
+    {
+        let request = d3rpc::AddPolygonFaceRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            polygon_face_descriptor: Some(face_desc.into()),
+        };
+        let response = self.call_once("add_polygon_face", request, |mut stub, request| async move {
+            stub.add_polygon_face(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -114,13 +208,14 @@ impl Client3D {
     pub fn add_cuboid(&mut self, cuboid_desc: CuboidDescriptor) -> Result<usize, Error>
     {
         let cuboid_desc_rpc: d3rpc::CuboidDescriptor = cuboid_desc.into();
-        let request = Request::new(
-            d3rpc::AddCuboidRequest {
-                client_name: self.client_name.clone(),
-                cuboid_descriptor: Some(cuboid_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_cuboid(request))?;
+        let request = d3rpc::AddCuboidRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            cuboid_descriptor: Some(cuboid_desc_rpc),
+        };
+        let response = self.call_once("add_cuboid", request, |mut stub, request| async move {
+            stub.add_cuboid(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -129,13 +224,14 @@ impl Client3D {
     pub fn add_cylinder(&mut self, cylinder_desc: CylinderDescriptor) -> Result<usize, Error>
     {
         let cylinder_desc_rpc: d3rpc::CylinderDescriptor = cylinder_desc.into();
-        let request = Request::new(
-            d3rpc::AddCylinderRequest {
-                client_name: self.client_name.clone(),
-                cylinder_descriptor: Some(cylinder_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_cylinder(request))?;
+        let request = d3rpc::AddCylinderRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            cylinder_descriptor: Some(cylinder_desc_rpc),
+        };
+        let response = self.call_once("add_cylinder", request, |mut stub, request| async move {
+            stub.add_cylinder(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -144,13 +240,14 @@ impl Client3D {
     pub fn add_disc(&mut self, disc_desc: DiscDescriptor) -> Result<usize, Error>
     {
         let disc_desc_rpc: d3rpc::DiscDescriptor = disc_desc.into();
-        let request = Request::new(
-            d3rpc::AddDiscRequest {
-                client_name: self.client_name.clone(),
-                disc_descriptor: Some(disc_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_disc(request))?;
+        let request = d3rpc::AddDiscRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            disc_descriptor: Some(disc_desc_rpc),
+        };
+        let response = self.call_once("add_disc", request, |mut stub, request| async move {
+            stub.add_disc(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -159,12 +256,79 @@ impl Client3D {
     pub fn add_sphere(&mut self, sphere_desc: SphereDescriptor) -> Result<usize, Error>
     {
         let sphere_desc_rpc: d3rpc::SphereDescriptor = sphere_desc.into();
-        let request = Request::new(
-            d3rpc::AddSphereRequest{
-                client_name: self.client_name.clone(),
-                sphere_descriptor: Some(sphere_desc_rpc)
-            });
-        let response = self.tokio_runtime.block_on(self.stub.add_sphere(request))?;
+        let request = d3rpc::AddSphereRequest{
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            sphere_descriptor: Some(sphere_desc_rpc)
+        };
+        let response = self.call_once("add_sphere", request, |mut stub, request| async move {
+            stub.add_sphere(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_draw_context
+    /// Sets the colors and cell type the `add_*_simple` methods draw shapes with. Shapes added
+    /// before keep their colors.
+    pub fn set_draw_context(&mut self, draw_context: DrawContext)
+    {
+        self.draw_context = draw_context;
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: draw_context
+    /// Returns the colors and cell type the `add_*_simple` methods draw shapes with.
+    pub fn draw_context(&self) -> DrawContext
+    {
+        self.draw_context
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_triangle_simple
+    /// Adds the triangle with the given vertices, drawn with the draw context of the client.
+    pub fn add_triangle_simple(&mut self, v1: Vec3, v2: Vec3, v3: Vec3) -> Result<usize, Error>
+    {
+        self.add_triangle(simple_triangle(&self.draw_context, v1, v2, v3))
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_cuboid_simple
+    /// Adds the cuboid with the given side lengths along the x, y and z-axes, with its corner
+    /// of smallest coordinates at `origin`, drawn with the draw context of the client.
+    pub fn add_cuboid_simple(
+        &mut self,
+        origin: Vec3,
+        lenx: f32,
+        leny: f32,
+        lenz: f32,
+    ) -> Result<usize, Error>
+    {
+        self.add_cuboid(simple_cuboid(&self.draw_context, origin, lenx, leny, lenz))
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_sphere_simple
+    /// Adds the sphere with the given centre and radius, its poles on the z-axis, drawn with the
+    /// draw context of the client.
+    pub fn add_sphere_simple(&mut self, origin: Vec3, radius: f32) -> Result<usize, Error>
+    {
+        self.add_sphere(simple_sphere(&self.draw_context, origin, radius))
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_ellipsoid
+    pub fn add_ellipsoid(&mut self, ellipsoid_desc: EllipsoidDescriptor) -> Result<usize, Error>
+    {
+        let ellipsoid_desc_rpc: d3rpc::EllipsoidDescriptor = ellipsoid_desc.into();
+        let request = d3rpc::AddEllipsoidRequest{
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            ellipsoid_descriptor: Some(ellipsoid_desc_rpc)
+        };
+        let response = self.call_once("add_ellipsoid", request, |mut stub, request| async move {
+            stub.add_ellipsoid(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
@@ -173,54 +337,599 @@ impl Client3D {
     pub fn add_axes(&mut self, axes_desc: AxesDescriptor) -> Result<usize, Error>
     {
         let axes_desc_rpc: d3rpc::AxesDescriptor = axes_desc.into();
-        let request = Request::new(
-            d3rpc::AddAxesRequest {
-                client_name: self.client_name.clone(),
-                axes_descriptor: Some(axes_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on( self.stub.add_axes(request))?;
+        let request = d3rpc::AddAxesRequest {
+            client_name: self.client_name.clone(),
+            name: String::new(),
+            axes_descriptor: Some(axes_desc_rpc),
+        };
+        let response = self.call_once("add_axes", request, |mut stub, request| async move {
+            stub.add_axes(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
     //}}}
     //{{{ fun: add_mesh
     pub fn add_mesh<'a>(&mut self, mesh: Mesh<'a>) -> Result<usize, Error>
+    {
+        self.add_named_mesh("", mesh)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_named_mesh
+    /// Adds a mesh tagged with the given name, which the server can look the mesh up by. An empty
+    /// name adds the mesh unnamed.
+    pub fn add_named_mesh<'a>(&mut self, name: &str, mesh: Mesh<'a>) -> Result<usize, Error>
     {
         let mesh_desc_rpc: d3rpc::MeshDescriptor = mesh.into();
-        let request = Request::new(
-            d3rpc::AddMeshRequest {
-                client_name: self.client_name.clone(),
-                mesh_descriptor: Some(mesh_desc_rpc),
-            }
-        );
-        let response = self.tokio_runtime.block_on(self.stub.add_mesh(request))?;
+        let request = d3rpc::AddMeshRequest {
+            client_name: self.client_name.clone(),
+            name: name.to_string(),
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let response = self.call_once("add_mesh", request, |mut stub, request| async move {
+            stub.add_mesh(request).await
+        })?;
         Ok(response.into_inner().id as usize)
     }
     //..............................................................................
     //}}}
+    //{{{ fun: append_to_mesh
+    /// Appends the vertices and cells of `mesh` to the mesh with the given id, which must have
+    /// the same cell type. The indices of `mesh` refer to its own vertices, the server offsets
+    /// them past those already in the mesh.
+    pub fn append_to_mesh<'a>(&mut self, id: usize, mesh: Mesh<'a>) -> Result<(), Error>
+    {
+        let mesh_desc_rpc: d3rpc::MeshDescriptor = mesh.into();
+        let request = d3rpc::AppendToMeshRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let _ = self.call_once("append_to_mesh", request, |mut stub, request| async move {
+            stub.append_to_mesh(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: replace_mesh
+    /// Replaces the geometry of the mesh with the given id by that of `mesh`, keeping the id, so
+    /// that a mesh can be animated without the references to it breaking.
+    pub fn replace_mesh<'a>(&mut self, id: usize, mesh: Mesh<'a>) -> Result<(), Error>
+    {
+        let mesh_desc_rpc: d3rpc::MeshDescriptor = mesh.into();
+        let request = d3rpc::ReplaceMeshRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let _ = self.call("replace_mesh", request, |mut stub, request| async move {
+            stub.replace_mesh(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: validate_mesh
+    /// Asks the server to check the geometry of `mesh`, without adding it to the scene, and
+    /// returns its report.
+    pub fn validate_mesh<'a>(&mut self, mesh: Mesh<'a>) -> Result<MeshDiagnostics, Error>
+    {
+        let mesh_desc_rpc: d3rpc::MeshDescriptor = mesh.into();
+        let request = d3rpc::ValidateMeshRequest {
+            client_name: self.client_name.clone(),
+            mesh_descriptor: Some(mesh_desc_rpc),
+        };
+        let response = self.call("validate_mesh", request, |mut stub, request| async move {
+            stub.validate_mesh(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_contours
+    /// Adds a line mesh holding the contours of `values`, one per vertex of the triangle mesh
+    /// with the given id, at each of `levels`, and returns the id of the new mesh.
+    pub fn add_contours(
+        &mut self,
+        id: usize,
+        values: &[f32],
+        levels: &[f32],
+        color: &Color,
+    ) -> Result<usize, Error>
+    {
+        let request = d3rpc::AddContoursRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            values: values.to_vec(),
+            levels: levels.to_vec(),
+            color: Some((*color).into()),
+        };
+        let response = self.call_once("add_contours", request, |mut stub, request| async move {
+            stub.add_contours(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_bounding_box
+    /// Adds a line mesh holding the edges of the bounding box of the mesh with the given id, and
+    /// returns the id of the new mesh.
+    pub fn add_bounding_box(&mut self, id: usize, color: &Color) -> Result<usize, Error>
+    {
+        let request = d3rpc::AddBoundingBoxRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            color: Some((*color).into()),
+        };
+        let response = self.call_once("add_bounding_box", request, |mut stub, request| async move {
+            stub.add_bounding_box(request).await
+        })?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: add_normals_visualization
+    /// Adds a line mesh holding a segment `length` long along the normal of each vertex of the
+    /// triangle mesh with the given id, and returns the id of the new mesh.
+    pub fn add_normals_visualization(
+        &mut self,
+        id: usize,
+        length: f32,
+        color: &Color,
+    ) -> Result<usize, Error>
+    {
+        let request = d3rpc::AddNormalsVisualizationRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            length: length,
+            color: Some((*color).into()),
+        };
+        let send = |mut stub: RpcClient, request| async move {
+            stub.add_normals_visualization(request).await
+        };
+        let response = self.call_once("add_normals_visualization", request, send)?;
+        Ok(response.into_inner().id as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_model
+    /// Sets the model matrix placing the mesh with the given id in the world, moving it without
+    /// resending its vertices.
+    pub fn set_mesh_model(&mut self, id: usize, model: &Mat4) -> Result<(), Error>
+    {
+        let request = d3rpc::SetMeshModelRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            model: model_to_values(model),
+        };
+        let _ = self.call("set_mesh_model", request, |mut stub, request| async move {
+            stub.set_mesh_model(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_tag
+    /// Puts the mesh with the given id in the group with the given tag, or in no group with
+    /// `None`, so that the group can be moved at once with [`Self::transform_group`].
+    pub fn set_mesh_tag(&mut self, id: usize, tag: Option<&str>) -> Result<(), Error>
+    {
+        let request = d3rpc::SetMeshTagRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            tag: tag.unwrap_or_default().to_string(),
+        };
+        let _ = self.call("set_mesh_tag", request, |mut stub, request| async move {
+            stub.set_mesh_tag(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: transform_group
+    /// Moves every mesh in the group with the given tag by `transform`, applied after the model
+    /// of each mesh, returning how many meshes were moved.
+    pub fn transform_group(&mut self, tag: &str, transform: &Mat4) -> Result<usize, Error>
+    {
+        let request = d3rpc::TransformGroupRequest {
+            client_name: self.client_name.clone(),
+            tag: tag.to_string(),
+            transform: model_to_values(transform),
+        };
+        // each call moves the group further, so it is never sent twice
+        let response = self.call_once("transform_group", request, |mut stub, request| async move {
+            stub.transform_group(request).await
+        })?;
+        Ok(response.into_inner().num_meshes as usize)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_color_mode
+    /// Draws the mesh with the given id with the colors of its vertices, or with a single color,
+    /// see [`MeshColorMode`].
+    pub fn set_mesh_color_mode(&mut self, id: usize, mode: MeshColorMode) -> Result<(), Error>
+    {
+        let request = d3rpc::SetMeshColorModeRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            uniform_color: color_mode_to_rpc(mode),
+        };
+        let _ = self.call("set_mesh_color_mode", request, |mut stub, request| async move {
+            stub.set_mesh_color_mode(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_mesh_options
+    /// Sets the options controlling how the mesh with the given id is drawn, such as the color of
+    /// the edges drawn over its triangle cells, see [`MeshOptions`].
+    pub fn set_mesh_options(&mut self, id: usize, options: &MeshOptions) -> Result<(), Error>
+    {
+        let request = d3rpc::SetMeshOptionsRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            options: Some((*options).into()),
+        };
+        let _ = self.call("set_mesh_options", request, |mut stub, request| async move {
+            stub.set_mesh_options(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: clear
     pub fn clear(&mut self) -> Result<(), Error>  
     {
-        let request = Request::new(
-            d3rpc::ClearRequest {
-                client_name: self.client_name.clone(),
-            }
-        );
-        let _ = self.tokio_runtime.block_on(self.stub.clear(request))?;
+        let request = d3rpc::ClearRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call("clear", request, |mut stub, request| async move {
+            stub.clear(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: get_camera
+    pub fn get_camera(&mut self) -> Result<CameraDescriptor, Error>
+    {
+        let request = d3rpc::GetCameraRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self.call("get_camera", request, |mut stub, request| async move {
+            stub.get_camera(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_camera
+    /// Points the camera at `focus` from `position`, moving it there smoothly if `animate` is set.
+    pub fn set_camera(
+        &mut self,
+        position: &Vec3,
+        focus: &Vec3,
+        animate: bool,
+    ) -> Result<(), Error>
+    {
+        let request = d3rpc::SetCameraRequest {
+            client_name: self.client_name.clone(),
+            position: Some((*position).into()),
+            focus: Some((*focus).into()),
+            animate: animate,
+        };
+        let _ = self.call("set_camera", request, |mut stub, request| async move {
+            stub.set_camera(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: focus_mesh
+    /// Points the camera at the mesh with the given id, from far enough away to frame all of it,
+    /// moving it there smoothly if `animate` is set.
+    pub fn focus_mesh(&mut self, id: usize, animate: bool) -> Result<(), Error>
+    {
+        let request = d3rpc::FocusMeshRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+            animate: animate,
+        };
+        let _ = self.call("focus_mesh", request, |mut stub, request| async move {
+            stub.focus_mesh(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_rendering_enabled
+    pub fn set_rendering_enabled(&mut self, enabled: bool) -> Result<(), Error>
+    {
+        let request = d3rpc::SetRenderingEnabledRequest {
+            client_name: self.client_name.clone(),
+            enabled: enabled,
+        };
+        let _ = self.call("set_rendering_enabled", request, |mut stub, request| async move {
+            stub.set_rendering_enabled(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: flush
+    /// Blocks until the viewer has rendered every mesh added so far, so that a screenshot taken
+    /// next shows them. Returns at once if rendering is disabled or no window is open, and fails
+    /// if the viewer has not rendered within five seconds.
+    pub fn flush(&mut self) -> Result<(), Error>
+    {
+        let request = d3rpc::FlushRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call("flush", request, |mut stub, request| async move {
+            stub.flush(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: open_window
+    /// Opens another window onto the scene, with a camera of its own. The window appears once the
+    /// viewer's event loop handles the request, shortly after this returns.
+    pub fn open_window(&mut self) -> Result<(), Error>
+    {
+        let request = d3rpc::OpenWindowRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call_once("open_window", request, |mut stub, request| async move {
+            stub.open_window(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_view_sensitivity
+    /// Sets how far the camera moves per key press and mouse wheel step, every value must be
+    /// finite and strictly positive.
+    pub fn set_view_sensitivity(&mut self, sensitivity: &ViewSensitivity) -> Result<(), Error>
+    {
+        let request = d3rpc::SetViewSensitivityRequest {
+            client_name: self.client_name.clone(),
+            key_pan_delta: sensitivity.key_pan_delta,
+            key_rotate_delta: sensitivity.key_rotate_delta,
+            zoom_speed: sensitivity.zoom_speed,
+        };
+        let _ = self.call("set_view_sensitivity", request, |mut stub, request| async move {
+            stub.set_view_sensitivity(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_background_gradient
+    /// Fills the background with a vertical gradient from `top` to `bottom`.
+    pub fn set_background_gradient(&mut self, top: &Color, bottom: &Color) -> Result<(), Error>
+    {
+        let request = d3rpc::SetBackgroundGradientRequest {
+            client_name: self.client_name.clone(),
+            top: Some((*top).into()),
+            bottom: Some((*bottom).into()),
+        };
+        let _ = self.call("set_background_gradient", request, |mut stub, request| async move {
+            stub.set_background_gradient(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: clear_background_gradient
+    /// Removes the background gradient, restoring the flat background.
+    pub fn clear_background_gradient(&mut self) -> Result<(), Error>
+    {
+        let request = d3rpc::SetBackgroundGradientRequest {
+            client_name: self.client_name.clone(),
+            top: None,
+            bottom: None,
+        };
+        let _ = self.call("set_background_gradient", request, |mut stub, request| async move {
+            stub.set_background_gradient(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_tessellation_quality
+    /// Sets the quality cylinders, discs, spheres and ellipsoids added from now on are
+    /// tessellated at when their descriptor leaves a subdivision count at zero.
+    pub fn set_tessellation_quality(&mut self, quality: TessellationQuality) -> Result<(), Error>
+    {
+        let (quality, chord_error) = quality.into();
+        let request = d3rpc::SetTessellationQualityRequest {
+            client_name: self.client_name.clone(),
+            quality: quality,
+            chord_error: chord_error,
+        };
+        let _ = self.call("set_tessellation_quality", request, |mut stub, request| async move {
+            stub.set_tessellation_quality(request).await
+        })?;
         Ok(())
     }
     //..............................................................................
     //}}}
+    //{{{ fun: set_clip_plane
+    /// Cuts the scene through the plane passing through `point` with normal `normal`, hiding
+    /// everything on the side the normal points to.
+    pub fn set_clip_plane(&mut self, point: &Vec3, normal: &Vec3) -> Result<(), Error>
+    {
+        let request = d3rpc::SetClipPlaneRequest {
+            client_name: self.client_name.clone(),
+            point: Some((*point).into()),
+            normal: Some((*normal).into()),
+        };
+        let _ = self.call("set_clip_plane", request, |mut stub, request| async move {
+            stub.set_clip_plane(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: clear_clip_plane
+    /// Removes the clipping plane, showing the whole scene again.
+    pub fn clear_clip_plane(&mut self) -> Result<(), Error>
+    {
+        let request = d3rpc::SetClipPlaneRequest {
+            client_name: self.client_name.clone(),
+            point: None,
+            normal: None,
+        };
+        let _ = self.call("set_clip_plane", request, |mut stub, request| async move {
+            stub.set_clip_plane(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_clip_planes
+    /// Cuts the scene through up to [`crate::MAX_CLIP_PLANES`] planes, each given as a point
+    /// and a normal pointing to the side it hides, combined according to `mode`. An empty slice
+    /// removes the clipping.
+    pub fn set_clip_planes(&mut self, planes: &[(Vec3, Vec3)], mode: ClipMode) -> Result<(), Error>
+    {
+        let request = d3rpc::SetClipPlanesRequest {
+            client_name: self.client_name.clone(),
+            planes: planes
+                .iter()
+                .map(|(point, normal)| d3rpc::ClipPlane {
+                    point: Some((*point).into()),
+                    normal: Some((*normal).into()),
+                })
+                .collect(),
+            mode: mode.into(),
+        };
+        let _ = self.call("set_clip_planes", request, |mut stub, request| async move {
+            stub.set_clip_planes(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: set_clip_box
+    /// Cuts the scene down to the axis-aligned box from `min` to `max`.
+    pub fn set_clip_box(&mut self, min: &Vec3, max: &Vec3) -> Result<(), Error>
+    {
+        self.set_clip_planes(&clip_box_planes(min, max), ClipMode::InsideAll)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: read_depth_buffer
+    /// Reads back the depth buffer of the viewer's first window as left by its last render, with
+    /// each depth being the distance in front of the camera.
+    pub fn read_depth_buffer(&mut self) -> Result<DepthBuffer, Error>
+    {
+        let request = d3rpc::ReadDepthBufferRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self.call("read_depth_buffer", request, |mut stub, request| async move {
+            stub.read_depth_buffer(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: scene_stats
+    /// Returns the number of meshes and vertices held by the server, along with its budget.
+    pub fn scene_stats(&mut self) -> Result<SceneStats, Error>
+    {
+        let request = d3rpc::GetSceneStatsRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self.call("get_scene_stats", request, |mut stub, request| async move {
+            stub.get_scene_stats(request).await
+        })?;
+        Ok(response.into_inner().into())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: scene_bounds
+    /// Returns the minimum and maximum corners of the smallest box enclosing every mesh held by
+    /// the server, or `None` if it holds no vertices.
+    pub fn scene_bounds(&mut self) -> Result<Option<(Vec3, Vec3)>, Error>
+    {
+        let request = d3rpc::SceneBoundsRequest {
+            client_name: self.client_name.clone(),
+        };
+        let response = self
+            .call("scene_bounds", request, |mut stub, request| async move {
+                stub.scene_bounds(request).await
+            })?
+            .into_inner();
+        match (response.empty, response.min, response.max)
+        {
+            (false, Some(min), Some(max)) => Ok(Some((min.into(), max.into()))),
+            _ => Ok(None),
+        }
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: exists
+    /// Returns whether the server holds a mesh with the given id, for checking a mesh is still
+    /// there before operating on it.
+    pub fn exists(&mut self, id: usize) -> Result<bool, Error>
+    {
+
+        let request = d3rpc::ExistsRequest {
+            client_name: self.client_name.clone(),
+            id: id as u64,
+        };
+        let response = self.call("exists", request, |mut stub, request| async move {
+            stub.exists(request).await
+        })?;
+        Ok(response.into_inner().exists)
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: disconnect
+    /// Tells the server this client is done with it. A server started with `--exit-on-idle`
+    /// shuts down once every client has disconnected, or gone quiet, for the idle timeout.
+    pub fn disconnect(&mut self) -> Result<(), Error>
+    {
+        let request = d3rpc::DisconnectRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call("disconnect", request, |mut stub, request| async move {
+            stub.disconnect(request).await
+        })?;
+        Ok(())
+    }
+    //..............................................................................
+    //}}}
+    //{{{ fun: ping
+    /// Checks the server is alive, returning the round-trip time of the request. This also keeps
+    /// the client active on a server started with `--exit-on-idle`.
+    pub fn ping(&mut self) -> Result<Duration, Error>
+    {
+        let request = d3rpc::PingRequest {
+            client_name: self.client_name.clone(),
+        };
+        let start = Instant::now();
+        let _ = self.call("ping", request, |mut stub, request| async move {
+            stub.ping(request).await
+        })?;
+        Ok(start.elapsed())
+    }
+    //..............................................................................
+    //}}}
     //{{{ fun: kill_server
     pub fn kill_server(&mut self) -> Result<(), Error>
     {
-        let request = Request::new(
-            d3rpc::KillServerRequest {
-                client_name: self.client_name.clone(),
-            }
-        );
-        let _ = self.tokio_runtime.block_on(self.stub.kill_server(request))?;
+        let request = d3rpc::KillServerRequest {
+            client_name: self.client_name.clone(),
+        };
+        let _ = self.call_once("kill_server", request, |mut stub, request| async move {
+            stub.kill_server(request).await
+        })?;
         Ok(())
     }
     //..............................................................................
@@ -233,5 +942,56 @@ impl Client3D {
 //-------------------------------------------------------------------------------------------------
 //{{{ mod: tests
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::connection::{is_broken_connection, RECONNECT_DELAY};
+
+    #[test]
+    fn status_error_test() {
+        let error = status_error("add_sphere")(tonic::Status::invalid_argument("Invalid sphere"));
+        assert!(matches!(
+            error,
+            Error::Validation { method: "add_sphere", ref message } if message == "Invalid sphere"
+        ));
+        let error = status_error("add_sphere")(tonic::Status::unavailable("connection refused"));
+        assert!(matches!(error, Error::StatusError(_)));
+    }
+
+    #[test]
+    fn reconnect_test() {
+        // a port nothing listens on, so that every connection is refused
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port() as usize;
+        let mut connection = Connection::new_lazy(port, RpcClient::new);
+        connection.set_reconnect_attempts(2);
+        let mut client = Client3D {
+            client_name: "test".to_string(),
+            connection: connection,
+            draw_context: DrawContext::default(),
+        };
+        // the call tries reconnecting twice before giving up with the transport error
+        let start = Instant::now();
+        match client.ping() {
+            Err(Error::StatusError(status)) => assert!(is_broken_connection(&status)),
+            other => panic!("expected a broken connection, got {:?}", other),
+        }
+        assert!(start.elapsed() >= 2 * RECONNECT_DELAY);
+
+        // adding a mesh is never sent twice, so it fails without reconnecting
+        let start = Instant::now();
+        match client.add_line(LineDescriptor {
+            v1: Vec3::new(0.0, 0.0, 0.0),
+            v2: Vec3::new(1.0, 1.0, 1.0),
+            color: Color::Black,
+            dash_pattern: None,
+        }) {
+            Err(Error::StatusError(status)) => assert!(is_broken_connection(&status)),
+            other => panic!("expected a broken connection, got {:?}", other),
+        }
+        assert!(start.elapsed() < RECONNECT_DELAY);
+    }
+}
 //}}}
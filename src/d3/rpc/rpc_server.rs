@@ -4,20 +4,23 @@
 //--------------------------------------------------------------------------------------------------
 
 //{{{ crate imports
-use crate::common::Validated;
+use crate::common::{model_from_values, AddMeshError, Color, Validated, MAX_CLIP_PLANES};
+use crate::core::MeshEditError;
 use super::common::*;
 use super::d3rpc;
 use crate::d3;
 use crate::d3::mesh::{
     AxesDescriptor, CuboidDescriptor, CylinderDescriptor, LineDescriptor, PlaneDescriptor,
-    SphereDescriptor, TriangleDescriptor, Mesh
+    SphereDescriptor, TriangleDescriptor, EllipsoidDescriptor, Mesh, Mesh3D, PolygonFaceDescriptor
 };
-use crate::d3::state::{State, State3D};
+use crate::d3::state::{AddShapeError, State, State3D};
 use crate::app::TopoHedralEvent;
+use crate::clients::{shutdown_when_idle, ClientTracker};
 //}}}
 //{{{ std imports
 use core::net::SocketAddr;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 //}}}
 //{{{ dep imports
 use tokio::sync::mpsc;
@@ -27,13 +30,41 @@ use winit::event_loop::EventLoopProxy;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
+//{{{ collection: constants
+/// The longest a flush waits for the scene to be rendered, in case no render pass is coming, for
+/// instance because the window is minimized.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+//}}}
+//{{{ fun: add_mesh_status
+/// Returns the status of an add request whose mesh the state turned away: resource exhausted if it
+/// does not fit within the scene budget, an invalid argument if it is empty.
+fn add_mesh_status(e: AddMeshError) -> Status {
+    match e {
+        AddMeshError::Budget(e) => Status::resource_exhausted(e.to_string()),
+        AddMeshError::Empty => Status::invalid_argument(e.to_string()),
+    }
+}
+//}}}
+
 //{{{ struct: StateServer
 struct StateServer {
     state: Arc<Mutex<State<'static>>>,
     shutdown_sender: mpsc::Sender<()>,
+    clients: Arc<Mutex<ClientTracker>>,
+    event_loop_proxy: EventLoopProxy<TopoHedralEvent>,
 }
 //..............................................................................
 //}}}
+//{{{ impl StateServer
+impl StateServer {
+    //{{{ fun: touch
+    /// Registers the client making a request, or records that it is still active.
+    fn touch(&self, client_name: &str) {
+        self.clients.lock().unwrap().touch(client_name, Instant::now());
+    }
+    //}}}
+}
+//}}}
 //{{{ impl d3rpc::state_service_server::StateService for StateServer
 #[tonic::async_trait]
 impl d3rpc::state_service_server::StateService for StateServer {
@@ -44,24 +75,32 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_line request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let line_desc = msg.line_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_line(&line_desc);
-            let add_line_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_line_result))
-        }
-        else 
-        {
-            Err(Status::invalid_argument("Invalid line descriptor"))    
+        let out = match msg.validate() {
+            Ok(()) => {
+                let line_desc = msg.line_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_line(&line_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_line_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_line_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid line descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -73,24 +112,32 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_triangle request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let triangle_desc = msg.triangle_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_triangle(&triangle_desc);
-            let add_triangle_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_triangle_result))  
-        }
-        else 
-        {
-            Err(Status::invalid_argument("Invalid triangle descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let triangle_desc = msg.triangle_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_triangle(&triangle_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_triangle_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_triangle_result))  
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid triangle descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -103,28 +150,70 @@ impl d3rpc::state_service_server::StateService for StateServer {
 
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_plane request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let plane_desc = msg.plane_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_plane(&plane_desc);
-            let add_triangle_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_triangle_result))  
-        }
-        else 
-        {
-            Err(Status::invalid_argument("Invalid triangle descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let plane_desc = msg.plane_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_plane(&plane_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_triangle_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_triangle_result))  
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid triangle descriptor: {}",
+                e
+            ))),
         };
         out
     }
     //}}}
+    //{{{ fun: add_polygon_face
+    async fn add_polygon_face(
+        &self,
+        request: tonic::Request<d3rpc::AddPolygonFaceRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_polygon_face request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(format!(
+                "Invalid polygon face descriptor: {}",
+                e
+            )));
+        }
+        let face_desc: PolygonFaceDescriptor = msg.polygon_face_descriptor.unwrap().into();
+        let mesh = Mesh::create_polygon_face(&face_desc)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let mut state = self.state.lock().unwrap();
+        let mesh_uid = state
+            .add_mesh(mesh)
+            .map_err(add_mesh_status)?;
+        if !msg.name.is_empty() {
+            state.set_mesh_name(mesh_uid, &msg.name);
+        }
+        Ok(Response::new(d3rpc::AddItemResponse { id: mesh_uid as u64 }))
+    }
+    //}}}
     //{{{ fun: add_cuboid
     async fn add_cuboid(
         &self,
@@ -132,21 +221,30 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner(); 
+        self.touch(&msg.client_name);
         //{{{ trace
         info!("Received add_cuboid request from {} on port {:?}", msg.client_name, addr);
         //}}}
-        let out = if msg.is_valid() {
-            let cuboid_desc = msg.cuboid_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_cuboid(&cuboid_desc);
-            let add_cuboid_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_cuboid_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid cuboid descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let cuboid_desc = msg.cuboid_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state.add_cuboid(&cuboid_desc).map_err(|e| match e {
+                    AddShapeError::Mesh(e) => Status::invalid_argument(e.to_string()),
+                    AddShapeError::Add(e) => add_mesh_status(e),
+                })?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_cuboid_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_cuboid_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid cuboid descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -158,24 +256,32 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_cylinder request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let cylinder_desc = msg.cylinder_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_cylinder(&cylinder_desc);
-            let add_cylinder_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_cylinder_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid cylinder descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let cylinder_desc = msg.cylinder_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_cylinder(&cylinder_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_cylinder_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_cylinder_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid cylinder descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -187,24 +293,32 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_disc request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let disc_desc = msg.disc_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_disc(&disc_desc);
-            let add_disc_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_disc_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid disc descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let disc_desc = msg.disc_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_disc(&disc_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_disc_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_disc_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid disc descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -216,24 +330,69 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_sphere request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let sphere_desc = msg.sphere_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_sphere(&sphere_desc);
-            let add_sphere_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_sphere_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid sphere descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let sphere_desc = msg.sphere_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_sphere(&sphere_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_sphere_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_sphere_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid sphere descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: add_ellipsoid
+    async fn add_ellipsoid(
+        &self,
+        request: tonic::Request<d3rpc::AddEllipsoidRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_ellipsoid request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let out = match msg.validate() {
+            Ok(()) => {
+                let ellipsoid_desc: EllipsoidDescriptor = msg.ellipsoid_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_ellipsoid(&ellipsoid_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_ellipsoid_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_ellipsoid_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid ellipsoid descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -245,21 +404,29 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!( "Received add_axes request from {} on port {:?}", msg.client_name , addr);
         //}}}
-        let out = if msg.is_valid() {
-            let axes_desc = msg.axes_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_axes(&axes_desc);
-            let add_axes_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_axes_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid axes descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let axes_desc = msg.axes_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_axes(&axes_desc)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_axes_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_axes_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid axes descriptor: {}",
+                e
+            ))),
         };
         out
     }
@@ -271,28 +438,351 @@ impl d3rpc::state_service_server::StateService for StateServer {
     ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_mesh request from {} on port {:?}",
             msg.client_name, addr
         );
         //}}}
-        let out = if msg.is_valid() {
-            let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
-            let mut state = self.state.lock().unwrap();
-            let mesh_uid = state.add_mesh(mesh);
-            let add_mesh_result = d3rpc::AddItemResponse {
-                id: mesh_uid as u64,
-            };
-            Ok(Response::new(add_mesh_result))
-        }
-        else
-        {
-            Err(Status::invalid_argument("Invalid mesh descriptor"))
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                let mesh_uid = state
+                    .add_mesh(mesh)
+                    .map_err(add_mesh_status)?;
+                if !msg.name.is_empty() {
+                    state.set_mesh_name(mesh_uid, &msg.name);
+                }
+                let add_mesh_result = d3rpc::AddItemResponse {
+                    id: mesh_uid as u64,
+                };
+                Ok(Response::new(add_mesh_result))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
         };
         out
     }
     //}}}
+    //{{{ fun: append_to_mesh
+    async fn append_to_mesh(
+        &self,
+        request: tonic::Request<d3rpc::AppendToMeshRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AppendToMeshResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received append_to_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                match state.append_to_mesh(msg.id as usize, mesh) {
+                    Ok(()) => Ok(Response::new(d3rpc::AppendToMeshResponse {})),
+                    Err(MeshEditError::NoMesh) => {
+                        Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+                    }
+                    Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+                    Err(e) => Err(Status::invalid_argument(format!(
+                        "Cannot append to mesh {}: {}",
+                        msg.id, e
+                    ))),
+                }
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: replace_mesh
+    async fn replace_mesh(
+        &self,
+        request: tonic::Request<d3rpc::ReplaceMeshRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::ReplaceMeshResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received replace_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let out = match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                let mut state = self.state.lock().unwrap();
+                match state.replace_mesh(msg.id as usize, mesh) {
+                    Ok(()) => Ok(Response::new(d3rpc::ReplaceMeshResponse {})),
+                    Err(MeshEditError::NoMesh) => {
+                        Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+                    }
+                    Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+                    Err(e) => Err(Status::internal(e.to_string())),
+                }
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        };
+        out
+    }
+    //}}}
+    //{{{ fun: validate_mesh
+    /// Reports on the geometry of the mesh without adding it to the scene.
+    async fn validate_mesh(
+        &self,
+        request: tonic::Request<d3rpc::ValidateMeshRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::MeshStatsResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received validate_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        match msg.validate() {
+            Ok(()) => {
+                let mesh: Mesh = msg.mesh_descriptor.unwrap().into();
+                Ok(Response::new(mesh.diagnostics().into()))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid mesh descriptor: {}",
+                e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: add_contours
+    async fn add_contours(
+        &self,
+        request: tonic::Request<d3rpc::AddContoursRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_contours request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let color = msg.color.map(Color::from).unwrap_or(Color::Black);
+        let mut state = self.state.lock().unwrap();
+        match state.add_contours(msg.id as usize, &msg.values, &msg.levels, &color) {
+            Ok(id) => Ok(Response::new(d3rpc::AddItemResponse { id: id as u64 })),
+            Err(MeshEditError::NoMesh) => {
+                Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+            }
+            Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Cannot add contours of mesh {}: {}",
+                msg.id, e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: add_bounding_box
+    async fn add_bounding_box(
+        &self,
+        request: tonic::Request<d3rpc::AddBoundingBoxRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_bounding_box request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let color = msg.color.map(Color::from).unwrap_or(Color::Black);
+        let mut state = self.state.lock().unwrap();
+        match state.add_bounding_box(msg.id as usize, &color) {
+            Ok(id) => Ok(Response::new(d3rpc::AddItemResponse { id: id as u64 })),
+            Err(MeshEditError::NoMesh) => {
+                Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+            }
+            Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Cannot add the bounding box of mesh {}: {}",
+                msg.id, e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: add_normals_visualization
+    async fn add_normals_visualization(
+        &self,
+        request: tonic::Request<d3rpc::AddNormalsVisualizationRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::AddItemResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received add_normals_visualization request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if !msg.length.is_finite() {
+            return Err(Status::invalid_argument("Normal length is not finite"));
+        }
+        let color = msg.color.map(Color::from).unwrap_or(Color::Black);
+        let mut state = self.state.lock().unwrap();
+        match state.add_normals_visualization(msg.id as usize, msg.length, &color) {
+            Ok(id) => Ok(Response::new(d3rpc::AddItemResponse { id: id as u64 })),
+            Err(MeshEditError::NoMesh) => {
+                Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+            }
+            Err(MeshEditError::Budget(e)) => Err(Status::resource_exhausted(e.to_string())),
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Cannot add the normals of mesh {}: {}",
+                msg.id, e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_model
+    async fn set_mesh_model(
+        &self,
+        request: tonic::Request<d3rpc::SetMeshModelRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetMeshModelResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_model request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let model = model_from_values(&msg.model).unwrap();
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_model(msg.id as usize, model) {
+            Ok(Response::new(d3rpc::SetMeshModelResponse {}))
+        } else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_tag
+    async fn set_mesh_tag(
+        &self,
+        request: tonic::Request<d3rpc::SetMeshTagRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetMeshTagResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_tag request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let tag = Some(msg.tag.as_str()).filter(|tag| !tag.is_empty());
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_tag(msg.id as usize, tag) {
+            Ok(Response::new(d3rpc::SetMeshTagResponse {}))
+        } else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: transform_group
+    async fn transform_group(
+        &self,
+        request: tonic::Request<d3rpc::TransformGroupRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::TransformGroupResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received transform_group request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let transform = model_from_values(&msg.transform).unwrap();
+        let num_meshes = self.state.lock().unwrap().transform_group(&msg.tag, transform);
+        Ok(Response::new(d3rpc::TransformGroupResponse {
+            num_meshes: num_meshes as u64,
+        }))
+    }
+    //}}}
+    //{{{ fun: set_mesh_color_mode
+    async fn set_mesh_color_mode(
+        &self,
+        request: tonic::Request<d3rpc::SetMeshColorModeRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetMeshColorModeResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_color_mode request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let mode = color_mode_from_rpc(msg.uniform_color);
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_color_mode(msg.id as usize, mode) {
+            Ok(Response::new(d3rpc::SetMeshColorModeResponse {}))
+        } else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: set_mesh_options
+    async fn set_mesh_options(
+        &self,
+        request: tonic::Request<d3rpc::SetMeshOptionsRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetMeshOptionsResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_mesh_options request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        if let Err(e) = msg.validate() {
+            return Err(Status::invalid_argument(e));
+        }
+        let options = msg.options.unwrap().into();
+        let mut state = self.state.lock().unwrap();
+        if state.set_mesh_options(msg.id as usize, options) {
+            Ok(Response::new(d3rpc::SetMeshOptionsResponse {}))
+        } else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
     //{{{ fun: clear
     async fn clear(
         &self,
@@ -301,6 +791,7 @@ impl d3rpc::state_service_server::StateService for StateServer {
 
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
         //{{{ trace
         info!(
             "Received add_mesh request from {} on port {:?}",
@@ -312,6 +803,409 @@ impl d3rpc::state_service_server::StateService for StateServer {
         Ok(Response::new(d3rpc::ClearResponse {}))
     }
     //}}}
+    //{{{ fun: get_camera
+    async fn get_camera(
+        &self,
+        request: tonic::Request<d3rpc::GetCameraRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::GetCameraResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received get_camera request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(state.camera().into()))
+    }
+    //}}}
+    //{{{ fun: set_camera
+    async fn set_camera(
+        &self,
+        request: tonic::Request<d3rpc::SetCameraRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetCameraResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_camera request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        match msg.validate() {
+            Ok(()) => {
+                let mut state = self.state.lock().unwrap();
+                state.set_camera(
+                    msg.position.unwrap().into(),
+                    msg.focus.unwrap().into(),
+                    msg.animate,
+                );
+                Ok(Response::new(d3rpc::SetCameraResponse {}))
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid camera: {}",
+                e
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: focus_mesh
+    async fn focus_mesh(
+        &self,
+        request: tonic::Request<d3rpc::FocusMeshRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::FocusMeshResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received focus_mesh request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        if state.focus_on_mesh(msg.id as usize, msg.animate) {
+            Ok(Response::new(d3rpc::FocusMeshResponse {}))
+        } else {
+            Err(Status::not_found(format!("No mesh with id {}", msg.id)))
+        }
+    }
+    //}}}
+    //{{{ fun: set_rendering_enabled
+    async fn set_rendering_enabled(
+        &self,
+        request: tonic::Request<d3rpc::SetRenderingEnabledRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetRenderingEnabledResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_rendering_enabled request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        state.set_rendering_enabled(msg.enabled);
+        Ok(Response::new(d3rpc::SetRenderingEnabledResponse {}))
+    }
+    //}}}
+    //{{{ fun: flush
+    async fn flush(
+        &self,
+        request: tonic::Request<d3rpc::FlushRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::FlushResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received flush request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // the lock must be released for the render loop to draw the frame waited on
+        let rendered = self.state.lock().unwrap().request_flush();
+        match tokio::time::timeout(FLUSH_TIMEOUT, rendered).await {
+            Ok(Ok(())) => Ok(Response::new(d3rpc::FlushResponse {})),
+            Ok(Err(_)) => Err(Status::unavailable("The window closed before rendering")),
+            Err(_) => Err(Status::deadline_exceeded("The scene was not rendered in time")),
+        }
+    }
+    //}}}
+    //{{{ fun: open_window
+    async fn open_window(
+        &self,
+        request: tonic::Request<d3rpc::OpenWindowRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::OpenWindowResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received open_window request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // windows can only be created on the thread running the event loop
+        match self.event_loop_proxy.send_event(TopoHedralEvent::OpenWindow) {
+            Ok(()) => Ok(Response::new(d3rpc::OpenWindowResponse {})),
+            Err(_) => Err(Status::unavailable("The event loop has closed")),
+        }
+    }
+    //}}}
+    //{{{ fun: set_view_sensitivity
+    async fn set_view_sensitivity(
+        &self,
+        request: tonic::Request<d3rpc::SetViewSensitivityRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetViewSensitivityResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_view_sensitivity request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        match state.set_view_sensitivity(msg.into()) {
+            Ok(()) => Ok(Response::new(d3rpc::SetViewSensitivityResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid view sensitivity: {} must be finite and positive",
+                field
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_background_gradient
+    async fn set_background_gradient(
+        &self,
+        request: tonic::Request<d3rpc::SetBackgroundGradientRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetBackgroundGradientResponse>, tonic::Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_background_gradient request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        // a missing color removes the gradient
+        match (msg.top, msg.bottom) {
+            (Some(top), Some(bottom)) => state.set_background_gradient(top.into(), bottom.into()),
+            _ => state.clear_background_gradient(),
+        }
+        Ok(Response::new(d3rpc::SetBackgroundGradientResponse {}))
+    }
+    //}}}
+    //{{{ fun: set_tessellation_quality
+    async fn set_tessellation_quality(
+        &self,
+        request: tonic::Request<d3rpc::SetTessellationQualityRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetTessellationQualityResponse>, tonic::Status>
+    {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_tessellation_quality request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let mut state = self.state.lock().unwrap();
+        match state.set_tessellation_quality((msg.quality, msg.chord_error).into()) {
+            Ok(()) => Ok(Response::new(d3rpc::SetTessellationQualityResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid tessellation quality: bad {}, it must be finite and positive",
+                field
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_clip_plane
+    async fn set_clip_plane(
+        &self,
+        request: tonic::Request<d3rpc::SetClipPlaneRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetClipPlaneResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_clip_plane request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // a missing point or normal removes the plane
+        let plane = match (msg.point, msg.normal) {
+            (Some(point), Some(normal)) => Some((point.into(), normal.into())),
+            _ => None,
+        };
+        let mut state = self.state.lock().unwrap();
+        match state.set_clip_plane(plane) {
+            Ok(()) => Ok(Response::new(d3rpc::SetClipPlaneResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid clip plane: {} must be finite and the normal non-zero",
+                field
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: set_clip_planes
+    async fn set_clip_planes(
+        &self,
+        request: tonic::Request<d3rpc::SetClipPlanesRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SetClipPlanesResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received set_clip_planes request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        // a missing point defaults to the origin and a missing normal is rejected as zero
+        let planes = msg
+            .planes
+            .into_iter()
+            .map(|plane| {
+                (
+                    plane.point.unwrap_or_default().into(),
+                    plane.normal.unwrap_or_default().into(),
+                )
+            })
+            .collect();
+        let mut state = self.state.lock().unwrap();
+        match state.set_clip_planes(planes, msg.mode.into()) {
+            Ok(()) => Ok(Response::new(d3rpc::SetClipPlanesResponse {})),
+            Err(field) => Err(Status::invalid_argument(format!(
+                "Invalid clip planes: bad {}, at most {} planes with finite points and non-zero \
+                 normals are allowed",
+                field, MAX_CLIP_PLANES
+            ))),
+        }
+    }
+    //}}}
+    //{{{ fun: read_depth_buffer
+    async fn read_depth_buffer(
+        &self,
+        request: tonic::Request<d3rpc::ReadDepthBufferRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::ReadDepthBufferResponse>, tonic::Status> {
+
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received read_depth_buffer request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        match state.read_depth_buffer() {
+            Some(depth_buffer) => Ok(Response::new(depth_buffer.into())),
+            None => Err(Status::failed_precondition(
+                "No window is open to read the depth buffer from",
+            )),
+        }
+    }
+    //}}}
+    //{{{ fun: get_scene_stats
+    async fn get_scene_stats(
+        &self,
+        request: tonic::Request<d3rpc::GetSceneStatsRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::GetSceneStatsResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received get_scene_stats request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(state.scene_stats().into()))
+    }
+    //}}}
+    //{{{ fun: scene_bounds
+    async fn scene_bounds(
+        &self,
+        request: tonic::Request<d3rpc::SceneBoundsRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::SceneBoundsResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received scene_bounds request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        let response = match state.scene_bounds() {
+            Some((min, max)) => d3rpc::SceneBoundsResponse {
+                min: Some(min.into()),
+                max: Some(max.into()),
+                empty: false,
+            },
+            None => d3rpc::SceneBoundsResponse {
+                min: None,
+                max: None,
+                empty: true,
+            },
+        };
+        Ok(Response::new(response))
+    }
+    //}}}
+    //{{{ fun: exists
+    async fn exists(
+        &self,
+        request: tonic::Request<d3rpc::ExistsRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::ExistsResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        //{{{ trace
+        info!(
+            "Received exists request for mesh {} from {} on port {:?}",
+            msg.id, msg.client_name, addr
+        );
+        //}}}
+        let state = self.state.lock().unwrap();
+        Ok(Response::new(d3rpc::ExistsResponse {
+            exists: state.contains_mesh(msg.id as usize),
+        }))
+    }
+    //}}}
+    //{{{ fun: disconnect
+    async fn disconnect(
+        &self,
+        request: tonic::Request<d3rpc::DisconnectRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::DisconnectResponse>, tonic::Status> {
+        let addr = request.remote_addr();
+        let msg = request.into_inner();
+        //{{{ trace
+        info!(
+            "Received disconnect request from {} on port {:?}",
+            msg.client_name, addr
+        );
+        //}}}
+        self.clients
+            .lock()
+            .unwrap()
+            .disconnect(&msg.client_name, Instant::now());
+        Ok(Response::new(d3rpc::DisconnectResponse {}))
+    }
+    //}}}
+    //{{{ fun: ping
+    async fn ping(
+        &self,
+        request: tonic::Request<d3rpc::PingRequest>,
+    ) -> std::result::Result<tonic::Response<d3rpc::PongResponse>, tonic::Status> {
+        let msg = request.into_inner();
+        self.touch(&msg.client_name);
+        let server_time_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Ok(Response::new(d3rpc::PongResponse {
+            server_time_millis: server_time_millis,
+        }))
+    }
+    //}}}
     //{{{ fun: kill_server
     async fn kill_server(
         &self,
@@ -320,6 +1214,7 @@ impl d3rpc::state_service_server::StateService for StateServer {
 
         let addr = request.remote_addr();
         let msg = request.into_inner();
+        self.touch(&msg.client_name);
 
         info!(
             "Received kill_server request from {} on port {:?}",
@@ -344,15 +1239,26 @@ pub async fn run_server(
     rpc_address: SocketAddr,
     shutdown_sender: mpsc::Sender<()>,
     mut shutdown_receiver: mpsc::Receiver<()>,
-    event_loop_proxy: EventLoopProxy<TopoHedralEvent>
+    event_loop_proxy: EventLoopProxy<TopoHedralEvent>,
+    exit_on_idle: Option<Duration>,
 )
 {
     info!("Starting RPC server on port {}", rpc_address);
 
 
+    let clients = ClientTracker::new_arc_mutex();
+    if let Some(timeout) = exit_on_idle {
+        //{{{ trace
+        info!("Shutting down after {:?} without active clients", timeout);
+        //}}}
+        tokio::spawn(shutdown_when_idle(clients.clone(), timeout, shutdown_sender.clone()));
+    }
+
     let state_server = StateServer {
         state: state,
         shutdown_sender: shutdown_sender,
+        clients: clients,
+        event_loop_proxy: event_loop_proxy.clone(),
     };
 
     let server = Server::builder()
@@ -372,7 +1278,12 @@ pub async fn run_server(
     //{{{ trace
     info!("Sending Shutdown Event");
     //}}}
-    event_loop_proxy.send_event(TopoHedralEvent::RcpShutdown).unwrap();
+    if let Err(e) = event_loop_proxy.send_event(TopoHedralEvent::RcpShutdown)
+    {
+        //{{{ trace
+        info!("Event loop already closed, shutdown event not delivered: {}", e);
+        //}}}
+    }
 }
 //}}}
 
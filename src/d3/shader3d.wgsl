@@ -1,9 +1,25 @@
 // This shader is a standard triangle shader, with a lighting model t
 
+// Matches MAX_CLIP_PLANES on the Rust side
+const MAX_CLIP_PLANES: u32 = 6u;
+const CLIP_INSIDE_ALL: u32 = 0u;
+const CLIP_INSIDE_ANY: u32 = 1u;
+
 struct ViewUniform {
     view_pos: vec4<f32>, 
     view_dir: vec4<f32>,
     view_proj: mat4x4<f32>,
+    viewport: vec2<f32>,
+    edge_width: f32,
+    _padding: f32,
+    highlight_color: vec4<f32>,
+    // A fragment is outside the plane p when dot(p.xyz, position) + p.w > 0, only the first
+    // clip_plane_count planes are used
+    clip_planes: array<vec4<f32>, MAX_CLIP_PLANES>,
+    clip_plane_count: u32,
+    // CLIP_INSIDE_ALL or CLIP_INSIDE_ANY
+    clip_mode: u32,
+    _clip_padding: vec2<u32>,
 };
 
 @group(0) @binding(0)
@@ -13,40 +29,210 @@ var<uniform> view: ViewUniform;
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
-    @location(2) line_color: vec3<f32>,
-    @location(3) tri_color: vec3<f32>,
+    @location(2) line_color: vec4<f32>,
+    @location(3) tri_color: vec4<f32>,
+}
+
+// The far end of an edge drawn with `vs_main_thick_edge`, the near end is a `VertexInput`
+struct EdgeEndInput {
+    @location(4) position: vec3<f32>,
+    @location(5) normal: vec3<f32>,
+    @location(6) line_color: vec4<f32>,
+    @location(7) tri_color: vec4<f32>,
+}
+
+// The vertex of a mesh with a uniform color, which keeps only the attributes of `VertexInput`
+// that are not colors
+struct CompactVertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
 }
 
+// Places the vertices of a mesh in the world, before the view
+struct MeshModel {
+    model: mat4x4<f32>,
+    // the inverse transpose of the model matrix, which carries the normals
+    normal_matrix: mat4x4<f32>,
+    // only of use to 2D meshes, and ignored here
+    layer_depth: f32,
+    // added to the depth of the line cells of the mesh, and of the edges of its triangle cells
+    // drawn as lines, pulling them in front of the triangles they lie on
+    line_depth_offset: f32,
+    edge_depth_offset: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> mesh_model: MeshModel;
+
+// The colors of a mesh with a uniform color, bound along with its compact vertices
+struct MeshColor {
+    line_color: vec4<f32>,
+    tri_color: vec4<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> mesh_color: MeshColor;
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) normal: vec3<f32>,
-    @location(1) line_color: vec3<f32>,
-    @location(2) tri_color: vec3<f32>,
+    @location(1) line_color: vec4<f32>,
+    @location(2) tri_color: vec4<f32>,
+    @location(3) world_position: vec3<f32>,
 }
 
 
-@vertex
-fn vs_main( model: VertexInput) -> VertexOutput {
+fn to_world(position: vec3<f32>) -> vec3<f32> {
+    let world = mesh_model.model * vec4<f32>(position, 1.0);
+    return world.xyz / world.w;
+}
+
+fn transform( model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
-    out.position = view.view_proj * vec4<f32>(model.position, 1.0);
-    out.normal = model.normal;
+    out.world_position = to_world(model.position);
+    out.position = view.view_proj * vec4<f32>(out.world_position, 1.0);
+    // zero normals, such as those of lines, stay zero rather than turning into NaNs
+    let normal = (mesh_model.normal_matrix * vec4<f32>(model.normal, 0.0)).xyz;
+    out.normal = select(normal, normalize(normal), dot(normal, normal) > 0.0);
     out.line_color = model.line_color;
     out.tri_color = model.tri_color;
     return out;
 }
 
+// Offsets the depth of a vertex by `offset`, scaled by w so that it survives the division by w
+fn offset_depth(out: VertexOutput, offset: f32) -> VertexOutput {
+    var offset_out = out;
+    offset_out.position.z += offset * out.position.w;
+    return offset_out;
+}
+
+@vertex
+fn vs_main( model: VertexInput) -> VertexOutput {
+    return transform(model);
+}
+
+// Draws the line cells of a mesh, offset in depth by its line depth bias
+@vertex
+fn vs_main_line( model: VertexInput) -> VertexOutput {
+    return offset_depth(transform(model), mesh_model.line_depth_offset);
+}
+
+// Draws the edges of the triangle cells of a mesh as line cells, offset in depth like the edges
+// drawn by line polygon mode
+@vertex
+fn vs_main_edge( model: VertexInput) -> VertexOutput {
+    return offset_depth(transform(model), mesh_model.edge_depth_offset);
+}
+
+// Draws the selected mesh with the highlight color in place of its own line color
+@vertex
+fn vs_main_highlight( model: VertexInput) -> VertexOutput {
+    var out = transform(model);
+    out.line_color = view.highlight_color;
+    return out;
+}
+
+fn with_mesh_color(model: CompactVertexInput) -> VertexInput {
+    return VertexInput(model.position, model.normal, mesh_color.line_color, mesh_color.tri_color);
+}
+
+// Draws a mesh with a uniform color, read from `mesh_color` rather than from its vertices
+@vertex
+fn vs_main_uniform( model: CompactVertexInput) -> VertexOutput {
+    return transform(with_mesh_color(model));
+}
+
+@vertex
+fn vs_main_uniform_line( model: CompactVertexInput) -> VertexOutput {
+    return offset_depth(transform(with_mesh_color(model)), mesh_model.line_depth_offset);
+}
+
+@vertex
+fn vs_main_uniform_edge( model: CompactVertexInput) -> VertexOutput {
+    return offset_depth(transform(with_mesh_color(model)), mesh_model.edge_depth_offset);
+}
+
+@vertex
+fn vs_main_uniform_highlight( model: CompactVertexInput) -> VertexOutput {
+    var out = transform(with_mesh_color(model));
+    out.line_color = view.highlight_color;
+    return out;
+}
+
+// Returns whether the corner `corner` of the quad built by `expand_segment` lies at the end b,
+// which is the case for corners 2, 3 and 5
+fn corner_at_b(corner: u32) -> bool {
+    return ((0x2Cu >> corner) & 1u) == 1u;
+}
+
+// Expands the segment between the clip space positions `a` and `b` into a quad `width` pixels
+// wide and returns the clip space position of its corner `corner`, from 0 to 5. The corners make
+// up the triangles (a-, a+, b-) and (b-, a+, b+), where - and + are the two sides of the segment.
+fn expand_segment(a: vec4<f32>, b: vec4<f32>, corner: u32, width: f32) -> vec4<f32> {
+    let at_b = corner_at_b(corner);
+    // corners 1, 4 and 5 lie on the + side
+    let side = f32((0x32u >> corner) & 1u) * 2.0 - 1.0;
+    // the direction of the segment on the screen, in pixels
+    let along = (b.xy / b.w - a.xy / a.w) * view.viewport;
+    var dir = vec2<f32>(1.0, 0.0);
+    if (dot(along, along) > 0.0) {
+        dir = normalize(along);
+    }
+    // half the width in pixels, where clip space spans 2 units across the viewport
+    let offset = side * width * vec2<f32>(-dir.y, dir.x) / view.viewport;
+    let end = select(a, b, at_b);
+    return vec4<f32>(end.xy + offset * end.w, end.z, end.w);
+}
+
+// Draws the edge from `a` to `b`, one per instance, as a quad `view.edge_width` pixels wide
+@vertex
+fn vs_main_thick_edge(
+    @builtin(vertex_index) corner: u32,
+    a: VertexInput,
+    b: EdgeEndInput,
+) -> VertexOutput {
+    var out = transform(a);
+    let b_world_position = to_world(b.position);
+    let b_position = view.view_proj * vec4<f32>(b_world_position, 1.0);
+    out.position = expand_segment(out.position, b_position, corner, view.edge_width);
+    out.world_position = select(out.world_position, b_world_position, corner_at_b(corner));
+    return out;
+}
+
 // let the color of the light be white
 const light_color: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
 const ambient_strength: f32 = 0.5;
 
+// Returns whether the point is clipped away, that is whether it lies outside any of the clipping
+// planes, or outside all of them in the CLIP_INSIDE_ANY mode
+fn is_clipped(world_position: vec3<f32>) -> bool {
+    var num_outside = 0u;
+    for (var i = 0u; i < view.clip_plane_count; i++) {
+        let plane = view.clip_planes[i];
+        if (dot(plane.xyz, world_position) + plane.w > 0.0) {
+            num_outside++;
+        }
+    }
+    if (view.clip_mode == CLIP_INSIDE_ANY) {
+        return num_outside > 0u && num_outside == view.clip_plane_count;
+    }
+    return num_outside > 0u;
+}
+
 @fragment 
 fn fs_main_line(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(in.line_color, 1.0);
+    if (is_clipped(in.world_position)) {
+        discard;
+    }
+    return in.line_color;
 }
 
 // Here we use a very simple diffuse-reflection light model.
 @fragment 
 fn fs_main_triangle(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (is_clipped(in.world_position)) {
+        discard;
+    }
 
     let light_dir: vec3<f32> =  normalize(vec3<f32>(1.0, 1.0, 1.0));
 
@@ -55,7 +241,7 @@ fn fs_main_triangle(in: VertexOutput) -> @location(0) vec4<f32> {
     let diffuse_strength: f32 = max(dot(in.normal, light_dir), 0.0);
     let diffuse_color: vec3<f32> = light_color * diffuse_strength;
 
-    let result = (ambient_color + diffuse_color)  * in.tri_color;
-    return vec4<f32>(result, 1.0);  
+    let result = (ambient_color + diffuse_color)  * in.tri_color.rgb;
+    return vec4<f32>(result, in.tri_color.a);  
 
 }
\ No newline at end of file
@@ -5,19 +5,101 @@
 
 //{{{ crate imports 
 use super::{
-    camera::View,
-    mesh::{AxesDescriptor, CuboidDescriptor, CylinderDescriptor, DiscDescriptor, LineDescriptor, Mesh, Mesh3D, PlaneDescriptor, SphereDescriptor, TriangleDescriptor},
+    camera::{CameraDescriptor, View},
+    mesh::{
+        AxesDescriptor, CuboidDescriptor, CylinderDescriptor, Descriptor, DiscDescriptor,
+        EllipsoidDescriptor, AxesPolicy, ColorBy, Error as MeshError, LineDescriptor, Mesh,
+        Mesh3D, PlaneDescriptor, SphereDescriptor, TriangleDescriptor,
+    },
     vertex::Vertex
 };
+use crate::common::{AddMeshError, ClipMode, Color, DrawContext, Vec2, Vec3, ViewSensitivity};
 use crate::core::{StateCore, ViewStateCore};
 use crate::events::EventController; 
 //}}}
 //{{{ std imports 
 //}}}
 //{{{ dep imports 
+use thiserror::Error;
 //}}}
 //--------------------------------------------------------------------------------------------------
 
+/// The number of latitude and longitude lines of the spheres added by `add_sphere_simple`.
+const SIMPLE_SPHERE_RESOLUTION: usize = 32;
+
+//{{{ enum: AddShapeError
+/// Raised when a shape cannot be added to the state, either because no mesh can be created from
+/// its descriptor or because the state turns the mesh away, see [`AddMeshError`].
+#[derive(Debug, Error)]
+pub enum AddShapeError {
+    #[error(transparent)]
+    Mesh(#[from] MeshError),
+    #[error(transparent)]
+    Add(#[from] AddMeshError),
+}
+//}}}
+
+//{{{ fun: simple_triangle
+/// Returns the triangle with the given vertices drawn with `context`, as added by the
+/// `add_triangle_simple` methods of the state and client.
+pub(crate) fn simple_triangle(context: &DrawContext, v1: Vec3, v2: Vec3, v3: Vec3)
+    -> TriangleDescriptor
+{
+    TriangleDescriptor {
+        v1: v1,
+        v2: v2,
+        v3: v3,
+        line_color: context.default_line_color,
+        tri_color: context.default_tri_color,
+        cell_type: context.default_cell_type,
+    }
+}
+//}}}
+//{{{ fun: simple_cuboid
+/// Returns the cuboid with the given side lengths along the x, y and z-axes and its corner of
+/// smallest coordinates at `origin`, drawn with `context`, as added by the `add_cuboid_simple`
+/// methods of the state and client.
+pub(crate) fn simple_cuboid(
+    context: &DrawContext,
+    origin: Vec3,
+    lenx: f32,
+    leny: f32,
+    lenz: f32,
+) -> CuboidDescriptor
+{
+    CuboidDescriptor {
+        origin: origin,
+        x_axis: Vec3::x(),
+        y_axis: Vec3::y(),
+        z_axis: Vec3::z(),
+        lenx: lenx,
+        leny: leny,
+        lenz: lenz,
+        line_color: context.default_line_color,
+        tri_color: context.default_tri_color,
+        cell_type: context.default_cell_type,
+        axes_policy: AxesPolicy::AsGiven,
+    }
+}
+//}}}
+//{{{ fun: simple_sphere
+/// Returns the sphere with the given centre and radius, its poles on the z-axis, drawn with
+/// `context`, as added by the `add_sphere_simple` methods of the state and client.
+pub(crate) fn simple_sphere(context: &DrawContext, origin: Vec3, radius: f32) -> SphereDescriptor
+{
+    SphereDescriptor {
+        origin: origin,
+        axis: Vec3::z(),
+        radius: radius,
+        n_lat: SIMPLE_SPHERE_RESOLUTION,
+        n_long: SIMPLE_SPHERE_RESOLUTION,
+        line_color: context.default_line_color,
+        tri_color: context.default_tri_color,
+        cell_type: context.default_cell_type,
+        color_by: ColorBy::None,
+    }
+}
+//}}}
 
 #[derive(Default, Debug)]
 pub struct ViewState
@@ -36,6 +118,16 @@ impl ViewState
             view_controller: EventController::default(),
         }
     }
+
+    pub fn view(&self) -> &View
+    {
+        &self.view
+    }
+
+    pub fn view_mut(&mut self) -> &mut View
+    {
+        &mut self.view
+    }
 }
 impl ViewStateCore for ViewState
 {
@@ -53,6 +145,91 @@ impl ViewStateCore for ViewState
     {
         bytemuck::bytes_of(&self.view.uniform)
     }
+
+    fn set_viewport_size(&mut self, width: u32, height: u32)
+    {
+        self.view.set_viewport_size(width as f32, height as f32);
+    }
+
+    fn reset_view(&mut self)
+    {
+        self.view.reset();
+    }
+
+    fn set_highlight_color(&mut self, color: &Color)
+    {
+        self.view.set_highlight_color(color);
+    }
+
+    fn set_edge_width(&mut self, width: f32)
+    {
+        self.view.set_edge_width(width);
+    }
+
+    fn sensitivity(&self) -> ViewSensitivity
+    {
+        ViewSensitivity {
+            key_pan_delta: self.view.options.key_pan_delta,
+            key_rotate_delta: self.view.options.key_orbit_delta,
+            zoom_speed: self.view.options.zoom_speed,
+        }
+    }
+
+    fn set_sensitivity(&mut self, sensitivity: &ViewSensitivity)
+    {
+        self.view.options.key_pan_delta = sensitivity.key_pan_delta;
+        self.view.options.key_orbit_delta = sensitivity.key_rotate_delta;
+        self.view.options.key_roll_delta = sensitivity.key_rotate_delta;
+        self.view.options.zoom_speed = sensitivity.zoom_speed;
+    }
+
+    fn pick_ray(&self, pixel: Vec2) -> (Vec3, Vec3)
+    {
+        self.view.screen_to_ray(pixel)
+    }
+
+    fn is_animating(&self) -> bool
+    {
+        self.view.is_animating()
+    }
+
+    fn linearize_depths(&self, depths: &mut [f32])
+    {
+        self.view.projection().linearize_depths(depths);
+    }
+
+    fn set_reversed_z(&mut self, enabled: bool)
+    {
+        self.view.set_reversed_z(enabled);
+    }
+
+    fn set_initial_octant(&mut self, octant: i8)
+    {
+        self.view
+            .set_initial_octant(octant)
+            .expect("the octant is checked by the state");
+    }
+
+    fn set_units_per_meter(&mut self, units_per_meter: f32)
+    {
+        self.view.set_units_per_meter(units_per_meter);
+    }
+
+    fn frame_box(&mut self, min: Vec3, max: Vec3, animate: bool)
+    {
+        let duration = if animate { self.view.options.animation_duration } else { 0.0 };
+        self.view.frame_box(min, max, duration);
+    }
+
+    fn set_clip_planes(&mut self, planes: &[(Vec3, Vec3)], mode: ClipMode)
+    {
+        self.view.set_clip_planes(planes, mode);
+    }
+
+    fn gizmo_uniform_buffer(&self) -> Option<&[u8]>
+    {
+        Some(bytemuck::bytes_of(self.view.gizmo_uniform()))
+    }
 }
 //..................................................................................................
 
@@ -63,35 +240,55 @@ pub trait State3D<'a>
     fn add_line(
         &mut self,
         line: &LineDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
     fn add_triangle(
         &mut self,
         triangle: &TriangleDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
     fn add_plane(
         &mut self,
         plane: &PlaneDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
+    /// Fails without adding anything if the axes policy of the cuboid rejects its axes.
     fn add_cuboid(
         &mut self,
         cuboid: &CuboidDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddShapeError>;
     fn add_cylinder(
         &mut self,
         cylinder: &CylinderDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
     fn add_disc(
         &mut self,
         disc: &DiscDescriptor,
-    ) -> usize;
+    ) -> Result<usize, AddMeshError>;
     fn add_sphere(
         &mut self,
         sphere: &SphereDescriptor,
-    ) -> usize; 
+    ) -> Result<usize, AddMeshError>; 
+    fn add_ellipsoid(
+        &mut self,
+        ellipsoid: &EllipsoidDescriptor,
+    ) -> Result<usize, AddMeshError>;
     fn add_axes(
         &mut self,
         axes_desc: &AxesDescriptor,
-    ) -> usize; 
+    ) -> Result<usize, AddMeshError>; 
+    /// Shows or hides the x, y and z axes in the bottom-left corner of every window, turning
+    /// with the camera. Pressing `g` in a window toggles them.
+    fn set_axes_gizmo(
+        &mut self,
+        visible: bool,
+    );
+    fn camera(&self) -> CameraDescriptor;
+    /// Points the camera of every window at `focus` from `position`. With `animate` set the
+    /// camera moves there smoothly over the animation duration of each view, otherwise it jumps.
+    fn set_camera(
+        &mut self,
+        position: Vec3,
+        focus: Vec3,
+        animate: bool,
+    );
 }
 
 impl<'a> State3D<'a> for State<'a>
@@ -100,7 +297,7 @@ impl<'a> State3D<'a> for State<'a>
     fn add_line(
         &mut self,
         line_desc: &LineDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let line_mesh = Mesh::create_line(line_desc);
         self.add_mesh(line_mesh)
@@ -109,7 +306,7 @@ impl<'a> State3D<'a> for State<'a>
     fn add_triangle(
             &mut self,
             triangle: &TriangleDescriptor,
-        ) -> usize {
+        ) -> Result<usize, AddMeshError> {
         let triangle_mesh = Mesh::create_triangle(triangle);
         self.add_mesh(triangle_mesh)
     }
@@ -117,7 +314,7 @@ impl<'a> State3D<'a> for State<'a>
     fn add_plane(
         &mut self,
         plane_desc: &PlaneDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let plane_mesh = Mesh::create_plane(plane_desc);
         self.add_mesh(plane_mesh)
@@ -126,44 +323,166 @@ impl<'a> State3D<'a> for State<'a>
     fn add_cuboid(
         &mut self,
         cuboid_desc: &CuboidDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddShapeError>
     {
-        let cuboid_mesh = Mesh::create_cuboid(cuboid_desc);
-        self.add_mesh(cuboid_mesh)
+        let cuboid_mesh = Mesh::create_cuboid(cuboid_desc)?;
+        Ok(self.add_mesh(cuboid_mesh)?)
     }
 
     fn add_cylinder(
         &mut self,
         cyl_desc: &CylinderDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
-        let cyl_mesh = Mesh::create_cylinder(cyl_desc);
+        let mut cyl_desc = cyl_desc.clone();
+        cyl_desc.apply_quality(self.tessellation_quality());
+        let cyl_mesh = Mesh::create_cylinder(&cyl_desc);
         self.add_mesh(cyl_mesh)
     }
 
     fn add_disc(
             &mut self,
             disc: &DiscDescriptor,
-        ) -> usize {
-        let disc_mesh = Mesh::create_disc(disc);
+        ) -> Result<usize, AddMeshError> {
+        let mut disc = disc.clone();
+        disc.apply_quality(self.tessellation_quality());
+        let disc_mesh = Mesh::create_disc(&disc);
         self.add_mesh(disc_mesh)
     }
 
     fn add_sphere(
         &mut self,
         sphere_desc: &SphereDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
-        let sphere_mesh = Mesh::create_sphere(sphere_desc);
+        let mut sphere_desc = sphere_desc.clone();
+        sphere_desc.apply_quality(self.tessellation_quality());
+        let sphere_mesh = Mesh::create_sphere(&sphere_desc);
         self.add_mesh(sphere_mesh)
     }
 
+    fn add_ellipsoid(
+        &mut self,
+        ellipsoid_desc: &EllipsoidDescriptor,
+    ) -> Result<usize, AddMeshError>
+    {
+        let mut ellipsoid_desc = ellipsoid_desc.clone();
+        ellipsoid_desc.apply_quality(self.tessellation_quality());
+        let ellipsoid_mesh = Mesh::create_ellipsoid(&ellipsoid_desc);
+        self.add_mesh(ellipsoid_mesh)
+    }
+
     fn add_axes(
         &mut self,
         axes_desc: &AxesDescriptor,
-    ) -> usize
+    ) -> Result<usize, AddMeshError>
     {
         let axes_mesh = Mesh::create_axes(axes_desc);
         self.add_mesh(axes_mesh)
     }
-}
\ No newline at end of file
+
+    fn set_axes_gizmo(
+        &mut self,
+        visible: bool,
+    )
+    {
+        if !self.has_gizmo()
+        {
+            let gizmo = Mesh::create_axes(&AxesDescriptor {
+                origin: Vec3::zeros(),
+                x_axis: Vec3::x(),
+                y_axis: Vec3::y(),
+                z_axis: Vec3::z(),
+                neg_len: 0.0,
+                pos_len: 1.0,
+            });
+            self.set_gizmo(Some(gizmo));
+        }
+        self.set_gizmo_visible(visible);
+    }
+
+    fn camera(&self) -> CameraDescriptor
+    {
+        self.view_state()
+            .map_or_else(|| View::default().camera_descriptor(), |vs| vs.view().camera_descriptor())
+    }
+
+    fn set_camera(
+        &mut self,
+        position: Vec3,
+        focus: Vec3,
+        animate: bool,
+    )
+    {
+        self.update_views(|vs| {
+            let view = vs.view_mut();
+            let duration = if animate { view.options.animation_duration } else { 0.0 };
+            view.animate_to(position, focus, duration);
+        });
+    }
+}
+//{{{ impl: State
+/// Simplified versions of the `add_*` methods of [`State3D`], taking only the geometry of the
+/// shapes and drawing them with the draw context of the state.
+impl<'a> State<'a>
+{
+    /// Adds the triangle with the given vertices, drawn with the draw context of the state.
+    pub fn add_triangle_simple(
+        &mut self,
+        v1: Vec3,
+        v2: Vec3,
+        v3: Vec3,
+    ) -> Result<usize, AddMeshError>
+    {
+        let triangle_desc = simple_triangle(&self.draw_context(), v1, v2, v3);
+        self.add_triangle(&triangle_desc)
+    }
+
+    /// Adds the cuboid with the given side lengths along the x, y and z-axes, with its corner
+    /// of smallest coordinates at `origin`, drawn with the draw context of the state.
+    pub fn add_cuboid_simple(
+        &mut self,
+        origin: Vec3,
+        lenx: f32,
+        leny: f32,
+        lenz: f32,
+    ) -> Result<usize, AddShapeError>
+    {
+        let cuboid_desc = simple_cuboid(&self.draw_context(), origin, lenx, leny, lenz);
+        self.add_cuboid(&cuboid_desc)
+    }
+
+    /// Adds the sphere with the given centre and radius, its poles on the z-axis, drawn with the
+    /// draw context of the state.
+    pub fn add_sphere_simple(
+        &mut self,
+        origin: Vec3,
+        radius: f32,
+    ) -> Result<usize, AddMeshError>
+    {
+        let sphere_desc = simple_sphere(&self.draw_context(), origin, radius);
+        self.add_sphere(&sphere_desc)
+    }
+
+    /// Adds the shape the descriptor holds through the matching `add_*` method of [`State3D`],
+    /// so that subdivision counts left at zero come from the tessellation quality of the state.
+    pub fn add_descriptor(
+        &mut self,
+        descriptor: &Descriptor,
+    ) -> Result<usize, AddShapeError>
+    {
+        let uid = match descriptor {
+            Descriptor::Line(line) => self.add_line(line)?,
+            Descriptor::Triangle(triangle) => self.add_triangle(triangle)?,
+            Descriptor::Plane(plane) => self.add_plane(plane)?,
+            Descriptor::Cuboid(cuboid) => self.add_cuboid(cuboid)?,
+            Descriptor::Cylinder(cylinder) => self.add_cylinder(cylinder)?,
+            Descriptor::Disc(disc) => self.add_disc(disc)?,
+            Descriptor::Sphere(sphere) => self.add_sphere(sphere)?,
+            Descriptor::Ellipsoid(ellipsoid) => self.add_ellipsoid(ellipsoid)?,
+            Descriptor::Axes(axes) => self.add_axes(axes)?,
+        };
+        Ok(uid)
+    }
+}
+//}}}
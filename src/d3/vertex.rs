@@ -19,20 +19,20 @@ pub struct Vertex {
     /// The internal data has the layout: ``[position normal color]`` where:
     /// - position: [x, y, z]
     /// - normal: [nx, ny, nz]
-    /// - line_color : [r, g, b]
-    /// - triangle_color : [r, g, b]
-    data: [f32; 12],
+    /// - line_color : [r, g, b, a]
+    /// - triangle_color : [r, g, b, a]
+    data: [f32; 14],
 }
 
 impl Vertex {
 
     pub fn new(vert_disc: &VertexDescriptor) -> Vertex
     {
-        let mut data = [0.0; 12];
+        let mut data = [0.0; 14];
         data[0..3].copy_from_slice(vert_disc.position.as_slice());
         data[3..6].copy_from_slice(vert_disc.normal.as_slice());
-        data[6..9].copy_from_slice(&vert_disc.line_color.to_rgb());
-        data[9..12].copy_from_slice(&vert_disc.triangle_color.to_rgb());
+        data[6..10].copy_from_slice(&vert_disc.line_color.to_rgba());
+        data[10..14].copy_from_slice(&vert_disc.triangle_color.to_rgba());
         Vertex { data }
     }
 
@@ -69,17 +69,42 @@ impl VertexCore for Vertex {
                 wgpu::VertexAttribute {
                     offset: mem::size_of::<[f32; 6]>() as wgpu::BufferAddress,
                     shader_location: 2,
-                    format: wgpu::VertexFormat::Float32x3,
+                    format: wgpu::VertexFormat::Float32x4,
                 },
                 wgpu::VertexAttribute {
-                    offset: mem::size_of::<[f32; 9]>() as wgpu::BufferAddress,
+                    offset: mem::size_of::<[f32; 10]>() as wgpu::BufferAddress,
                     shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+            ],
+        }
+    }
+
+    fn compact_desc() -> wgpu::VertexBufferLayout<'static> {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<[f32; 6]>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                    shader_location: 1,
                     format: wgpu::VertexFormat::Float32x3,
                 },
             ],
         }
     }
 
+    fn write_compact(vertex: &[f32], out: &mut Vec<f32>) {
+        // the position and normal lead the vertex data
+        out.extend_from_slice(&vertex[0..6]);
+    }
+
     fn position_offset() -> usize 
     {
         0
@@ -91,16 +116,31 @@ impl VertexCore for Vertex {
     }
 
     fn triangle_color_offset() -> usize {   
-        9
+        10
     }
 
     fn len() -> usize {
-       12 
+       14 
+    }
+
+    fn position(vertex: &[f32]) -> Vec3 {
+        let start = Vertex::position_offset();
+        Vec3::from_column_slice(&vertex[start..start + 3])
     }
 
     fn dim() -> usize {
         3
     }
+
+    fn set_normal(vertex: &mut [f32], normal: &Vec3) {
+        let start = Vertex::normal_offset();
+        vertex[start..start + 3].copy_from_slice(normal.as_slice());
+    }
+
+    fn normal(vertex: &[f32]) -> Option<Vec3> {
+        let start = Vertex::normal_offset();
+        Some(Vec3::from_column_slice(&vertex[start..start + 3]))
+    }
 }
 //..................................................................................................
 
@@ -146,30 +186,30 @@ impl<'a> VertexViewCore<'a> for VertexView<'a> {
     }
 
 
-    fn set_line_color(&mut self, color:  &[f32; 3]) {
+    fn set_line_color(&mut self, color: &[f32; 4]) {
         let start = Vertex::line_color_offset();
-        let end = Vertex::line_color_offset() + 3;
+        let end = Vertex::line_color_offset() + 4;
         self.vertex_data[start..end].copy_from_slice(color);
     }   
 
-    fn get_line_color(&self) -> [f32; 3] {
+    fn get_line_color(&self) -> [f32; 4] {
         let start = Vertex::line_color_offset();
-        let end = Vertex::line_color_offset() + 3;
-        let mut color = [0.0; 3];
+        let end = Vertex::line_color_offset() + 4;
+        let mut color = [0.0; 4];
         color.copy_from_slice(&self.vertex_data[start..end]);
         color
     }
 
-    fn set_triangle_color(&mut self, color: &[f32; 3]) {
+    fn set_triangle_color(&mut self, color: &[f32; 4]) {
         let start = Vertex::triangle_color_offset();
-        let end = Vertex::triangle_color_offset() + 3;
+        let end = Vertex::triangle_color_offset() + 4;
         self.vertex_data[start..end].copy_from_slice(color);
     }
 
-    fn get_triangle_color(&self) -> [f32; 3] {
+    fn get_triangle_color(&self) -> [f32; 4] {
         let start = Vertex::triangle_color_offset();
-        let end = Vertex::triangle_color_offset() + 3;
-        let mut color = [0.0; 3];
+        let end = Vertex::triangle_color_offset() + 4;
+        let mut color = [0.0; 4];
         color.copy_from_slice(&self.vertex_data[start..end]);
         color
     }
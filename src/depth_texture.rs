@@ -1,5 +1,100 @@
+use clap::ValueEnum;
 use wgpu;
 
+/// The precision of the depth buffer, chosen when the GPU context is created.
+///
+/// Higher precision avoids z-fighting between distant, nearly coplanar faces in scenes with a
+/// large extent, at the cost of memory. Only the 16 and 32-bit formats can be read back with
+/// `read_depth_buffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum DepthFormat
+{
+    /// 16-bit normalized integer depth.
+    Depth16,
+    /// At least 24 bits of depth, the exact format is left to the adapter.
+    Depth24,
+    /// 32-bit floating point depth.
+    #[default]
+    Depth32,
+}
+
+impl DepthFormat
+{
+    pub fn texture_format(&self) -> wgpu::TextureFormat
+    {
+        match self
+        {
+            DepthFormat::Depth16 => wgpu::TextureFormat::Depth16Unorm,
+            DepthFormat::Depth24 => wgpu::TextureFormat::Depth24Plus,
+            DepthFormat::Depth32 => wgpu::TextureFormat::Depth32Float,
+        }
+    }
+}
+
+/// How the depth buffer is laid out, shared by the depth texture and every pipeline testing
+/// against it.
+///
+/// With reversed-Z the near plane maps to a depth of one and the far plane to zero, which spreads
+/// the precision of a floating point depth buffer far more evenly over a perspective view. Depth
+/// tests then pass for greater depths, and depth biases change sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthMode
+{
+    pub format: wgpu::TextureFormat,
+    pub reversed_z: bool,
+}
+
+impl DepthMode
+{
+    /// The comparison passing fragments nearer to the camera than the stored depth.
+    pub fn compare(&self) -> wgpu::CompareFunction
+    {
+        if self.reversed_z
+        {
+            wgpu::CompareFunction::Greater
+        }
+        else
+        {
+            wgpu::CompareFunction::Less
+        }
+    }
+
+    /// The depth bias pulling fragments towards the camera for negative `depth_bias`, or pushing
+    /// them away for positive values, whichever way the depth runs.
+    pub fn bias(&self, depth_bias: i32) -> wgpu::DepthBiasState
+    {
+        let depth_bias = if self.reversed_z { -depth_bias } else { depth_bias };
+        wgpu::DepthBiasState {
+            constant: depth_bias,
+            slope_scale: depth_bias as f32,
+            clamp: 0.0,
+        }
+    }
+
+    /// The offset the shaders add to the depth of lines with the depth bias `depth_bias`, pulling
+    /// them towards the camera for negative values like [`Self::bias`]. The depth bias of a
+    /// pipeline only applies to triangles, so lines are offset in their vertex shader instead.
+    ///
+    /// A unit of bias is a few steps of the depth format at the far plane, where the depth is
+    /// least precise, so that the offset survives rounding to the depth buffer.
+    pub fn line_offset(&self, depth_bias: i32) -> f32
+    {
+        let unit = match self.format
+        {
+            wgpu::TextureFormat::Depth16Unorm => 2.0 / 65536.0,
+            _ => 8.0 / 16777216.0,
+        };
+        let depth_bias = if self.reversed_z { -depth_bias } else { depth_bias };
+        depth_bias as f32 * unit
+    }
+
+    /// The depth the buffer is cleared to, that of the far plane.
+    pub fn clear_value(&self) -> f32
+    {
+        if self.reversed_z { 0.0 } else { 1.0 }
+    }
+}
+
 pub struct DepthTexture
 {
     pub texture: wgpu::Texture,
@@ -9,11 +104,34 @@ pub struct DepthTexture
 
 impl DepthTexture
 {
+    /// The format used when none is requested, or the requested one is not supported.
     pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 
+    /// Returns the texture format for the requested depth format if the adapter can render to
+    /// it, falling back to [`Self::DEPTH_FORMAT`] otherwise.
+    pub fn supported_format(
+        adapter: &wgpu::Adapter,
+        requested: DepthFormat,
+    ) -> wgpu::TextureFormat
+    {
+        let format = requested.texture_format();
+        let features = adapter.get_texture_format_features(format);
+        if features
+            .allowed_usages
+            .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+        {
+            format
+        }
+        else
+        {
+            Self::DEPTH_FORMAT
+        }
+    }
+
     pub fn create_depth_texture(
         device: &wgpu::Device,
         config: &wgpu::SurfaceConfiguration,
+        format: wgpu::TextureFormat,
         label: &str,
     ) -> Self
     {
@@ -22,15 +140,23 @@ impl DepthTexture
             height: config.height,
             depth_or_array_layers: 1,
         };
+        // the 24-bit format has no fixed layout, so it cannot be copied out of the texture
+        let copy_src = match format
+        {
+            wgpu::TextureFormat::Depth24Plus => wgpu::TextureUsages::empty(),
+            _ => wgpu::TextureUsages::COPY_SRC,
+        };
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some(label),
             size,
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: Self::DEPTH_FORMAT,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[Self::DEPTH_FORMAT],
+            format: format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | copy_src,
+            view_formats: &[format],
         });
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
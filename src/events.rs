@@ -97,6 +97,7 @@ impl Default for KeyStrokeState {
 /// - `mouse_button_pressed_state`: Tracks the current state of mouse button presses.
 /// - `mouse_position`: Stores the current mouse position.
 /// - `mouse_position_delta`: Stores the change in mouse position since the last update.
+/// - `cursor_in_window`: Whether the cursor is over the window, so that `mouse_position` is current.
 /// - `mouse_wheel_delta`: Stores the change in mouse wheel scroll since the last update.
 /// - `key_modifier_state`: Tracks the current state of keyboard modifiers (e.g., Shift, Ctrl, Alt).
 /// - `resized_state`: Tracks whether the window has been resized.
@@ -106,6 +107,7 @@ pub struct EventController {
     pub mouse_button_pressed_state: MouseButtonPressedState,
     pub mouse_position: [f32; 2],
     pub mouse_position_delta: [f32; 2],
+    pub cursor_in_window: bool,
     pub mouse_wheel_delta: Option<f32>,
     pub key_modifier_state: winit::keyboard::ModifiersState,
     pub resized_state: ResizedState,
@@ -183,6 +185,15 @@ impl EventController {
         self.mouse_position_delta[0] = del_x;
 
         self.mouse_position_delta[1] = del_y;
+
+        self.cursor_in_window = true;
+    }
+    //}}}
+    //{{{ fun: cursor_left_update
+    /// Records that the cursor has left the window, the last `mouse_position` is kept but no
+    /// longer tracks the cursor.
+    pub fn cursor_left_update(&mut self) {
+        self.cursor_in_window = false;
     }
     //}}}
     //{{{ fun: key_input_update
@@ -326,6 +337,7 @@ mod tests {
         assert_eq!(event_controller.mouse_position[1], 200.0);
         assert_eq!(event_controller.mouse_position_delta[0], 100.0);
         assert_eq!(event_controller.mouse_position_delta[1], 200.0);
+        assert!(event_controller.cursor_in_window);
 
         // Test subsequent mouse position update
         event_controller.cursor_moved_update(PhysicalPosition { x: 150.0, y: 250.0 });
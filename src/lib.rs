@@ -2,10 +2,17 @@ mod colormap;
 mod common;
 mod depth_texture;
 mod events;
+mod clients;
+mod connection;
 mod core;
 
-pub use common::{Color, Vec2, Vec3, VecD, CellType};
+pub use common::{
+    Color, Vec2, Vec3, VecD, Mat4, CellType, DepthBuffer, ViewSensitivity, BudgetError, SceneBudget,
+    SceneStats, ClipMode, FaceCulling, FrontFace, MeshColorMode, MeshOptions, MeshDiagnostics,
+    DrawContext, TessellationQuality, AddMeshError, MAX_CLIP_PLANES,
+};
 pub use colormap::{Colormap, ColormapError};
+pub use depth_texture::DepthFormat;
 pub mod d2;
 pub mod d3;
 pub mod app;
@@ -0,0 +1,38 @@
+use topohedral_viewer::d3::{self, SphereDescriptor, State3D};
+use topohedral_viewer::{CellType, Color, Mat4, Vec3};
+
+#[test]
+fn frame_callback_test() {
+    let mut state = d3::State::new();
+    let uid = state
+        .add_sphere(&SphereDescriptor {
+            origin: Vec3::zeros(),
+            axis: Vec3::z(),
+            radius: 1.0,
+            n_lat: 8,
+            n_long: 16,
+            line_color: Color::Black,
+            tri_color: Color::Red,
+            cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
+        })
+        .unwrap();
+
+    // an animation moving the sphere along x by one unit a frame
+    let mut frame = 0.0;
+    state.set_frame_callback(Some(Box::new(move |state| {
+        frame += 1.0;
+        let model = Mat4::new_translation(&Vec3::new(frame, 0.0, 0.0));
+        assert!(state.set_mesh_model(uid, model));
+    })));
+    assert!(state.is_animating());
+    state.run_frame_callback();
+    state.run_frame_callback();
+    let model = state.get_mesh(uid).unwrap().model();
+    assert_eq!(model, Mat4::new_translation(&Vec3::new(2.0, 0.0, 0.0)));
+
+    state.set_frame_callback(None);
+    state.run_frame_callback();
+    assert_eq!(state.get_mesh(uid).unwrap().model(), model);
+    assert!(!state.is_animating());
+}
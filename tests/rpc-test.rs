@@ -2,7 +2,10 @@ use topohedral_tracing::{info, init, topo_log};
 use topohedral_viewer::app::locate_executable;
 use topohedral_viewer::d3::PlaneDescriptor;
 use topohedral_viewer::{d2, d2::Mesh2D, d3, d3::Mesh3D};
-use topohedral_viewer::{CellType, Color, Vec2, Vec3};
+use topohedral_viewer::{
+    CellType, ClipMode, Color, DrawContext, Mat4, MeshColorMode, MeshOptions, TessellationQuality,
+    Vec2, Vec3,
+};
 
 use std::process::{Command, Stdio};
 use std::thread::sleep;
@@ -33,6 +36,9 @@ fn d2_rpc_test() {
         //}}}
         let mut client = d2::Client2D::new(50051).unwrap();
         //}}}
+        //{{{ com: ping
+        client.ping().expect("Failed to ping server");
+        //}}}
         //{{{ com: add axes
         //{{{ trace
         info!("Addding axes");
@@ -62,6 +68,8 @@ fn d2_rpc_test() {
                 line_color: Color::Red,
                 tri_color: Color::Green,
                 cell_type: CellType::Triangle,
+                screen_space: false,
+                fill_and_outline: false,
             })
             .unwrap();
         assert_eq!(square_id1, 6);
@@ -76,6 +84,8 @@ fn d2_rpc_test() {
                 line_color: Color::Red,
                 tri_color: Color::Green,
                 cell_type: CellType::Line,
+                screen_space: false,
+                fill_and_outline: false,
             })
             .unwrap();
         assert_eq!(square_id2, 7);
@@ -92,6 +102,8 @@ fn d2_rpc_test() {
                 line_color: Color::Red,
                 tri_color: Color::Green,
                 cell_type: CellType::Triangle,
+                screen_space: false,
+                fill_and_outline: false,
             })
             .unwrap();
 
@@ -105,6 +117,8 @@ fn d2_rpc_test() {
                 line_color: Color::Red,
                 tri_color: Color::Green,
                 cell_type: CellType::Line,
+                screen_space: false,
+                fill_and_outline: false,
             })
             .unwrap();
 
@@ -120,6 +134,12 @@ fn d2_rpc_test() {
         let v2 = Vec2::new(-0.2, -0.1);
         let v3 = Vec2::new(-0.1, -0.2);
         mesh.add_triangle(&v1, &v2, &v3, &Color::Black, &Color::Cyan);
+        // checking a mesh leaves the scene, and so the next id, as it is
+        let diagnostics = client.validate_mesh(mesh.clone()).unwrap();
+        assert_eq!((diagnostics.num_vertices, diagnostics.num_triangles), (3, 1));
+        assert!(diagnostics.is_manifold && !diagnostics.has_degenerate);
+        let (min, max) = diagnostics.bounds.unwrap();
+        assert_eq!((min.xy(), max.xy()), (Vec2::new(-0.2, -0.2), Vec2::new(-0.1, -0.1)));
         let mesh_id = client.add_mesh(mesh).unwrap();
 
         assert_eq!(mesh_id, 10);
@@ -127,12 +147,77 @@ fn d2_rpc_test() {
         info!("mesh_id: {}", mesh_id);
         //}}}
         //}}}
+        //{{{ com: add lines
+        let line_id1 = client
+            .add_line(d2::LineDescriptor {
+                v1: Vec2::new(-0.5, -0.5),
+                v2: Vec2::new(0.5, -0.5),
+                color: Color::Blue,
+                dash_pattern: None,
+            })
+            .unwrap();
+        assert_eq!(line_id1, 11);
+
+        let line_id2 = client
+            .add_line(d2::LineDescriptor {
+                v1: Vec2::new(-0.5, -0.5),
+                v2: Vec2::new(-0.5, 0.5),
+                color: Color::Magenta,
+                dash_pattern: Some((0.1, 0.05)),
+            })
+            .unwrap();
+        assert_eq!(line_id2, 12);
+        //{{{ trace
+        info!("line_id1: {} line_id2: {}", line_id1, line_id2);
+        //}}}
+        //}}}
+        //{{{ com: move and recolor a line
+        let shift = Mat4::new_translation(&Vec3::new(0.25, 0.0, 0.0));
+        client.set_mesh_model(line_id1, &shift).unwrap();
+        assert!(client.set_mesh_model(line_id2 + 1000, &shift).is_err());
+        client.set_mesh_color_mode(line_id1, MeshColorMode::Uniform(Color::Red)).unwrap();
+        assert!(client.set_mesh_color_mode(line_id2 + 1000, MeshColorMode::PerVertex).is_err());
+        let options = MeshOptions { dash_pattern: Some((0.1, 0.05)), ..MeshOptions::default() };
+        client.set_mesh_options(line_id1, &options).unwrap();
+        //}}}
+        //{{{ com: move both lines as a group
+        client.set_mesh_tag(line_id1, Some("lines")).unwrap();
+        client.set_mesh_tag(line_id2, Some("lines")).unwrap();
+        assert!(client.set_mesh_tag(line_id2 + 1000, Some("lines")).is_err());
+        assert_eq!(client.transform_group("lines", &shift).unwrap(), 2);
+        assert_eq!(client.transform_group("missing", &shift).unwrap(), 0);
+        assert!(client.transform_group("", &shift).is_err());
+        //}}}
+        //{{{ com: flush
+        client.flush().expect("Failed to flush");
+        //}}}
+        //{{{ com: open a second window
+        client.open_window().expect("Failed to open a window");
+        //}}}
+        //{{{ com: get camera
+        let camera = client.get_camera().unwrap();
+        assert_eq!(camera.position, Vec2::zeros());
+        assert_eq!(camera.rotation, 0.0);
+        assert_eq!(camera.zoom, 1.0);
+        //}}}
+        //{{{ com: add simple shapes
+        client.set_draw_context(DrawContext {
+            default_cell_type: CellType::Line,
+            ..DrawContext::default()
+        });
+        let rect_id = client.add_rectangle_simple(Vec2::new(0.5, 0.5), 0.2, 0.1).unwrap();
+        let circle_id = client.add_circle_simple(Vec2::new(-0.5, 0.5), 0.1).unwrap();
+        assert!(client.exists(rect_id).unwrap() && client.exists(circle_id).unwrap());
+        //}}}
         //{{{ com: clear and kill server
         sleep(Duration::from_millis(5000));
         //{{{ trace
         info!("Clearing scene");
         //}}}
+        assert!(client.exists(line_id2).unwrap());
         client.clear().expect("Failed to clear");
+        assert_eq!(client.scene_bounds().unwrap(), None);
+        assert!(!client.exists(line_id2).unwrap());
         //{{{ trace
         info!("Killing server");
         //}}}
@@ -166,6 +251,14 @@ fn d3_rpc_test() {
         //}}}
         let mut client = d3::Client3D::new(50051).unwrap();
         //}}}
+        //{{{ com: ping
+        client.ping().expect("Failed to ping server");
+        //}}}
+        //{{{ com: background gradient
+        client
+            .set_background_gradient(&Color::Navy, &Color::Other((0.8, 0.8, 0.9)))
+            .expect("Failed to set background gradient");
+        //}}}
         //{{{ com: add axes
         //{{{ trace
         info!("Adding axes");
@@ -222,6 +315,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(plane_id1, 8);
         let plane_id2 = client.add_plane(d3::PlaneDescriptor{
@@ -235,6 +329,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Line,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(plane_id2, 9);
         //}}}
@@ -250,6 +345,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            axes_policy: d3::AxesPolicy::AsGiven,
         }).unwrap();
         assert_eq!(cuboid_id1, 10);
 
@@ -264,6 +360,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Line,
+            axes_policy: d3::AxesPolicy::AsGiven,
         }).unwrap();
         assert_eq!(cuboid_id2, 11);
         //}}}
@@ -274,10 +371,12 @@ fn d3_rpc_test() {
             radius: 1.0,
             height: 3.0,
             num_sides: 20,
+            cap_num_sides: None,
             line_color: Color::Red,
             tri_color: Color::Green,
             open: false,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(cyl_id1, 12);
         let cyl_id2 = client.add_cylinder(d3::CylinderDescriptor{
@@ -286,10 +385,12 @@ fn d3_rpc_test() {
             radius: 1.0,
             height: 3.0,
             num_sides: 20,
+            cap_num_sides: None,
             line_color: Color::Purple,
             tri_color: Color::Gray,
             open: false,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(cyl_id2, 13);
         let cyl_id3 = client.add_cylinder(d3::CylinderDescriptor{
@@ -298,10 +399,12 @@ fn d3_rpc_test() {
             radius: 1.0,
             height: 3.0,
             num_sides: 20,
+            cap_num_sides: None,
             line_color: Color::Red,
             tri_color: Color::Green,
             open: true,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(cyl_id3, 14);
         let cyl_id4 = client.add_cylinder(d3::CylinderDescriptor{
@@ -310,10 +413,12 @@ fn d3_rpc_test() {
             radius: 1.0,
             height: 3.0,
             num_sides: 20,
+            cap_num_sides: None,
             line_color: Color::Purple,
             tri_color: Color::Gray,
             open: true,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(cyl_id4, 15);
         //}}}
@@ -326,6 +431,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(disc_id1, 16);
         let disc_id2 = client.add_disc(d3::DiscDescriptor{
@@ -336,6 +442,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Line,
+            color_by: d3::ColorBy::None,
         }).unwrap();
         assert_eq!(disc_id2, 17);
         //}}}
@@ -349,6 +456,7 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
         }).unwrap();    
         assert_eq!(sphere_id1, 18);
         let sphere_id2 = client.add_sphere(d3::SphereDescriptor{
@@ -360,15 +468,204 @@ fn d3_rpc_test() {
             line_color: Color::Red,
             tri_color: Color::Green,
             cell_type: CellType::Line,
+            color_by: d3::ColorBy::None,
         }).unwrap();    
         assert_eq!(sphere_id2, 19);
+        // the server rejects a bad descriptor as a validation error, naming the request
+        let bad_sphere = client.add_sphere(d3::SphereDescriptor{
+            origin: Vec3::zeros(),
+            axis: Vec3::new(0.0, 1.0, 0.0),
+            radius: -1.0,
+            n_lat: 0,
+            n_long: 0,
+            line_color: Color::Red,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
+        });
+        assert!(matches!(
+            bad_sphere,
+            Err(d3::ClientError::Validation { method: "add_sphere", .. })
+        ));
+        //}}}
+        //{{{ com: get camera
+        let camera = client.get_camera().unwrap();
+        assert_eq!(camera.focus, Vec3::zeros());
+        assert!(camera.fov > 0.0);
+        //}}}
+        //{{{ com: add contours
+        // the latitude row of each of the 20 x 20 vertices of the first sphere
+        let values: Vec<f32> = (0..400).map(|i| (i / 20) as f32).collect();
+        let contour_id = client
+            .add_contours(sphere_id1, &values, &[4.5, 9.5, 14.5], &Color::Black)
+            .unwrap();
+        assert_eq!(contour_id, 20);
+        assert!(client.add_contours(sphere_id2, &values, &[4.5], &Color::Black).is_err());
+        //}}}
+        //{{{ com: add lines
+        let line_id1 = client
+            .add_line(d3::LineDescriptor {
+                v1: Vec3::new(-2.0, -2.0, -2.0),
+                v2: Vec3::new(2.0, -2.0, -2.0),
+                color: Color::Blue,
+                dash_pattern: None,
+            })
+            .unwrap();
+        assert_eq!(line_id1, 21);
+
+        let line_id2 = client
+            .add_line(d3::LineDescriptor {
+                v1: Vec3::new(-2.0, -2.0, -2.0),
+                v2: Vec3::new(-2.0, -2.0, 2.0),
+                color: Color::Magenta,
+                dash_pattern: Some((0.1, 0.05)),
+            })
+            .unwrap();
+        assert_eq!(line_id2, 22);
+        assert!(client
+            .add_line(d3::LineDescriptor {
+                v1: Vec3::zeros(),
+                v2: Vec3::zeros(),
+                color: Color::Blue,
+                dash_pattern: None,
+            })
+            .is_err());
+        //}}}
+        //{{{ com: add empty mesh
+        // no value reaches the level, so the contours have no cells and are never drawn
+        let empty_id = client.add_contours(sphere_id1, &values, &[100.0], &Color::Black).unwrap();
+        assert_eq!(empty_id, 23);
+        //}}}
+        //{{{ com: add bounding box
+        let box_id = client.add_bounding_box(sphere_id2, &Color::Red).unwrap();
+        assert_eq!(box_id, 24);
+        assert!(client.add_bounding_box(sphere_id2 + 1000, &Color::Red).is_err());
+        //}}}
+        //{{{ com: move and recolor the bounding box
+        let shift = Mat4::new_translation(&Vec3::new(0.0, 0.0, 0.5));
+        client.set_mesh_model(box_id, &shift).unwrap();
+        assert!(client.set_mesh_model(box_id + 1000, &shift).is_err());
+        client.set_mesh_color_mode(box_id, MeshColorMode::Uniform(Color::Blue)).unwrap();
+        assert!(client.set_mesh_color_mode(box_id + 1000, MeshColorMode::PerVertex).is_err());
+        let options = MeshOptions { dash_pattern: Some((0.1, 0.05)), ..MeshOptions::default() };
+        client.set_mesh_options(box_id, &options).unwrap();
+        //}}}
+        //{{{ com: move the spheres as a group
+        client.set_mesh_tag(sphere_id1, Some("spheres")).unwrap();
+        client.set_mesh_tag(sphere_id2, Some("spheres")).unwrap();
+        assert!(client.set_mesh_tag(sphere_id2 + 1000, Some("spheres")).is_err());
+        assert_eq!(client.transform_group("spheres", &shift).unwrap(), 2);
+        client.set_mesh_tag(sphere_id1, None).unwrap();
+        assert_eq!(client.transform_group("spheres", &shift).unwrap(), 1);
+        //}}}
+        //{{{ com: add normals visualization
+        let normals_id = client.add_normals_visualization(sphere_id1, 0.2, &Color::Blue).unwrap();
+        assert_eq!(normals_id, 25);
+        // the second sphere is made of lines, which have no normals
+        assert!(client.add_normals_visualization(sphere_id2, 0.2, &Color::Blue).is_err());
+        assert!(client.add_normals_visualization(sphere_id1, f32::NAN, &Color::Blue).is_err());
+        //}}}
+        //{{{ com: add polygon face
+        // a regular pentagon standing in the xz-plane
+        let pentagon: Vec<Vec3> = (0..5)
+            .map(|i| {
+                let angle = i as f32 * 2.0 * std::f32::consts::PI / 5.0;
+                Vec3::new(3.0 + angle.cos(), 0.0, angle.sin())
+            })
+            .collect();
+        let face_id = client
+            .add_polygon_face(d3::PolygonFaceDescriptor {
+                points: pentagon,
+                line_color: Color::Black,
+                tri_color: Color::Cyan,
+                cell_type: CellType::Triangle,
+            })
+            .unwrap();
+        assert_eq!(face_id, 26);
+        let warped_face = client.add_polygon_face(d3::PolygonFaceDescriptor {
+            points: vec![Vec3::zeros(), Vec3::x(), Vec3::new(1.0, 1.0, 1.0), Vec3::y()],
+            line_color: Color::Black,
+            tri_color: Color::Cyan,
+            cell_type: CellType::Triangle,
+        });
+        assert!(matches!(
+            warped_face,
+            Err(d3::ClientError::Validation { method: "add_polygon_face", .. })
+        ));
+        //}}}
+        //{{{ com: scene bounds
+        let (min, max) = client.scene_bounds().unwrap().unwrap();
+        assert!(min <= Vec3::new(-2.0, -2.0, -2.0) && max >= Vec3::new(-2.0, -2.0, 2.0));
+        //}}}
+        //{{{ com: flush
+        client.flush().expect("Failed to flush");
+        //}}}
+        //{{{ com: open a second window
+        client.open_window().expect("Failed to open a window");
+        //}}}
+        //{{{ com: focus mesh
+        client.focus_mesh(sphere_id2, true).unwrap();
+        assert!(client.focus_mesh(sphere_id2 + 1000, false).is_err());
+        //}}}
+        //{{{ com: clip plane
+        client.set_clip_plane(&Vec3::new(0.0, 0.0, 0.0), &Vec3::new(1.0, 1.0, 0.0)).unwrap();
+        assert!(client.set_clip_plane(&Vec3::zeros(), &Vec3::zeros()).is_err());
+        sleep(Duration::from_millis(2000));
+        client
+            .set_clip_planes(
+                &[(Vec3::zeros(), Vec3::x()), (Vec3::zeros(), Vec3::y())],
+                ClipMode::InsideAny,
+            )
+            .unwrap();
+        let too_many = [(Vec3::zeros(), Vec3::x()); 7];
+        assert!(client.set_clip_planes(&too_many, ClipMode::InsideAll).is_err());
+        sleep(Duration::from_millis(2000));
+        client.set_clip_box(&Vec3::new(-1.0, -1.0, -1.0), &Vec3::new(1.0, 1.0, 1.0)).unwrap();
+        sleep(Duration::from_millis(2000));
+        client.clear_clip_plane().unwrap();
+        //}}}
+        //{{{ com: add simple shapes
+        client.set_draw_context(DrawContext {
+            default_tri_color: Color::Green,
+            ..DrawContext::default()
+        });
+        let triangle_id = client
+            .add_triangle_simple(Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0), Vec3::z())
+            .unwrap();
+        let cuboid_id = client.add_cuboid_simple(Vec3::new(2.0, 2.0, 0.0), 0.5, 0.5, 0.5).unwrap();
+        let sphere_id = client.add_sphere_simple(Vec3::new(-2.0, 2.0, 0.0), 0.5).unwrap();
+        assert!(client.exists(triangle_id).unwrap() && client.exists(cuboid_id).unwrap());
+        assert!(client.exists(sphere_id).unwrap());
+        //}}}
+        //{{{ com: add a sphere tessellated at the quality
+        // the subdivisions of this one are left at zero, so come from the tessellation quality
+        client
+            .set_tessellation_quality(TessellationQuality::High)
+            .expect("Failed to set tessellation quality");
+        let num_vertices = client.scene_stats().unwrap().num_vertices;
+        let quality_sphere_id = client.add_sphere(d3::SphereDescriptor{
+            origin: Vec3::new(-2.0, -2.0, 0.0),
+            axis: Vec3::new(0.0, 0.0, 1.0),
+            radius: 0.5,
+            n_lat: 0,
+            n_long: 0,
+            line_color: Color::Red,
+            tri_color: Color::Green,
+            cell_type: CellType::Triangle,
+            color_by: d3::ColorBy::None,
+        }).unwrap();
+        assert!(client.exists(quality_sphere_id).unwrap());
+        assert_eq!(client.scene_stats().unwrap().num_vertices - num_vertices, 32 * 64);
         //}}}
         //{{{ com: clear and kill server
         sleep(Duration::from_millis(10000));
         //{{{ trace
         info!("Clearing scene");
         //}}}
+        assert!(client.exists(face_id).unwrap());
         client.clear().expect("Failed to clear");
+        assert_eq!(client.scene_bounds().unwrap(), None);
+        assert!(!client.exists(face_id).unwrap());
         //{{{ trace
         info!("Killing server");
         //}}}